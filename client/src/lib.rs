@@ -0,0 +1,561 @@
+//! Typed read-only client for the DSID contract.
+//!
+//! Kept in its own crate so the contract builds without the Concordium Rust SDK.
+//!
+//! Wraps `invoke_instance` calls of the Concordium Rust SDK for every view
+//! entrypoint of the contract so backend services can reuse the parameter and
+//! response types of this crate instead of redefining them.
+use concordium_cis2::{
+    OperatorOfQueryParams, OperatorOfQueryResponse, SupportsQueryResponse,
+    TokenMetadataQueryResponse,
+};
+use concordium_rust_sdk::{
+    endpoints::QueryError,
+    smart_contracts::common::{Amount, OwnedParameter, OwnedReceiveName},
+    types::{
+        smart_contracts::{ContractContext, InvokeContractResult},
+        Address, ContractAddress, Energy, RejectReason,
+    },
+    v2::{self, IntoBlockIdentifier},
+};
+use concordium_std::{from_bytes, to_bytes, AccountAddress, Deserial, Serial};
+
+use dsid_alpha_v1_contracts::{
+    contract::{
+        account_profile::AccountProfileResponse,
+        balance_of_deduplicated::BalanceOfDeduplicatedResponse,
+        category_score_of::{CategoryScoreQueryParams, CategoryScoreQueryResponse},
+        credential_entry::CredentialEntryQueryResponse,
+        credential_status::CredentialStatusQueryResponse,
+        decimals_of::DecimalsOfQueryResponse,
+        expiring_between::{ExpiringBetweenParams, ExpiringBetweenResponse},
+        expiry_of::ExpiryOfQueryResponse,
+        export_balances::{ExportBalancesParams, ExportBalancesResponse},
+        fee_ledger::FeeLedgerResponse,
+        history_of::{HistoryOfParams, HistoryOfResponse},
+        holder_token_metadata::{HolderTokenMetadataParams, HolderTokenMetadataResponse},
+        inspect_balance::InspectBalanceResponse,
+        list_errors::ListErrorsResponse,
+        list_tokens::{ListTokensParams, ListTokensResponse},
+        mint::MintParams,
+        minter_stats::{MinterStatsParams, MinterStatsResponse},
+        nonce_of::{NonceOfQueryParams, NonceOfQueryResponse},
+        pending_revocation_of::PendingRevocationQueryResponse,
+        reminder_preference_of::{ReminderPreferenceQueryParams, ReminderPreferenceQueryResponse},
+        revocation_info::RevocationInfoQueryResponse,
+        revocation_keys::RevocationKeysResponse,
+        stats::ContractStats,
+        status_of::StatusOfQueryResponse,
+        supports_permit::SupportsPermitQueryParams,
+        token_info::TokenInfoQueryResponse,
+        token_metadata_localized::{TokenMetadataLocalizedParams, TokenMetadataLocalizedResponse},
+        tokens_by_category::TokensByCategoryResponse,
+        valid_from_of::ValidFromOfQueryResponse,
+        validate_mint::ValidateMintResponse,
+        verify::{VerifyParams, VerifyResponse},
+        verify_commitment::VerifyCommitmentParams,
+        verify_for::VerifyForParams,
+        view_config::ConfigView,
+        view_version::VersionInfo,
+    },
+    errors::CustomError,
+    types::{
+        ContractBalanceOfQueryParams, ContractBalanceOfQueryResponse, ContractError,
+        ContractExpiryOfQueryParams, ContractTokenMetadataQueryParams, CredentialQuery,
+        CredentialQueryParams, Snapshot, SnapshotId, TokenCategory,
+    },
+};
+
+/// Name of the contract as used in the receive names of its entrypoints.
+const CONTRACT_NAME: &str = "cis2_dsid";
+
+/// Maximum energy a single view invocation may use.
+const INVOKE_ENERGY: Energy = Energy { energy: 1_000_000 };
+
+/// Reject codes used by the CIS-2 errors of `Cis2Error`.
+const REJECT_INVALID_TOKEN_ID: i32 = -42000001;
+const REJECT_INSUFFICIENT_FUNDS: i32 = -42000002;
+const REJECT_UNAUTHORIZED: i32 = -42000003;
+
+#[derive(Debug)]
+pub enum DsidClientError {
+    /// The query to the node failed.
+    Query(QueryError),
+    /// The parameter exceeds the maximum parameter size.
+    ParameterTooLarge,
+    /// The contract rejected the invocation.
+    Contract(ContractError),
+    /// The invocation failed for a reason not raised by the contract logic.
+    Failed(RejectReason),
+    /// The contract returned a value which could not be decoded.
+    Decode,
+}
+
+impl From<QueryError> for DsidClientError {
+    fn from(qe: QueryError) -> Self {
+        Self::Query(qe)
+    }
+}
+
+/// Maps a reject code returned by the contract back to the `ContractError`.
+/// - Returns None if the code is not produced by this contract.
+pub fn contract_error_from_reject_code(code: i32) -> Option<ContractError> {
+    match code {
+        REJECT_INVALID_TOKEN_ID => Some(ContractError::InvalidTokenId),
+        REJECT_INSUFFICIENT_FUNDS => Some(ContractError::InsufficientFunds),
+        REJECT_UNAUTHORIZED => Some(ContractError::Unauthorized),
+        _ => CustomError::variants()
+            .into_iter()
+            .find(|e| e.reject_code() == code)
+            .map(ContractError::Custom),
+    }
+}
+
+/// Read-only client for a single instance of the DSID contract.
+#[derive(Clone, Debug)]
+pub struct DsidClient {
+    client: v2::Client,
+    address: ContractAddress,
+}
+
+impl DsidClient {
+    pub fn new(client: v2::Client, address: ContractAddress) -> Self {
+        Self { client, address }
+    }
+
+    /// Address of the contract instance this client queries.
+    pub fn address(&self) -> ContractAddress {
+        self.address
+    }
+
+    /// Invokes the `accountProfile` view.
+    pub async fn account_profile(
+        &mut self,
+        account: AccountAddress,
+        block: impl IntoBlockIdentifier,
+    ) -> Result<AccountProfileResponse, DsidClientError> {
+        self.view("accountProfile", &account, block).await
+    }
+
+    /// Invokes the `balanceOf` view.
+    pub async fn balance_of(
+        &mut self,
+        params: &ContractBalanceOfQueryParams,
+        block: impl IntoBlockIdentifier,
+    ) -> Result<ContractBalanceOfQueryResponse, DsidClientError> {
+        self.view("balanceOf", params, block).await
+    }
+
+    /// Invokes the `balanceOfDeduplicated` view.
+    pub async fn balance_of_deduplicated(
+        &mut self,
+        params: &ContractBalanceOfQueryParams,
+        block: impl IntoBlockIdentifier,
+    ) -> Result<BalanceOfDeduplicatedResponse, DsidClientError> {
+        self.view("balanceOfDeduplicated", params, block).await
+    }
+
+    /// Invokes the `balanceOfOrZero` view.
+    pub async fn balance_of_or_zero(
+        &mut self,
+        params: &ContractBalanceOfQueryParams,
+        block: impl IntoBlockIdentifier,
+    ) -> Result<ContractBalanceOfQueryResponse, DsidClientError> {
+        self.view("balanceOfOrZero", params, block).await
+    }
+
+    /// Invokes the `expiryOf` view.
+    pub async fn expiry_of(
+        &mut self,
+        params: &ContractExpiryOfQueryParams,
+        block: impl IntoBlockIdentifier,
+    ) -> Result<ExpiryOfQueryResponse, DsidClientError> {
+        self.view("expiryOf", params, block).await
+    }
+
+    /// Invokes the `expiringBetween` view.
+    pub async fn expiring_between(
+        &mut self,
+        params: &ExpiringBetweenParams,
+        block: impl IntoBlockIdentifier,
+    ) -> Result<ExpiringBetweenResponse, DsidClientError> {
+        self.view("expiringBetween", params, block).await
+    }
+
+    /// Invokes the `exportBalances` view.
+    pub async fn export_balances(
+        &mut self,
+        params: &ExportBalancesParams,
+        block: impl IntoBlockIdentifier,
+    ) -> Result<ExportBalancesResponse, DsidClientError> {
+        self.view("exportBalances", params, block).await
+    }
+
+    /// Invokes the `listTokens` view.
+    pub async fn list_tokens(
+        &mut self,
+        params: &ListTokensParams,
+        block: impl IntoBlockIdentifier,
+    ) -> Result<ListTokensResponse, DsidClientError> {
+        self.view("listTokens", params, block).await
+    }
+
+    /// Invokes the `verifyCommitment` view.
+    pub async fn verify_commitment(
+        &mut self,
+        params: &VerifyCommitmentParams,
+        block: impl IntoBlockIdentifier,
+    ) -> Result<bool, DsidClientError> {
+        self.view("verifyCommitment", params, block).await
+    }
+
+    /// Invokes the `historyOf` view.
+    pub async fn history_of(
+        &mut self,
+        params: &HistoryOfParams,
+        block: impl IntoBlockIdentifier,
+    ) -> Result<HistoryOfResponse, DsidClientError> {
+        self.view("historyOf", params, block).await
+    }
+
+    /// Invokes the `validFromOf` view.
+    pub async fn valid_from_of(
+        &mut self,
+        params: &ContractExpiryOfQueryParams,
+        block: impl IntoBlockIdentifier,
+    ) -> Result<ValidFromOfQueryResponse, DsidClientError> {
+        self.view("validFromOf", params, block).await
+    }
+
+    /// Invokes the `statusOf` view.
+    pub async fn status_of(
+        &mut self,
+        params: &ContractBalanceOfQueryParams,
+        block: impl IntoBlockIdentifier,
+    ) -> Result<StatusOfQueryResponse, DsidClientError> {
+        self.view("statusOf", params, block).await
+    }
+
+    /// Invokes the `tokenMetadata` view.
+    pub async fn token_metadata(
+        &mut self,
+        params: &ContractTokenMetadataQueryParams,
+        block: impl IntoBlockIdentifier,
+    ) -> Result<TokenMetadataQueryResponse, DsidClientError> {
+        self.view("tokenMetadata", params, block).await
+    }
+
+    /// Invokes the `tokenInfo` view.
+    pub async fn token_info(
+        &mut self,
+        params: &ContractTokenMetadataQueryParams,
+        block: impl IntoBlockIdentifier,
+    ) -> Result<TokenInfoQueryResponse, DsidClientError> {
+        self.view("tokenInfo", params, block).await
+    }
+
+    /// Invokes the `decimalsOf` view.
+    pub async fn decimals_of(
+        &mut self,
+        params: &ContractTokenMetadataQueryParams,
+        block: impl IntoBlockIdentifier,
+    ) -> Result<DecimalsOfQueryResponse, DsidClientError> {
+        self.view("decimalsOf", params, block).await
+    }
+
+    /// Invokes the `tokensByCategory` view.
+    pub async fn tokens_by_category(
+        &mut self,
+        category: TokenCategory,
+        block: impl IntoBlockIdentifier,
+    ) -> Result<TokensByCategoryResponse, DsidClientError> {
+        self.view("tokensByCategory", &category, block).await
+    }
+
+    /// Invokes the `categoryScoreOf` view.
+    pub async fn category_score_of(
+        &mut self,
+        params: &CategoryScoreQueryParams,
+        block: impl IntoBlockIdentifier,
+    ) -> Result<CategoryScoreQueryResponse, DsidClientError> {
+        self.view("categoryScoreOf", params, block).await
+    }
+
+    /// Invokes the `federatedScoreOf` view.
+    pub async fn federated_score_of(
+        &mut self,
+        params: &CategoryScoreQueryParams,
+        block: impl IntoBlockIdentifier,
+    ) -> Result<CategoryScoreQueryResponse, DsidClientError> {
+        self.view("federatedScoreOf", params, block).await
+    }
+
+    /// Invokes the `feeLedger` view.
+    pub async fn fee_ledger(
+        &mut self,
+        block: impl IntoBlockIdentifier,
+    ) -> Result<FeeLedgerResponse, DsidClientError> {
+        self.view("feeLedger", &(), block).await
+    }
+
+    /// Invokes the `viewSnapshot` view.
+    pub async fn view_snapshot(
+        &mut self,
+        snapshot_id: SnapshotId,
+        block: impl IntoBlockIdentifier,
+    ) -> Result<Snapshot, DsidClientError> {
+        self.view("viewSnapshot", &snapshot_id, block).await
+    }
+
+    /// Invokes the `verify` view.
+    pub async fn verify(
+        &mut self,
+        params: &VerifyParams,
+        block: impl IntoBlockIdentifier,
+    ) -> Result<VerifyResponse, DsidClientError> {
+        self.view("verify", params, block).await
+    }
+
+    /// Invokes the `verifyFor` view.
+    pub async fn verify_for(
+        &mut self,
+        params: &VerifyForParams,
+        block: impl IntoBlockIdentifier,
+    ) -> Result<bool, DsidClientError> {
+        self.view("verifyFor", params, block).await
+    }
+
+    /// Invokes the `credentialStatus` view.
+    pub async fn credential_status(
+        &mut self,
+        params: &CredentialQueryParams,
+        block: impl IntoBlockIdentifier,
+    ) -> Result<CredentialStatusQueryResponse, DsidClientError> {
+        self.view("credentialStatus", params, block).await
+    }
+
+    /// Invokes the `credentialEntry` view.
+    pub async fn credential_entry(
+        &mut self,
+        params: &CredentialQueryParams,
+        block: impl IntoBlockIdentifier,
+    ) -> Result<CredentialEntryQueryResponse, DsidClientError> {
+        self.view("credentialEntry", params, block).await
+    }
+
+    /// Invokes the `revocationInfo` view.
+    pub async fn revocation_info(
+        &mut self,
+        params: &CredentialQueryParams,
+        block: impl IntoBlockIdentifier,
+    ) -> Result<RevocationInfoQueryResponse, DsidClientError> {
+        self.view("revocationInfo", params, block).await
+    }
+
+    /// Invokes the `minterStats` view.
+    pub async fn minter_stats(
+        &mut self,
+        params: &MinterStatsParams,
+        block: impl IntoBlockIdentifier,
+    ) -> Result<MinterStatsResponse, DsidClientError> {
+        self.view("minterStats", params, block).await
+    }
+
+    /// Invokes the `pendingRevocationOf` view.
+    pub async fn pending_revocation_of(
+        &mut self,
+        params: &CredentialQueryParams,
+        block: impl IntoBlockIdentifier,
+    ) -> Result<PendingRevocationQueryResponse, DsidClientError> {
+        self.view("pendingRevocationOf", params, block).await
+    }
+
+    /// Invokes the `listErrors` view.
+    pub async fn list_errors(
+        &mut self,
+        block: impl IntoBlockIdentifier,
+    ) -> Result<ListErrorsResponse, DsidClientError> {
+        self.view("listErrors", &(), block).await
+    }
+
+    /// Invokes the `revocationKeys` view.
+    pub async fn revocation_keys(
+        &mut self,
+        block: impl IntoBlockIdentifier,
+    ) -> Result<RevocationKeysResponse, DsidClientError> {
+        self.view("revocationKeys", &(), block).await
+    }
+
+    /// Invokes the `tokenMetadataLocalized` view.
+    pub async fn token_metadata_localized(
+        &mut self,
+        params: &TokenMetadataLocalizedParams,
+        block: impl IntoBlockIdentifier,
+    ) -> Result<TokenMetadataLocalizedResponse, DsidClientError> {
+        self.view("tokenMetadataLocalized", params, block).await
+    }
+
+    /// Invokes the `holderTokenMetadata` view.
+    pub async fn holder_token_metadata(
+        &mut self,
+        params: &HolderTokenMetadataParams,
+        block: impl IntoBlockIdentifier,
+    ) -> Result<HolderTokenMetadataResponse, DsidClientError> {
+        self.view("holderTokenMetadata", params, block).await
+    }
+
+    /// Invokes the `nonceOf` view.
+    pub async fn nonce_of(
+        &mut self,
+        params: &NonceOfQueryParams,
+        block: impl IntoBlockIdentifier,
+    ) -> Result<NonceOfQueryResponse, DsidClientError> {
+        self.view("nonceOf", params, block).await
+    }
+
+    /// Invokes the `reminderPreferenceOf` view.
+    pub async fn reminder_preference_of(
+        &mut self,
+        params: &ReminderPreferenceQueryParams,
+        block: impl IntoBlockIdentifier,
+    ) -> Result<ReminderPreferenceQueryResponse, DsidClientError> {
+        self.view("reminderPreferenceOf", params, block).await
+    }
+
+    /// Invokes the `stats` view.
+    pub async fn stats(
+        &mut self,
+        block: impl IntoBlockIdentifier,
+    ) -> Result<ContractStats, DsidClientError> {
+        self.view("stats", &(), block).await
+    }
+
+    /// Invokes the `viewConfig` view.
+    pub async fn view_config(
+        &mut self,
+        block: impl IntoBlockIdentifier,
+    ) -> Result<ConfigView, DsidClientError> {
+        self.view("viewConfig", &(), block).await
+    }
+
+    /// Invokes the `viewVersion` view.
+    pub async fn view_version(
+        &mut self,
+        block: impl IntoBlockIdentifier,
+    ) -> Result<VersionInfo, DsidClientError> {
+        self.view("viewVersion", &(), block).await
+    }
+
+    /// Invokes the `operatorOf` view.
+    pub async fn operator_of(
+        &mut self,
+        params: &OperatorOfQueryParams,
+        block: impl IntoBlockIdentifier,
+    ) -> Result<OperatorOfQueryResponse, DsidClientError> {
+        self.view("operatorOf", params, block).await
+    }
+
+    /// Invokes the `supportsPermit` view.
+    pub async fn supports_permit(
+        &mut self,
+        params: &SupportsPermitQueryParams,
+        block: impl IntoBlockIdentifier,
+    ) -> Result<SupportsQueryResponse, DsidClientError> {
+        self.view("supportsPermit", params, block).await
+    }
+
+    /// Invokes the `validateMint` view as the given sender of the `mint` invocation.
+    pub async fn validate_mint(
+        &mut self,
+        params: &MintParams,
+        sender: Address,
+        block: impl IntoBlockIdentifier,
+    ) -> Result<ValidateMintResponse, DsidClientError> {
+        self.view_as("validateMint", params, Some(sender), block)
+            .await
+    }
+
+    /// Invokes the `inspectBalance` view as the given admin of the contract.
+    pub async fn inspect_balance(
+        &mut self,
+        params: &CredentialQuery,
+        admin: Address,
+        block: impl IntoBlockIdentifier,
+    ) -> Result<InspectBalanceResponse, DsidClientError> {
+        self.view_as("inspectBalance", params, Some(admin), block)
+            .await
+    }
+
+    /// Invokes an entrypoint of the contract and decodes its return value.
+    /// - Rejections raised by the contract are mapped to `ContractError`.
+    async fn view<P: Serial, R: Deserial>(
+        &mut self,
+        entrypoint: &str,
+        params: &P,
+        block: impl IntoBlockIdentifier,
+    ) -> Result<R, DsidClientError> {
+        self.view_as(entrypoint, params, None, block).await
+    }
+
+    /// Invokes an entrypoint of the contract as the given invoker and decodes its return value.
+    /// - Rejections raised by the contract are mapped to `ContractError`.
+    async fn view_as<P: Serial, R: Deserial>(
+        &mut self,
+        entrypoint: &str,
+        params: &P,
+        invoker: Option<Address>,
+        block: impl IntoBlockIdentifier,
+    ) -> Result<R, DsidClientError> {
+        let parameter = OwnedParameter::try_from(to_bytes(params))
+            .map_err(|_| DsidClientError::ParameterTooLarge)?;
+        let context = ContractContext {
+            invoker,
+            contract: self.address,
+            amount: Amount::zero(),
+            method: OwnedReceiveName::new_unchecked(format!("{}.{}", CONTRACT_NAME, entrypoint)),
+            parameter,
+            energy: INVOKE_ENERGY,
+        };
+
+        let result = self.client.invoke_instance(block, &context).await?.response;
+        match result {
+            InvokeContractResult::Success { return_value, .. } => {
+                let bytes = return_value.ok_or(DsidClientError::Decode)?.value;
+                from_bytes(&bytes).map_err(|_| DsidClientError::Decode)
+            }
+            InvokeContractResult::Failure { reason, .. } => {
+                if let RejectReason::RejectedReceive { reject_reason, .. } = &reason {
+                    if let Some(error) = contract_error_from_reject_code(*reject_reason) {
+                        return Err(DsidClientError::Contract(error));
+                    }
+                }
+                Err(DsidClientError::Failed(reason))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_contract_error_from_reject_code() {
+        assert_eq!(
+            contract_error_from_reject_code(-42000001),
+            Some(ContractError::InvalidTokenId)
+        );
+        assert_eq!(
+            contract_error_from_reject_code(-42000003),
+            Some(ContractError::Unauthorized)
+        );
+        // Reject codes are explicit and never change.
+        let code = CustomError::TokenHasValidBalances.reject_code();
+        assert_eq!(code, -6);
+        assert_eq!(
+            contract_error_from_reject_code(code),
+            Some(ContractError::Custom(CustomError::TokenHasValidBalances))
+        );
+        assert_eq!(contract_error_from_reject_code(1), None);
+    }
+}
@@ -0,0 +1,131 @@
+use concordium_cis2::MetadataUrl;
+use concordium_std::*;
+
+use crate::{
+    contract::balance_of::is_hidden_from_sender,
+    state::State,
+    types::{ContractError, ContractResult, ContractTokenAmount, ContractTokenId},
+};
+
+/// Balance of a token held by the account.
+#[derive(Debug, Serialize, SchemaType, PartialEq)]
+pub struct AccountProfileEntry {
+    pub token_id: ContractTokenId,
+    /// Stored amount, also for expired and frozen balances.
+    pub amount: ContractTokenAmount,
+    pub expiry: Timestamp,
+    pub valid_from: Option<Timestamp>,
+    /// Address with the exclusive right to mint and revoke the token.
+    pub issuer: Option<Address>,
+    pub frozen: bool,
+    /// Metadata url of the balance, the metadata url of the token if it has none.
+    pub metadata_url: MetadataUrl,
+}
+
+#[derive(Debug, Serialize, SchemaType, PartialEq)]
+pub struct AccountProfileResponse {
+    pub entries: Vec<AccountProfileEntry>,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "accountProfile",
+    parameter = "AccountAddress",
+    return_value = "AccountProfileResponse",
+    error = "ContractError"
+)]
+/// Gets every balance of an account with its expiry, issuer, freeze status and metadata url,
+/// replacing separate `balanceOf`, `expiryOf`, `tokenInfo` and `holderTokenMetadata` calls.
+/// - Balances hidden by the holder, see `setVisibility`, are left out unless the sender is the
+///   holder, the admin or a verifier.
+pub fn account_profile<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<AccountProfileResponse> {
+    let account: AccountAddress = ctx.parameter_cursor().get()?;
+    let state = host.state();
+    let mut entries = Vec::new();
+    for (token_id, balance) in state.get_account_balances(account) {
+        if is_hidden_from_sender(ctx, state, &token_id, account)? {
+            continue;
+        }
+        entries.push(AccountProfileEntry {
+            token_id,
+            amount: balance.amount,
+            expiry: balance.expiry,
+            valid_from: balance.valid_from,
+            issuer: state.get_token_issuer(&token_id)?,
+            frozen: balance.frozen,
+            metadata_url: state.get_holder_metadata(&token_id, account)?,
+        });
+    }
+
+    Ok(AccountProfileResponse { entries })
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+    const TOKEN_1: ContractTokenId = TokenIdU8(3);
+
+    #[concordium_test]
+    fn test_account_profile() {
+        let mut ctx = TestReceiveContext::empty();
+        let parameter = to_bytes(&ACCOUNT_1);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        for token_id in [TOKEN_0, TOKEN_1] {
+            state.add_token(
+                &mut state_builder,
+                token_id,
+                MetadataUrl {
+                    url: format!("https://example.com/{}", token_id.0),
+                    hash: None,
+                },
+            );
+        }
+        state
+            .set_token_issuer(TOKEN_0, Some(Address::Account(ACCOUNT_0)))
+            .expect("Failed to set issuer");
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_1,
+                2.into(),
+                Timestamp::from_timestamp_millis(100),
+                None,
+            )
+            .expect("Failed to mint token");
+        state
+            .set_balance_frozen(TOKEN_0, ACCOUNT_1, true)
+            .expect("Failed to freeze balance");
+        let host = TestHost::new(state, state_builder);
+
+        // Only the token held by the account is returned.
+        let result = account_profile(&ctx, &host);
+        assert_eq!(
+            result,
+            Ok(AccountProfileResponse {
+                entries: vec![AccountProfileEntry {
+                    token_id: TOKEN_0,
+                    amount: 2.into(),
+                    expiry: Timestamp::from_timestamp_millis(100),
+                    valid_from: None,
+                    issuer: Some(Address::Account(ACCOUNT_0)),
+                    frozen: true,
+                    metadata_url: MetadataUrl {
+                        url: "https://example.com/2".to_string(),
+                        hash: None,
+                    },
+                }],
+            })
+        );
+    }
+}
@@ -0,0 +1,154 @@
+use concordium_std::*;
+
+use crate::{
+    contract::{
+        mint::{ensure_unique_person, mint_balance, record_mint_volume, MintParam},
+        update_subscribers::notify_subscribers,
+    },
+    errors::CustomError,
+    state::State,
+    types::{ContractError, ContractResult, ContractTokenId},
+};
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct ActivateParams {
+    pub token_id: ContractTokenId,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "activate",
+    parameter = "ActivateParams",
+    error = "ContractError",
+    enable_logger,
+    mutable
+)]
+/// Activates the pending mint of a token for the sender, see `mintPending`, which mints the balance.
+/// Since only the holder can activate, activating proves control of the account.
+/// - This function fails if the sender is not an account.
+/// - This function fails if the token does not exist.
+/// - This function fails if the sender has no pending mint of the token.
+/// - This function fails if the activation deadline of the pending mint has passed.
+/// - This function fails if the token requires unique persons and the identity registry does not confirm the sender.
+/// - This function fails if the balance can not be minted, e.g. minting of the token is paused.
+pub fn activate<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    let account = match ctx.sender() {
+        Address::Account(account) => account,
+        Address::Contract(_) => bail!(ContractError::Custom(CustomError::AccountsOnly)),
+    };
+
+    let params: ActivateParams = ctx.parameter_cursor().get()?;
+    let now = ctx.metadata().slot_time();
+    ensure_unique_person(host, [&params.token_id], account)?;
+    let state = host.state_mut();
+    let pending_mint = state.take_pending_mint(params.token_id, account)?;
+    ensure!(
+        pending_mint
+            .deadline
+            .map_or(true, |deadline| now <= deadline),
+        ContractError::Custom(CustomError::ActivationExpired)
+    );
+    let minted = mint_balance(
+        state,
+        logger,
+        params.token_id,
+        account,
+        MintParam {
+            amount: pending_mint.amount,
+            expiry: Some(pending_mint.expiry),
+            valid_from: pending_mint.valid_from,
+            metadata_url: pending_mint.metadata_url,
+        },
+        pending_mint.issuer,
+        now,
+    )?;
+    record_mint_volume(state, logger, 1, u64::from(pending_mint.amount.0), now)?;
+
+    // Notify the subscribers about the new balance.
+    notify_subscribers(host, logger, minted.notifications)?;
+
+    Ok(())
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::types::PendingMint;
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+
+    fn activate_with(host: &mut TestHost<State<TestStateApi>>, now: u64) -> ContractResult<()> {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(ACCOUNT_1));
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(now));
+        let parameter = to_bytes(&ActivateParams { token_id: TOKEN_0 });
+        ctx.set_parameter(&parameter);
+        let mut logger = TestLogger::init();
+        activate(&ctx, host, &mut logger)
+    }
+
+    fn host_with_pending_mint() -> TestHost<State<TestStateApi>> {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        state
+            .add_pending_mint(
+                TOKEN_0,
+                ACCOUNT_1,
+                PendingMint {
+                    amount: 10.into(),
+                    expiry: Timestamp::from_timestamp_millis(100),
+                    valid_from: None,
+                    metadata_url: None,
+                    issuer: Address::Account(ACCOUNT_0),
+                    deadline: Some(Timestamp::from_timestamp_millis(50)),
+                },
+            )
+            .expect("Failed to add pending mint");
+        TestHost::new(state, state_builder)
+    }
+
+    #[concordium_test]
+    fn test_activate() {
+        let mut host = host_with_pending_mint();
+        assert_eq!(activate_with(&mut host, 20), Ok(()));
+        assert_eq!(
+            host.state().get_account_balance(
+                TOKEN_0,
+                ACCOUNT_1,
+                Timestamp::from_timestamp_millis(20)
+            ),
+            Ok(10.into())
+        );
+
+        // A pending mint can only be activated once.
+        assert_eq!(
+            activate_with(&mut host, 30),
+            Err(ContractError::Custom(CustomError::PendingMintNotFound))
+        );
+    }
+
+    #[concordium_test]
+    fn test_activate_after_deadline() {
+        let mut host = host_with_pending_mint();
+        assert_eq!(
+            activate_with(&mut host, 60),
+            Err(ContractError::Custom(CustomError::ActivationExpired))
+        );
+    }
+}
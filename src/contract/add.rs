@@ -1,65 +1,120 @@
-use concordium_cis2::{Cis2Event, MetadataUrl, TokenMetadataEvent};
+use concordium_cis2::{Cis2Event, TokenMetadataEvent};
 use concordium_std::*;
 
 use crate::{
+    errors::CustomError,
     state::State,
-    types::{ContractError, ContractResult, ContractTokenAmount, ContractTokenId},
+    types::{AddTokenParams, ContractError, ContractResult, ContractTokenAmount, ContractTokenId},
 };
 
-#[derive(SchemaType, Deserial, Serial)]
-pub struct AddTokenParams {
-    pub token_id: ContractTokenId,
-    pub metadata_url: MetadataUrl,
-}
-
 #[derive(SchemaType, Deserial, Serial)]
 pub struct AddParams {
     pub tokens: Vec<AddTokenParams>,
 }
 
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct AddResponse {
+    /// Ids of the added tokens, in the order of the parameter.
+    pub added: Vec<ContractTokenId>,
+}
+
 #[receive(
     contract = "cis2_dsid",
     name = "add",
     parameter = "AddParams",
+    return_value = "AddResponse",
     error = "ContractError",
     enable_logger,
     mutable
 )]
 /// Adds a token to the contract.
+/// Returns the ids of the added tokens.
 /// - This function fails if the token already exists.
-/// - This function fails if the sender is not the owner of the contract.
+/// - This function fails if the token id is allocated to another issuer or outside the range allocated to the issuer.
+/// - This function fails if metadata hashes are required and the metadata url has no hash.
+/// - This function fails if unique metadata urls are enforced and the metadata url is already used.
+/// - This function fails if the sender is not the admin of the contract.
+/// - This function fails if tokens have to be proposed and approved, see `proposeToken`.
 pub fn add<S: HasStateApi>(
     ctx: &impl HasReceiveContext,
     host: &mut impl HasHost<State<S>, StateApiType = S>,
     logger: &mut impl HasLogger,
-) -> ContractResult<()> {
-    // Check that the sender is the owner of the contract.
+) -> ContractResult<AddResponse> {
+    // Check that the sender is the admin of the contract.
     ensure!(
-        ctx.sender().matches_account(&ctx.owner()),
+        host.state().is_contract_admin(&ctx.sender(), ctx.owner()),
         ContractError::Unauthorized
     );
 
+    // Ensure that tokens may be added without the approval of a second admin.
+    ensure!(
+        !host.state().config().token_approval_required,
+        ContractError::Custom(CustomError::ProposalRequired)
+    );
+
     let params: AddParams = ctx.parameter_cursor().get()?;
     let (state, state_builder) = host.state_and_builder();
+    state.ensure_batch_size(|limits| limits.admin, params.tokens.len())?;
+    let mut added = Vec::with_capacity(params.tokens.len());
     for token in params.tokens {
-        let token_id = token.token_id;
-        let metadata_url = token.metadata_url;
+        added.push(token.token_id);
+        add_token(state, state_builder, logger, token)?;
+    }
 
-        // Ensure that the token does not already exist.
-        ensure!(!state.has_token(token_id), ContractError::InvalidTokenId);
+    Ok(AddResponse { added })
+}
 
-        // Add the token to the state.
-        state.add_token(state_builder, token_id, metadata_url.to_owned());
+/// Adds a single token to the state and logs its metadata.
+/// - This function does not check the sender.
+pub(crate) fn add_token<S: HasStateApi>(
+    state: &mut State<S>,
+    state_builder: &mut StateBuilder<S>,
+    logger: &mut impl HasLogger,
+    token: AddTokenParams,
+) -> ContractResult<()> {
+    let token_id = token.token_id;
+    let metadata_url = token.metadata_url;
 
-        // Log the token metadata.
-        logger.log(&Cis2Event::TokenMetadata::<_, ContractTokenAmount>(
-            TokenMetadataEvent {
-                token_id,
-                metadata_url,
-            },
-        ))?;
+    // Ensure that the token does not already exist.
+    ensure!(!state.has_token(token_id), ContractError::InvalidTokenId);
+
+    // Ensure that the token id is allocated to the issuer of the token.
+    state.check_id_range(&token_id, token.issuer)?;
+
+    // Ensure that the metadata url has a hash, if required.
+    ensure!(
+        !state.config().metadata_hash_required || metadata_url.hash.is_some(),
+        ContractError::Custom(CustomError::MetadataHashRequired)
+    );
+
+    // Ensure that the metadata url is not used by another token, if required.
+    ensure!(
+        !state.config().unique_metadata_urls || !state.has_metadata_url(&metadata_url),
+        ContractError::Custom(CustomError::DuplicateMetadataUrl)
+    );
+
+    // Add the token to the state.
+    state.add_token(state_builder, token_id, metadata_url.to_owned());
+    state.set_token_category(token_id, token.category)?;
+    state.set_token_issuer(token_id, token.issuer)?;
+    state.set_token_default_validity(token_id, token.default_validity)?;
+    state.set_token_renewal_policy(token_id, token.renewal_policy)?;
+    state.set_token_private(token_id, token.private)?;
+    state.set_token_unique_person(token_id, token.unique_person)?;
+    state.set_token_amount_unit(token_id, token.amount_unit)?;
+    state.set_token_amount_rule(token_id, token.amount_rule)?;
+    if state.config().new_tokens_paused {
+        state.set_token_paused(token_id, true)?;
     }
 
+    // Log the token metadata.
+    logger.log(&Cis2Event::TokenMetadata::<_, ContractTokenAmount>(
+        TokenMetadataEvent {
+            token_id,
+            metadata_url,
+        },
+    ))?;
+
     Ok(())
 }
 
@@ -67,6 +122,7 @@ pub fn add<S: HasStateApi>(
 mod tests {
     use super::*;
 
+    use crate::types::{AmountRule, AmountUnit, ContractConfig, RenewalPolicy};
     use concordium_cis2::*;
     use concordium_std::test_infrastructure::*;
 
@@ -86,6 +142,14 @@ mod tests {
                 url: "https://example.com".to_owned(),
                 hash: None,
             },
+            category: 0,
+            issuer: None,
+            default_validity: None,
+            renewal_policy: RenewalPolicy::IssuerOnly,
+            private: false,
+            unique_person: false,
+            amount_unit: AmountUnit::default(),
+            amount_rule: AmountRule::default(),
         };
         let add_token_param_1 = AddTokenParams {
             token_id: TOKEN_1,
@@ -93,6 +157,20 @@ mod tests {
                 url: "https://example.com/1".to_owned(),
                 hash: None,
             },
+            category: 1,
+            issuer: None,
+            default_validity: None,
+            renewal_policy: RenewalPolicy::IssuerOnly,
+            private: false,
+            unique_person: false,
+            amount_unit: AmountUnit {
+                decimals: 2,
+                unit: Some("points".to_string()),
+            },
+            amount_rule: AmountRule::Range {
+                min: 0.into(),
+                max: 1000.into(),
+            },
         };
         let add_param = AddParams {
             tokens: vec![add_token_param_0, add_token_param_1],
@@ -103,13 +181,33 @@ mod tests {
         let state = State::empty(&mut state_builder);
         let mut host = TestHost::new(state, state_builder);
         let mut logger = TestLogger::init();
-        let result: ContractResult<()> = add(&ctx, &mut host, &mut logger);
-        assert_eq!(result, Ok(()));
+        let result: ContractResult<AddResponse> = add(&ctx, &mut host, &mut logger);
+        assert_eq!(
+            result,
+            Ok(AddResponse {
+                added: vec![TOKEN_0, TOKEN_1],
+            })
+        );
 
         // Check that the token was added to the state.
         let state = host.state();
         assert!(state.has_token(TOKEN_0));
         assert!(state.has_token(TOKEN_1));
+        assert_eq!(state.get_token_category(&TOKEN_1), Ok(1));
+        assert_eq!(
+            state.get_token_amount_unit(&TOKEN_1),
+            Ok(AmountUnit {
+                decimals: 2,
+                unit: Some("points".to_string()),
+            })
+        );
+        assert_eq!(
+            state.get_token_amount_rule(&TOKEN_1),
+            Ok(AmountRule::Range {
+                min: 0.into(),
+                max: 1000.into(),
+            })
+        );
 
         // Check that state has token metadata.
         assert_eq!(
@@ -167,6 +265,14 @@ mod tests {
                 url: "https://example.com".to_owned(),
                 hash: None,
             },
+            category: 0,
+            issuer: None,
+            default_validity: None,
+            renewal_policy: RenewalPolicy::IssuerOnly,
+            private: false,
+            unique_person: false,
+            amount_unit: AmountUnit::default(),
+            amount_rule: AmountRule::default(),
         };
         let add_token_param_1 = AddTokenParams {
             token_id: TOKEN_0,
@@ -174,6 +280,14 @@ mod tests {
                 url: "https://example.com/1".to_owned(),
                 hash: None,
             },
+            category: 0,
+            issuer: None,
+            default_validity: None,
+            renewal_policy: RenewalPolicy::IssuerOnly,
+            private: false,
+            unique_person: false,
+            amount_unit: AmountUnit::default(),
+            amount_rule: AmountRule::default(),
         };
         let add_param = AddParams {
             tokens: vec![add_token_param_0, add_token_param_1],
@@ -192,7 +306,7 @@ mod tests {
         );
         let mut host = TestHost::new(state, state_builder);
         let mut logger = TestLogger::init();
-        let result: ContractResult<()> = add(&ctx, &mut host, &mut logger);
+        let result: ContractResult<AddResponse> = add(&ctx, &mut host, &mut logger);
         assert_eq!(result, Err(ContractError::InvalidTokenId));
     }
 
@@ -207,6 +321,14 @@ mod tests {
                 url: "https://example.com".to_owned(),
                 hash: None,
             },
+            category: 0,
+            issuer: None,
+            default_validity: None,
+            renewal_policy: RenewalPolicy::IssuerOnly,
+            private: false,
+            unique_person: false,
+            amount_unit: AmountUnit::default(),
+            amount_rule: AmountRule::default(),
         };
         let add_token_param_1 = AddTokenParams {
             token_id: TOKEN_1,
@@ -214,6 +336,14 @@ mod tests {
                 url: "https://example.com/1".to_owned(),
                 hash: None,
             },
+            category: 0,
+            issuer: None,
+            default_validity: None,
+            renewal_policy: RenewalPolicy::IssuerOnly,
+            private: false,
+            unique_person: false,
+            amount_unit: AmountUnit::default(),
+            amount_rule: AmountRule::default(),
         };
         let add_param = AddParams {
             tokens: vec![add_token_param_0, add_token_param_1],
@@ -224,7 +354,122 @@ mod tests {
         let state = State::empty(&mut state_builder);
         let mut host = TestHost::new(state, state_builder);
         let mut logger = TestLogger::init();
-        let result: ContractResult<()> = add(&ctx, &mut host, &mut logger);
+        let result: ContractResult<AddResponse> = add(&ctx, &mut host, &mut logger);
         assert_eq!(result, Err(ContractError::Unauthorized));
     }
+
+    #[concordium_test]
+    fn test_add_fails_if_metadata_url_is_not_unique() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        let add_param = AddParams {
+            tokens: vec![AddTokenParams {
+                token_id: TOKEN_1,
+                metadata_url: MetadataUrl {
+                    url: "https://example.com".to_owned(),
+                    hash: None,
+                },
+                category: 0,
+                issuer: None,
+                default_validity: None,
+                renewal_policy: RenewalPolicy::IssuerOnly,
+                private: false,
+                unique_person: false,
+                amount_unit: AmountUnit::default(),
+                amount_rule: AmountRule::default(),
+            }],
+        };
+        let parameter = to_bytes(&add_param);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.set_config(ContractConfig {
+            unique_metadata_urls: true,
+            ..Default::default()
+        });
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_owned(),
+                hash: None,
+            },
+        );
+        let mut host = TestHost::new(state, state_builder);
+        let mut logger = TestLogger::init();
+        let result: ContractResult<AddResponse> = add(&ctx, &mut host, &mut logger);
+        assert_eq!(
+            result,
+            Err(ContractError::Custom(CustomError::DuplicateMetadataUrl))
+        );
+
+        // The same url with a different hash is accepted.
+        let add_param = AddParams {
+            tokens: vec![AddTokenParams {
+                token_id: TOKEN_1,
+                metadata_url: MetadataUrl {
+                    url: "https://example.com".to_owned(),
+                    hash: Some([1; 32]),
+                },
+                category: 0,
+                issuer: None,
+                default_validity: None,
+                renewal_policy: RenewalPolicy::IssuerOnly,
+                private: false,
+                unique_person: false,
+                amount_unit: AmountUnit::default(),
+                amount_rule: AmountRule::default(),
+            }],
+        };
+        let parameter = to_bytes(&add_param);
+        ctx.set_parameter(&parameter);
+        let result: ContractResult<AddResponse> = add(&ctx, &mut host, &mut logger);
+        assert_eq!(
+            result,
+            Ok(AddResponse {
+                added: vec![TOKEN_1],
+            })
+        );
+    }
+
+    #[concordium_test]
+    fn test_add_fails_if_metadata_hash_is_missing() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        let add_param = AddParams {
+            tokens: vec![AddTokenParams {
+                token_id: TOKEN_0,
+                metadata_url: MetadataUrl {
+                    url: "https://example.com".to_owned(),
+                    hash: None,
+                },
+                category: 0,
+                issuer: None,
+                default_validity: None,
+                renewal_policy: RenewalPolicy::IssuerOnly,
+                private: false,
+                unique_person: false,
+                amount_unit: AmountUnit::default(),
+                amount_rule: AmountRule::default(),
+            }],
+        };
+        let parameter = to_bytes(&add_param);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.set_config(ContractConfig {
+            metadata_hash_required: true,
+            ..Default::default()
+        });
+        let mut host = TestHost::new(state, state_builder);
+        let mut logger = TestLogger::init();
+        let result: ContractResult<AddResponse> = add(&ctx, &mut host, &mut logger);
+        assert_eq!(
+            result,
+            Err(ContractError::Custom(CustomError::MetadataHashRequired))
+        );
+        assert!(!host.state().has_token(TOKEN_0));
+    }
 }
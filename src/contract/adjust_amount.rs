@@ -0,0 +1,224 @@
+use concordium_cis2::{Cis2Error, Cis2Event, MintEvent};
+use concordium_std::*;
+
+use crate::{
+    contract::{
+        revoke::log_burn,
+        update_subscribers::{notify_subscribers, DsidNotification},
+    },
+    errors::CustomError,
+    state::State,
+    types::{
+        BalanceChange, ContractError, ContractResult, ContractTokenAmount, ContractTokenId,
+        HistoryEntry, ADJUSTED_REASON,
+    },
+};
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct AdjustAmountParams {
+    pub token_id: ContractTokenId,
+    /// Owner of the corrected balance.
+    pub account: AccountAddress,
+    /// The corrected amount of the balance.
+    pub amount: ContractTokenAmount,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "adjustAmount",
+    parameter = "AdjustAmountParams",
+    error = "ContractError",
+    enable_logger,
+    mutable
+)]
+/// Corrects the amount of an existing balance without changing its expiry.
+/// Logs a Mint event for an increase and a Burn event for a decrease of the amount.
+/// - This function fails if the token has an issuer and the sender is not the issuer.
+/// - This function fails if the token has no issuer and the sender is not the admin of the contract.
+/// - This function fails if the token does not exist.
+/// - This function fails if the account does not have a balance of the token or the balance has expired.
+/// - This function fails if the amount is not allowed by the amount rule of the token.
+pub fn adjust_amount<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    let params: AdjustAmountParams = ctx.parameter_cursor().get()?;
+    let now = ctx.metadata().slot_time();
+    let is_owner = host.state().is_contract_admin(&ctx.sender(), ctx.owner());
+    let state = host.state_mut();
+    // Check that the sender is allowed to mint the token.
+    ensure!(
+        state.can_issue(&params.token_id, &ctx.sender(), is_owner)?,
+        ContractError::Unauthorized
+    );
+    let expiry = state
+        .get_account_balance_expiry(params.token_id, params.account)?
+        .ok_or(Cis2Error::Custom(CustomError::CredentialNotFound))?;
+    // Ensure the balance has not expired.
+    ensure!(expiry > now, Cis2Error::Custom(CustomError::TokenExpired));
+    ensure!(
+        state
+            .get_token_amount_rule(&params.token_id)?
+            .allows(params.amount),
+        Cis2Error::Custom(CustomError::AmountNotAllowed)
+    );
+
+    let previous = state.adjust_amount(params.token_id, params.account, params.amount)?;
+    if params.amount > previous {
+        // Log the added tokens.
+        logger.log(&Cis2Event::Mint::<_, ContractTokenAmount>(MintEvent {
+            token_id: params.token_id,
+            owner: Address::Account(params.account),
+            amount: ContractTokenAmount::from(params.amount.0 - previous.0),
+        }))?;
+    } else if params.amount < previous {
+        // Log the removed tokens.
+        let amount = ContractTokenAmount::from(previous.0 - params.amount.0);
+        state.record_burn(params.token_id, amount)?;
+        log_burn(
+            logger,
+            params.token_id,
+            params.account,
+            amount,
+            ADJUSTED_REASON,
+        )?;
+    }
+    state.record_history(
+        params.token_id,
+        params.account,
+        HistoryEntry {
+            timestamp: now,
+            change: BalanceChange::Adjust,
+            amount: params.amount,
+            expiry,
+            actor: ctx.sender(),
+        },
+    )?;
+
+    // Notify the subscribers about the corrected balance.
+    notify_subscribers(
+        host,
+        logger,
+        vec![DsidNotification {
+            token_id: params.token_id,
+            account: params.account,
+            amount: params.amount,
+            expiry,
+        }],
+    )?;
+
+    Ok(())
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::types::{ContractEvent, DsidBurnEvent};
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ADDRESS_0: Address = Address::Account(ACCOUNT_0);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+
+    #[concordium_test]
+    fn test_adjust_amount() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(10));
+        let params = AdjustAmountParams {
+            token_id: TOKEN_0,
+            account: ACCOUNT_0,
+            amount: 4.into(),
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_0,
+                10.into(),
+                Timestamp::from_timestamp_millis(100),
+                None,
+            )
+            .expect("Failed to mint token");
+        let mut host = TestHost::new(state, state_builder);
+        let mut logger = TestLogger::init();
+
+        let result = adjust_amount(&ctx, &mut host, &mut logger);
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            host.state().get_account_balance(
+                TOKEN_0,
+                ACCOUNT_0,
+                Timestamp::from_timestamp_millis(10)
+            ),
+            Ok(4.into())
+        );
+        // The expiry is kept.
+        assert_eq!(
+            host.state().get_account_balance_expiry(TOKEN_0, ACCOUNT_0),
+            Ok(Some(Timestamp::from_timestamp_millis(100)))
+        );
+        assert_eq!(
+            logger.logs,
+            vec![
+                to_bytes(&Cis2Event::Burn::<_, ContractTokenAmount>(BurnEvent {
+                    token_id: TOKEN_0,
+                    owner: ADDRESS_0,
+                    amount: 6.into(),
+                })),
+                to_bytes(&ContractEvent::DsidBurn(DsidBurnEvent {
+                    token_id: TOKEN_0,
+                    owner: ADDRESS_0,
+                    amount: 6.into(),
+                    reason: ADJUSTED_REASON,
+                })),
+            ]
+        );
+        assert_eq!(host.state().get_token_totals(&TOKEN_0), Ok((10, 6)));
+    }
+
+    #[concordium_test]
+    fn test_adjust_amount_no_balance() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        let params = AdjustAmountParams {
+            token_id: TOKEN_0,
+            account: ACCOUNT_0,
+            amount: 4.into(),
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        let mut host = TestHost::new(state, state_builder);
+        let mut logger = TestLogger::init();
+        let result = adjust_amount(&ctx, &mut host, &mut logger);
+        assert_eq!(
+            result,
+            Err(ContractError::Custom(CustomError::CredentialNotFound))
+        );
+    }
+}
@@ -0,0 +1,168 @@
+use concordium_std::*;
+
+use crate::{
+    errors::CustomError,
+    state::State,
+    types::{ContractError, ContractResult},
+};
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct ApproveActionParams {
+    /// SHA-256 hash of the entrypoint name followed by the parameter of the approved invocation.
+    pub action_hash: HashSha2256,
+    /// Time after which the approvals of the action can no longer be used.
+    /// Only the expiry of the first approval is used.
+    pub expiry: Timestamp,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "approveAction",
+    parameter = "ApproveActionParams",
+    error = "ContractError",
+    mutable
+)]
+/// Approves a destructive admin action, which can be executed by the owner once enough admins approved it.
+/// - This function fails if the sender is not an admin.
+/// - This function fails if the expiry is in the past.
+pub fn approve_action<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    let params: ApproveActionParams = ctx.parameter_cursor().get()?;
+    let state = host.state_mut();
+    // Check that the sender is an admin.
+    let admin = match ctx.sender() {
+        Address::Account(account) if state.is_admin(&account) => account,
+        _ => bail!(ContractError::Unauthorized),
+    };
+    ensure!(
+        params.expiry > ctx.metadata().slot_time(),
+        ContractError::Custom(CustomError::ActionExpired)
+    );
+
+    state.approve_action(params.action_hash, admin, params.expiry);
+    Ok(())
+}
+
+/// Ensures that the current invocation of an entrypoint has been approved by enough admins and uses the approvals.
+/// - This function does nothing if no approval threshold is set.
+pub(crate) fn ensure_approved<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    state: &mut State<S>,
+    crypto_primitives: &impl HasCryptoPrimitives,
+    entrypoint: &str,
+) -> ContractResult<()> {
+    if state.approval_threshold() == 0 {
+        return Ok(());
+    }
+
+    let action_hash = action_hash(ctx, crypto_primitives, entrypoint)?;
+    state.use_approvals(&action_hash, ctx.metadata().slot_time())
+}
+
+/// Computes the hash of the current invocation of an entrypoint.
+/// The hash is the SHA-256 hash of the entrypoint name followed by the parameter.
+pub(crate) fn action_hash(
+    ctx: &impl HasReceiveContext,
+    crypto_primitives: &impl HasCryptoPrimitives,
+    entrypoint: &str,
+) -> ContractResult<HashSha2256> {
+    let mut cursor = ctx.parameter_cursor();
+    let mut parameter = vec![0u8; cursor.size() as usize];
+    cursor.read_exact(&mut parameter)?;
+    let mut action = entrypoint.as_bytes().to_vec();
+    action.extend(parameter);
+    Ok(crypto_primitives.hash_sha2_256(&action))
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const ACCOUNT_2: AccountAddress = AccountAddress([2u8; 32]);
+    const ACTION_0: HashSha2256 = HashSha2256([1u8; 32]);
+
+    fn approve(
+        host: &mut TestHost<State<TestStateApi>>,
+        admin: AccountAddress,
+        expiry: u64,
+    ) -> ContractResult<()> {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(admin));
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(10));
+        let params = ApproveActionParams {
+            action_hash: ACTION_0,
+            expiry: Timestamp::from_timestamp_millis(expiry),
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        approve_action(&ctx, host)
+    }
+
+    #[concordium_test]
+    fn test_approve_action() {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state
+            .set_admins(vec![ACCOUNT_0, ACCOUNT_1, ACCOUNT_2], 2)
+            .expect("Failed to set admins");
+        let mut host = TestHost::new(state, state_builder);
+        let now = Timestamp::from_timestamp_millis(10);
+
+        // A single approval is not enough.
+        assert_eq!(approve(&mut host, ACCOUNT_0, 100), Ok(()));
+        assert_eq!(approve(&mut host, ACCOUNT_0, 100), Ok(()));
+        assert_eq!(
+            host.state_mut().use_approvals(&ACTION_0, now),
+            Err(ContractError::Custom(CustomError::ActionNotApproved))
+        );
+
+        assert_eq!(approve(&mut host, ACCOUNT_0, 100), Ok(()));
+        assert_eq!(approve(&mut host, ACCOUNT_1, 100), Ok(()));
+        assert_eq!(host.state_mut().use_approvals(&ACTION_0, now), Ok(()));
+        // The approvals can only be used once.
+        assert_eq!(
+            host.state_mut().use_approvals(&ACTION_0, now),
+            Err(ContractError::Custom(CustomError::ActionNotApproved))
+        );
+    }
+
+    #[concordium_test]
+    fn test_approve_action_expired() {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state
+            .set_admins(vec![ACCOUNT_0, ACCOUNT_1], 1)
+            .expect("Failed to set admins");
+        let mut host = TestHost::new(state, state_builder);
+
+        assert_eq!(
+            approve(&mut host, ACCOUNT_0, 10),
+            Err(ContractError::Custom(CustomError::ActionExpired))
+        );
+        assert_eq!(approve(&mut host, ACCOUNT_0, 20), Ok(()));
+        assert_eq!(
+            host.state_mut()
+                .use_approvals(&ACTION_0, Timestamp::from_timestamp_millis(20)),
+            Err(ContractError::Custom(CustomError::ActionExpired))
+        );
+    }
+
+    #[concordium_test]
+    fn test_approve_action_not_admin() {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state
+            .set_admins(vec![ACCOUNT_0], 1)
+            .expect("Failed to set admins");
+        let mut host = TestHost::new(state, state_builder);
+        assert_eq!(
+            approve(&mut host, ACCOUNT_1, 100),
+            Err(ContractError::Unauthorized)
+        );
+    }
+}
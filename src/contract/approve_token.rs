@@ -0,0 +1,193 @@
+use concordium_std::*;
+
+use crate::{
+    contract::add::add_token,
+    errors::CustomError,
+    state::State,
+    types::{ContractError, ContractResult, ContractTokenId},
+};
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct ApproveTokenParams {
+    pub token_id: ContractTokenId,
+    /// False rejects the proposal without adding the token.
+    pub approve: bool,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "approveToken",
+    parameter = "ApproveTokenParams",
+    error = "ContractError",
+    enable_logger,
+    mutable
+)]
+/// Approves and adds, or rejects, a token proposed using `proposeToken`.
+/// The added token logs its metadata like `add`.
+/// - This function fails if the sender is neither the admin of the contract nor an admin.
+/// - This function fails if the sender proposed the token.
+/// - This function fails if the token has not been proposed.
+/// - This function fails if the token can not be added, see `add`.
+pub fn approve_token<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    let params: ApproveTokenParams = ctx.parameter_cursor().get()?;
+    let (state, state_builder) = host.state_and_builder();
+    // Check that the sender is an admin.
+    let approver = match ctx.sender() {
+        Address::Account(account)
+            if state.is_contract_admin(&ctx.sender(), ctx.owner()) || state.is_admin(&account) =>
+        {
+            account
+        }
+        _ => bail!(ContractError::Unauthorized),
+    };
+
+    let proposal = state.take_token_proposal(&params.token_id)?;
+    // Ensure the token is approved by a second admin.
+    ensure!(proposal.proposer != approver, ContractError::Unauthorized);
+    if params.approve {
+        add_token(state, state_builder, logger, proposal.token)?;
+    }
+
+    Ok(())
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::{
+        contract::{
+            add::{add, AddParams},
+            propose_token::propose_token,
+        },
+        types::{
+            AddTokenParams, AmountRule, AmountUnit, ContractConfig, ContractTokenAmount,
+            RenewalPolicy,
+        },
+    };
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+
+    fn token_params() -> AddTokenParams {
+        AddTokenParams {
+            token_id: TOKEN_0,
+            metadata_url: MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+            category: 0,
+            issuer: None,
+            default_validity: None,
+            renewal_policy: RenewalPolicy::IssuerOnly,
+            private: false,
+            unique_person: false,
+            amount_unit: AmountUnit::default(),
+            amount_rule: AmountRule::default(),
+        }
+    }
+
+    fn approve_token_with(
+        host: &mut TestHost<State<TestStateApi>>,
+        logger: &mut TestLogger,
+        sender: AccountAddress,
+        approve: bool,
+    ) -> ContractResult<()> {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(sender));
+        ctx.set_owner(ACCOUNT_0);
+        let parameter = to_bytes(&ApproveTokenParams {
+            token_id: TOKEN_0,
+            approve,
+        });
+        ctx.set_parameter(&parameter);
+        approve_token(&ctx, host, logger)
+    }
+
+    fn host_with_proposal() -> TestHost<State<TestStateApi>> {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.set_config(ContractConfig {
+            token_approval_required: true,
+            ..Default::default()
+        });
+        state
+            .set_admins(vec![ACCOUNT_0, ACCOUNT_1], 0)
+            .expect("Failed to set admins");
+        let mut host = TestHost::new(state, state_builder);
+
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(ACCOUNT_0));
+        ctx.set_owner(ACCOUNT_0);
+        let parameter = to_bytes(&token_params());
+        ctx.set_parameter(&parameter);
+        assert_eq!(propose_token(&ctx, &mut host), Ok(()));
+        host
+    }
+
+    #[concordium_test]
+    fn test_approve_token() {
+        let mut host = host_with_proposal();
+        let mut logger = TestLogger::init();
+
+        // The proposer can not approve their own proposal.
+        assert_eq!(
+            approve_token_with(&mut host, &mut logger, ACCOUNT_0, true),
+            Err(ContractError::Unauthorized)
+        );
+        assert_eq!(
+            approve_token_with(&mut host, &mut logger, ACCOUNT_1, true),
+            Ok(())
+        );
+        assert!(host.state().has_token(TOKEN_0));
+        assert_eq!(
+            logger.logs,
+            vec![to_bytes(
+                &Cis2Event::TokenMetadata::<_, ContractTokenAmount>(TokenMetadataEvent {
+                    token_id: TOKEN_0,
+                    metadata_url: token_params().metadata_url,
+                })
+            )]
+        );
+        // The proposal is used up.
+        assert_eq!(
+            approve_token_with(&mut host, &mut logger, ACCOUNT_1, true),
+            Err(ContractError::Custom(CustomError::ProposalNotFound))
+        );
+    }
+
+    #[concordium_test]
+    fn test_approve_token_reject() {
+        let mut host = host_with_proposal();
+        let mut logger = TestLogger::init();
+        assert_eq!(
+            approve_token_with(&mut host, &mut logger, ACCOUNT_1, false),
+            Ok(())
+        );
+        assert!(!host.state().has_token(TOKEN_0));
+        assert!(logger.logs.is_empty());
+    }
+
+    #[concordium_test]
+    fn test_add_requires_proposal() {
+        let mut host = host_with_proposal();
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(ACCOUNT_0));
+        ctx.set_owner(ACCOUNT_0);
+        let parameter = to_bytes(&AddParams {
+            tokens: vec![token_params()],
+        });
+        ctx.set_parameter(&parameter);
+        let result = add(&ctx, &mut host, &mut TestLogger::init());
+        assert_eq!(
+            result.map(|_| ()),
+            Err(ContractError::Custom(CustomError::ProposalRequired))
+        );
+    }
+}
@@ -0,0 +1,109 @@
+use concordium_std::*;
+
+use crate::{
+    errors::CustomError,
+    state::State,
+    types::{ContractError, ContractResult, ContractTokenId},
+};
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct AuthorizeIssuanceParams {
+    pub token_id: ContractTokenId,
+    /// Address which may mint the token to the sender.
+    pub issuer: Address,
+    /// True to give consent, false to withdraw it.
+    pub authorized: bool,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "authorizeIssuance",
+    parameter = "AuthorizeIssuanceParams",
+    error = "ContractError",
+    mutable
+)]
+/// Gives or withdraws the consent of the sender to be issued a token by an issuer.
+/// Consent is only checked for tokens which require it.
+/// - This function fails if the sender is not an account.
+/// - This function fails if the token does not exist.
+pub fn authorize_issuance<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    let holder = match ctx.sender() {
+        Address::Account(account) => account,
+        Address::Contract(_) => bail!(ContractError::Custom(CustomError::AccountsOnly)),
+    };
+
+    let params: AuthorizeIssuanceParams = ctx.parameter_cursor().get()?;
+    host.state_mut()
+        .set_issuance_consent(params.token_id, holder, params.issuer, params.authorized)
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const ADDRESS_0: Address = Address::Account(ACCOUNT_0);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+
+    #[concordium_test]
+    fn test_authorize_issuance() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(ACCOUNT_1));
+        let params = AuthorizeIssuanceParams {
+            token_id: TOKEN_0,
+            issuer: ADDRESS_0,
+            authorized: true,
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        state
+            .set_token_consent_required(TOKEN_0, true)
+            .expect("Failed to require consent");
+        let mut host = TestHost::new(state, state_builder);
+        assert_eq!(
+            host.state()
+                .has_issuance_consent(&TOKEN_0, ACCOUNT_1, ADDRESS_0),
+            Ok(false)
+        );
+
+        let result: ContractResult<()> = authorize_issuance(&ctx, &mut host);
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            host.state()
+                .has_issuance_consent(&TOKEN_0, ACCOUNT_1, ADDRESS_0),
+            Ok(true)
+        );
+
+        // Withdraw the consent.
+        let params = AuthorizeIssuanceParams {
+            token_id: TOKEN_0,
+            issuer: ADDRESS_0,
+            authorized: false,
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let result: ContractResult<()> = authorize_issuance(&ctx, &mut host);
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            host.state()
+                .has_issuance_consent(&TOKEN_0, ACCOUNT_1, ADDRESS_0),
+            Ok(false)
+        );
+    }
+}
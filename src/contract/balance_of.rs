@@ -9,6 +9,12 @@ use crate::{errors::CustomError, state::State, types::*};
     return_value = "ContractBalanceOfQueryResponse",
     error = "ContractError"
 )]
+/// Gets the balance for each of the queried tokens and accounts.
+/// The balances are returned in the order of the queries, one for each query including repeated ones.
+/// - Balances hidden by their holder, see `setVisibility`, are 0 unless the sender is the holder,
+///   the admin or a verifier.
+/// - This function fails if any of the tokens does not exist.
+/// - This function fails if any of the addresses is a contract.
 pub fn balance_of<S: HasStateApi>(
     ctx: &impl HasReceiveContext,
     host: &impl HasHost<State<S>, StateApiType = S>,
@@ -16,13 +22,13 @@ pub fn balance_of<S: HasStateApi>(
     // Parse the parameter.
     let params: ContractBalanceOfQueryParams = ctx.parameter_cursor().get()?;
     let state = host.state();
+    state.ensure_batch_size(|limits| limits.query, params.queries.len())?;
+    let now = ctx.metadata().slot_time();
     let response: Vec<ContractTokenAmount> = params
         .queries
         .iter()
         .map(|q| match q.address {
-            Address::Account(address) => {
-                state.get_account_balance(q.token_id, address, ctx.metadata().slot_time())
-            }
+            Address::Account(address) => visible_balance(ctx, state, q.token_id, address, now),
             Address::Contract(_) => Err(ContractError::Custom(CustomError::AccountsOnly)),
         })
         .collect::<Result<Vec<ContractTokenAmount>, ContractError>>()?;
@@ -31,6 +37,35 @@ pub fn balance_of<S: HasStateApi>(
     Ok(result)
 }
 
+/// Gets the balance of an account as seen by the sender, 0 if the holder hides it from the sender.
+/// - This function fails if the token does not exist.
+pub(crate) fn visible_balance<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    state: &State<S>,
+    token_id: ContractTokenId,
+    account: AccountAddress,
+    now: Timestamp,
+) -> ContractResult<ContractTokenAmount> {
+    let balance = state.get_account_balance(token_id, account, now)?;
+    if is_hidden_from_sender(ctx, state, &token_id, account)? {
+        return Ok(ContractTokenAmount::from(0));
+    }
+    Ok(balance)
+}
+
+/// Checks if the holder hides their balance of a token from the sender, see `setVisibility`.
+/// Only the holder, the admin and verifiers can read hidden balances.
+/// - This function fails if the token does not exist.
+pub(crate) fn is_hidden_from_sender<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    state: &State<S>,
+    token_id: &ContractTokenId,
+    account: AccountAddress,
+) -> ContractResult<bool> {
+    Ok(state.is_balance_hidden(token_id, account)?
+        && !state.can_read_hidden_balance(&ctx.sender(), account, ctx.owner()))
+}
+
 #[concordium_cfg_test]
 mod tests {
     use super::*;
@@ -96,6 +131,7 @@ mod tests {
                 ACCOUNT_0,
                 1.into(),
                 Timestamp::from_timestamp_millis(100),
+                None,
             )
             .expect("Failed to mint token");
         state
@@ -104,6 +140,7 @@ mod tests {
                 ACCOUNT_0,
                 1.into(),
                 Timestamp::from_timestamp_millis(200),
+                None,
             )
             .expect("Failed to mint token");
         state
@@ -112,6 +149,7 @@ mod tests {
                 ACCOUNT_1,
                 1.into(),
                 Timestamp::from_timestamp_millis(250),
+                None,
             )
             .expect("Failed to mint token");
         state
@@ -120,6 +158,7 @@ mod tests {
                 ACCOUNT_1,
                 1.into(),
                 Timestamp::from_timestamp_millis(300),
+                None,
             )
             .expect("Failed to mint token");
 
@@ -138,4 +177,45 @@ mod tests {
         claim_eq!(result.0[1], 1.into());
         claim_eq!(result.0[1], 1.into());
     }
+
+    #[concordium_test]
+    fn test_balance_of_alias() {
+        let mut alias = ACCOUNT_1.0;
+        alias[31] = 7;
+        let alias = AccountAddress(alias);
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(150));
+        let params = ContractBalanceOfQueryParams {
+            queries: vec![BalanceOfQuery {
+                address: concordium_std::Address::Account(alias),
+                token_id: TOKEN_0,
+            }],
+        };
+        let parameter = &to_bytes(&params);
+        ctx.set_parameter(parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: String::new(),
+                hash: None,
+            },
+        );
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_1,
+                1.into(),
+                Timestamp::from_timestamp_millis(250),
+                None,
+            )
+            .expect("Failed to mint token");
+
+        // The balance minted to the account is found using its alias.
+        let host = TestHost::new(state, state_builder);
+        let result = balance_of(&ctx, &host).unwrap();
+        claim_eq!(result.0[0], 1.into());
+    }
 }
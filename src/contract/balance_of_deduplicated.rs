@@ -0,0 +1,124 @@
+use concordium_std::*;
+
+use crate::{
+    contract::balance_of::visible_balance,
+    errors::CustomError,
+    state::{canonical_account, State},
+    types::*,
+};
+
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct BalanceOfDeduplicatedResponse {
+    /// Balance of each distinct query, in the order of their first occurrence.
+    pub balances: Vec<ContractTokenAmount>,
+    /// For each query, in the order of the queries, the index of its balance in `balances`.
+    pub indices: Vec<u16>,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "balanceOfDeduplicated",
+    parameter = "ContractBalanceOfQueryParams",
+    return_value = "BalanceOfDeduplicatedResponse",
+    error = "ContractError"
+)]
+/// Gets the balance for each of the queried tokens and accounts, like `balanceOf`,
+/// but looks up and returns the balance of repeated queries only once.
+/// Queries of different aliases of the same account are repeated queries.
+/// - This function fails if any of the tokens does not exist.
+/// - This function fails if any of the addresses is a contract.
+pub fn balance_of_deduplicated<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<BalanceOfDeduplicatedResponse> {
+    // Parse the parameter.
+    let params: ContractBalanceOfQueryParams = ctx.parameter_cursor().get()?;
+    let state = host.state();
+    state.ensure_batch_size(|limits| limits.query, params.queries.len())?;
+    let now = ctx.metadata().slot_time();
+
+    let mut queried: Vec<(ContractTokenId, AccountAddress)> = Vec::new();
+    let mut balances = Vec::new();
+    let mut indices = Vec::with_capacity(params.queries.len());
+    for q in params.queries {
+        let account = match q.address {
+            Address::Account(address) => canonical_account(address),
+            Address::Contract(_) => bail!(ContractError::Custom(CustomError::AccountsOnly)),
+        };
+        let index = match queried
+            .iter()
+            .position(|query| *query == (q.token_id, account))
+        {
+            Some(index) => index,
+            None => {
+                balances.push(visible_balance(ctx, state, q.token_id, account, now)?);
+                queried.push((q.token_id, account));
+                queried.len() - 1
+            }
+        };
+        // The number of queries is serialized as a u16, so the index fits.
+        indices.push(index as u16);
+    }
+
+    Ok(BalanceOfDeduplicatedResponse { balances, indices })
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+
+    #[concordium_test]
+    fn test_balance_of_deduplicated() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(10));
+        let query = |account| BalanceOfQuery {
+            address: Address::Account(account),
+            token_id: TOKEN_0,
+        };
+        let params = ContractBalanceOfQueryParams {
+            queries: vec![
+                query(ACCOUNT_1),
+                query(ACCOUNT_0),
+                query(ACCOUNT_1),
+                query(canonical_account(ACCOUNT_1)),
+            ],
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_1,
+                2.into(),
+                Timestamp::from_timestamp_millis(100),
+                None,
+            )
+            .expect("Failed to mint token");
+        let host = TestHost::new(state, state_builder);
+
+        let result = balance_of_deduplicated(&ctx, &host);
+        assert_eq!(
+            result,
+            Ok(BalanceOfDeduplicatedResponse {
+                balances: vec![2.into(), 0.into()],
+                indices: vec![0, 1, 0, 0],
+            })
+        );
+    }
+}
@@ -0,0 +1,97 @@
+use concordium_std::*;
+
+use crate::{contract::balance_of::visible_balance, errors::CustomError, state::State, types::*};
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "balanceOfOrZero",
+    parameter = "ContractBalanceOfQueryParams",
+    return_value = "ContractBalanceOfQueryResponse",
+    error = "ContractError"
+)]
+/// Gets the balance for each of the queried tokens and accounts, like `balanceOf`.
+/// Unknown tokens have a balance of 0 instead of failing the whole query.
+/// - Balances hidden by their holder are 0, see `balanceOf`.
+/// - This function fails if any of the addresses is a contract.
+pub fn balance_of_or_zero<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<ContractBalanceOfQueryResponse> {
+    // Parse the parameter.
+    let params: ContractBalanceOfQueryParams = ctx.parameter_cursor().get()?;
+    let state = host.state();
+    state.ensure_batch_size(|limits| limits.query, params.queries.len())?;
+    let now = ctx.metadata().slot_time();
+    let response: Vec<ContractTokenAmount> = params
+        .queries
+        .iter()
+        .map(|q| match q.address {
+            Address::Account(address) => {
+                match visible_balance(ctx, state, q.token_id, address, now) {
+                    Err(ContractError::InvalidTokenId) => Ok(ContractTokenAmount::from(0)),
+                    result => result,
+                }
+            }
+            Address::Contract(_) => Err(ContractError::Custom(CustomError::AccountsOnly)),
+        })
+        .collect::<Result<Vec<ContractTokenAmount>, ContractError>>()?;
+
+    let result = ContractBalanceOfQueryResponse::from(response);
+    Ok(result)
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+    const TOKEN_1: ContractTokenId = TokenIdU8(3);
+
+    #[concordium_test]
+    fn test_balance_of_or_zero() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(10));
+        let params = ContractBalanceOfQueryParams {
+            queries: vec![
+                BalanceOfQuery {
+                    address: Address::Account(ACCOUNT_0),
+                    token_id: TOKEN_0,
+                },
+                BalanceOfQuery {
+                    address: Address::Account(ACCOUNT_0),
+                    token_id: TOKEN_1,
+                },
+            ],
+        };
+        let parameter = &to_bytes(&params);
+        ctx.set_parameter(parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: String::new(),
+                hash: None,
+            },
+        );
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_0,
+                5.into(),
+                Timestamp::from_timestamp_millis(100),
+                None,
+            )
+            .expect("Failed to mint token");
+        let host = TestHost::new(state, state_builder);
+
+        // The unknown token does not fail the query.
+        let result = balance_of_or_zero(&ctx, &host).unwrap();
+        claim_eq!(result.0[0], 5.into());
+        claim_eq!(result.0[1], 0.into());
+    }
+}
@@ -0,0 +1,422 @@
+use concordium_std::*;
+
+use crate::{
+    contract::{
+        add::add_token,
+        approve_action::ensure_approved,
+        create_bundle::CreateBundleParams,
+        remove::remove_token,
+        schedule_action::ensure_scheduled,
+        set_allowlist_required::SetAllowlistRequiredParams,
+        set_consent_required::SetConsentRequiredParams,
+        set_dependency::SetDependencyParams,
+        set_exclusive_group::SetExclusiveGroupParams,
+        set_id_range::SetIdRangeParams,
+        set_localized_metadata::SetLocalizedMetadataParams,
+        set_max_lifetime::SetMaxLifetimeParams,
+        set_min_amount::SetMinAmountParams,
+        set_monotonic_expiry::SetMonotonicExpiryParams,
+        set_schema_ref::{set_token_schema_ref, SetSchemaRefParams},
+        update_issuer_contracts::{update_issuer_contract, UpdateIssuerContractParams},
+        update_minters::{update_minter, MinterUpdate, UpdateMinterParams},
+        update_revocation_keys::{update_revocation_key, UpdateRevocationKeyParams},
+        update_roles::{update_role, UpdateRoleParams},
+        update_subscribers::{update_subscriber, UpdateSubscriberParams},
+    },
+    errors::CustomError,
+    state::State,
+    types::{
+        AddTokenParams, ContractConfig, ContractError, ContractResult, ContractTokenId,
+        TokenCategory,
+    },
+};
+
+#[derive(Serial, Deserial, SchemaType)]
+pub enum AdminOperation {
+    /// Adds a token, same as the `add` entrypoint.
+    AddToken(AddTokenParams),
+    /// Removes a token, same as the `remove` entrypoint.
+    RemoveToken(ContractTokenId),
+    /// Replaces the contract configuration, same as the `setConfig` entrypoint.
+    SetConfig(ContractConfig),
+    /// Adds or removes a subscriber, same as the `updateSubscribers` entrypoint.
+    UpdateSubscriber(UpdateSubscriberParams),
+    /// Adds or removes a revocation key, same as the `updateRevocationKeys` entrypoint.
+    UpdateRevocationKey(UpdateRevocationKeyParams),
+    /// Pauses minting of a token, same as the `pauseToken` entrypoint.
+    PauseToken(ContractTokenId),
+    /// Resumes minting of a token, same as the `unpauseToken` entrypoint.
+    UnpauseToken(ContractTokenId),
+    /// Deprecates a token, same as the `deprecate` entrypoint.
+    Deprecate(ContractTokenId),
+    /// Sets whether minting a token requires consent, same as the `setConsentRequired` entrypoint.
+    SetConsentRequired(SetConsentRequiredParams),
+    /// Sets or removes the metadata url of a token for a locale, same as the `setLocalizedMetadata` entrypoint.
+    SetLocalizedMetadata(SetLocalizedMetadataParams),
+    /// Allocates a range of token ids to an issuer, same as the `setIdRange` entrypoint.
+    SetIdRange(SetIdRangeParams),
+    /// Grants or revokes the minter role, same as the `updateMinters` entrypoint.
+    UpdateMinter(UpdateMinterParams),
+    /// Resumes minting paused by the mint volume limit, same as the `resumeMinting` entrypoint.
+    ResumeMinting,
+    /// Sets whether minting a token requires the allowlist, same as the `setAllowlistRequired` entrypoint.
+    SetAllowlistRequired(SetAllowlistRequiredParams),
+    /// Sets the smallest amount of a token which can be minted, same as the `setMinAmount` entrypoint.
+    SetMinAmount(SetMinAmountParams),
+    /// Sets the metadata schema checksum of a token, same as the `setSchemaRef` entrypoint.
+    SetSchemaRef(SetSchemaRefParams),
+    /// Grants or revokes a pauser, freezer, fee manager or verifier role, same as the `updateRoles` entrypoint.
+    UpdateRole(UpdateRoleParams),
+    /// Creates, replaces or removes a bundle of tokens, same as the `createBundle` entrypoint.
+    CreateBundle(CreateBundleParams),
+    /// Sets or removes the token a token depends on, same as the `setDependency` entrypoint.
+    SetDependency(SetDependencyParams),
+    /// Adds or removes an issuer contract, same as the `updateIssuerContracts` entrypoint.
+    UpdateIssuerContract(UpdateIssuerContractParams),
+    /// Sets or removes the exclusive group of a token, same as the `setExclusiveGroup` entrypoint.
+    SetExclusiveGroup(SetExclusiveGroupParams),
+    /// Sets whether the expiries of a token may only be extended, same as the `setMonotonicExpiry` entrypoint.
+    SetMonotonicExpiry(SetMonotonicExpiryParams),
+    /// Sets or removes the maximum lifetime of the balances of a token, same as the `setMaxLifetime` entrypoint.
+    SetMaxLifetime(SetMaxLifetimeParams),
+    /// Pauses minting of every token of a category, same as the `pauseCategory` entrypoint.
+    PauseCategory(TokenCategory),
+    /// Resumes minting of every token of a category, same as the `unpauseCategory` entrypoint.
+    UnpauseCategory(TokenCategory),
+}
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct BatchAdminParams {
+    /// Operations which are executed in order.
+    pub operations: Vec<AdminOperation>,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "batchAdmin",
+    parameter = "BatchAdminParams",
+    error = "ContractError",
+    enable_logger,
+    crypto_primitives,
+    mutable
+)]
+/// Executes a list of admin operations in order.
+/// - This function fails if any of the operations fails, in which case none of them are applied.
+/// - This function fails if the sender is not the admin of the contract.
+/// - This function fails if tokens have to be proposed and approved and the operations add a token.
+/// - This function fails if approvals are required, the operations remove a token and the invocation has not been approved by enough admins.
+/// - This function fails if a timelock delay is set, the operations replace the configuration or revoke a minter role and the invocation has not been scheduled or the delay has not elapsed.
+pub fn batch_admin<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    crypto_primitives: &impl HasCryptoPrimitives,
+) -> ContractResult<()> {
+    // Check that the sender is the admin of the contract.
+    ensure!(
+        host.state().is_contract_admin(&ctx.sender(), ctx.owner()),
+        ContractError::Unauthorized
+    );
+
+    let params: BatchAdminParams = ctx.parameter_cursor().get()?;
+    let now = ctx.metadata().slot_time();
+    let (state, state_builder) = host.state_and_builder();
+    state.ensure_batch_size(|limits| limits.admin, params.operations.len())?;
+    // Removing tokens requires the same approvals as the `remove` entrypoint.
+    if params
+        .operations
+        .iter()
+        .any(|operation| matches!(operation, AdminOperation::RemoveToken(_)))
+    {
+        ensure_approved(ctx, state, crypto_primitives, "batchAdmin")?;
+    }
+    // Replacing the configuration and revoking minter roles require the same timelock as the
+    // `setConfig` and `updateMinters` entrypoints.
+    if params.operations.iter().any(|operation| {
+        matches!(
+            operation,
+            AdminOperation::SetConfig(_)
+                | AdminOperation::UpdateMinter(UpdateMinterParams {
+                    update: MinterUpdate::Remove,
+                    ..
+                })
+        )
+    }) {
+        ensure_scheduled(ctx, state, crypto_primitives, "batchAdmin")?;
+    }
+    for operation in params.operations {
+        match operation {
+            AdminOperation::AddToken(token) => {
+                // Tokens which need a second approval can not be added by a batch either.
+                ensure!(
+                    !state.config().token_approval_required,
+                    ContractError::Custom(CustomError::ProposalRequired)
+                );
+                add_token(state, state_builder, logger, token)?
+            }
+            AdminOperation::RemoveToken(token_id) => remove_token(state, logger, token_id, now)?,
+            AdminOperation::SetConfig(config) => state.set_config(config),
+            AdminOperation::UpdateSubscriber(param) => update_subscriber(state, param)?,
+            AdminOperation::UpdateRevocationKey(param) => update_revocation_key(state, param),
+            AdminOperation::PauseToken(token_id) => state.set_token_paused(token_id, true)?,
+            AdminOperation::UnpauseToken(token_id) => state.set_token_paused(token_id, false)?,
+            AdminOperation::Deprecate(token_id) => state.deprecate_token(token_id)?,
+            AdminOperation::SetConsentRequired(param) => {
+                state.set_token_consent_required(param.token_id, param.required)?
+            }
+            AdminOperation::SetLocalizedMetadata(param) => {
+                state.set_localized_metadata(param.token_id, param.locale, param.metadata_url)?
+            }
+            AdminOperation::SetIdRange(param) => state.set_id_range(param.issuer, param.range)?,
+            AdminOperation::UpdateMinter(param) => update_minter(state, param),
+            AdminOperation::ResumeMinting => state.resume_minting(now),
+            AdminOperation::SetAllowlistRequired(param) => {
+                state.set_token_allowlist_required(param.token_id, param.required)?
+            }
+            AdminOperation::SetMinAmount(param) => {
+                state.set_token_min_amount(param.token_id, param.min_amount)?
+            }
+            AdminOperation::SetSchemaRef(param) => set_token_schema_ref(state, logger, param)?,
+            AdminOperation::UpdateRole(param) => update_role(state, param),
+            AdminOperation::CreateBundle(param) => {
+                state.set_bundle(param.bundle_id, param.components)?
+            }
+            AdminOperation::SetDependency(param) => {
+                state.set_token_dependency(param.token_id, param.depends_on)?
+            }
+            AdminOperation::UpdateIssuerContract(param) => update_issuer_contract(state, param),
+            AdminOperation::SetExclusiveGroup(param) => {
+                state.set_token_exclusive_group(param.token_id, param.group)?
+            }
+            AdminOperation::SetMonotonicExpiry(param) => {
+                state.set_token_monotonic_expiry(param.token_id, param.monotonic)?
+            }
+            AdminOperation::SetMaxLifetime(param) => {
+                state.set_token_max_lifetime(param.token_id, param.max_lifetime)?
+            }
+            AdminOperation::PauseCategory(category) => {
+                state.set_category_paused(category, true);
+            }
+            AdminOperation::UnpauseCategory(category) => {
+                state.set_category_paused(category, false);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::types::{AmountRule, AmountUnit, RenewalPolicy};
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ADDRESS_0: Address = Address::Account(ACCOUNT_0);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+    const TOKEN_1: ContractTokenId = TokenIdU8(3);
+    const ACTION_0: HashSha2256 = HashSha2256([1u8; 32]);
+
+    #[concordium_test]
+    fn test_batch_admin() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(10));
+        let params = BatchAdminParams {
+            operations: vec![
+                AdminOperation::SetConfig(ContractConfig {
+                    unique_metadata_urls: true,
+                    ..Default::default()
+                }),
+                AdminOperation::AddToken(AddTokenParams {
+                    token_id: TOKEN_0,
+                    metadata_url: MetadataUrl {
+                        url: "https://example.com".to_string(),
+                        hash: None,
+                    },
+                    category: 0,
+                    issuer: None,
+                    default_validity: None,
+                    renewal_policy: RenewalPolicy::IssuerOnly,
+                    private: false,
+                    unique_person: false,
+                    amount_unit: AmountUnit::default(),
+                    amount_rule: AmountRule::default(),
+                }),
+                AdminOperation::AddToken(AddTokenParams {
+                    token_id: TOKEN_1,
+                    metadata_url: MetadataUrl {
+                        url: "https://example.com/1".to_string(),
+                        hash: None,
+                    },
+                    category: 0,
+                    issuer: None,
+                    default_validity: None,
+                    renewal_policy: RenewalPolicy::IssuerOnly,
+                    private: false,
+                    unique_person: false,
+                    amount_unit: AmountUnit::default(),
+                    amount_rule: AmountRule::default(),
+                }),
+                AdminOperation::RemoveToken(TOKEN_0),
+            ],
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let mut logger = TestLogger::init();
+        let result: ContractResult<()> =
+            batch_admin(&ctx, &mut host, &mut logger, &TestCryptoPrimitives::new());
+        assert_eq!(result, Ok(()));
+
+        let state = host.state();
+        assert!(state.config().unique_metadata_urls);
+        assert!(!state.has_token(TOKEN_0));
+        assert!(state.has_token(TOKEN_1));
+        assert_eq!(logger.logs.len(), 3);
+    }
+
+    #[concordium_test]
+    fn test_batch_admin_fails_on_invalid_operation() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(10));
+        let params = BatchAdminParams {
+            operations: vec![
+                AdminOperation::AddToken(AddTokenParams {
+                    token_id: TOKEN_0,
+                    metadata_url: MetadataUrl {
+                        url: "https://example.com".to_string(),
+                        hash: None,
+                    },
+                    category: 0,
+                    issuer: None,
+                    default_validity: None,
+                    renewal_policy: RenewalPolicy::IssuerOnly,
+                    private: false,
+                    unique_person: false,
+                    amount_unit: AmountUnit::default(),
+                    amount_rule: AmountRule::default(),
+                }),
+                AdminOperation::RemoveToken(TOKEN_1),
+            ],
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let mut logger = TestLogger::init();
+        let result: ContractResult<()> =
+            batch_admin(&ctx, &mut host, &mut logger, &TestCryptoPrimitives::new());
+        assert_eq!(result, Err(ContractError::InvalidTokenId));
+    }
+
+    #[concordium_test]
+    fn test_batch_admin_not_owner() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(AccountAddress([1u8; 32]));
+        let params = BatchAdminParams {
+            operations: vec![AdminOperation::RemoveToken(TOKEN_0)],
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let mut logger = TestLogger::init();
+        let result: ContractResult<()> =
+            batch_admin(&ctx, &mut host, &mut logger, &TestCryptoPrimitives::new());
+        assert_eq!(result, Err(ContractError::Unauthorized));
+    }
+
+    #[concordium_test]
+    fn test_batch_admin_scheduled() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(10));
+        let minter = Address::Account(AccountAddress([1u8; 32]));
+        let params = BatchAdminParams {
+            operations: vec![AdminOperation::UpdateMinter(UpdateMinterParams {
+                update: MinterUpdate::Remove,
+                address: minter,
+            })],
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.update_minter(minter, true);
+        state.set_timelock_delay(Duration::from_millis(100));
+        let mut host = TestHost::new(state, state_builder);
+        let mut logger = TestLogger::init();
+        let crypto_primitives = TestCryptoPrimitives::new();
+        crypto_primitives.setup_hash_sha2_256_mock(|_| ACTION_0);
+
+        // Revoking a minter role through a batch has to be scheduled as well.
+        let result: ContractResult<()> =
+            batch_admin(&ctx, &mut host, &mut logger, &crypto_primitives);
+        assert_eq!(
+            result,
+            Err(ContractError::Custom(CustomError::ActionNotScheduled))
+        );
+        assert!(host.state().is_minter(&minter));
+
+        host.state_mut()
+            .schedule_action(ACTION_0, Timestamp::from_timestamp_millis(10));
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(110));
+        let result: ContractResult<()> =
+            batch_admin(&ctx, &mut host, &mut logger, &crypto_primitives);
+        assert_eq!(result, Ok(()));
+        assert!(!host.state().is_minter(&minter));
+    }
+
+    #[concordium_test]
+    fn test_batch_admin_pause_category() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(10));
+        let params = BatchAdminParams {
+            operations: vec![
+                AdminOperation::PauseCategory(1),
+                AdminOperation::UnpauseCategory(2),
+            ],
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        for (token_id, category) in [(TOKEN_0, 1), (TOKEN_1, 2)] {
+            state.add_token(
+                &mut state_builder,
+                token_id,
+                MetadataUrl {
+                    url: format!("https://example.com/{}", category),
+                    hash: None,
+                },
+            );
+            state
+                .set_token_category(token_id, category)
+                .expect("Failed to set category");
+        }
+        state
+            .set_token_paused(TOKEN_1, true)
+            .expect("Failed to pause token");
+        let mut host = TestHost::new(state, state_builder);
+        let mut logger = TestLogger::init();
+        let result: ContractResult<()> =
+            batch_admin(&ctx, &mut host, &mut logger, &TestCryptoPrimitives::new());
+        assert_eq!(result, Ok(()));
+        assert_eq!(host.state().is_token_paused(&TOKEN_0), Ok(true));
+        assert_eq!(host.state().is_token_paused(&TOKEN_1), Ok(false));
+    }
+}
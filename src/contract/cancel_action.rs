@@ -0,0 +1,95 @@
+use concordium_std::*;
+
+use crate::{
+    state::State,
+    types::{ActionCancelledEvent, ContractError, ContractEvent, ContractResult},
+};
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "cancelAction",
+    parameter = "HashSha2256",
+    error = "ContractError",
+    enable_logger,
+    mutable
+)]
+/// Cancels a scheduled action.
+/// - This function fails if the sender is not the admin of the contract.
+/// - This function fails if the action has not been scheduled.
+pub fn cancel_action<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    // Check that the sender is the admin of the contract.
+    ensure!(
+        host.state().is_contract_admin(&ctx.sender(), ctx.owner()),
+        ContractError::Unauthorized
+    );
+
+    let action_hash: HashSha2256 = ctx.parameter_cursor().get()?;
+    host.state_mut().cancel_action(&action_hash)?;
+    logger.log(&ContractEvent::ActionCancelled(ActionCancelledEvent {
+        action_hash,
+    }))?;
+
+    Ok(())
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::errors::CustomError;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ADDRESS_0: Address = Address::Account(ACCOUNT_0);
+    const ACTION_0: HashSha2256 = HashSha2256([1u8; 32]);
+
+    #[concordium_test]
+    fn test_cancel_action() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        let parameter = to_bytes(&ACTION_0);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.schedule_action(ACTION_0, Timestamp::from_timestamp_millis(10));
+        let mut host = TestHost::new(state, state_builder);
+        let mut logger = TestLogger::init();
+
+        let result: ContractResult<()> = cancel_action(&ctx, &mut host, &mut logger);
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            logger.logs,
+            vec![to_bytes(&ContractEvent::ActionCancelled(
+                ActionCancelledEvent {
+                    action_hash: ACTION_0,
+                }
+            ))]
+        );
+
+        // The action is no longer scheduled.
+        let result: ContractResult<()> = cancel_action(&ctx, &mut host, &mut logger);
+        assert_eq!(
+            result,
+            Err(ContractError::Custom(CustomError::ActionNotScheduled))
+        );
+    }
+
+    #[concordium_test]
+    fn test_cancel_action_not_owner() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(AccountAddress([1u8; 32]));
+        let parameter = to_bytes(&ACTION_0);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let mut logger = TestLogger::init();
+        let result: ContractResult<()> = cancel_action(&ctx, &mut host, &mut logger);
+        assert_eq!(result, Err(ContractError::Unauthorized));
+    }
+}
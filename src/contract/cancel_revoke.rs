@@ -0,0 +1,102 @@
+use concordium_std::*;
+
+use crate::{
+    state::State,
+    types::{ContractError, ContractResult, CredentialQuery},
+};
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "cancelRevoke",
+    parameter = "CredentialQuery",
+    error = "ContractError",
+    mutable
+)]
+/// Cancels the scheduled revocation of a balance, e.g. after the holder won the dispute.
+/// - This function fails if the token has an issuer and the sender is not the issuer.
+/// - This function fails if the token has no issuer and the sender is not the admin of the contract.
+/// - This function fails if the token does not exist.
+/// - This function fails if no revocation of the balance is scheduled.
+pub fn cancel_revoke<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    let params: CredentialQuery = ctx.parameter_cursor().get()?;
+    let is_owner = host.state().is_contract_admin(&ctx.sender(), ctx.owner());
+    let state = host.state_mut();
+    // Check that the sender is allowed to revoke the token.
+    ensure!(
+        state.can_issue(&params.token_id, &ctx.sender(), is_owner)?,
+        ContractError::Unauthorized
+    );
+    state.take_scheduled_revocation(params.token_id, params.account)?;
+    Ok(())
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::{
+        errors::CustomError,
+        types::{ContractTokenId, ScheduledRevocation},
+    };
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+
+    #[concordium_test]
+    fn test_cancel_revoke() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(ACCOUNT_0));
+        ctx.set_owner(ACCOUNT_0);
+        let parameter = to_bytes(&CredentialQuery {
+            token_id: TOKEN_0,
+            account: ACCOUNT_1,
+        });
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_1,
+                1.into(),
+                Timestamp::from_timestamp_millis(100),
+                None,
+            )
+            .expect("Failed to mint token");
+        state
+            .schedule_revocation(
+                TOKEN_0,
+                ACCOUNT_1,
+                ScheduledRevocation {
+                    reason: 1,
+                    effective_at: Timestamp::from_timestamp_millis(50),
+                },
+            )
+            .expect("Failed to schedule revocation");
+        let mut host = TestHost::new(state, state_builder);
+
+        assert_eq!(cancel_revoke(&ctx, &mut host), Ok(()));
+        assert_eq!(
+            host.state().get_scheduled_revocation(TOKEN_0, ACCOUNT_1),
+            Ok(None)
+        );
+        // Cancelling again fails since nothing is scheduled.
+        assert_eq!(
+            cancel_revoke(&ctx, &mut host),
+            Err(ContractError::Custom(CustomError::RevocationNotScheduled))
+        );
+    }
+}
@@ -0,0 +1,171 @@
+use concordium_std::*;
+
+use crate::{
+    contract::balance_of::is_hidden_from_sender,
+    state::State,
+    types::{ContractError, ContractResult, TokenCategory},
+};
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct CategoryScoreQuery {
+    pub category: TokenCategory,
+    pub account: AccountAddress,
+}
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct CategoryScoreQueryParams {
+    #[concordium(size_length = 2)]
+    pub queries: Vec<CategoryScoreQuery>,
+}
+
+#[derive(Debug, Serialize, SchemaType)]
+pub struct CategoryScoreQueryResponse(#[concordium(size_length = 2)] pub Vec<u64>);
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "categoryScoreOf",
+    parameter = "CategoryScoreQueryParams",
+    return_value = "CategoryScoreQueryResponse",
+    error = "ContractError"
+)]
+/// Gets the sum of the balances of an account over all tokens of a category.
+/// - Expired balances and balances which are not valid yet are not included.
+/// - Balances hidden by their holder are not included unless the sender is the holder, the admin
+///   or a verifier, see `setVisibility`.
+pub fn category_score_of<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<CategoryScoreQueryResponse> {
+    // Parse the parameter.
+    let params: CategoryScoreQueryParams = ctx.parameter_cursor().get()?;
+    let state = host.state();
+    state.ensure_batch_size(|limits| limits.query, params.queries.len())?;
+    let now = ctx.metadata().slot_time();
+    let response: Vec<u64> = params
+        .queries
+        .iter()
+        .map(|q| {
+            state.get_category_score(q.category, q.account, now, |token_id| {
+                !is_hidden_from_sender(ctx, state, token_id, q.account).unwrap_or(true)
+            })
+        })
+        .collect();
+
+    Ok(CategoryScoreQueryResponse(response))
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::types::ContractTokenId;
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const ACCOUNT_2: AccountAddress = AccountAddress([2u8; 32]);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+    const TOKEN_1: ContractTokenId = TokenIdU8(3);
+    const TOKEN_2: ContractTokenId = TokenIdU8(4);
+
+    #[concordium_test]
+    fn test_category_score_of() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(150));
+        let params = CategoryScoreQueryParams {
+            queries: vec![
+                CategoryScoreQuery {
+                    category: 1,
+                    account: ACCOUNT_0,
+                },
+                CategoryScoreQuery {
+                    category: 2,
+                    account: ACCOUNT_0,
+                },
+            ],
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        for (token_id, category, expiry) in
+            [(TOKEN_0, 1, 200), (TOKEN_1, 2, 100), (TOKEN_2, 1, 300)]
+        {
+            state.add_token(
+                &mut state_builder,
+                token_id,
+                MetadataUrl {
+                    url: format!("https://example.com/{}", token_id.0),
+                    hash: None,
+                },
+            );
+            state
+                .set_token_category(token_id, category)
+                .expect("Failed to set category");
+            state
+                .mint(
+                    token_id,
+                    ACCOUNT_0,
+                    10.into(),
+                    Timestamp::from_timestamp_millis(expiry),
+                    None,
+                )
+                .expect("Failed to mint token");
+        }
+        let host = TestHost::new(state, state_builder);
+        let result = category_score_of(&ctx, &host).unwrap();
+        // The balance of TOKEN_1 has expired.
+        assert_eq!(result.0, vec![20, 0]);
+    }
+
+    #[concordium_test]
+    fn test_category_score_of_hidden() {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_1,
+                10.into(),
+                Timestamp::from_timestamp_millis(200),
+                None,
+            )
+            .expect("Failed to mint token");
+        state
+            .set_balance_hidden(TOKEN_0, ACCOUNT_1, true)
+            .expect("Failed to hide balance");
+        state
+            .set_token_category(TOKEN_0, 1)
+            .expect("Failed to set category");
+        let host = TestHost::new(state, state_builder);
+        let parameter = to_bytes(&CategoryScoreQueryParams {
+            queries: vec![CategoryScoreQuery {
+                category: 1,
+                account: ACCOUNT_1,
+            }],
+        });
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(ACCOUNT_2));
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(150));
+        ctx.set_parameter(&parameter);
+
+        assert_eq!(
+            category_score_of(&ctx, &host).map(|response| response.0),
+            Ok(vec![0])
+        );
+        ctx.set_sender(Address::Account(ACCOUNT_1));
+        assert_eq!(
+            category_score_of(&ctx, &host).map(|response| response.0),
+            Ok(vec![10])
+        );
+    }
+}
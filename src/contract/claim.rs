@@ -0,0 +1,238 @@
+use concordium_cis2::Cis2Error;
+use concordium_std::*;
+
+use crate::{
+    contract::{
+        mint::{ensure_unique_person, mint_balance, record_mint_volume, MintParam},
+        update_subscribers::notify_subscribers,
+    },
+    errors::CustomError,
+    state::State,
+    types::{ContractError, ContractResult, ContractTokenAmount, ContractTokenId},
+};
+
+/// Balance an issuer allows an account to claim, signed with an issuer key of the token.
+#[derive(Serial, Deserial, SchemaType)]
+pub struct Voucher {
+    /// Address of the contract the voucher is meant for.
+    pub contract_address: ContractAddress,
+    /// Id chosen by the issuer, each voucher of a token can only be claimed once.
+    pub voucher_id: u64,
+    pub token_id: ContractTokenId,
+    /// Owner of the claimed balance.
+    pub account: AccountAddress,
+    pub amount: ContractTokenAmount,
+    /// The expiry of the claimed balance.
+    pub expiry: Timestamp,
+    /// Time at which the voucher was signed, used to select the valid issuer key.
+    pub issued_at: Timestamp,
+    /// Time until which the voucher can be claimed.
+    pub valid_until: Timestamp,
+}
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct ClaimParams {
+    /// Issuer key which signed the voucher.
+    pub signer: PublicKeyEd25519,
+    /// Signature of the serialized voucher.
+    pub signature: SignatureEd25519,
+    pub voucher: Voucher,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "claim",
+    parameter = "ClaimParams",
+    error = "ContractError",
+    enable_logger,
+    crypto_primitives,
+    mutable
+)]
+/// Mints the balance of a voucher signed by an issuer key of the token.
+/// Anyone can submit the voucher, the balance is minted to the account of the voucher.
+/// - This function fails if the voucher is meant for another contract.
+/// - This function fails if the voucher is no longer valid or has already been claimed.
+/// - This function fails if the account of the voucher has called a self-service entrypoint within the configured cooldown.
+/// - This function fails if the signature is not valid.
+/// - This function fails if the signer was not an issuer key of the token when the voucher was issued.
+/// - This function fails if the token requires unique persons and the identity registry does not confirm the account.
+/// - This function fails if the token requires consent and the account has not consented to be issued the token by its issuer.
+/// - This function fails if the token requires an allowlist and the account is not on it.
+/// - This function fails if the balance can not be minted, e.g. minting of the token is paused.
+pub fn claim<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    crypto_primitives: &impl HasCryptoPrimitives,
+) -> ContractResult<()> {
+    let params: ClaimParams = ctx.parameter_cursor().get()?;
+    let voucher = params.voucher;
+    ensure_eq!(
+        voucher.contract_address,
+        ctx.self_address(),
+        ContractError::Custom(CustomError::WrongContract)
+    );
+    let now = ctx.metadata().slot_time();
+    ensure!(
+        now <= voucher.valid_until,
+        ContractError::Custom(CustomError::VoucherExpired)
+    );
+
+    // Check the signature of the voucher.
+    let voucher_bytes = to_bytes(&voucher);
+    ensure!(
+        crypto_primitives.verify_ed25519_signature(params.signer, params.signature, &voucher_bytes),
+        ContractError::Custom(CustomError::WrongSignature)
+    );
+
+    ensure_unique_person(host, [&voucher.token_id], voucher.account)?;
+    let state = host.state_mut();
+    state.ensure_issuer_key(&voucher.token_id, &params.signer, voucher.issued_at)?;
+    state.use_voucher(voucher.token_id, voucher.voucher_id)?;
+    state.record_self_service_action(voucher.account, now)?;
+    let issuer = state
+        .get_token_issuer(&voucher.token_id)?
+        .unwrap_or_else(|| Address::Account(state.contract_admin(ctx.owner())));
+    // Ensure the account consents to the issuance, if required.
+    ensure!(
+        state.has_issuance_consent(&voucher.token_id, voucher.account, issuer)?,
+        Cis2Error::Custom(CustomError::ConsentRequired)
+    );
+    let minted = mint_balance(
+        state,
+        logger,
+        voucher.token_id,
+        voucher.account,
+        MintParam {
+            amount: voucher.amount,
+            expiry: Some(voucher.expiry),
+            valid_from: None,
+            metadata_url: None,
+        },
+        issuer,
+        now,
+    )?;
+    record_mint_volume(state, logger, 1, u64::from(voucher.amount.0), now)?;
+
+    // Notify the subscribers about the new balance.
+    notify_subscribers(host, logger, minted.notifications)?;
+
+    Ok(())
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::types::IssuerKeyValidity;
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const SELF_ADDRESS: ContractAddress = ContractAddress {
+        index: 1,
+        subindex: 0,
+    };
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+    const KEY_0: PublicKeyEd25519 = PublicKeyEd25519([1u8; 32]);
+    const KEY_1: PublicKeyEd25519 = PublicKeyEd25519([2u8; 32]);
+    const SIGNATURE_0: SignatureEd25519 = SignatureEd25519([3u8; 64]);
+
+    fn claim_with(
+        host: &mut TestHost<State<TestStateApi>>,
+        signer: PublicKeyEd25519,
+        voucher_id: u64,
+        issued_at: u64,
+    ) -> ContractResult<()> {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_self_address(SELF_ADDRESS);
+        ctx.set_owner(AccountAddress([0u8; 32]));
+        ctx.set_sender(Address::Account(ACCOUNT_1));
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(80));
+        let parameter = to_bytes(&ClaimParams {
+            signer,
+            signature: SIGNATURE_0,
+            voucher: Voucher {
+                contract_address: SELF_ADDRESS,
+                voucher_id,
+                token_id: TOKEN_0,
+                account: ACCOUNT_1,
+                amount: 10.into(),
+                expiry: Timestamp::from_timestamp_millis(200),
+                issued_at: Timestamp::from_timestamp_millis(issued_at),
+                valid_until: Timestamp::from_timestamp_millis(issued_at + 50),
+            },
+        });
+        ctx.set_parameter(&parameter);
+        let mut logger = TestLogger::init();
+        let crypto_primitives = TestCryptoPrimitives::new();
+        crypto_primitives
+            .setup_verify_ed25519_signature_mock(|_, signature, _| signature == SIGNATURE_0);
+        claim(&ctx, host, &mut logger, &crypto_primitives)
+    }
+
+    #[concordium_test]
+    fn test_claim_with_rotated_keys() {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        // Key 0 was rotated to key 1 at time 50.
+        state
+            .set_issuer_key(
+                TOKEN_0,
+                KEY_0,
+                IssuerKeyValidity {
+                    active_from: Timestamp::from_timestamp_millis(0),
+                    retired_at: Some(Timestamp::from_timestamp_millis(50)),
+                },
+            )
+            .expect("Failed to set issuer key");
+        state
+            .set_issuer_key(
+                TOKEN_0,
+                KEY_1,
+                IssuerKeyValidity {
+                    active_from: Timestamp::from_timestamp_millis(50),
+                    retired_at: None,
+                },
+            )
+            .expect("Failed to set issuer key");
+        let mut host = TestHost::new(state, state_builder);
+
+        // Vouchers signed by the retired key before its retirement are valid.
+        assert_eq!(claim_with(&mut host, KEY_0, 0, 40), Ok(()));
+        assert_eq!(
+            host.state().get_account_balance(
+                TOKEN_0,
+                ACCOUNT_1,
+                Timestamp::from_timestamp_millis(100)
+            ),
+            Ok(10.into())
+        );
+        assert_eq!(
+            claim_with(&mut host, KEY_0, 1, 60),
+            Err(ContractError::Custom(CustomError::UnknownIssuerKey))
+        );
+        assert_eq!(
+            claim_with(&mut host, KEY_1, 2, 40),
+            Err(ContractError::Custom(CustomError::UnknownIssuerKey))
+        );
+        assert_eq!(claim_with(&mut host, KEY_1, 3, 60), Ok(()));
+
+        // Vouchers can only be claimed until they expire, and only once.
+        assert_eq!(
+            claim_with(&mut host, KEY_0, 4, 20),
+            Err(ContractError::Custom(CustomError::VoucherExpired))
+        );
+        assert_eq!(
+            claim_with(&mut host, KEY_1, 3, 60),
+            Err(ContractError::Custom(CustomError::VoucherAlreadyUsed))
+        );
+    }
+}
@@ -0,0 +1,215 @@
+use concordium_std::*;
+
+use crate::{
+    contract::{
+        mint::{ensure_unique_person, mint_balance, record_mint_volume, MintParam},
+        update_subscribers::notify_subscribers,
+    },
+    errors::CustomError,
+    state::State,
+    types::{ContractError, ContractResult, ContractTokenAmount, ContractTokenId},
+};
+
+/// Leaf of a Merkle tree of claimable balances, hashed with SHA-256 in its serialized form.
+#[derive(Serial, Deserial, SchemaType)]
+pub struct IssuanceLeaf {
+    pub account: AccountAddress,
+    pub token_id: ContractTokenId,
+    pub amount: ContractTokenAmount,
+    pub expiry: Timestamp,
+}
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct ClaimFromRootParams {
+    pub token_id: ContractTokenId,
+    /// The published Merkle root containing the balance.
+    pub root: HashSha2256,
+    pub amount: ContractTokenAmount,
+    pub expiry: Timestamp,
+    /// Hashes of the siblings on the path from the leaf to the root.
+    pub proof: Vec<HashSha2256>,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "claimFromRoot",
+    parameter = "ClaimFromRootParams",
+    error = "ContractError",
+    enable_logger,
+    crypto_primitives,
+    mutable
+)]
+/// Mints a balance to the sender which is contained in a published Merkle root.
+/// The minted balance replaces an existing balance of the sender, like `mint`.
+/// - This function fails if the sender is not an account.
+/// - This function fails if the root has not been published for the token.
+/// - This function fails if the proof does not lead from the leaf of the sender to the root.
+/// - This function fails if the balance has already been claimed.
+/// - This function fails if the token requires unique persons and the identity registry does not confirm the sender.
+/// - This function fails if minting of the token is paused or the token has been deprecated.
+/// - This function fails if the expiry is in the past.
+/// - This function fails if the token requires an allowlist and the sender is not on it.
+pub fn claim_from_root<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    crypto_primitives: &impl HasCryptoPrimitives,
+) -> ContractResult<()> {
+    let account = match ctx.sender() {
+        Address::Account(account) => account,
+        Address::Contract(_) => bail!(ContractError::Custom(CustomError::AccountsOnly)),
+    };
+    let params: ClaimFromRootParams = ctx.parameter_cursor().get()?;
+    ensure_unique_person(host, [&params.token_id], account)?;
+    let state = host.state_mut();
+    let publisher = state.get_issuance_root_publisher(&params.token_id, &params.root)?;
+
+    // Check that the balance is contained in the root.
+    let leaf = crypto_primitives.hash_sha2_256(&to_bytes(&IssuanceLeaf {
+        account,
+        token_id: params.token_id,
+        amount: params.amount,
+        expiry: params.expiry,
+    }));
+    ensure!(
+        merkle_root(crypto_primitives, leaf, &params.proof) == params.root,
+        ContractError::Custom(CustomError::InvalidMerkleProof)
+    );
+    state.use_issuance_leaf(params.token_id, leaf)?;
+
+    let minted = mint_balance(
+        state,
+        logger,
+        params.token_id,
+        account,
+        MintParam {
+            amount: params.amount,
+            expiry: Some(params.expiry),
+            valid_from: None,
+            metadata_url: None,
+        },
+        publisher,
+        ctx.metadata().slot_time(),
+    )?;
+    record_mint_volume(
+        state,
+        logger,
+        1,
+        u64::from(params.amount.0),
+        ctx.metadata().slot_time(),
+    )?;
+
+    // Notify the subscribers about the new balance.
+    notify_subscribers(host, logger, minted.notifications)?;
+
+    Ok(())
+}
+
+/// Computes the root of a Merkle tree from a leaf and the hashes of its siblings.
+/// Each pair of hashes is sorted before hashing, so the proof does not encode the position of the leaf.
+pub(crate) fn merkle_root(
+    crypto_primitives: &impl HasCryptoPrimitives,
+    leaf: HashSha2256,
+    proof: &[HashSha2256],
+) -> HashSha2256 {
+    proof.iter().fold(leaf, |node, sibling| {
+        let (left, right) = if node.0 <= sibling.0 {
+            (node.0, sibling.0)
+        } else {
+            (sibling.0, node.0)
+        };
+        let mut pair = left.to_vec();
+        pair.extend_from_slice(&right);
+        crypto_primitives.hash_sha2_256(&pair)
+    })
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const ADDRESS_0: Address = Address::Account(ACCOUNT_0);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+
+    /// Deterministic stand-in for SHA-256.
+    fn test_hash(data: &[u8]) -> HashSha2256 {
+        let mut hash = [0u8; 32];
+        for (i, byte) in data.iter().enumerate() {
+            hash[i % 32] = hash[i % 32].rotate_left(3) ^ byte.wrapping_add(i as u8);
+        }
+        HashSha2256(hash)
+    }
+
+    #[concordium_test]
+    fn test_claim_from_root() {
+        let crypto_primitives = TestCryptoPrimitives::new();
+        crypto_primitives.setup_hash_sha2_256_mock(test_hash);
+        let leaf = |account| {
+            test_hash(&to_bytes(&IssuanceLeaf {
+                account,
+                token_id: TOKEN_0,
+                amount: 10.into(),
+                expiry: Timestamp::from_timestamp_millis(100),
+            }))
+        };
+        let root = merkle_root(&crypto_primitives, leaf(ACCOUNT_1), &[leaf(ACCOUNT_0)]);
+
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(ACCOUNT_1));
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(10));
+        let params = ClaimFromRootParams {
+            token_id: TOKEN_0,
+            root,
+            amount: 10.into(),
+            expiry: Timestamp::from_timestamp_millis(100),
+            proof: vec![leaf(ACCOUNT_0)],
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        state
+            .add_issuance_root(TOKEN_0, root, ADDRESS_0)
+            .expect("Failed to publish root");
+        let mut host = TestHost::new(state, state_builder);
+        let mut logger = TestLogger::init();
+
+        let result = claim_from_root(&ctx, &mut host, &mut logger, &crypto_primitives);
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            host.state().get_account_balance(
+                TOKEN_0,
+                ACCOUNT_1,
+                Timestamp::from_timestamp_millis(10)
+            ),
+            Ok(10.into())
+        );
+
+        // The balance can only be claimed once.
+        let result = claim_from_root(&ctx, &mut host, &mut logger, &crypto_primitives);
+        assert_eq!(
+            result,
+            Err(ContractError::Custom(CustomError::AlreadyClaimed))
+        );
+
+        // The leaf of another account does not lead to the root.
+        ctx.set_sender(ADDRESS_0);
+        let result = claim_from_root(&ctx, &mut host, &mut logger, &crypto_primitives);
+        assert_eq!(
+            result,
+            Err(ContractError::Custom(CustomError::InvalidMerkleProof))
+        );
+    }
+}
@@ -0,0 +1,104 @@
+use concordium_std::*;
+
+use crate::{
+    state::State,
+    types::{ContractError, ContractResult, ContractTokenId},
+};
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct CommitmentUpdate {
+    pub account: AccountAddress,
+    /// SHA-256 hash of the serialized `CommitmentOpening` of the balance, `None` removes the commitment.
+    pub commitment: Option<HashSha2256>,
+}
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct CommitParams {
+    pub token_id: ContractTokenId,
+    pub updates: Vec<CommitmentUpdate>,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "commit",
+    parameter = "CommitParams",
+    error = "ContractError",
+    mutable
+)]
+/// Stores or removes commitments of balances of a private token.
+/// - This function fails if the token has an issuer and the sender is not the issuer.
+/// - This function fails if the token has no issuer and the sender is not the admin of the contract.
+/// - This function fails if the token does not exist or is not private.
+pub fn commit<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    let params: CommitParams = ctx.parameter_cursor().get()?;
+    let is_owner = host.state().is_contract_admin(&ctx.sender(), ctx.owner());
+    let state = host.state_mut();
+    state.ensure_batch_size(|limits| limits.mint, params.updates.len())?;
+    // Check that the sender is allowed to mint the token.
+    ensure!(
+        state.can_issue(&params.token_id, &ctx.sender(), is_owner)?,
+        ContractError::Unauthorized
+    );
+
+    for update in params.updates {
+        state.set_commitment(params.token_id, update.account, update.commitment)?;
+    }
+
+    Ok(())
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::errors::CustomError;
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ADDRESS_0: Address = Address::Account(ACCOUNT_0);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+
+    #[concordium_test]
+    fn test_commit() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        let params = CommitParams {
+            token_id: TOKEN_0,
+            updates: vec![CommitmentUpdate {
+                account: ACCOUNT_0,
+                commitment: Some(HashSha2256([1u8; 32])),
+            }],
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        let mut host = TestHost::new(state, state_builder);
+
+        // Commitments can only be stored for private tokens.
+        let result = commit(&ctx, &mut host);
+        assert_eq!(result, Err(ContractError::Custom(CustomError::PublicToken)));
+
+        host.state_mut()
+            .set_token_private(TOKEN_0, true)
+            .expect("Failed to set token private");
+        let result = commit(&ctx, &mut host);
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            host.state().get_commitment(&TOKEN_0, &ACCOUNT_0),
+            Ok(Some(HashSha2256([1u8; 32])))
+        );
+    }
+}
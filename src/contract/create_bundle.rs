@@ -0,0 +1,90 @@
+use concordium_std::*;
+
+use crate::{
+    state::State,
+    types::{BundleComponent, BundleId, ContractError, ContractResult},
+};
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct CreateBundleParams {
+    pub bundle_id: BundleId,
+    /// Tokens minted by `mintBundle`, an empty list removes the bundle.
+    pub components: Vec<BundleComponent>,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "createBundle",
+    parameter = "CreateBundleParams",
+    error = "ContractError",
+    mutable
+)]
+/// Creates or replaces a bundle of tokens which are minted together using `mintBundle`.
+/// - This function fails if the sender is not the admin of the contract.
+/// - This function fails if the number of components exceeds the configured mint batch limit.
+/// - This function fails if any of the tokens does not exist.
+pub fn create_bundle<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    // Check that the sender is the admin of the contract.
+    ensure!(
+        host.state().is_contract_admin(&ctx.sender(), ctx.owner()),
+        ContractError::Unauthorized
+    );
+
+    let params: CreateBundleParams = ctx.parameter_cursor().get()?;
+    let state = host.state_mut();
+    // A bundle is minted in a single call, like the tokens of `mint`.
+    state.ensure_batch_size(|limits| limits.mint, params.components.len())?;
+    state.set_bundle(params.bundle_id, params.components)
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ADDRESS_0: Address = Address::Account(ACCOUNT_0);
+
+    #[concordium_test]
+    fn test_create_bundle_unknown_token() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        let params = CreateBundleParams {
+            bundle_id: 1,
+            components: vec![BundleComponent {
+                token_id: concordium_cis2::TokenIdU8(2),
+                amount: 1.into(),
+                validity: None,
+            }],
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let result: ContractResult<()> = create_bundle(&ctx, &mut host);
+        assert_eq!(result, Err(ContractError::InvalidTokenId));
+    }
+
+    #[concordium_test]
+    fn test_create_bundle_not_owner() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(AccountAddress([1u8; 32]));
+        let params = CreateBundleParams {
+            bundle_id: 1,
+            components: vec![],
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let result: ContractResult<()> = create_bundle(&ctx, &mut host);
+        assert_eq!(result, Err(ContractError::Unauthorized));
+    }
+}
@@ -0,0 +1,203 @@
+use concordium_cis2::MetadataUrl;
+use concordium_std::*;
+
+use crate::{
+    contract::balance_of::is_hidden_from_sender,
+    errors::CustomError,
+    state::State,
+    types::{
+        ContractError, ContractResult, ContractTokenAmount, ContractTokenId, CredentialQueryParams,
+        CredentialStatus,
+    },
+};
+
+#[derive(Debug, Serialize, SchemaType, PartialEq)]
+pub struct CredentialEntry {
+    pub token_id: ContractTokenId,
+    pub holder: AccountAddress,
+    /// The stored amount, regardless of the status.
+    pub amount: ContractTokenAmount,
+    /// The time from which the credential is valid, None if it was valid immediately.
+    pub valid_from: Option<Timestamp>,
+    /// The time until which the credential is valid.
+    pub valid_until: Timestamp,
+    pub status: CredentialStatus,
+    /// The metadata url of the token.
+    pub metadata_url: MetadataUrl,
+}
+
+#[derive(Debug, Serialize, SchemaType)]
+pub struct CredentialEntryQueryResponse(#[concordium(size_length = 2)] pub Vec<CredentialEntry>);
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "credentialEntry",
+    parameter = "CredentialQueryParams",
+    return_value = "CredentialEntryQueryResponse",
+    error = "ContractError"
+)]
+/// Gets the CIS-4 style entry of each of the queried credentials.
+/// - This function fails if any of the tokens does not exist.
+/// - This function fails if any of the accounts does not have a balance of the token.
+/// - This function fails if any of the balances is hidden by its holder, like a missing balance,
+///   unless the sender is the holder, the admin or a verifier.
+pub fn credential_entry<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<CredentialEntryQueryResponse> {
+    // Parse the parameter.
+    let params: CredentialQueryParams = ctx.parameter_cursor().get()?;
+    let state = host.state();
+    state.ensure_batch_size(|limits| limits.query, params.queries.len())?;
+    let now = ctx.metadata().slot_time();
+    let response: Vec<CredentialEntry> = params
+        .queries
+        .iter()
+        .map(|q| {
+            ensure!(
+                !is_hidden_from_sender(ctx, state, &q.token_id, q.account)?,
+                ContractError::Custom(CustomError::CredentialNotFound)
+            );
+            let balance = state
+                .get_account_balance_state(q.token_id, q.account)?
+                .ok_or(ContractError::Custom(CustomError::CredentialNotFound))?;
+            Ok(CredentialEntry {
+                token_id: q.token_id,
+                holder: q.account,
+                amount: balance.amount,
+                valid_from: balance.valid_from,
+                valid_until: balance.expiry,
+                status: state.get_credential_status(q.token_id, q.account, now)?,
+                metadata_url: state.get_token_metadata(&q.token_id)?,
+            })
+        })
+        .collect::<Result<Vec<CredentialEntry>, ContractError>>()?;
+
+    Ok(CredentialEntryQueryResponse(response))
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::types::*;
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const ACCOUNT_2: AccountAddress = AccountAddress([2u8; 32]);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+    const TOKEN_1: ContractTokenId = TokenIdU8(3);
+
+    #[concordium_test]
+    fn test_credential_entry() {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_0,
+                10.into(),
+                Timestamp::from_timestamp_millis(200),
+                None,
+            )
+            .expect("Failed to mint token");
+        let host = TestHost::new(state, state_builder);
+
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(150));
+        let params = CredentialQueryParams {
+            queries: vec![CredentialQuery {
+                token_id: TOKEN_0,
+                account: ACCOUNT_0,
+            }],
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let result = credential_entry(&ctx, &host).unwrap();
+        assert_eq!(
+            result.0,
+            vec![CredentialEntry {
+                token_id: TOKEN_0,
+                holder: ACCOUNT_0,
+                amount: 10.into(),
+                valid_from: None,
+                valid_until: Timestamp::from_timestamp_millis(200),
+                status: CredentialStatus::Active,
+                metadata_url: MetadataUrl {
+                    url: "https://example.com".to_string(),
+                    hash: None,
+                },
+            }]
+        );
+
+        // Querying a token which does not exist fails.
+        let params = CredentialQueryParams {
+            queries: vec![CredentialQuery {
+                token_id: TOKEN_1,
+                account: ACCOUNT_0,
+            }],
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let result = credential_entry(&ctx, &host);
+        assert_eq!(result.map(|r| r.0), Err(ContractError::InvalidTokenId));
+    }
+
+    #[concordium_test]
+    fn test_credential_entry_hidden() {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_1,
+                10.into(),
+                Timestamp::from_timestamp_millis(200),
+                None,
+            )
+            .expect("Failed to mint token");
+        state
+            .set_balance_hidden(TOKEN_0, ACCOUNT_1, true)
+            .expect("Failed to hide balance");
+        let host = TestHost::new(state, state_builder);
+        let parameter = to_bytes(&CredentialQueryParams {
+            queries: vec![CredentialQuery {
+                token_id: TOKEN_0,
+                account: ACCOUNT_1,
+            }],
+        });
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(ACCOUNT_2));
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(150));
+        ctx.set_parameter(&parameter);
+
+        // The hidden credential can not be told apart from a missing one.
+        assert_eq!(
+            credential_entry(&ctx, &host).map(|response| response.0.len()),
+            Err(ContractError::Custom(CustomError::CredentialNotFound))
+        );
+        ctx.set_sender(Address::Account(ACCOUNT_0));
+        assert_eq!(
+            credential_entry(&ctx, &host).map(|response| response.0.len()),
+            Ok(1)
+        );
+    }
+}
@@ -0,0 +1,212 @@
+use concordium_std::*;
+
+use crate::{
+    contract::balance_of::is_hidden_from_sender,
+    errors::CustomError,
+    state::State,
+    types::{ContractError, ContractResult, CredentialQueryParams, CredentialStatus},
+};
+
+#[derive(Debug, Serialize, SchemaType)]
+pub struct CredentialStatusQueryResponse(#[concordium(size_length = 2)] pub Vec<CredentialStatus>);
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "credentialStatus",
+    parameter = "CredentialQueryParams",
+    return_value = "CredentialStatusQueryResponse",
+    error = "ContractError"
+)]
+/// Gets the CIS-4 style status of each of the queried credentials.
+/// - This function fails if any of the tokens does not exist.
+/// - This function fails if any of the accounts does not have a balance of the token.
+/// - This function fails if any of the balances is hidden by its holder, like a missing balance,
+///   unless the sender is the holder, the admin or a verifier.
+pub fn credential_status<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<CredentialStatusQueryResponse> {
+    // Parse the parameter.
+    let params: CredentialQueryParams = ctx.parameter_cursor().get()?;
+    let state = host.state();
+    state.ensure_batch_size(|limits| limits.query, params.queries.len())?;
+    let now = ctx.metadata().slot_time();
+    let response: Vec<CredentialStatus> = params
+        .queries
+        .iter()
+        .map(|q| {
+            ensure!(
+                !is_hidden_from_sender(ctx, state, &q.token_id, q.account)?,
+                ContractError::Custom(CustomError::CredentialNotFound)
+            );
+            state.get_credential_status(q.token_id, q.account, now)
+        })
+        .collect::<Result<Vec<CredentialStatus>, ContractError>>()?;
+
+    Ok(CredentialStatusQueryResponse(response))
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::{errors::CustomError, types::*};
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const ACCOUNT_2: AccountAddress = AccountAddress([2u8; 32]);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+
+    #[concordium_test]
+    fn test_credential_status() {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_0,
+                10.into(),
+                Timestamp::from_timestamp_millis(200),
+                None,
+            )
+            .expect("Failed to mint token");
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_1,
+                10.into(),
+                Timestamp::from_timestamp_millis(100),
+                None,
+            )
+            .expect("Failed to mint token");
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_2,
+                10.into(),
+                Timestamp::from_timestamp_millis(300),
+                Some(Timestamp::from_timestamp_millis(200)),
+            )
+            .expect("Failed to mint token");
+        let host = TestHost::new(state, state_builder);
+
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(150));
+        let params = CredentialQueryParams {
+            queries: vec![
+                CredentialQuery {
+                    token_id: TOKEN_0,
+                    account: ACCOUNT_0,
+                },
+                CredentialQuery {
+                    token_id: TOKEN_0,
+                    account: ACCOUNT_1,
+                },
+                CredentialQuery {
+                    token_id: TOKEN_0,
+                    account: ACCOUNT_2,
+                },
+            ],
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let result = credential_status(&ctx, &host).unwrap();
+        assert_eq!(
+            result.0,
+            vec![
+                CredentialStatus::Active,
+                CredentialStatus::Expired,
+                CredentialStatus::NotActivated
+            ]
+        );
+    }
+
+    #[concordium_test]
+    fn test_credential_status_not_found() {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        let host = TestHost::new(state, state_builder);
+
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(150));
+        let params = CredentialQueryParams {
+            queries: vec![CredentialQuery {
+                token_id: TOKEN_0,
+                account: ACCOUNT_0,
+            }],
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let result = credential_status(&ctx, &host);
+        assert_eq!(
+            result.map(|r| r.0),
+            Err(ContractError::Custom(CustomError::CredentialNotFound))
+        );
+    }
+
+    #[concordium_test]
+    fn test_credential_status_hidden() {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_1,
+                10.into(),
+                Timestamp::from_timestamp_millis(200),
+                None,
+            )
+            .expect("Failed to mint token");
+        state
+            .set_balance_hidden(TOKEN_0, ACCOUNT_1, true)
+            .expect("Failed to hide balance");
+        let mut host = TestHost::new(state, state_builder);
+        let parameter = to_bytes(&CredentialQueryParams {
+            queries: vec![CredentialQuery {
+                token_id: TOKEN_0,
+                account: ACCOUNT_1,
+            }],
+        });
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(ACCOUNT_2));
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(150));
+        ctx.set_parameter(&parameter);
+
+        assert_eq!(
+            credential_status(&ctx, &host).map(|response| response.0),
+            Err(ContractError::Custom(CustomError::CredentialNotFound))
+        );
+        host.state_mut()
+            .update_role(Role::Verifier, Address::Account(ACCOUNT_2), true);
+        assert_eq!(
+            credential_status(&ctx, &host).map(|response| response.0),
+            Ok(vec![CredentialStatus::Active])
+        );
+    }
+}
@@ -0,0 +1,105 @@
+use concordium_std::*;
+
+use crate::{
+    state::State,
+    types::{AmountUnit, ContractError, ContractResult, ContractTokenMetadataQueryParams},
+};
+
+#[derive(Debug, Serialize, SchemaType)]
+pub struct DecimalsOfQueryResponse(#[concordium(size_length = 2)] pub Vec<AmountUnit>);
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "decimalsOf",
+    parameter = "ContractTokenMetadataQueryParams",
+    return_value = "DecimalsOfQueryResponse",
+    error = "ContractError"
+)]
+/// Gets the decimals and unit of the amounts of each of the queried tokens.
+/// - This function fails if any of the tokens does not exist.
+pub fn decimals_of<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<DecimalsOfQueryResponse> {
+    // Parse the parameter.
+    let params: ContractTokenMetadataQueryParams = ctx.parameter_cursor().get()?;
+    let state = host.state();
+    state.ensure_batch_size(|limits| limits.query, params.queries.len())?;
+    let response = params
+        .queries
+        .iter()
+        .map(|token_id| state.get_token_amount_unit(token_id))
+        .collect::<ContractResult<Vec<AmountUnit>>>()?;
+
+    Ok(DecimalsOfQueryResponse(response))
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::types::ContractTokenId;
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+    const TOKEN_1: ContractTokenId = TokenIdU8(3);
+
+    #[concordium_test]
+    fn test_decimals_of() {
+        let mut ctx = TestReceiveContext::empty();
+        let params = ContractTokenMetadataQueryParams {
+            queries: vec![TOKEN_0, TOKEN_1],
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        for token_id in [TOKEN_0, TOKEN_1] {
+            state.add_token(
+                &mut state_builder,
+                token_id,
+                MetadataUrl {
+                    url: format!("https://example.com/{}", token_id.0),
+                    hash: None,
+                },
+            );
+        }
+        state
+            .set_token_amount_unit(
+                TOKEN_1,
+                AmountUnit {
+                    decimals: 2,
+                    unit: Some("percent".to_string()),
+                },
+            )
+            .expect("Failed to set amount unit");
+        let host = TestHost::new(state, state_builder);
+
+        let result = decimals_of(&ctx, &host).unwrap();
+        assert_eq!(
+            result.0,
+            vec![
+                AmountUnit::default(),
+                AmountUnit {
+                    decimals: 2,
+                    unit: Some("percent".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[concordium_test]
+    fn test_decimals_of_invalid_token_id() {
+        let mut ctx = TestReceiveContext::empty();
+        let params = ContractTokenMetadataQueryParams {
+            queries: vec![TOKEN_0],
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let host = TestHost::new(state, state_builder);
+        let result = decimals_of(&ctx, &host);
+        assert_eq!(result.map(|r| r.0), Err(ContractError::InvalidTokenId));
+    }
+}
@@ -0,0 +1,113 @@
+use concordium_std::*;
+
+use crate::{
+    state::State,
+    types::{ContractError, ContractResult, ContractTokenId},
+};
+
+#[derive(SchemaType, Deserial, Serial)]
+pub struct DeprecateParams {
+    pub tokens: Vec<ContractTokenId>,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "deprecate",
+    parameter = "DeprecateParams",
+    error = "ContractError",
+    mutable
+)]
+/// Deprecates tokens. New balances can no longer be minted while existing balances stay
+/// queryable until they expire, after which the token can be removed.
+/// - This function fails if the token does not exist.
+/// - This function fails if the sender is not the admin of the contract.
+pub fn deprecate<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    // Check that the sender is the admin of the contract.
+    ensure!(
+        host.state().is_contract_admin(&ctx.sender(), ctx.owner()),
+        ContractError::Unauthorized
+    );
+
+    let params: DeprecateParams = ctx.parameter_cursor().get()?;
+    let state = host.state_mut();
+    state.ensure_batch_size(|limits| limits.admin, params.tokens.len())?;
+    for token_id in params.tokens {
+        state.deprecate_token(token_id)?;
+    }
+
+    Ok(())
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const ADDRESS_0: Address = Address::Account(ACCOUNT_0);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+
+    #[concordium_test]
+    fn test_deprecate() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        let params = DeprecateParams {
+            tokens: vec![TOKEN_0],
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_1,
+                10.into(),
+                Timestamp::from_timestamp_millis(200),
+                None,
+            )
+            .expect("Failed to mint token");
+        let mut host = TestHost::new(state, state_builder);
+        let result: ContractResult<()> = deprecate(&ctx, &mut host);
+        assert_eq!(result, Ok(()));
+
+        // Existing balances stay queryable.
+        let state = host.state();
+        assert_eq!(state.is_token_deprecated(&TOKEN_0), Ok(true));
+        assert_eq!(
+            state.get_account_balance(TOKEN_0, ACCOUNT_1, Timestamp::from_timestamp_millis(100)),
+            Ok(10.into())
+        );
+    }
+
+    #[concordium_test]
+    fn test_deprecate_not_owner() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_1);
+        let params = DeprecateParams {
+            tokens: vec![TOKEN_0],
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let result: ContractResult<()> = deprecate(&ctx, &mut host);
+        assert_eq!(result, Err(ContractError::Unauthorized));
+    }
+}
@@ -0,0 +1,216 @@
+use concordium_std::*;
+
+use crate::{
+    contract::{
+        revoke::{revoke_balance, RevokeParam},
+        update_subscribers::notify_subscribers,
+    },
+    errors::CustomError,
+    state::State,
+    types::{ContractError, ContractResult, CredentialQuery},
+};
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "executeRevoke",
+    parameter = "CredentialQuery",
+    error = "ContractError",
+    enable_logger,
+    mutable
+)]
+/// Executes a revocation scheduled using `scheduleRevoke` once its dispute window has ended,
+/// like `revoke` with the scheduled reason. Anyone can execute a due revocation.
+/// - This function fails if the token does not exist.
+/// - This function fails if no revocation of the balance is scheduled.
+/// - This function fails if the revocation is not effective yet.
+pub fn execute_revoke<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    let params: CredentialQuery = ctx.parameter_cursor().get()?;
+    let now = ctx.metadata().slot_time();
+    let state = host.state_mut();
+    let scheduled = state
+        .get_scheduled_revocation(params.token_id, params.account)?
+        .ok_or(ContractError::Custom(CustomError::RevocationNotScheduled))?;
+    ensure!(
+        scheduled.effective_at <= now,
+        ContractError::Custom(CustomError::RevocationNotDue)
+    );
+    state.take_scheduled_revocation(params.token_id, params.account)?;
+    let notification = revoke_balance(
+        state,
+        logger,
+        RevokeParam {
+            token_id: params.token_id,
+            account: params.account,
+            reason: scheduled.reason,
+        },
+        ctx.sender(),
+        now,
+    )?;
+
+    // Notify the subscribers about the revoked balance.
+    notify_subscribers(host, logger, vec![notification])?;
+
+    Ok(())
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::types::{ContractTokenId, RevocationInfo, ScheduledRevocation};
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const ACCOUNT_2: AccountAddress = AccountAddress([2u8; 32]);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+    const DAY: u64 = 86_400_000;
+
+    /// Mints balances of ACCOUNT_1 and ACCOUNT_2 and schedules the revocation of the one of ACCOUNT_1.
+    fn state_with_scheduled_revocation(
+        state_builder: &mut TestStateBuilder,
+    ) -> State<TestStateApi> {
+        let mut state = State::empty(state_builder);
+        state.add_token(
+            state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        for account in [ACCOUNT_1, ACCOUNT_2] {
+            state
+                .mint(
+                    TOKEN_0,
+                    account,
+                    1.into(),
+                    Timestamp::from_timestamp_millis(100),
+                    None,
+                )
+                .expect("Failed to mint token");
+        }
+        state
+            .schedule_revocation(
+                TOKEN_0,
+                ACCOUNT_1,
+                ScheduledRevocation {
+                    reason: 1,
+                    effective_at: Timestamp::from_timestamp_millis(50),
+                },
+            )
+            .expect("Failed to schedule revocation");
+        state
+    }
+
+    /// Mints a new balance of ACCOUNT_1 which is valid for two days.
+    fn remint(state: &mut State<TestStateApi>) {
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_1,
+                1.into(),
+                Timestamp::from_timestamp_millis(3 * DAY),
+                None,
+            )
+            .expect("Failed to mint token");
+    }
+
+    fn execute_revoke_with(
+        host: &mut TestHost<State<TestStateApi>>,
+        now: u64,
+    ) -> ContractResult<()> {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(ACCOUNT_0));
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(now));
+        let parameter = to_bytes(&CredentialQuery {
+            token_id: TOKEN_0,
+            account: ACCOUNT_1,
+        });
+        ctx.set_parameter(&parameter);
+        execute_revoke(&ctx, host, &mut TestLogger::init())
+    }
+
+    #[concordium_test]
+    fn test_execute_revoke() {
+        let mut state_builder = TestStateBuilder::new();
+        let state = state_with_scheduled_revocation(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+
+        // The revocation can not be executed during the dispute window.
+        assert_eq!(
+            execute_revoke_with(&mut host, 40),
+            Err(ContractError::Custom(CustomError::RevocationNotDue))
+        );
+        assert_eq!(execute_revoke_with(&mut host, 50), Ok(()));
+        let now = Timestamp::from_timestamp_millis(50);
+        assert_eq!(
+            host.state().get_account_balance(TOKEN_0, ACCOUNT_1, now),
+            Ok(0.into())
+        );
+        assert_eq!(
+            host.state().get_revocation(TOKEN_0, ACCOUNT_1),
+            Ok(Some(RevocationInfo {
+                reason: 1,
+                timestamp: now,
+            }))
+        );
+        assert_eq!(
+            host.state().get_scheduled_revocation(TOKEN_0, ACCOUNT_1),
+            Ok(None)
+        );
+    }
+
+    #[concordium_test]
+    fn test_execute_revoke_after_remint() {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = state_with_scheduled_revocation(&mut state_builder);
+        remint(&mut state);
+        let mut host = TestHost::new(state, state_builder);
+
+        // The revocation scheduled for the replaced balance does not apply to the new one.
+        assert_eq!(
+            execute_revoke_with(&mut host, DAY),
+            Err(ContractError::Custom(CustomError::RevocationNotScheduled))
+        );
+    }
+
+    #[concordium_test]
+    fn test_execute_revoke_after_sweep() {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = state_with_scheduled_revocation(&mut state_builder);
+        let (_, removed) = state.prune_expired(10, Timestamp::from_timestamp_millis(DAY));
+        assert_eq!(removed.len(), 2);
+        remint(&mut state);
+        let mut host = TestHost::new(state, state_builder);
+
+        assert_eq!(
+            host.state().get_scheduled_revocation(TOKEN_0, ACCOUNT_1),
+            Ok(None)
+        );
+        assert_eq!(
+            execute_revoke_with(&mut host, DAY),
+            Err(ContractError::Custom(CustomError::RevocationNotScheduled))
+        );
+    }
+
+    #[concordium_test]
+    fn test_execute_revoke_after_remove_chunk() {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = state_with_scheduled_revocation(&mut state_builder);
+        // Removes the balance of ACCOUNT_1, which comes first, and keeps the token.
+        let result = state.clear_balances(TOKEN_0, 1, Timestamp::from_timestamp_millis(DAY));
+        assert_eq!(result, Ok((vec![(ACCOUNT_1, 0.into())], false)));
+        remint(&mut state);
+        let mut host = TestHost::new(state, state_builder);
+
+        assert_eq!(
+            execute_revoke_with(&mut host, DAY),
+            Err(ContractError::Custom(CustomError::RevocationNotScheduled))
+        );
+    }
+}
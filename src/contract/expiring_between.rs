@@ -0,0 +1,197 @@
+use concordium_std::*;
+
+use crate::{
+    contract::balance_of::is_hidden_from_sender,
+    state::State,
+    types::{ContractError, ContractResult, ContractTokenId},
+};
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct ExpiringBetweenParams {
+    /// Balances expiring at or after this time are returned.
+    pub from: Timestamp,
+    /// Balances expiring before this time are returned.
+    pub to: Timestamp,
+    /// Number of balances to skip, used to request the next page.
+    pub skip: u32,
+    /// Maximum number of balances to return.
+    pub limit: u16,
+}
+
+#[derive(Debug, Serialize, SchemaType, PartialEq)]
+pub struct ExpiringBalance {
+    pub token_id: ContractTokenId,
+    pub account: AccountAddress,
+    pub expiry: Timestamp,
+}
+
+#[derive(Debug, Serialize, SchemaType)]
+pub struct ExpiringBetweenResponse {
+    /// Balances ordered by the day of their expiry.
+    #[concordium(size_length = 2)]
+    pub balances: Vec<ExpiringBalance>,
+    /// Whether more balances are left, request them by increasing `skip`.
+    pub more: bool,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "expiringBetween",
+    parameter = "ExpiringBetweenParams",
+    return_value = "ExpiringBetweenResponse",
+    error = "ContractError"
+)]
+/// Gets a page of the balances which expire within a time window.
+/// - Balances hidden by their holder are left out unless the sender is the holder, the admin or
+///   a verifier, see `setVisibility`.
+/// - This function fails if the limit exceeds the configured batch limit of queries.
+pub fn expiring_between<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<ExpiringBetweenResponse> {
+    // Parse the parameter.
+    let params: ExpiringBetweenParams = ctx.parameter_cursor().get()?;
+    let state = host.state();
+    state.ensure_batch_size(|limits| limits.query, params.limit.into())?;
+    let (balances, more) = state.expiring_between(
+        params.from,
+        params.to,
+        params.skip,
+        params.limit,
+        |token_id, account| !is_hidden_from_sender(ctx, state, token_id, account).unwrap_or(true),
+    );
+
+    Ok(ExpiringBetweenResponse {
+        balances: balances
+            .into_iter()
+            .map(|(token_id, account, expiry)| ExpiringBalance {
+                token_id,
+                account,
+                expiry,
+            })
+            .collect(),
+        more,
+    })
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const ACCOUNT_2: AccountAddress = AccountAddress([2u8; 32]);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+    const DAY: u64 = 86_400_000;
+
+    #[concordium_test]
+    fn test_expiring_between() {
+        let mut ctx = TestReceiveContext::empty();
+        let params = ExpiringBetweenParams {
+            from: Timestamp::from_timestamp_millis(DAY),
+            to: Timestamp::from_timestamp_millis(3 * DAY),
+            skip: 0,
+            limit: 1,
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        for (account, expiry) in [
+            (ACCOUNT_0, 2 * DAY),
+            (ACCOUNT_1, DAY / 2),
+            (ACCOUNT_2, 3 * DAY),
+        ] {
+            state
+                .mint(
+                    TOKEN_0,
+                    account,
+                    1.into(),
+                    Timestamp::from_timestamp_millis(expiry),
+                    None,
+                )
+                .expect("Failed to mint token");
+        }
+        let host = TestHost::new(state, state_builder);
+
+        let result = expiring_between(&ctx, &host).unwrap();
+        assert_eq!(
+            result.balances,
+            vec![ExpiringBalance {
+                token_id: TOKEN_0,
+                account: ACCOUNT_0,
+                expiry: Timestamp::from_timestamp_millis(2 * DAY),
+            }]
+        );
+        assert!(!result.more);
+    }
+
+    #[concordium_test]
+    fn test_expiring_between_hidden() {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_1,
+                10.into(),
+                Timestamp::from_timestamp_millis(2 * DAY),
+                None,
+            )
+            .expect("Failed to mint token");
+        state
+            .set_balance_hidden(TOKEN_0, ACCOUNT_1, true)
+            .expect("Failed to hide balance");
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_0,
+                1.into(),
+                Timestamp::from_timestamp_millis(2 * DAY),
+                None,
+            )
+            .expect("Failed to mint token");
+        let host = TestHost::new(state, state_builder);
+        let parameter = to_bytes(&ExpiringBetweenParams {
+            from: Timestamp::from_timestamp_millis(DAY),
+            to: Timestamp::from_timestamp_millis(3 * DAY),
+            skip: 0,
+            limit: 2,
+        });
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(ACCOUNT_2));
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(150));
+        ctx.set_parameter(&parameter);
+
+        let accounts = |ctx: &TestReceiveContext| {
+            expiring_between(ctx, &host)
+                .expect("Failed to query expiring balances")
+                .balances
+                .into_iter()
+                .map(|balance| balance.account)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(accounts(&ctx), vec![ACCOUNT_0]);
+        ctx.set_sender(Address::Account(ACCOUNT_1));
+        assert_eq!(accounts(&ctx).len(), 2);
+    }
+}
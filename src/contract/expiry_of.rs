@@ -1,9 +1,22 @@
 use concordium_std::*;
 
-use crate::{errors::CustomError, state::State, types::*};
+use crate::{
+    contract::balance_of::is_hidden_from_sender, errors::CustomError, state::State, types::*,
+};
+
+/// Expiry of the balance of a token for an account.
+#[derive(Debug, Serialize, SchemaType, PartialEq)]
+pub enum ExpiryOfResult {
+    /// The token does not exist.
+    NoSuchToken,
+    /// The account does not have a balance of the token.
+    NoBalance,
+    /// The expiry of the balance.
+    Expiry(Timestamp),
+}
 
 #[derive(Debug, Serialize, SchemaType)]
-pub struct ExpiryOfQueryResponse(#[concordium(size_length = 2)] pub Vec<Option<Timestamp>>);
+pub struct ExpiryOfQueryResponse(#[concordium(size_length = 2)] pub Vec<ExpiryOfResult>);
 
 #[receive(
     contract = "cis2_dsid",
@@ -12,6 +25,11 @@ pub struct ExpiryOfQueryResponse(#[concordium(size_length = 2)] pub Vec<Option<T
     return_value = "ExpiryOfQueryResponse",
     error = "ContractError"
 )]
+/// Gets the expiry of the balance for each of the queried tokens and accounts.
+/// Unknown tokens are reported as `NoSuchToken` instead of failing the whole query.
+/// Balances which expired longer ago than the configured duration are reported as `NoBalance`.
+/// Balances hidden by their holder are reported as `NoBalance`, see `balanceOf`.
+/// - This function fails if any of the addresses is a contract.
 pub fn expiry_of<S: HasStateApi>(
     ctx: &impl HasReceiveContext,
     host: &impl HasHost<State<S>, StateApiType = S>,
@@ -19,14 +37,32 @@ pub fn expiry_of<S: HasStateApi>(
     // Parse the parameter.
     let params: ContractExpiryOfQueryParams = ctx.parameter_cursor().get()?;
     let state = host.state();
-    let response: Vec<Option<Timestamp>> = params
+    state.ensure_batch_size(|limits| limits.query, params.queries.len())?;
+    let now = ctx.metadata().slot_time();
+    let response: Vec<ExpiryOfResult> = params
         .queries
         .iter()
         .map(|q| match q.address {
-            Address::Account(address) => state.get_account_balance_expiry(q.token_id, address),
+            Address::Account(address) => {
+                match state.get_account_balance_expiry(q.token_id, address) {
+                    Ok(Some(expiry)) if state.config().hides_expiry(now, expiry) => {
+                        Ok(ExpiryOfResult::NoBalance)
+                    }
+                    Ok(Some(expiry)) => {
+                        if is_hidden_from_sender(ctx, state, &q.token_id, address)? {
+                            Ok(ExpiryOfResult::NoBalance)
+                        } else {
+                            Ok(ExpiryOfResult::Expiry(expiry))
+                        }
+                    }
+                    Ok(None) => Ok(ExpiryOfResult::NoBalance),
+                    Err(ContractError::InvalidTokenId) => Ok(ExpiryOfResult::NoSuchToken),
+                    Err(error) => Err(error),
+                }
+            }
             Address::Contract(_) => Err(ContractError::Custom(CustomError::AccountsOnly)),
         })
-        .collect::<Result<Vec<Option<Timestamp>>, ContractError>>()?;
+        .collect::<Result<Vec<ExpiryOfResult>, ContractError>>()?;
 
     let result = ExpiryOfQueryResponse(response);
     Ok(result)
@@ -40,8 +76,10 @@ mod tests {
 
     const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
     const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const ACCOUNT_2: AccountAddress = AccountAddress([2u8; 32]);
     const TOKEN_0: ContractTokenId = TokenIdU8(2);
     const TOKEN_1: ContractTokenId = TokenIdU8(3);
+    const TOKEN_2: ContractTokenId = TokenIdU8(4);
 
     #[concordium_test]
     fn test_expiry_of() {
@@ -65,6 +103,10 @@ mod tests {
                     address: concordium_std::Address::Account(ACCOUNT_1),
                     token_id: TOKEN_1,
                 },
+                ContractExpiryOfQuery {
+                    address: concordium_std::Address::Account(ACCOUNT_1),
+                    token_id: TOKEN_2,
+                },
             ],
         };
         let parameter = &to_bytes(&params);
@@ -97,6 +139,7 @@ mod tests {
                 ACCOUNT_0,
                 10.into(),
                 Timestamp::from_timestamp_millis(100),
+                None,
             )
             .unwrap();
         state
@@ -105,6 +148,7 @@ mod tests {
                 ACCOUNT_0,
                 20.into(),
                 Timestamp::from_timestamp_millis(200),
+                None,
             )
             .unwrap();
         state
@@ -113,6 +157,7 @@ mod tests {
                 ACCOUNT_1,
                 30.into(),
                 Timestamp::from_timestamp_millis(300),
+                None,
             )
             .unwrap();
 
@@ -121,11 +166,127 @@ mod tests {
         assert_eq!(
             result.0,
             vec![
-                Some(Timestamp::from_timestamp_millis(100)),
-                Some(Timestamp::from_timestamp_millis(200)),
-                Some(Timestamp::from_timestamp_millis(300)),
+                ExpiryOfResult::Expiry(Timestamp::from_timestamp_millis(100)),
+                ExpiryOfResult::Expiry(Timestamp::from_timestamp_millis(200)),
+                ExpiryOfResult::Expiry(Timestamp::from_timestamp_millis(300)),
+                ExpiryOfResult::NoBalance,
+                // Unknown tokens do not fail the query.
+                ExpiryOfResult::NoSuchToken,
+            ]
+        );
+    }
+
+    #[concordium_test]
+    fn test_expiry_of_hide_expired_after() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(150));
+        let params = ContractExpiryOfQueryParams {
+            queries: vec![
+                ContractExpiryOfQuery {
+                    address: concordium_std::Address::Account(ACCOUNT_0),
+                    token_id: TOKEN_0,
+                },
+                ContractExpiryOfQuery {
+                    address: concordium_std::Address::Account(ACCOUNT_1),
+                    token_id: TOKEN_0,
+                },
+            ],
+        };
+        let parameter = &to_bytes(&params);
+        ctx.set_parameter(parameter);
+
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.set_config(ContractConfig {
+            hide_expired_after: Some(Duration::from_millis(50)),
+            ..Default::default()
+        });
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_0,
+                10.into(),
+                Timestamp::from_timestamp_millis(100),
                 None,
+            )
+            .unwrap();
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_1,
+                10.into(),
+                Timestamp::from_timestamp_millis(120),
+                None,
+            )
+            .unwrap();
+
+        let host = TestHost::new(state, state_builder);
+        let result = expiry_of(&ctx, &host).unwrap();
+        assert_eq!(
+            result.0,
+            vec![
+                // Expired 50ms ago, which is hidden.
+                ExpiryOfResult::NoBalance,
+                ExpiryOfResult::Expiry(Timestamp::from_timestamp_millis(120)),
             ]
         );
     }
+
+    #[concordium_test]
+    fn test_expiry_of_hidden() {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_1,
+                10.into(),
+                Timestamp::from_timestamp_millis(200),
+                None,
+            )
+            .expect("Failed to mint token");
+        state
+            .set_balance_hidden(TOKEN_0, ACCOUNT_1, true)
+            .expect("Failed to hide balance");
+        let host = TestHost::new(state, state_builder);
+        let parameter = to_bytes(&ContractExpiryOfQueryParams {
+            queries: vec![ContractExpiryOfQuery {
+                address: Address::Account(ACCOUNT_1),
+                token_id: TOKEN_0,
+            }],
+        });
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(ACCOUNT_2));
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(150));
+        ctx.set_parameter(&parameter);
+
+        assert_eq!(
+            expiry_of(&ctx, &host).map(|response| response.0),
+            Ok(vec![ExpiryOfResult::NoBalance])
+        );
+        ctx.set_sender(Address::Account(ACCOUNT_1));
+        assert_eq!(
+            expiry_of(&ctx, &host).map(|response| response.0),
+            Ok(vec![ExpiryOfResult::Expiry(
+                Timestamp::from_timestamp_millis(200)
+            )])
+        );
+    }
 }
@@ -0,0 +1,256 @@
+use concordium_std::*;
+
+use crate::{
+    contract::balance_of::is_hidden_from_sender,
+    errors::CustomError,
+    state::State,
+    types::{ContractError, ContractResult, ContractTokenAmount, ContractTokenId},
+};
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct ExportBalancesParams {
+    /// Balance to continue at, the cursor returned by the previous call.
+    pub cursor: Option<(ContractTokenId, AccountAddress)>,
+    /// Maximum number of balances to return.
+    pub limit: u16,
+    /// Rolling hash returned by the previous call, all zeros for the first call.
+    pub hash: HashSha2256,
+}
+
+/// Balance as stored in the state, including expired and frozen balances.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct ExportedBalance {
+    pub token_id: ContractTokenId,
+    pub account: AccountAddress,
+    pub amount: ContractTokenAmount,
+    pub expiry: Timestamp,
+    pub valid_from: Option<Timestamp>,
+    pub frozen: bool,
+}
+
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct ExportBalancesResponse {
+    pub balances: Vec<ExportedBalance>,
+    /// Cursor for the next call, None once all balances have been returned.
+    pub cursor: Option<(ContractTokenId, AccountAddress)>,
+    /// SHA-256 hash of the previous hash and each returned balance, chained in order.
+    pub hash: HashSha2256,
+    /// Number of balances of this chunk left out because they are hidden from the sender.
+    pub skipped: u16,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "exportBalances",
+    parameter = "ExportBalancesParams",
+    return_value = "ExportBalancesResponse",
+    error = "ContractError",
+    crypto_primitives
+)]
+/// Gets the balances of all tokens in a stable order, a chunk at a time, e.g. for disaster recovery.
+/// Call again with the returned cursor and hash until the cursor is None. The final hash covers
+/// every returned balance, so the caller can check its copy of the export; the contract does not
+/// store the hash.
+/// - Balances hidden by their holder are left out unless the sender is the holder, the admin or a
+///   verifier, see `setVisibility`. The number of left out balances is returned as `skipped`.
+/// - This function fails if the limit is 0.
+/// - This function fails if the limit exceeds the configured batch limit.
+pub fn export_balances<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+    crypto_primitives: &impl HasCryptoPrimitives,
+) -> ContractResult<ExportBalancesResponse> {
+    let params: ExportBalancesParams = ctx.parameter_cursor().get()?;
+    ensure!(
+        params.limit > 0,
+        ContractError::Custom(CustomError::InvalidLimit)
+    );
+    let state = host.state();
+    state.ensure_batch_size(|limits| limits.query, usize::from(params.limit))?;
+
+    let (balances, cursor) = state.export_balances(params.cursor, params.limit);
+    let mut hash = params.hash;
+    let mut exported_balances = Vec::with_capacity(balances.len());
+    let mut skipped = 0;
+    for (token_id, account, balance) in balances {
+        if is_hidden_from_sender(ctx, state, &token_id, account)? {
+            skipped += 1;
+            continue;
+        }
+        let exported = ExportedBalance {
+            token_id,
+            account,
+            amount: balance.amount,
+            expiry: balance.expiry,
+            valid_from: balance.valid_from,
+            frozen: balance.frozen,
+        };
+        hash = crypto_primitives.hash_sha2_256(&[&hash.0[..], &to_bytes(&exported)].concat());
+        exported_balances.push(exported);
+    }
+
+    Ok(ExportBalancesResponse {
+        balances: exported_balances,
+        cursor,
+        hash,
+        skipped,
+    })
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::state::canonical_account;
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const ACCOUNT_2: AccountAddress = AccountAddress([2u8; 32]);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+    const TOKEN_1: ContractTokenId = TokenIdU8(3);
+
+    fn export_balances_with(
+        host: &TestHost<State<TestStateApi>>,
+        cursor: Option<(ContractTokenId, AccountAddress)>,
+    ) -> ContractResult<ExportBalancesResponse> {
+        let mut ctx = TestReceiveContext::empty();
+        let params = ExportBalancesParams {
+            cursor,
+            limit: 2,
+            hash: HashSha2256([0; 32]),
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let crypto_primitives = TestCryptoPrimitives::new();
+        crypto_primitives.setup_hash_sha2_256_mock(|_| HashSha2256([1; 32]));
+        export_balances(&ctx, host, &crypto_primitives)
+    }
+
+    #[concordium_test]
+    fn test_export_balances() {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        for token_id in [TOKEN_0, TOKEN_1] {
+            state.add_token(
+                &mut state_builder,
+                token_id,
+                MetadataUrl {
+                    url: format!("https://example.com/{}", token_id.0),
+                    hash: None,
+                },
+            );
+            for account in [ACCOUNT_0, ACCOUNT_1] {
+                state
+                    .mint(
+                        token_id,
+                        account,
+                        1.into(),
+                        Timestamp::from_timestamp_millis(100),
+                        None,
+                    )
+                    .expect("Failed to mint token");
+            }
+        }
+        let host = TestHost::new(state, state_builder);
+
+        let first = export_balances_with(&host, None).expect("Failed to export balances");
+        assert_eq!(
+            first
+                .balances
+                .iter()
+                .map(|balance| (balance.token_id, balance.account))
+                .collect::<Vec<_>>(),
+            vec![
+                (TOKEN_0, ACCOUNT_0),
+                (TOKEN_0, canonical_account(ACCOUNT_1))
+            ]
+        );
+        assert_eq!(first.cursor, Some((TOKEN_1, ACCOUNT_0)));
+        assert_eq!(first.hash, HashSha2256([1; 32]));
+
+        let second = export_balances_with(&host, first.cursor).expect("Failed to export balances");
+        assert_eq!(
+            second
+                .balances
+                .iter()
+                .map(|balance| (balance.token_id, balance.account))
+                .collect::<Vec<_>>(),
+            vec![
+                (TOKEN_1, ACCOUNT_0),
+                (TOKEN_1, canonical_account(ACCOUNT_1))
+            ]
+        );
+        assert_eq!(second.cursor, None);
+    }
+
+    #[concordium_test]
+    fn test_export_balances_hidden() {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_1,
+                10.into(),
+                Timestamp::from_timestamp_millis(200),
+                None,
+            )
+            .expect("Failed to mint token");
+        state
+            .set_balance_hidden(TOKEN_0, ACCOUNT_1, true)
+            .expect("Failed to hide balance");
+        let host = TestHost::new(state, state_builder);
+        let parameter = to_bytes(&ExportBalancesParams {
+            cursor: None,
+            limit: 2,
+            hash: HashSha2256([0; 32]),
+        });
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(ACCOUNT_2));
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(150));
+        ctx.set_parameter(&parameter);
+        let crypto_primitives = TestCryptoPrimitives::new();
+        crypto_primitives.setup_hash_sha2_256_mock(|_| HashSha2256([1; 32]));
+
+        // Hidden balances are left out for others, the admin exports them.
+        let response =
+            export_balances(&ctx, &host, &crypto_primitives).expect("Failed to export balances");
+        assert_eq!(response.balances, vec![]);
+        assert_eq!(response.hash, HashSha2256([0; 32]));
+        assert_eq!(response.skipped, 1);
+        ctx.set_sender(Address::Account(ACCOUNT_0));
+        let response =
+            export_balances(&ctx, &host, &crypto_primitives).expect("Failed to export balances");
+        assert_eq!(response.balances.len(), 1);
+        assert_eq!(response.skipped, 0);
+    }
+
+    #[concordium_test]
+    fn test_export_balances_zero_limit() {
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let host = TestHost::new(state, state_builder);
+        let mut ctx = TestReceiveContext::empty();
+        let parameter = to_bytes(&ExportBalancesParams {
+            cursor: None,
+            limit: 0,
+            hash: HashSha2256([0; 32]),
+        });
+        ctx.set_parameter(&parameter);
+        let result = export_balances(&ctx, &host, &TestCryptoPrimitives::new());
+        assert_eq!(
+            result,
+            Err(ContractError::Custom(CustomError::InvalidLimit))
+        );
+    }
+}
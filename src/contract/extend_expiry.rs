@@ -0,0 +1,235 @@
+use concordium_cis2::Cis2Error;
+use concordium_std::*;
+
+use crate::{
+    contract::update_subscribers::{notify_subscribers, DsidNotification},
+    errors::CustomError,
+    state::State,
+    types::{
+        BalanceChange, ContractError, ContractEvent, ContractResult, ContractTokenId,
+        DsidRenewEvent, HistoryEntry,
+    },
+};
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct ExtendExpiryParams {
+    pub token_id: ContractTokenId,
+    /// Balances expiring on the same day (UTC) as this time are extended.
+    pub bucket: Timestamp,
+    /// The new expiry of the balances.
+    pub expiry: Timestamp,
+    /// Account to continue at, the cursor returned by the previous call.
+    pub cursor: Option<AccountAddress>,
+    /// Maximum number of balances to visit.
+    pub limit: u16,
+}
+
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct ExtendExpiryResponse {
+    /// Number of extended balances.
+    pub extended: u32,
+    /// Cursor for the next call, None once the whole bucket has been visited.
+    pub cursor: Option<AccountAddress>,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "extendExpiry",
+    parameter = "ExtendExpiryParams",
+    return_value = "ExtendExpiryResponse",
+    error = "ContractError",
+    enable_logger,
+    mutable
+)]
+/// Extends the expiry of all balances of a token expiring on the same day, e.g. to renew a cohort.
+/// Balances are visited in chunks of `limit`, call again with the returned cursor until it is None.
+/// Balances which already expire at or after the new expiry, or whose hard expiry is before it, are kept.
+/// - This function fails if the token has an issuer and the sender is not the issuer.
+/// - This function fails if the token has no issuer and the sender is not the admin of the contract.
+/// - This function fails if the limit exceeds the configured batch limit.
+/// - This function fails if the token does not exist.
+/// - This function fails if minting of the token is paused or the token has been deprecated.
+/// - This function fails if the expiry is in the past or exceeds the maximum validity of the contract configuration.
+pub fn extend_expiry<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<ExtendExpiryResponse> {
+    let params: ExtendExpiryParams = ctx.parameter_cursor().get()?;
+    let now = ctx.metadata().slot_time();
+    let is_owner = host.state().is_contract_admin(&ctx.sender(), ctx.owner());
+    let state = host.state_mut();
+
+    // Check that the sender is allowed to renew the balances.
+    ensure!(
+        state.can_issue(&params.token_id, &ctx.sender(), is_owner)?,
+        ContractError::Unauthorized
+    );
+    state.ensure_batch_size(|limits| limits.admin, usize::from(params.limit))?;
+    ensure!(
+        !state.is_token_paused(&params.token_id)?,
+        Cis2Error::Custom(CustomError::TokenPaused)
+    );
+    ensure!(
+        !state.is_token_deprecated(&params.token_id)?,
+        Cis2Error::Custom(CustomError::TokenDeprecated)
+    );
+    let expiry = state.config().round_expiry(params.expiry);
+    ensure!(expiry > now, Cis2Error::Custom(CustomError::TokenExpired));
+    ensure!(
+        !state.config().exceeds_max_validity(now, expiry),
+        Cis2Error::Custom(CustomError::ValidityTooLong)
+    );
+
+    let (extended, cursor) = state.extend_expiry_cohort(
+        params.token_id,
+        params.bucket,
+        expiry,
+        params.cursor,
+        params.limit,
+    )?;
+    let mut notifications = Vec::new();
+    for (account, amount) in extended {
+        state.record_history(
+            params.token_id,
+            account,
+            HistoryEntry {
+                timestamp: now,
+                change: BalanceChange::Renew,
+                amount,
+                expiry,
+                actor: ctx.sender(),
+            },
+        )?;
+        // Log the new expiry for indexers.
+        logger.log(&ContractEvent::DsidRenew(DsidRenewEvent {
+            token_id: params.token_id,
+            owner: Address::Account(account),
+            expiry,
+            renewer: ctx.sender(),
+        }))?;
+        notifications.push(DsidNotification {
+            token_id: params.token_id,
+            account,
+            amount,
+            expiry,
+        });
+    }
+    let response = ExtendExpiryResponse {
+        extended: notifications.len() as u32,
+        cursor,
+    };
+
+    // Notify the subscribers about the new expiries.
+    notify_subscribers(host, logger, notifications)?;
+
+    Ok(response)
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::state::canonical_account;
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const ACCOUNT_2: AccountAddress = AccountAddress([2u8; 32]);
+    const ADDRESS_0: Address = Address::Account(ACCOUNT_0);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+    const DAY: u64 = 86_400_000;
+
+    fn extend_expiry_with(
+        host: &mut TestHost<State<TestStateApi>>,
+        sender: Address,
+        cursor: Option<AccountAddress>,
+    ) -> ContractResult<ExtendExpiryResponse> {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(sender);
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(DAY));
+        let params = ExtendExpiryParams {
+            token_id: TOKEN_0,
+            bucket: Timestamp::from_timestamp_millis(3 * DAY),
+            expiry: Timestamp::from_timestamp_millis(10 * DAY),
+            cursor,
+            limit: 1,
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut logger = TestLogger::init();
+        extend_expiry(&ctx, host, &mut logger)
+    }
+
+    fn host_with_cohort() -> TestHost<State<TestStateApi>> {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        for (account, expiry) in [
+            (ACCOUNT_0, 3 * DAY + 1),
+            (ACCOUNT_1, 3 * DAY + 2),
+            (ACCOUNT_2, 4 * DAY),
+        ] {
+            state
+                .mint(
+                    TOKEN_0,
+                    account,
+                    1.into(),
+                    Timestamp::from_timestamp_millis(expiry),
+                    None,
+                )
+                .expect("Failed to mint token");
+        }
+        TestHost::new(state, state_builder)
+    }
+
+    #[concordium_test]
+    fn test_extend_expiry() {
+        let mut host = host_with_cohort();
+
+        let result = extend_expiry_with(&mut host, ADDRESS_0, None);
+        assert_eq!(
+            result,
+            Ok(ExtendExpiryResponse {
+                extended: 1,
+                cursor: Some(canonical_account(ACCOUNT_1)),
+            })
+        );
+        let result = extend_expiry_with(&mut host, ADDRESS_0, Some(ACCOUNT_1));
+        assert_eq!(
+            result,
+            Ok(ExtendExpiryResponse {
+                extended: 1,
+                cursor: None,
+            })
+        );
+
+        let state = host.state();
+        for account in [ACCOUNT_0, ACCOUNT_1] {
+            assert_eq!(
+                state.get_account_balance_expiry(TOKEN_0, account),
+                Ok(Some(Timestamp::from_timestamp_millis(10 * DAY)))
+            );
+        }
+        // Balances of other days are not extended.
+        assert_eq!(
+            state.get_account_balance_expiry(TOKEN_0, ACCOUNT_2),
+            Ok(Some(Timestamp::from_timestamp_millis(4 * DAY)))
+        );
+    }
+
+    #[concordium_test]
+    fn test_extend_expiry_unauthorized() {
+        let mut host = host_with_cohort();
+        let result = extend_expiry_with(&mut host, Address::Account(ACCOUNT_1), None);
+        assert_eq!(result, Err(ContractError::Unauthorized));
+    }
+}
@@ -0,0 +1,231 @@
+use concordium_std::*;
+
+use crate::{
+    contract::{
+        balance_of::is_hidden_from_sender,
+        category_score_of::{CategoryScoreQueryParams, CategoryScoreQueryResponse},
+    },
+    errors::CustomError,
+    state::State,
+    types::{ContractError, ContractResult},
+};
+
+const CATEGORY_SCORE_ENTRYPOINT: &str = "categoryScoreOf";
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "federatedScoreOf",
+    parameter = "CategoryScoreQueryParams",
+    return_value = "CategoryScoreQueryResponse",
+    error = "ContractError",
+    mutable
+)]
+/// Gets the sum of the balances of an account over all tokens of a category,
+/// summed over this contract and the sibling contracts of the federation.
+/// The siblings are queried using their `categoryScoreOf` entrypoint.
+/// - Expired balances and balances which are not valid yet are not included.
+/// - Balances hidden by their holder are not included unless the sender is the holder, the admin
+///   or a verifier, see `setVisibility`.
+/// - This function fails if any of the siblings fails or returns an invalid response.
+pub fn federated_score_of<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<CategoryScoreQueryResponse> {
+    // Parse the parameter.
+    let params: CategoryScoreQueryParams = ctx.parameter_cursor().get()?;
+    let state = host.state();
+    state.ensure_batch_size(|limits| limits.query, params.queries.len())?;
+    let now = ctx.metadata().slot_time();
+    let mut scores: Vec<u64> = params
+        .queries
+        .iter()
+        .map(|q| {
+            state.get_category_score(q.category, q.account, now, |token_id| {
+                !is_hidden_from_sender(ctx, state, token_id, q.account).unwrap_or(true)
+            })
+        })
+        .collect();
+
+    let federation = state.config().federation.clone();
+    for sibling in federation {
+        let (_, return_value) = host
+            .invoke_contract(
+                &sibling,
+                &params,
+                EntrypointName::new_unchecked(CATEGORY_SCORE_ENTRYPOINT),
+                Amount::zero(),
+            )
+            .map_err(|_| ContractError::Custom(CustomError::FederationFailed))?;
+        let response: CategoryScoreQueryResponse = return_value
+            .ok_or(ContractError::Custom(CustomError::FederationFailed))?
+            .get()
+            .map_err(|_| ContractError::Custom(CustomError::FederationFailed))?;
+        ensure!(
+            response.0.len() == scores.len(),
+            ContractError::Custom(CustomError::FederationFailed)
+        );
+        for (score, sibling_score) in scores.iter_mut().zip(response.0) {
+            *score = score.saturating_add(sibling_score);
+        }
+    }
+
+    Ok(CategoryScoreQueryResponse(scores))
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::{
+        contract::category_score_of::CategoryScoreQuery,
+        types::{ContractConfig, ContractTokenId},
+    };
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const ACCOUNT_2: AccountAddress = AccountAddress([2u8; 32]);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+    const SIBLING_0: ContractAddress = ContractAddress {
+        index: 2,
+        subindex: 0,
+    };
+    const SIBLING_1: ContractAddress = ContractAddress {
+        index: 3,
+        subindex: 0,
+    };
+
+    #[concordium_test]
+    fn test_federated_score_of() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(10));
+        let params = CategoryScoreQueryParams {
+            queries: vec![CategoryScoreQuery {
+                category: 1,
+                account: ACCOUNT_0,
+            }],
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        state
+            .set_token_category(TOKEN_0, 1)
+            .expect("Failed to set category");
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_0,
+                5.into(),
+                Timestamp::from_timestamp_millis(100),
+                None,
+            )
+            .expect("Failed to mint token");
+        state.set_config(ContractConfig {
+            federation: vec![SIBLING_0, SIBLING_1],
+            ..Default::default()
+        });
+        let mut host = TestHost::new(state, state_builder);
+        for (sibling, score) in [(SIBLING_0, 3), (SIBLING_1, 2)] {
+            host.setup_mock_entrypoint(
+                sibling,
+                OwnedEntrypointName::new_unchecked(CATEGORY_SCORE_ENTRYPOINT.into()),
+                MockFn::returning_ok(CategoryScoreQueryResponse(vec![score])),
+            );
+        }
+
+        let result = federated_score_of(&ctx, &mut host);
+        assert_eq!(result.map(|response| response.0), Ok(vec![10]));
+    }
+
+    #[concordium_test]
+    fn test_federated_score_of_invalid_response() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(10));
+        let params = CategoryScoreQueryParams {
+            queries: vec![CategoryScoreQuery {
+                category: 1,
+                account: ACCOUNT_0,
+            }],
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.set_config(ContractConfig {
+            federation: vec![SIBLING_0],
+            ..Default::default()
+        });
+        let mut host = TestHost::new(state, state_builder);
+        host.setup_mock_entrypoint(
+            SIBLING_0,
+            OwnedEntrypointName::new_unchecked(CATEGORY_SCORE_ENTRYPOINT.into()),
+            MockFn::returning_ok(CategoryScoreQueryResponse(vec![])),
+        );
+
+        let result = federated_score_of(&ctx, &mut host);
+        assert_eq!(
+            result.map(|response| response.0),
+            Err(ContractError::Custom(CustomError::FederationFailed))
+        );
+    }
+
+    #[concordium_test]
+    fn test_federated_score_of_hidden() {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_1,
+                10.into(),
+                Timestamp::from_timestamp_millis(200),
+                None,
+            )
+            .expect("Failed to mint token");
+        state
+            .set_balance_hidden(TOKEN_0, ACCOUNT_1, true)
+            .expect("Failed to hide balance");
+        state
+            .set_token_category(TOKEN_0, 1)
+            .expect("Failed to set category");
+        let mut host = TestHost::new(state, state_builder);
+        let parameter = to_bytes(&CategoryScoreQueryParams {
+            queries: vec![CategoryScoreQuery {
+                category: 1,
+                account: ACCOUNT_1,
+            }],
+        });
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(ACCOUNT_2));
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(150));
+        ctx.set_parameter(&parameter);
+
+        assert_eq!(
+            federated_score_of(&ctx, &mut host).map(|response| response.0),
+            Ok(vec![0])
+        );
+        ctx.set_sender(Address::Account(ACCOUNT_1));
+        assert_eq!(
+            federated_score_of(&ctx, &mut host).map(|response| response.0),
+            Ok(vec![10])
+        );
+    }
+}
@@ -0,0 +1,119 @@
+use concordium_cis2::{TokenAmountU64, TokenIdVec};
+use concordium_std::*;
+
+use crate::{
+    state::State,
+    types::{ContractError, ContractResult},
+};
+
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct FeeLedgerEntry {
+    /// Contract of the fee token.
+    pub contract: ContractAddress,
+    pub token_id: TokenIdVec,
+    /// Sum of the fees collected in the token.
+    pub amount: TokenAmountU64,
+}
+
+#[derive(Debug, Serialize, SchemaType)]
+pub struct FeeLedgerResponse {
+    /// Renewal fees in CCD collected by `renew` and not withdrawn yet.
+    pub renewal_fees: Amount,
+    /// Fees collected in each fee token.
+    #[concordium(size_length = 2)]
+    pub tokens: Vec<FeeLedgerEntry>,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "feeLedger",
+    return_value = "FeeLedgerResponse",
+    error = "ContractError"
+)]
+/// Gets the renewal fees in CCD held by the contract and the fees collected by `renewWithToken`
+/// for each fee token ever configured.
+pub fn fee_ledger<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<FeeLedgerResponse> {
+    let state = host.state();
+    let tokens = state
+        .fee_ledger()
+        .into_iter()
+        .map(|(contract, token_id, amount)| FeeLedgerEntry {
+            contract,
+            token_id,
+            amount,
+        })
+        .collect();
+
+    Ok(FeeLedgerResponse {
+        renewal_fees: state.collected_renewal_fees(),
+        tokens,
+    })
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::types::FeeToken;
+    use concordium_std::test_infrastructure::*;
+
+    const FEE_CONTRACT: ContractAddress = ContractAddress {
+        index: 1,
+        subindex: 0,
+    };
+
+    fn fee_token(token_id: u8, amount: u64) -> FeeToken {
+        FeeToken {
+            contract: FEE_CONTRACT,
+            token_id: TokenIdVec(vec![token_id]),
+            amount: TokenAmountU64(amount),
+            recipient: AccountAddress([0u8; 32]),
+        }
+    }
+
+    #[concordium_test]
+    fn test_fee_ledger() {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        // Fees accumulate per fee token, also across changes of the fee.
+        state.credit_fee(&fee_token(0, 5));
+        state.credit_fee(&fee_token(0, 7));
+        state.credit_fee(&fee_token(1, 3));
+        state.credit_renewal_fee(Amount::from_micro_ccd(2));
+        state.credit_renewal_fee(Amount::from_micro_ccd(4));
+        let host = TestHost::new(state, state_builder);
+
+        let response =
+            fee_ledger(&TestReceiveContext::empty(), &host).expect("Failed to read the fee ledger");
+        assert_eq!(response.renewal_fees, Amount::from_micro_ccd(6));
+        assert_eq!(
+            response.tokens,
+            vec![
+                FeeLedgerEntry {
+                    contract: FEE_CONTRACT,
+                    token_id: TokenIdVec(vec![0]),
+                    amount: TokenAmountU64(12),
+                },
+                FeeLedgerEntry {
+                    contract: FEE_CONTRACT,
+                    token_id: TokenIdVec(vec![1]),
+                    amount: TokenAmountU64(3),
+                },
+            ]
+        );
+    }
+
+    #[concordium_test]
+    fn test_fee_ledger_empty() {
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let host = TestHost::new(state, state_builder);
+
+        let response =
+            fee_ledger(&TestReceiveContext::empty(), &host).expect("Failed to read the fee ledger");
+        assert_eq!(response.renewal_fees, Amount::zero());
+        assert_eq!(response.tokens, vec![]);
+    }
+}
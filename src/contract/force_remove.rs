@@ -0,0 +1,189 @@
+use concordium_std::*;
+
+use crate::{
+    contract::{
+        approve_action::ensure_approved,
+        remove::remove_token,
+        revoke::log_burn,
+        update_subscribers::{notify_subscribers, DsidNotification},
+    },
+    state::State,
+    types::{ContractError, ContractResult, ContractTokenAmount, ContractTokenId, REMOVED_REASON},
+};
+
+#[derive(SchemaType, Deserial, Serial)]
+pub struct ForceRemoveParams {
+    pub token_id: ContractTokenId,
+    /// Maximum number of balances which are removed in this call.
+    pub max_entries: u32,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "forceRemove",
+    parameter = "ForceRemoveParams",
+    return_value = "bool",
+    error = "ContractError",
+    enable_logger,
+    crypto_primitives,
+    mutable
+)]
+/// Burns up to `max_entries` remaining balances of a token and removes the token once no balances are left.
+/// Returns true if the token has been removed, otherwise the call has to be repeated.
+/// - This function fails if the token does not exist.
+/// - This function fails if the sender is not the admin of the contract.
+/// - This function fails if approvals are required and the invocation has not been approved by enough admins.
+pub fn force_remove<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    crypto_primitives: &impl HasCryptoPrimitives,
+) -> ContractResult<bool> {
+    // Check that the sender is the admin of the contract.
+    ensure!(
+        host.state().is_contract_admin(&ctx.sender(), ctx.owner()),
+        ContractError::Unauthorized
+    );
+
+    let params: ForceRemoveParams = ctx.parameter_cursor().get()?;
+    let now = ctx.metadata().slot_time();
+    let state = host.state_mut();
+    ensure_approved(ctx, state, crypto_primitives, "forceRemove")?;
+    let (removed, done) = state.clear_balances(params.token_id, params.max_entries, now)?;
+
+    let mut notifications = Vec::new();
+    for (account, amount) in removed {
+        if amount == ContractTokenAmount::from(0) {
+            continue;
+        }
+        // Log the burned tokens.
+        state.record_burn(params.token_id, amount)?;
+        log_burn(logger, params.token_id, account, amount, REMOVED_REASON)?;
+        notifications.push(DsidNotification {
+            token_id: params.token_id,
+            account,
+            amount: ContractTokenAmount::from(0),
+            expiry: now,
+        });
+    }
+
+    if done {
+        // No balances are left, remove the token.
+        remove_token(state, logger, params.token_id, now)?;
+    }
+
+    // Notify the subscribers about the burned balances.
+    notify_subscribers(host, logger, notifications)?;
+
+    Ok(done)
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::{
+        state::canonical_account,
+        types::{ContractEvent, DsidBurnEvent},
+    };
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const ACCOUNT_2: AccountAddress = AccountAddress([2u8; 32]);
+    const ADDRESS_0: Address = Address::Account(ACCOUNT_0);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+
+    #[concordium_test]
+    fn test_force_remove() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(100));
+        let params = ForceRemoveParams {
+            token_id: TOKEN_0,
+            max_entries: 1,
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_1,
+                10.into(),
+                Timestamp::from_timestamp_millis(200),
+                None,
+            )
+            .expect("Failed to mint token");
+        // Expired balances are removed without a burn event.
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_2,
+                20.into(),
+                Timestamp::from_timestamp_millis(50),
+                None,
+            )
+            .expect("Failed to mint token");
+        let mut host = TestHost::new(state, state_builder);
+        let mut logger = TestLogger::init();
+
+        // The first call removes one balance.
+        let result = force_remove(&ctx, &mut host, &mut logger, &TestCryptoPrimitives::new());
+        assert_eq!(result, Ok(false));
+        assert!(host.state().has_token(TOKEN_0));
+        assert_eq!(
+            logger.logs,
+            vec![
+                to_bytes(&Cis2Event::Burn::<_, ContractTokenAmount>(BurnEvent {
+                    token_id: TOKEN_0,
+                    // Balances are stored under the canonical alias of the account.
+                    owner: Address::Account(canonical_account(ACCOUNT_1)),
+                    amount: 10.into(),
+                })),
+                to_bytes(&ContractEvent::DsidBurn(DsidBurnEvent {
+                    token_id: TOKEN_0,
+                    owner: Address::Account(canonical_account(ACCOUNT_1)),
+                    amount: 10.into(),
+                    reason: REMOVED_REASON,
+                })),
+            ]
+        );
+
+        // The second call removes the last balance and the token.
+        let result = force_remove(&ctx, &mut host, &mut logger, &TestCryptoPrimitives::new());
+        assert_eq!(result, Ok(true));
+        assert!(!host.state().has_token(TOKEN_0));
+        // The empty token metadata is logged.
+        assert_eq!(logger.logs.len(), 3);
+    }
+
+    #[concordium_test]
+    fn test_force_remove_not_owner() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_1);
+        let params = ForceRemoveParams {
+            token_id: TOKEN_0,
+            max_entries: 1,
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let mut logger = TestLogger::init();
+        let result = force_remove(&ctx, &mut host, &mut logger, &TestCryptoPrimitives::new());
+        assert_eq!(result, Err(ContractError::Unauthorized));
+    }
+}
@@ -0,0 +1,209 @@
+use concordium_std::*;
+
+use crate::{
+    contract::update_subscribers::{notify_subscribers, DsidNotification},
+    errors::CustomError,
+    state::State,
+    types::{ContractError, ContractEvent, ContractResult, ContractTokenId, DsidFreezeEvent, Role},
+};
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct FreezeBalanceParams {
+    pub token_id: ContractTokenId,
+    pub account: AccountAddress,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "freezeBalance",
+    parameter = "FreezeBalanceParams",
+    error = "ContractError",
+    enable_logger,
+    mutable
+)]
+/// Freezes the balance of an account, e.g. while the credential is disputed.
+/// A frozen balance is 0 but keeps its amount and expiry, see `unfreezeBalance`.
+/// - This function fails if the sender is not a freezer and the token has an issuer which is not the sender.
+/// - This function fails if the sender is not a freezer and the token has no issuer and the sender is neither the admin of the contract nor a minter.
+/// - This function fails if the token does not exist.
+/// - This function fails if the account does not have a balance of the token.
+pub fn freeze_balance<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    let params: FreezeBalanceParams = ctx.parameter_cursor().get()?;
+    update_frozen(ctx, host, logger, params, true)
+}
+
+/// Freezes or unfreezes a balance, logs the change and notifies the subscribers.
+/// - This function fails if the sender may not mint the token and is not a freezer.
+/// - This function fails if the account does not have a balance of the token.
+pub(crate) fn update_frozen<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    params: FreezeBalanceParams,
+    frozen: bool,
+) -> ContractResult<()> {
+    let state = host.state_mut();
+    // Minters may freeze balances of tokens without an issuer, like the admin.
+    let is_minter =
+        state.is_contract_admin(&ctx.sender(), ctx.owner()) || state.is_minter(&ctx.sender());
+    // Freezers may freeze balances of any token, without being able to mint it.
+    ensure!(
+        state.has_role(Role::Freezer, &ctx.sender())
+            || state.can_issue(&params.token_id, &ctx.sender(), is_minter)?,
+        ContractError::Unauthorized
+    );
+    state.set_balance_frozen(params.token_id, params.account, frozen)?;
+
+    let event = DsidFreezeEvent {
+        token_id: params.token_id,
+        owner: Address::Account(params.account),
+        actor: ctx.sender(),
+    };
+    if frozen {
+        logger.log(&ContractEvent::DsidFreeze(event))?;
+    } else {
+        logger.log(&ContractEvent::DsidUnfreeze(event))?;
+    }
+
+    // Notify the subscribers about the balance, which reads as 0 while frozen.
+    let now = ctx.metadata().slot_time();
+    let notification = DsidNotification {
+        token_id: params.token_id,
+        account: params.account,
+        amount: state.get_account_balance(params.token_id, params.account, now)?,
+        expiry: state
+            .get_account_balance_expiry(params.token_id, params.account)?
+            .ok_or(ContractError::Custom(CustomError::CredentialNotFound))?,
+    };
+    notify_subscribers(host, logger, vec![notification])?;
+
+    Ok(())
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::{contract::update_subscribers::DsidNotifyParams, types::CredentialStatus};
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const ADDRESS_0: Address = Address::Account(ACCOUNT_0);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+    const CONTRACT_0: ContractAddress = ContractAddress {
+        index: 2,
+        subindex: 0,
+    };
+
+    fn state_with_balance(state_builder: &mut TestStateBuilder) -> State<TestStateApi> {
+        let mut state = State::empty(state_builder);
+        state.add_token(
+            state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_1,
+                10.into(),
+                Timestamp::from_timestamp_millis(200),
+                None,
+            )
+            .expect("Failed to mint token");
+        state
+    }
+
+    #[concordium_test]
+    fn test_freeze_balance() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(100));
+        let params = FreezeBalanceParams {
+            token_id: TOKEN_0,
+            account: ACCOUNT_1,
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = state_with_balance(&mut state_builder);
+        state
+            .add_subscriber(CONTRACT_0)
+            .expect("Failed to add subscriber");
+        let mut host = TestHost::new(state, state_builder);
+        // Subscribers are notified that the balance reads as 0.
+        host.setup_mock_entrypoint(
+            CONTRACT_0,
+            OwnedEntrypointName::new_unchecked("dsidNotify".to_string()),
+            MockFn::new_v1(|parameter, _, _, _| {
+                let params: DsidNotifyParams =
+                    from_bytes(parameter.as_ref()).map_err(|_| CallContractError::Trap)?;
+                claim_eq!(
+                    params.notifications,
+                    vec![DsidNotification {
+                        token_id: TOKEN_0,
+                        account: ACCOUNT_1,
+                        amount: 0.into(),
+                        expiry: Timestamp::from_timestamp_millis(200),
+                    }]
+                );
+                Ok((false, ()))
+            }),
+        );
+        let mut logger = TestLogger::init();
+        let result: ContractResult<()> = freeze_balance(&ctx, &mut host, &mut logger);
+        assert_eq!(result, Ok(()));
+
+        // The balance reads as 0 but keeps its data.
+        let state = host.state();
+        let now = Timestamp::from_timestamp_millis(100);
+        assert_eq!(
+            state.get_account_balance(TOKEN_0, ACCOUNT_1, now),
+            Ok(0.into())
+        );
+        assert_eq!(
+            state.get_account_balance_expiry(TOKEN_0, ACCOUNT_1),
+            Ok(Some(Timestamp::from_timestamp_millis(200)))
+        );
+        assert_eq!(
+            state.get_credential_status(TOKEN_0, ACCOUNT_1, now),
+            Ok(CredentialStatus::Frozen)
+        );
+        assert_eq!(
+            logger.logs,
+            vec![to_bytes(&ContractEvent::DsidFreeze(DsidFreezeEvent {
+                token_id: TOKEN_0,
+                owner: Address::Account(ACCOUNT_1),
+                actor: ADDRESS_0,
+            }))]
+        );
+    }
+
+    #[concordium_test]
+    fn test_freeze_balance_unauthorized() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(ACCOUNT_1));
+        ctx.set_owner(ACCOUNT_0);
+        let params = FreezeBalanceParams {
+            token_id: TOKEN_0,
+            account: ACCOUNT_1,
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let state = state_with_balance(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let mut logger = TestLogger::init();
+        let result: ContractResult<()> = freeze_balance(&ctx, &mut host, &mut logger);
+        assert_eq!(result, Err(ContractError::Unauthorized));
+    }
+}
@@ -0,0 +1,146 @@
+use concordium_std::*;
+
+use crate::{
+    contract::balance_of::is_hidden_from_sender,
+    state::State,
+    types::{ContractError, ContractResult, ContractTokenId, HistoryEntry},
+};
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct HistoryOfParams {
+    pub token_id: ContractTokenId,
+    pub account: AccountAddress,
+}
+
+#[derive(Debug, Serialize, SchemaType)]
+pub struct HistoryOfResponse(#[concordium(size_length = 2)] pub Vec<HistoryEntry>);
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "historyOf",
+    parameter = "HistoryOfParams",
+    return_value = "HistoryOfResponse",
+    error = "ContractError"
+)]
+/// Gets the latest changes of the balance of an account, oldest first.
+/// Only the latest 16 changes are kept.
+/// - The history of a balance hidden by its holder is empty unless the sender is the holder,
+///   the admin or a verifier, see `setVisibility`.
+/// - This function fails if the token does not exist.
+pub fn history_of<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<HistoryOfResponse> {
+    // Parse the parameter.
+    let params: HistoryOfParams = ctx.parameter_cursor().get()?;
+    let state = host.state();
+    let history = state.get_history(&params.token_id, params.account)?;
+    if is_hidden_from_sender(ctx, state, &params.token_id, params.account)? {
+        return Ok(HistoryOfResponse(Vec::new()));
+    }
+    Ok(HistoryOfResponse(history))
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::types::BalanceChange;
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ADDRESS_0: Address = Address::Account(ACCOUNT_0);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const ACCOUNT_2: AccountAddress = AccountAddress([2u8; 32]);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+
+    fn entry(timestamp: u64) -> HistoryEntry {
+        HistoryEntry {
+            timestamp: Timestamp::from_timestamp_millis(timestamp),
+            change: BalanceChange::Mint,
+            amount: 1.into(),
+            expiry: Timestamp::from_timestamp_millis(1000),
+            actor: ADDRESS_0,
+        }
+    }
+
+    #[concordium_test]
+    fn test_history_of() {
+        let mut ctx = TestReceiveContext::empty();
+        let params = HistoryOfParams {
+            token_id: TOKEN_0,
+            account: ACCOUNT_0,
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        for timestamp in 0..20 {
+            state
+                .record_history(TOKEN_0, ACCOUNT_0, entry(timestamp))
+                .expect("Failed to record history");
+        }
+        let host = TestHost::new(state, state_builder);
+
+        // Only the latest 16 entries are kept.
+        let result = history_of(&ctx, &host).unwrap();
+        assert_eq!(result.0, (4..20).map(entry).collect::<Vec<_>>());
+    }
+
+    #[concordium_test]
+    fn test_history_of_hidden() {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_1,
+                10.into(),
+                Timestamp::from_timestamp_millis(200),
+                None,
+            )
+            .expect("Failed to mint token");
+        state
+            .set_balance_hidden(TOKEN_0, ACCOUNT_1, true)
+            .expect("Failed to hide balance");
+        state
+            .record_history(TOKEN_0, ACCOUNT_1, entry(0))
+            .expect("Failed to record history");
+        let host = TestHost::new(state, state_builder);
+        let parameter = to_bytes(&HistoryOfParams {
+            token_id: TOKEN_0,
+            account: ACCOUNT_1,
+        });
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(ACCOUNT_2));
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(150));
+        ctx.set_parameter(&parameter);
+
+        assert_eq!(
+            history_of(&ctx, &host).map(|response| response.0),
+            Ok(vec![])
+        );
+        ctx.set_sender(Address::Account(ACCOUNT_1));
+        assert_eq!(
+            history_of(&ctx, &host).map(|response| response.0),
+            Ok(vec![entry(0)])
+        );
+    }
+}
@@ -0,0 +1,190 @@
+use concordium_cis2::MetadataUrl;
+use concordium_std::*;
+
+use crate::{
+    contract::balance_of::is_hidden_from_sender,
+    state::State,
+    types::{ContractError, ContractResult, ContractTokenId},
+};
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct HolderMetadataQuery {
+    pub token_id: ContractTokenId,
+    pub account: AccountAddress,
+}
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct HolderTokenMetadataParams {
+    #[concordium(size_length = 2)]
+    pub queries: Vec<HolderMetadataQuery>,
+}
+
+#[derive(Debug, Serialize, SchemaType)]
+pub struct HolderTokenMetadataResponse(#[concordium(size_length = 2)] pub Vec<MetadataUrl>);
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "holderTokenMetadata",
+    parameter = "HolderTokenMetadataParams",
+    return_value = "HolderTokenMetadataResponse",
+    error = "ContractError"
+)]
+/// Gets the metadata url attached to the balance of each of the queried accounts.
+/// The metadata url of the token is returned for balances without a metadata url.
+/// The metadata url of the token is also returned for balances hidden by their holder from the
+/// sender, see `setVisibility`.
+/// - This function fails if any of the tokens does not exist.
+pub fn holder_token_metadata<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<HolderTokenMetadataResponse> {
+    // Parse the parameter.
+    let params: HolderTokenMetadataParams = ctx.parameter_cursor().get()?;
+    let state = host.state();
+    state.ensure_batch_size(|limits| limits.query, params.queries.len())?;
+    let response = params
+        .queries
+        .iter()
+        .map(|query| {
+            if is_hidden_from_sender(ctx, state, &query.token_id, query.account)? {
+                return state.get_token_metadata(&query.token_id);
+            }
+            state.get_holder_metadata(&query.token_id, query.account)
+        })
+        .collect::<ContractResult<Vec<MetadataUrl>>>()?;
+
+    Ok(HolderTokenMetadataResponse(response))
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const ACCOUNT_2: AccountAddress = AccountAddress([2u8; 32]);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+
+    #[concordium_test]
+    fn test_holder_token_metadata() {
+        let mut ctx = TestReceiveContext::empty();
+        let params = HolderTokenMetadataParams {
+            queries: vec![
+                HolderMetadataQuery {
+                    token_id: TOKEN_0,
+                    account: ACCOUNT_0,
+                },
+                HolderMetadataQuery {
+                    token_id: TOKEN_0,
+                    account: ACCOUNT_1,
+                },
+            ],
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        state
+            .set_holder_metadata(
+                TOKEN_0,
+                ACCOUNT_0,
+                Some(MetadataUrl {
+                    url: "https://example.com/certificates/0".to_string(),
+                    hash: Some([1; 32]),
+                }),
+            )
+            .expect("Failed to set holder metadata");
+        let host = TestHost::new(state, state_builder);
+
+        let result = holder_token_metadata(&ctx, &host).unwrap();
+        // Balances without a metadata url fall back to the metadata url of the token.
+        assert_eq!(
+            result.0,
+            vec![
+                MetadataUrl {
+                    url: "https://example.com/certificates/0".to_string(),
+                    hash: Some([1; 32]),
+                },
+                MetadataUrl {
+                    url: "https://example.com".to_string(),
+                    hash: None,
+                },
+            ]
+        );
+    }
+
+    #[concordium_test]
+    fn test_holder_token_metadata_hidden() {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_1,
+                10.into(),
+                Timestamp::from_timestamp_millis(200),
+                None,
+            )
+            .expect("Failed to mint token");
+        state
+            .set_balance_hidden(TOKEN_0, ACCOUNT_1, true)
+            .expect("Failed to hide balance");
+        state
+            .set_holder_metadata(
+                TOKEN_0,
+                ACCOUNT_1,
+                Some(MetadataUrl {
+                    url: "https://example.com/certificates/1".to_string(),
+                    hash: None,
+                }),
+            )
+            .expect("Failed to set holder metadata");
+        let host = TestHost::new(state, state_builder);
+        let parameter = to_bytes(&HolderTokenMetadataParams {
+            queries: vec![HolderMetadataQuery {
+                token_id: TOKEN_0,
+                account: ACCOUNT_1,
+            }],
+        });
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(ACCOUNT_2));
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(150));
+        ctx.set_parameter(&parameter);
+
+        // Others get the metadata url of the token, as for accounts without a balance.
+        assert_eq!(
+            holder_token_metadata(&ctx, &host).map(|response| response.0),
+            Ok(vec![MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            }])
+        );
+        ctx.set_sender(Address::Account(ACCOUNT_1));
+        assert_eq!(
+            holder_token_metadata(&ctx, &host).map(|response| response.0),
+            Ok(vec![MetadataUrl {
+                url: "https://example.com/certificates/1".to_string(),
+                hash: None,
+            }])
+        );
+    }
+}
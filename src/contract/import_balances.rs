@@ -0,0 +1,171 @@
+use concordium_std::*;
+
+use crate::{
+    state::State,
+    types::{
+        BalanceChange, ContractError, ContractEvent, ContractResult, ContractTokenAmount,
+        ContractTokenId, DsidImportEvent, HistoryEntry,
+    },
+};
+
+/// Balance of the legacy contract.
+#[derive(Serial, Deserial, SchemaType, Clone)]
+pub struct ImportedBalance {
+    pub account: AccountAddress,
+    pub token_id: ContractTokenId,
+    pub amount: ContractTokenAmount,
+    pub expiry: Timestamp,
+    /// Time at which the balance was minted by the legacy contract.
+    pub issued_at: Timestamp,
+}
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct ImportBalancesParams {
+    pub balances: Vec<ImportedBalance>,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "importBalances",
+    parameter = "ImportBalancesParams",
+    error = "ContractError",
+    enable_logger,
+    mutable
+)]
+/// Imports balances of a legacy contract, a chunk at a time.
+/// Imported balances replace existing balances and log a `DsidImport` event instead of the
+/// CIS-2 `Mint` event, the time they were issued is recorded in their history.
+/// - This function fails if the sender is not the admin of the contract.
+/// - This function fails if the number of balances exceeds the configured batch limit.
+/// - This function fails if any of the tokens does not exist.
+pub fn import_balances<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    // Check that the sender is the admin of the contract.
+    ensure!(
+        host.state().is_contract_admin(&ctx.sender(), ctx.owner()),
+        ContractError::Unauthorized
+    );
+
+    let params: ImportBalancesParams = ctx.parameter_cursor().get()?;
+    let state = host.state_mut();
+    state.ensure_batch_size(|limits| limits.admin, params.balances.len())?;
+    for balance in params.balances {
+        state.mint(
+            balance.token_id,
+            balance.account,
+            balance.amount,
+            balance.expiry,
+            None,
+        )?;
+        state.record_history(
+            balance.token_id,
+            balance.account,
+            HistoryEntry {
+                timestamp: balance.issued_at,
+                change: BalanceChange::Mint,
+                amount: balance.amount,
+                expiry: balance.expiry,
+                actor: ctx.sender(),
+            },
+        )?;
+        logger.log(&ContractEvent::DsidImport(DsidImportEvent {
+            token_id: balance.token_id,
+            owner: Address::Account(balance.account),
+            amount: balance.amount,
+            expiry: balance.expiry,
+            issued_at: balance.issued_at,
+        }))?;
+    }
+
+    Ok(())
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const ADDRESS_0: Address = Address::Account(ACCOUNT_0);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+
+    fn import_balances_with(
+        host: &mut TestHost<State<TestStateApi>>,
+        logger: &mut TestLogger,
+        owner: AccountAddress,
+        balance: &ImportedBalance,
+    ) -> ContractResult<()> {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(owner);
+        let params = ImportBalancesParams {
+            balances: vec![balance.clone()],
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        import_balances(&ctx, host, logger)
+    }
+
+    #[concordium_test]
+    fn test_import_balances() {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        let mut host = TestHost::new(state, state_builder);
+        let mut logger = TestLogger::init();
+        let balance = ImportedBalance {
+            account: ACCOUNT_1,
+            token_id: TOKEN_0,
+            amount: 3.into(),
+            expiry: Timestamp::from_timestamp_millis(100),
+            issued_at: Timestamp::from_timestamp_millis(5),
+        };
+
+        let result = import_balances_with(&mut host, &mut logger, ACCOUNT_0, &balance);
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            host.state().get_account_balance_expiry(TOKEN_0, ACCOUNT_1),
+            Ok(Some(Timestamp::from_timestamp_millis(100)))
+        );
+        // Only the import is logged, no CIS-2 `Mint` event.
+        assert_eq!(
+            logger.logs,
+            vec![to_bytes(&ContractEvent::DsidImport(DsidImportEvent {
+                token_id: TOKEN_0,
+                owner: Address::Account(ACCOUNT_1),
+                amount: 3.into(),
+                expiry: Timestamp::from_timestamp_millis(100),
+                issued_at: Timestamp::from_timestamp_millis(5),
+            }))]
+        );
+    }
+
+    #[concordium_test]
+    fn test_import_balances_not_owner() {
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let mut logger = TestLogger::init();
+        let balance = ImportedBalance {
+            account: ACCOUNT_1,
+            token_id: TOKEN_0,
+            amount: 3.into(),
+            expiry: Timestamp::from_timestamp_millis(100),
+            issued_at: Timestamp::from_timestamp_millis(5),
+        };
+        let result = import_balances_with(&mut host, &mut logger, ACCOUNT_1, &balance);
+        assert_eq!(result, Err(ContractError::Unauthorized));
+    }
+}
@@ -1,15 +1,31 @@
 use concordium_std::*;
 
-use crate::state::State;
+use crate::{state::State, types::ContractConfig};
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct InitParams {
+    /// Account with the admin role, see `transferAdmin`.
+    pub admin: AccountAddress,
+    /// Initial contract configuration, see `setConfig`.
+    pub config: ContractConfig,
+}
 
 /// Initialize contract instance with a no token types.
-#[init(contract = "cis2_dsid", event = "crate::types::ContractEvent")]
+#[init(
+    contract = "cis2_dsid",
+    parameter = "InitParams",
+    event = "crate::types::ContractEvent"
+)]
 pub fn init<S: HasStateApi>(
-    _ctx: &impl HasInitContext,
+    ctx: &impl HasInitContext,
     state_builder: &mut StateBuilder<S>,
 ) -> InitResult<State<S>> {
+    let params: InitParams = ctx.parameter_cursor().get()?;
     // Construct the initial contract state.
-    Ok(State::empty(state_builder))
+    let mut state = State::empty(state_builder);
+    state.set_contract_admin(params.admin);
+    state.set_config(params.config);
+    Ok(state)
 }
 
 #[concordium_cfg_test]
@@ -17,11 +33,26 @@ mod tests {
     use super::*;
     use concordium_std::test_infrastructure::*;
 
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+
     #[concordium_test]
     fn test_init() {
-        let ctx = TestInitContext::empty();
+        let mut ctx = TestInitContext::empty();
+        let config = ContractConfig {
+            metadata_hash_required: true,
+            max_validity: Some(Duration::from_days(365)),
+            ..Default::default()
+        };
+        let parameter = to_bytes(&InitParams {
+            admin: ACCOUNT_1,
+            config: config.clone(),
+        });
+        ctx.set_parameter(&parameter);
         let mut state_builder = TestStateBuilder::new();
         let result: InitResult<State<TestStateApi>> = init(&ctx, &mut state_builder);
-        claim!(result.is_ok(), "Expected Ok");
+        let state = result.expect_report("Expected Ok");
+        assert_eq!(state.contract_admin(ACCOUNT_0), ACCOUNT_1);
+        assert_eq!(state.config(), &config);
     }
 }
@@ -0,0 +1,74 @@
+use concordium_std::*;
+
+use crate::{
+    errors::CustomError,
+    state::State,
+    types::{ContractError, ContractResult},
+};
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "initAdmin",
+    parameter = "AccountAddress",
+    error = "ContractError",
+    mutable
+)]
+/// Sets the admin of an instance upgraded from state version 1, which did not store roles.
+/// `migrate` converts such a state without an admin, until then the owner of the instance acts as the admin.
+/// - This function fails if the sender is not the owner of the instance.
+/// - This function fails if the admin has already been set.
+pub fn init_admin<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    // Check that the sender is the owner of the instance.
+    ensure!(
+        ctx.sender().matches_account(&ctx.owner()),
+        ContractError::Unauthorized
+    );
+    ensure!(
+        !host.state().has_contract_admin(),
+        ContractError::Custom(CustomError::AdminAlreadySet)
+    );
+
+    let admin: AccountAddress = ctx.parameter_cursor().get()?;
+    host.state_mut().set_contract_admin(admin);
+    Ok(())
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const ADDRESS_0: Address = Address::Account(ACCOUNT_0);
+
+    #[concordium_test]
+    fn test_init_admin() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        let parameter = to_bytes(&ACCOUNT_1);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+
+        let result = init_admin(&ctx, &mut host);
+        assert_eq!(result, Ok(()));
+        // The owner of the instance is no longer the admin.
+        assert!(!host.state().is_contract_admin(&ADDRESS_0, ACCOUNT_0));
+        assert!(host
+            .state()
+            .is_contract_admin(&Address::Account(ACCOUNT_1), ACCOUNT_0));
+
+        // The admin can only be initialized once.
+        let result = init_admin(&ctx, &mut host);
+        assert_eq!(
+            result,
+            Err(ContractError::Custom(CustomError::AdminAlreadySet))
+        );
+    }
+}
@@ -0,0 +1,113 @@
+use concordium_std::*;
+
+pub use crate::state::TokenBalanceState;
+use crate::{
+    state::State,
+    types::{ContractError, ContractResult, CredentialQuery},
+};
+
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct InspectBalanceResponse {
+    /// The balance as stored, None if the account has no stored balance of the token.
+    pub balance: Option<TokenBalanceState>,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "inspectBalance",
+    parameter = "CredentialQuery",
+    return_value = "InspectBalanceResponse",
+    error = "ContractError"
+)]
+/// Gets the stored balance of a token for an account, e.g. for troubleshooting.
+/// Unlike `balanceOf`, expired, frozen and not yet valid balances are returned as stored.
+/// - This function fails if the sender is not the admin of the contract.
+/// - This function fails if the token does not exist.
+pub fn inspect_balance<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<InspectBalanceResponse> {
+    // Check that the sender is the admin of the contract.
+    ensure!(
+        host.state().is_contract_admin(&ctx.sender(), ctx.owner()),
+        ContractError::Unauthorized
+    );
+
+    let params: CredentialQuery = ctx.parameter_cursor().get()?;
+    let balance = host
+        .state()
+        .get_account_balance_state(params.token_id, params.account)?;
+    Ok(InspectBalanceResponse { balance })
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::types::ContractTokenId;
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+
+    fn inspect_balance_with(sender: AccountAddress) -> ContractResult<InspectBalanceResponse> {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(sender));
+        ctx.set_owner(ACCOUNT_0);
+        let parameter = to_bytes(&CredentialQuery {
+            token_id: TOKEN_0,
+            account: ACCOUNT_1,
+        });
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_1,
+                3.into(),
+                Timestamp::from_timestamp_millis(100),
+                None,
+            )
+            .expect("Failed to mint token");
+        state
+            .set_balance_frozen(TOKEN_0, ACCOUNT_1, true)
+            .expect("Failed to freeze balance");
+        let host = TestHost::new(state, state_builder);
+        inspect_balance(&ctx, &host)
+    }
+
+    #[concordium_test]
+    fn test_inspect_balance() {
+        // The frozen balance is returned with its stored amount.
+        assert_eq!(
+            inspect_balance_with(ACCOUNT_0),
+            Ok(InspectBalanceResponse {
+                balance: Some(TokenBalanceState {
+                    amount: 3.into(),
+                    expiry: Timestamp::from_timestamp_millis(100),
+                    valid_from: None,
+                    frozen: true,
+                    hard_expiry: None,
+                }),
+            })
+        );
+    }
+
+    #[concordium_test]
+    fn test_inspect_balance_not_owner() {
+        assert_eq!(
+            inspect_balance_with(ACCOUNT_1),
+            Err(ContractError::Unauthorized)
+        );
+    }
+}
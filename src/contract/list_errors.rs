@@ -0,0 +1,87 @@
+use concordium_std::*;
+
+use crate::{
+    errors::{CustomError, ErrorCategory},
+    state::State,
+    types::{ContractError, ContractResult},
+};
+
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct ErrorCode {
+    /// Reject code of the error.
+    pub code: i32,
+    /// Name of the error, e.g. `TokenExpired`.
+    pub name: String,
+    /// Category of the error, None for the CIS-2 errors.
+    pub category: Option<ErrorCategory>,
+}
+
+#[derive(Debug, Serialize, SchemaType)]
+pub struct ListErrorsResponse(#[concordium(size_length = 2)] pub Vec<ErrorCode>);
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "listErrors",
+    return_value = "ListErrorsResponse",
+    error = "ContractError"
+)]
+/// Gets the reject code and name of every error the contract can fail with,
+/// the CIS-2 errors followed by the custom errors.
+pub fn list_errors<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    _host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<ListErrorsResponse> {
+    let cis2_errors = [
+        (ContractError::InvalidTokenId, "InvalidTokenId"),
+        (ContractError::InsufficientFunds, "InsufficientFunds"),
+        (ContractError::Unauthorized, "Unauthorized"),
+    ];
+    let mut errors: Vec<ErrorCode> = cis2_errors
+        .into_iter()
+        .map(|(error, name)| ErrorCode {
+            code: Reject::from(error).error_code.get(),
+            name: name.to_string(),
+            category: None,
+        })
+        .collect();
+    errors.extend(CustomError::variants().into_iter().map(|error| ErrorCode {
+        code: error.reject_code(),
+        name: format!("{:?}", error),
+        category: Some(error.category()),
+    }));
+
+    Ok(ListErrorsResponse(errors))
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use concordium_std::test_infrastructure::*;
+
+    #[concordium_test]
+    fn test_list_errors() {
+        let ctx = TestReceiveContext::empty();
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let host = TestHost::new(state, state_builder);
+
+        let ListErrorsResponse(errors) = list_errors(&ctx, &host).expect("Failed to list errors");
+        assert_eq!(errors.len(), 3 + CustomError::variants().len());
+        assert_eq!(
+            errors[0],
+            ErrorCode {
+                code: -42000001,
+                name: "InvalidTokenId".to_string(),
+                category: None,
+            }
+        );
+        assert_eq!(
+            errors[4],
+            ErrorCode {
+                code: -2,
+                name: "LogFull".to_string(),
+                category: Some(ErrorCategory::External),
+            }
+        );
+    }
+}
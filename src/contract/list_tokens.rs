@@ -0,0 +1,113 @@
+use concordium_cis2::MetadataUrl;
+use concordium_std::*;
+
+use crate::{
+    state::State,
+    types::{ContractError, ContractResult, ContractTokenId},
+};
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct ListTokensParams {
+    /// Number of tokens to skip, used to request the next page.
+    pub skip: u32,
+    /// Maximum number of tokens to return.
+    pub limit: u16,
+    /// Whether to return the metadata url of each token.
+    pub include_metadata: bool,
+}
+
+#[derive(Debug, Serialize, SchemaType, PartialEq)]
+pub struct ListedToken {
+    pub token_id: ContractTokenId,
+    /// The metadata url of the token, if requested.
+    pub metadata_url: Option<MetadataUrl>,
+}
+
+#[derive(Debug, Serialize, SchemaType)]
+pub struct ListTokensResponse {
+    /// Tokens ordered by token id.
+    #[concordium(size_length = 2)]
+    pub tokens: Vec<ListedToken>,
+    /// Whether more tokens are left, request them by increasing `skip`.
+    pub more: bool,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "listTokens",
+    parameter = "ListTokensParams",
+    return_value = "ListTokensResponse",
+    error = "ContractError"
+)]
+/// Gets a page of the registered tokens.
+/// - This function fails if the limit exceeds the configured batch limit of queries.
+pub fn list_tokens<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<ListTokensResponse> {
+    // Parse the parameter.
+    let params: ListTokensParams = ctx.parameter_cursor().get()?;
+    let state = host.state();
+    state.ensure_batch_size(|limits| limits.query, params.limit.into())?;
+    let (tokens, more) = state.list_tokens(params.skip, params.limit, params.include_metadata);
+
+    Ok(ListTokensResponse {
+        tokens: tokens
+            .into_iter()
+            .map(|(token_id, metadata_url)| ListedToken {
+                token_id,
+                metadata_url,
+            })
+            .collect(),
+        more,
+    })
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+    const TOKEN_1: ContractTokenId = TokenIdU8(3);
+    const TOKEN_2: ContractTokenId = TokenIdU8(4);
+
+    #[concordium_test]
+    fn test_list_tokens() {
+        let mut ctx = TestReceiveContext::empty();
+        let params = ListTokensParams {
+            skip: 1,
+            limit: 1,
+            include_metadata: true,
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        for token_id in [TOKEN_0, TOKEN_1, TOKEN_2] {
+            state.add_token(
+                &mut state_builder,
+                token_id,
+                MetadataUrl {
+                    url: format!("https://example.com/{}", token_id.0),
+                    hash: None,
+                },
+            );
+        }
+        let host = TestHost::new(state, state_builder);
+
+        let result = list_tokens(&ctx, &host).unwrap();
+        assert_eq!(
+            result.tokens,
+            vec![ListedToken {
+                token_id: TOKEN_1,
+                metadata_url: Some(MetadataUrl {
+                    url: "https://example.com/3".to_string(),
+                    hash: None,
+                }),
+            }]
+        );
+        assert!(result.more);
+    }
+}
@@ -0,0 +1,250 @@
+use concordium_std::*;
+
+use crate::{
+    state::{migrate_state, stored_state_version, State, STATE_VERSION},
+    types::{ContractError, ContractResult},
+};
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "migrate",
+    error = "ContractError",
+    mutable,
+    low_level
+)]
+/// Migrates the state to the layout of the current module.
+/// Invoked by `upgrade` after switching to a new module. The state is read in the layout of its
+/// stored version, since it can not be read in the layout of the new module yet.
+/// - This function fails if the sender is neither the contract itself nor the admin of the contract.
+/// - This function fails if the state has been written by a newer version of the contract.
+pub fn migrate<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<S, StateApiType = S>,
+) -> ContractResult<()> {
+    let (state_api, state_builder) = host.state_and_builder();
+    migrate_root(ctx, state_api, state_builder)
+}
+
+/// Migrates the state stored in the root like `migrate`.
+fn migrate_root<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    state_api: &mut S,
+    state_builder: &mut StateBuilder<S>,
+) -> ContractResult<()> {
+    // Check that the sender is the contract itself or the admin of the contract.
+    let is_admin = if stored_state_version(state_api)? == STATE_VERSION {
+        let state: State<S> = state_api.read_root()?;
+        state.is_contract_admin(&ctx.sender(), ctx.owner())
+    } else {
+        // Layouts before roles were stored have the owner of the instance as admin.
+        ctx.sender().matches_account(&ctx.owner())
+    };
+    ensure!(
+        ctx.sender().matches_contract(&ctx.self_address()) || is_admin,
+        ContractError::Unauthorized
+    );
+
+    migrate_state(state_api, state_builder)
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::{
+        contract::init_admin::init_admin,
+        errors::CustomError,
+        legacy_state::{ContractConfigV1, StateV1, TokenBalanceStateV1, TokenStateV1},
+        types::{BatchLimits, ContractTokenId, RevocationInfo},
+    };
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const ACCOUNT_2: AccountAddress = AccountAddress([2u8; 32]);
+    const SELF_ADDRESS: ContractAddress = ContractAddress {
+        index: 1,
+        subindex: 0,
+    };
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+
+    fn ctx_with<'a>(sender: Address) -> TestReceiveContext<'a> {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(sender);
+        ctx.set_self_address(SELF_ADDRESS);
+        ctx.set_owner(ACCOUNT_0);
+        ctx
+    }
+
+    /// Writes a state of layout version 1 with a balance and a revocation of TOKEN_0.
+    fn state_api_with_v1() -> (TestStateApi, TestStateBuilder) {
+        let mut state_api = TestStateApi::new();
+        let mut state_builder = StateBuilder::open(state_api.clone());
+        let mut balances = state_builder.new_map();
+        balances.insert(
+            ACCOUNT_1,
+            TokenBalanceStateV1 {
+                amount: 2.into(),
+                expiry: Timestamp::from_timestamp_millis(100),
+                valid_from: None,
+            },
+        );
+        let mut revocations = state_builder.new_map();
+        revocations.insert(
+            ACCOUNT_2,
+            RevocationInfo {
+                reason: 1,
+                timestamp: Timestamp::from_timestamp_millis(5),
+            },
+        );
+        let mut tokens = state_builder.new_map();
+        tokens.insert(
+            TOKEN_0,
+            TokenStateV1 {
+                balances,
+                metadata: MetadataUrl {
+                    url: "https://example.com".to_string(),
+                    hash: None,
+                },
+                revocations,
+                category: 3,
+                paused: true,
+                issuer: None,
+                default_validity: Some(Duration::from_millis(50)),
+            },
+        );
+        let old = StateV1 {
+            version: 1,
+            tokens,
+            metadata_urls: state_builder.new_map(),
+            config: ContractConfigV1 {
+                unique_metadata_urls: true,
+                metadata_hash_required: false,
+                batch_limits: BatchLimits::default(),
+            },
+            snapshots: state_builder.new_map(),
+            next_snapshot_id: 4,
+            subscribers: state_builder.new_set(),
+            revocation_keys: state_builder.new_map(),
+            mint_request_keys: state_builder.new_set(),
+            mint_request_slots: state_builder.new_map(),
+            next_mint_request: 0,
+        };
+        state_api.write_root(&old);
+        (state_api, state_builder)
+    }
+
+    #[concordium_test]
+    fn test_migrate() {
+        let ctx = ctx_with(Address::Contract(SELF_ADDRESS));
+        let mut state_api = TestStateApi::new();
+        let mut state_builder = StateBuilder::open(state_api.clone());
+        let state = State::empty(&mut state_builder);
+        state_api.write_root(&state);
+        let result = migrate_root(&ctx, &mut state_api, &mut state_builder);
+        assert_eq!(result, Ok(()));
+        assert_eq!(stored_state_version(&state_api), Ok(STATE_VERSION));
+    }
+
+    #[concordium_test]
+    fn test_migrate_v1() {
+        let ctx = ctx_with(Address::Contract(SELF_ADDRESS));
+        let (mut state_api, mut state_builder) = state_api_with_v1();
+        let result = migrate_root(&ctx, &mut state_api, &mut state_builder);
+        assert_eq!(result, Ok(()));
+
+        // The state is readable in the current layout and keeps the balances and settings.
+        let state: State<TestStateApi> = state_api.read_root().expect("Failed to read state");
+        let now = Timestamp::from_timestamp_millis(10);
+        assert_eq!(state.version(), STATE_VERSION);
+        assert_eq!(
+            state.get_account_balance(TOKEN_0, ACCOUNT_1, now),
+            Ok(2.into())
+        );
+        assert!(state.has_balances(TOKEN_0, now));
+        assert_eq!(state.get_token_category(&TOKEN_0), Ok(3));
+        assert_eq!(state.is_token_paused(&TOKEN_0), Ok(true));
+        assert_eq!(
+            state.get_token_default_validity(&TOKEN_0),
+            Ok(Some(Duration::from_millis(50)))
+        );
+        assert_eq!(
+            state.get_revocation(TOKEN_0, ACCOUNT_2),
+            Ok(Some(RevocationInfo {
+                reason: 1,
+                timestamp: Timestamp::from_timestamp_millis(5),
+            }))
+        );
+        assert!(state.config().unique_metadata_urls);
+        // The owner of the instance stays the admin until `initAdmin` is called.
+        assert!(!state.has_contract_admin());
+        assert!(state.is_contract_admin(&Address::Account(ACCOUNT_0), ACCOUNT_0));
+    }
+
+    #[concordium_test]
+    fn test_migrate_v1_init_admin() {
+        let ctx = ctx_with(Address::Contract(SELF_ADDRESS));
+        let (mut state_api, mut state_builder) = state_api_with_v1();
+        assert_eq!(
+            migrate_root(&ctx, &mut state_api, &mut state_builder),
+            Ok(())
+        );
+        let state: State<TestStateApi> = state_api.read_root().expect("Failed to read state");
+        let mut host = TestHost::new(state, state_builder);
+
+        // The owner adopts the stored roles by setting the admin once.
+        let mut ctx = ctx_with(Address::Account(ACCOUNT_0));
+        let parameter = to_bytes(&ACCOUNT_2);
+        ctx.set_parameter(&parameter);
+        assert_eq!(init_admin(&ctx, &mut host), Ok(()));
+        assert!(host
+            .state()
+            .is_contract_admin(&Address::Account(ACCOUNT_2), ACCOUNT_0));
+        assert!(!host
+            .state()
+            .is_contract_admin(&Address::Account(ACCOUNT_0), ACCOUNT_0));
+    }
+
+    #[concordium_test]
+    fn test_migrate_v1_by_owner() {
+        let (mut state_api, mut state_builder) = state_api_with_v1();
+        let result = migrate_root(
+            &ctx_with(Address::Account(ACCOUNT_1)),
+            &mut state_api,
+            &mut state_builder,
+        );
+        assert_eq!(result, Err(ContractError::Unauthorized));
+
+        let result = migrate_root(
+            &ctx_with(Address::Account(ACCOUNT_0)),
+            &mut state_api,
+            &mut state_builder,
+        );
+        assert_eq!(result, Ok(()));
+        assert_eq!(stored_state_version(&state_api), Ok(STATE_VERSION));
+    }
+
+    #[concordium_test]
+    fn test_migrate_newer_version() {
+        let ctx = ctx_with(Address::Contract(SELF_ADDRESS));
+        let mut state_api = TestStateApi::new();
+        let mut state_builder = StateBuilder::open(state_api.clone());
+        state_api.write_root(&(STATE_VERSION + 1));
+        let result = migrate_root(&ctx, &mut state_api, &mut state_builder);
+        assert_eq!(
+            result,
+            Err(ContractError::Custom(CustomError::UnsupportedStateVersion))
+        );
+    }
+
+    #[concordium_test]
+    fn test_migrate_unauthorized() {
+        let ctx = ctx_with(Address::Account(AccountAddress([1u8; 32])));
+        let mut state_api = TestStateApi::new();
+        let mut state_builder = StateBuilder::open(state_api.clone());
+        let state = State::empty(&mut state_builder);
+        state_api.write_root(&state);
+        let result = migrate_root(&ctx, &mut state_api, &mut state_builder);
+        assert_eq!(result, Err(ContractError::Unauthorized));
+    }
+}
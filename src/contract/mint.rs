@@ -1,90 +1,453 @@
-use concordium_cis2::{BurnEvent, Cis2Error, Cis2Event, MintEvent};
+use concordium_cis2::{Cis2Error, Cis2Event, MetadataUrl, MintEvent};
 use concordium_std::*;
 
 use crate::{
+    contract::{
+        revoke::{log_burn, revoke_balance, RevokeParam},
+        update_subscribers::{notify_subscribers, DsidNotification},
+    },
     errors::CustomError,
     state::State,
-    types::{ContractError, ContractResult, ContractTokenAmount, ContractTokenId},
+    types::{
+        BalanceChange, ContractError, ContractEvent, ContractResult, ContractTokenAmount,
+        ContractTokenId, DsidBatchMintEvent, DsidMintEvent, HistoryEntry, EXCLUDED_REASON,
+        SUPERSEDED_REASON,
+    },
 };
 
+/// Name of the entrypoint invoked on the identity registry, which takes an account and
+/// returns whether it belongs to a unique person.
+const UNIQUE_PERSON_ENTRYPOINT: &str = "isUniquePerson";
+
 #[derive(Serial, Deserial, SchemaType)]
 pub struct MintParam {
     /// The amount of tokens to mint.
     pub amount: ContractTokenAmount,
     /// The expiry of the minted tokens.
-    pub expiry: Timestamp,
+    /// If omitted, the expiry is the current time plus the default validity of the token.
+    pub expiry: Option<Timestamp>,
+    /// The time from which the minted tokens are valid, None if they are valid immediately.
+    pub valid_from: Option<Timestamp>,
+    /// Metadata url of this balance, e.g. a personalized certificate.
+    /// If omitted, the metadata url of the token is used.
+    pub metadata_url: Option<MetadataUrl>,
 }
 
 #[derive(Serial, Deserial, SchemaType)]
 pub struct MintParams {
     /// Owner of the newly minted tokens.
     pub owner: AccountAddress,
+    /// Optional key chosen by the caller, a mint with a recently used key is rejected.
+    pub idempotency_key: Option<HashSha2256>,
     /// A collection of tokens to mint.
     pub tokens: collections::BTreeMap<ContractTokenId, MintParam>,
 }
 
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq, Default)]
+pub struct MintResponse {
+    /// Number of minted balances.
+    pub minted: u32,
+    /// Number of minted balances which replaced an existing balance of the owner.
+    pub replaced: u32,
+    /// Number of replaced balances which still had a valid amount, which was burned.
+    pub burned: u32,
+}
+
+/// Result of minting a single balance, see `mint_balance`.
+pub(crate) struct MintedBalance {
+    /// Notifications for the subscribers, of the minted balance followed by the balances
+    /// of its exclusive group it replaced.
+    pub notifications: Vec<DsidNotification>,
+    /// Whether an existing balance was replaced.
+    pub replaced: bool,
+    /// Whether a valid amount of the replaced balance was burned.
+    pub burned: bool,
+}
+
 #[receive(
     contract = "cis2_dsid",
     name = "mint",
     parameter = "MintParams",
+    return_value = "MintResponse",
     error = "ContractError",
     enable_logger,
+    crypto_primitives,
     mutable
 )]
 /// Mint tokens to the contract.
-/// - This function fails if the sender is not the owner of the contract.
+/// If the contract configuration summarizes mint events, a single `DsidBatchMint` event is logged
+/// instead of the events of each minted balance.
+/// Returns the number of minted, replaced and burned balances.
+/// - This function fails if the token has an issuer and the sender is not the issuer.
+/// - This function fails if the token has no issuer and the sender is neither the admin of the contract nor a minter.
+/// - This function fails if the number of tokens exceeds the configured batch limit.
 /// - This function fails if the token does not exist.
+/// - This function fails if minting of the token is paused.
+/// - This function fails if minting has been paused because the mint volume limit was exceeded.
+/// - This function fails if the token has been deprecated.
+/// - This function fails if the token is private.
+/// - This function fails if the token requires consent and the owner has not consented to be issued the token by the sender.
+/// - This function fails if the token requires an allowlist and the owner is not on it.
+/// - This function fails if no expiry is given and the token has no default validity.
+/// - This function fails if the expiry is not after `valid_from`, or the token has monotonic
+///   expiries and the expiry shortens the unexpired balance of the owner.
+/// - This function fails if the idempotency key has recently been used.
+/// - This function fails if a token requires unique persons and the identity registry does not confirm the owner.
 pub fn mint<S: HasStateApi>(
     ctx: &impl HasReceiveContext,
     host: &mut impl HasHost<State<S>, StateApiType = S>,
     logger: &mut impl HasLogger,
-) -> ContractResult<()> {
-    // Check that the sender is the owner of the contract.
+    crypto_primitives: &impl HasCryptoPrimitives,
+) -> ContractResult<MintResponse> {
+    let params: MintParams = ctx.parameter_cursor().get()?;
+    // Minters may mint tokens without an issuer, like the admin.
+    let is_minter = host.state().is_contract_admin(&ctx.sender(), ctx.owner())
+        || host.state().is_minter(&ctx.sender());
+    mint_tokens(ctx, host, logger, crypto_primitives, params, is_minter)
+}
+
+/// Mints the tokens of a `mint` parameter as the sender, see `mint`.
+/// - This function does not check whether the sender may mint tokens without an issuer,
+///   which is given as `is_minter`.
+pub(crate) fn mint_tokens<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    crypto_primitives: &impl HasCryptoPrimitives,
+    params: MintParams,
+    is_minter: bool,
+) -> ContractResult<MintResponse> {
+    // Only the admin and minters can mint without minting any tokens.
     ensure!(
-        ctx.sender().matches_account(&ctx.owner()),
+        is_minter || !params.tokens.is_empty(),
         ContractError::Unauthorized
     );
-
-    let params: MintParams = ctx.parameter_cursor().get()?;
+    ensure_unique_person(host, params.tokens.keys(), params.owner)?;
     let state = host.state_mut();
+    state.ensure_batch_size(|limits| limits.mint, params.tokens.len())?;
+    if let Some(key) = params.idempotency_key {
+        // Reject retries of an already executed mint.
+        state.use_mint_request_key(key)?;
+    }
+    let summarize = state.config().summarize_mint_events;
+    let batch = summarize.then(|| DsidBatchMintEvent {
+        owner: Address::Account(params.owner),
+        issuer: ctx.sender(),
+        entries: params.tokens.len() as u32,
+        batch_hash: crypto_primitives.hash_sha2_256(&to_bytes(&params)),
+    });
+    let now = ctx.metadata().slot_time();
+    let mut notifications = Vec::new();
+    let mut response = MintResponse::default();
+    let mut amount = 0u64;
     for (token_id, mint_param) in params.tokens {
-        // Ensure token has not already expired
+        // Check that the sender is allowed to mint the token.
+        ensure!(
+            state.can_issue(&token_id, &ctx.sender(), is_minter)?,
+            ContractError::Unauthorized
+        );
+        // Ensure the owner consents to the issuance, if required.
         ensure!(
-            mint_param.expiry > ctx.metadata().slot_time(),
-            Cis2Error::Custom(CustomError::TokenExpired)
-        );
-        // Mint the tokens.
-        let existing_balance =
-            state.mint(token_id, params.owner, mint_param.amount, mint_param.expiry)?;
-
-        if let Some(balance) = existing_balance {
-            // There was an existing balance
-            let amount = balance.get_balance(ctx.metadata().slot_time());
-            if amount > ContractTokenAmount::from(0) {
-                // The existing balances has a valid amount.
-                // Log the burned tokens.
-                logger.log(&Cis2Event::Burn::<_, ContractTokenAmount>(BurnEvent {
-                    token_id,
-                    owner: Address::Account(params.owner),
-                    amount,
-                }))?;
-            }
+            state.has_issuance_consent(&token_id, params.owner, ctx.sender())?,
+            Cis2Error::Custom(CustomError::ConsentRequired)
+        );
+        amount = amount.saturating_add(u64::from(mint_param.amount.0));
+        let minted = if summarize {
+            mint_balance(
+                state,
+                &mut SilentLogger,
+                token_id,
+                params.owner,
+                mint_param,
+                ctx.sender(),
+                now,
+            )?
+        } else {
+            mint_balance(
+                state,
+                logger,
+                token_id,
+                params.owner,
+                mint_param,
+                ctx.sender(),
+                now,
+            )?
+        };
+        response.minted += 1;
+        response.replaced += u32::from(minted.replaced);
+        response.burned += u32::from(minted.burned);
+        notifications.extend(minted.notifications);
+    }
+    if let Some(batch) = batch {
+        logger.log(&ContractEvent::DsidBatchMint(batch))?;
+    }
+    record_mint_volume(state, logger, response.minted, amount, now)?;
+
+    // Notify the subscribers about the new balances.
+    notify_subscribers(host, logger, notifications)?;
+
+    Ok(response)
+}
+
+/// Logger which drops all events, used when the events of a mint are summarized.
+struct SilentLogger;
+
+impl HasLogger for SilentLogger {
+    fn init() -> Self {
+        SilentLogger
+    }
+
+    fn log_raw(&mut self, _event: &[u8]) -> Result<(), LogError> {
+        Ok(())
+    }
+}
+
+/// Adds minted balances to the mint volume limit and logs a `MintingPaused` event if this
+/// exceeds the limit. The balances are still minted, later mints fail until `resumeMinting`.
+pub(crate) fn record_mint_volume<S: HasStateApi>(
+    state: &mut State<S>,
+    logger: &mut impl HasLogger,
+    count: u32,
+    amount: u64,
+    now: Timestamp,
+) -> ContractResult<()> {
+    if let Some(volume) = state.record_mint_volume(count, amount, now) {
+        logger.log(&ContractEvent::MintingPaused(volume))?;
+    }
+    Ok(())
+}
+
+/// Confirms with the identity registry that an account is a unique person, if any of the tokens requires it.
+/// - This function fails if any of the tokens does not exist.
+/// - This function fails if no identity registry is configured, or it fails or returns an invalid response.
+/// - This function fails if the identity registry does not confirm the account.
+pub(crate) fn ensure_unique_person<'a, S: HasStateApi>(
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    token_ids: impl IntoIterator<Item = &'a ContractTokenId>,
+    account: AccountAddress,
+) -> ContractResult<()> {
+    let mut required = false;
+    for token_id in token_ids {
+        required |= host.state().is_token_unique_person(token_id)?;
+    }
+    if !required {
+        return Ok(());
+    }
+
+    let registry = host
+        .state()
+        .config()
+        .identity_registry
+        .ok_or(ContractError::Custom(CustomError::IdentityRegistryFailed))?;
+    let (_, return_value) = host
+        .invoke_contract(
+            &registry,
+            &account,
+            EntrypointName::new_unchecked(UNIQUE_PERSON_ENTRYPOINT),
+            Amount::zero(),
+        )
+        .map_err(|_| ContractError::Custom(CustomError::IdentityRegistryFailed))?;
+    let unique: bool = return_value
+        .ok_or(ContractError::Custom(CustomError::IdentityRegistryFailed))?
+        .get()
+        .map_err(|_| ContractError::Custom(CustomError::IdentityRegistryFailed))?;
+    ensure!(unique, ContractError::Custom(CustomError::NotUniquePerson));
+    Ok(())
+}
+
+/// Mints a single balance, replacing an existing balance, and logs the burned and minted amounts.
+/// Balances of the owner of the other tokens of the exclusive group of the token are revoked.
+/// - This function does not check the sender or the consent of the owner.
+/// - This function fails if the balance can not be minted, see `check_mint_param`.
+/// - This function fails if the expiry is not after `valid_from`.
+/// - This function fails if the token has monotonic expiries and the expiry shortens the
+///   unexpired balance of the owner.
+/// - This function fails if the expiry is after the hard expiry of the balance of the owner.
+/// - This function fails if the token requires an allowlist and the owner is not on it.
+/// - Returns the notifications for the subscribers and whether an existing balance was replaced.
+pub(crate) fn mint_balance<S: HasStateApi>(
+    state: &mut State<S>,
+    logger: &mut impl HasLogger,
+    token_id: ContractTokenId,
+    owner: AccountAddress,
+    mint_param: MintParam,
+    issuer: Address,
+    now: Timestamp,
+) -> ContractResult<MintedBalance> {
+    let expiry = check_mint_param(state, &token_id, &mint_param, now)?;
+    // Ensure the expiry is consistent with the start of the validity and the existing balance.
+    state.ensure_valid_expiry(&token_id, owner, expiry, mint_param.valid_from, now)?;
+    let hard_expiry = state.get_hard_expiry(&token_id, owner, now)?;
+    // Ensure the owner has been pre-approved, if required.
+    ensure!(
+        state.is_allowlisted(&token_id, owner)?,
+        Cis2Error::Custom(CustomError::NotAllowlisted)
+    );
+    // Mint the tokens.
+    let existing_balance = state.mint(
+        token_id,
+        owner,
+        mint_param.amount,
+        expiry,
+        mint_param.valid_from,
+    )?;
+    state.set_holder_metadata(token_id, owner, mint_param.metadata_url)?;
+    // A mint of 0 does not store a balance.
+    if mint_param.amount > ContractTokenAmount::from(0) {
+        state.set_hard_expiry(token_id, owner, hard_expiry)?;
+    }
+
+    let replaced = existing_balance.is_some();
+    let mut burned = false;
+    if let Some(balance) = existing_balance {
+        // There was an existing balance
+        let amount = balance.get_balance(now);
+        if amount > ContractTokenAmount::from(0) {
+            // The existing balances has a valid amount.
+            // Log the burned tokens.
+            state.record_burn(token_id, amount)?;
+            burned = true;
+            log_burn(logger, token_id, owner, amount, SUPERSEDED_REASON)?;
         }
+    }
+
+    state.record_history(
+        token_id,
+        owner,
+        HistoryEntry {
+            timestamp: now,
+            change: BalanceChange::Mint,
+            amount: mint_param.amount,
+            expiry,
+            actor: issuer,
+        },
+    )?;
 
-        // Log the minted tokens.
+    // Log the minted tokens, a mint of 0 only burns the replaced balance.
+    if mint_param.amount > ContractTokenAmount::from(0) {
+        state.record_minter_mint(issuer, mint_param.amount);
         logger.log(&Cis2Event::Mint::<_, ContractTokenAmount>(MintEvent {
             token_id,
-            owner: Address::Account(params.owner),
+            owner: Address::Account(owner),
+            amount: mint_param.amount,
+        }))?;
+        // Log the expiry and issuer for indexers.
+        logger.log(&ContractEvent::DsidMint(DsidMintEvent {
+            token_id,
+            owner: Address::Account(owner),
             amount: mint_param.amount,
+            expiry,
+            issuer,
         }))?;
     }
 
-    Ok(())
+    let mut notifications = vec![DsidNotification {
+        token_id,
+        account: owner,
+        amount: mint_param.amount,
+        expiry,
+    }];
+    // Revoke the balances of the other tokens of the exclusive group.
+    if mint_param.amount > ContractTokenAmount::from(0) {
+        for excluded in state.get_excluded_tokens(&token_id, owner)? {
+            notifications.push(revoke_balance(
+                state,
+                logger,
+                RevokeParam {
+                    token_id: excluded,
+                    account: owner,
+                    reason: EXCLUDED_REASON,
+                },
+                issuer,
+                now,
+            )?);
+        }
+    }
+
+    Ok(MintedBalance {
+        notifications,
+        replaced,
+        burned,
+    })
+}
+
+/// Checks that a balance can be minted and returns its expiry.
+/// - This function does not check the sender or the consent of the owner.
+/// - This function fails if minting has been paused because the mint volume limit was exceeded.
+/// - This function fails if minting of the token is paused or the token has been deprecated.
+/// - This function fails if the token is private.
+/// - This function fails if the amount is below the minimum amount of the token.
+/// - This function fails if the amount is not allowed by the amount rule of the token.
+/// - This function fails if no expiry is given and the token has no default validity.
+/// - This function fails if the expiry is in the past by more than the clock skew tolerance.
+/// - This function fails if the expiry exceeds the maximum validity of the contract configuration.
+/// - The expiry is rounded up to the expiry granularity of the contract configuration.
+pub(crate) fn check_mint_param<S: HasStateApi>(
+    state: &State<S>,
+    token_id: &ContractTokenId,
+    mint_param: &MintParam,
+    now: Timestamp,
+) -> ContractResult<Timestamp> {
+    // Ensure minting has not been paused by the mint volume limit.
+    ensure!(
+        !state.is_minting_paused(),
+        ContractError::Custom(CustomError::MintingPaused)
+    );
+    // Ensure minting of the token is not paused.
+    ensure!(
+        !state.is_token_paused(token_id)?,
+        Cis2Error::Custom(CustomError::TokenPaused)
+    );
+    // Ensure the token has not been deprecated.
+    ensure!(
+        !state.is_token_deprecated(token_id)?,
+        Cis2Error::Custom(CustomError::TokenDeprecated)
+    );
+    // Ensure the amounts of the token are not public.
+    ensure!(
+        !state.is_token_private(token_id)?,
+        Cis2Error::Custom(CustomError::PrivateToken)
+    );
+    ensure!(
+        mint_param.amount >= state.get_token_min_amount(token_id)?,
+        Cis2Error::Custom(CustomError::ZeroAmount)
+    );
+    ensure!(
+        state
+            .get_token_amount_rule(token_id)?
+            .allows(mint_param.amount),
+        Cis2Error::Custom(CustomError::AmountNotAllowed)
+    );
+    let expiry = match mint_param.expiry {
+        Some(expiry) => expiry,
+        None => state
+            .get_token_default_validity(token_id)?
+            .and_then(|validity| now.checked_add(validity))
+            .ok_or(Cis2Error::Custom(CustomError::ExpiryRequired))?,
+    };
+    let expiry = state.config().round_expiry(expiry);
+    // Ensure token has not already expired, within the clock skew tolerance.
+    ensure!(
+        !state.config().is_past_mint_expiry(now, expiry),
+        Cis2Error::Custom(CustomError::TokenExpired)
+    );
+    ensure!(
+        !state.config().exceeds_max_validity(now, expiry),
+        Cis2Error::Custom(CustomError::ValidityTooLong)
+    );
+    Ok(expiry)
 }
 
 #[concordium_cfg_test]
 mod tests {
     use super::*;
+    use crate::{
+        contract::update_subscribers::DsidNotifyParams,
+        types::{
+            AmountRule, BatchLimits, ContractConfig, DsidBurnEvent, MintVolume, MintVolumeLimit,
+            SubscriberNotifyFailedEvent,
+        },
+    };
     use concordium_cis2::*;
     use concordium_std::test_infrastructure::*;
 
@@ -103,19 +466,24 @@ mod tests {
 
         let mint_params = MintParams {
             owner: ACCOUNT_2,
+            idempotency_key: None,
             tokens: collections::BTreeMap::from_iter(vec![
                 (
                     TOKEN_0,
                     MintParam {
                         amount: ContractTokenAmount::from(100),
-                        expiry: Timestamp::from_timestamp_millis(100),
+                        expiry: Some(Timestamp::from_timestamp_millis(100)),
+                        valid_from: None,
+                        metadata_url: None,
                     },
                 ),
                 (
                     TOKEN_1,
                     MintParam {
                         amount: ContractTokenAmount::from(200),
-                        expiry: Timestamp::from_timestamp_millis(200),
+                        expiry: Some(Timestamp::from_timestamp_millis(200)),
+                        valid_from: None,
+                        metadata_url: None,
                     },
                 ),
             ]),
@@ -143,9 +511,17 @@ mod tests {
         );
         let mut host = TestHost::new(state, state_builder);
         let mut logger = TestLogger::init();
-        let result: ContractResult<()> = mint(&ctx, &mut host, &mut logger);
+        let result: ContractResult<MintResponse> =
+            mint(&ctx, &mut host, &mut logger, &TestCryptoPrimitives::new());
 
-        assert!(result.is_ok());
+        assert_eq!(
+            result,
+            Ok(MintResponse {
+                minted: 2,
+                replaced: 0,
+                burned: 0,
+            })
+        );
 
         // Check that the tokens were minted.
         let state = host.state();
@@ -160,7 +536,7 @@ mod tests {
         assert_eq!(token_1_balance, Ok(ContractTokenAmount::from(200)));
 
         let events = logger.logs;
-        assert_eq!(events.len(), 2);
+        assert_eq!(events.len(), 4);
         assert_eq!(
             events[0],
             to_bytes(&Cis2Event::Mint::<_, ContractTokenAmount>(MintEvent {
@@ -171,12 +547,32 @@ mod tests {
         );
         assert_eq!(
             events[1],
+            to_bytes(&ContractEvent::DsidMint(DsidMintEvent {
+                token_id: TOKEN_0,
+                owner: Address::Account(ACCOUNT_2),
+                amount: ContractTokenAmount::from(100),
+                expiry: Timestamp::from_timestamp_millis(100),
+                issuer: ADDRESS_0,
+            }))
+        );
+        assert_eq!(
+            events[2],
             to_bytes(&Cis2Event::Mint::<_, ContractTokenAmount>(MintEvent {
                 token_id: TOKEN_1,
                 owner: Address::Account(ACCOUNT_2),
                 amount: ContractTokenAmount::from(200),
             }))
         );
+        assert_eq!(
+            events[3],
+            to_bytes(&ContractEvent::DsidMint(DsidMintEvent {
+                token_id: TOKEN_1,
+                owner: Address::Account(ACCOUNT_2),
+                amount: ContractTokenAmount::from(200),
+                expiry: Timestamp::from_timestamp_millis(200),
+                issuer: ADDRESS_0,
+            }))
+        );
     }
 
     #[concordium_test]
@@ -188,11 +584,14 @@ mod tests {
 
         let mint_params = MintParams {
             owner: ACCOUNT_2,
+            idempotency_key: None,
             tokens: collections::BTreeMap::from_iter(vec![(
                 TOKEN_0,
                 MintParam {
                     amount: ContractTokenAmount::from(100),
-                    expiry: Timestamp::from_timestamp_millis(50),
+                    expiry: Some(Timestamp::from_timestamp_millis(50)),
+                    valid_from: None,
+                    metadata_url: None,
                 },
             )]),
         };
@@ -211,7 +610,8 @@ mod tests {
         );
         let mut host = TestHost::new(state, state_builder);
         let mut logger = TestLogger::init();
-        let result: ContractResult<()> = mint(&ctx, &mut host, &mut logger);
+        let result: ContractResult<MintResponse> =
+            mint(&ctx, &mut host, &mut logger, &TestCryptoPrimitives::new());
 
         assert!(result.is_err());
         assert_eq!(
@@ -220,6 +620,66 @@ mod tests {
         );
     }
 
+    #[concordium_test]
+    fn test_mint_expired_within_tolerance() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(99));
+
+        let mint_params = MintParams {
+            owner: ACCOUNT_2,
+            idempotency_key: None,
+            tokens: collections::BTreeMap::from_iter(vec![(
+                TOKEN_0,
+                MintParam {
+                    amount: ContractTokenAmount::from(100),
+                    expiry: Some(Timestamp::from_timestamp_millis(50)),
+                    valid_from: None,
+                    metadata_url: None,
+                },
+            )]),
+        };
+        let parameter_bytes = to_bytes(&mint_params);
+        ctx.set_parameter(&parameter_bytes);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.set_config(ContractConfig {
+            clock_skew_tolerance: Some(Duration::from_millis(60)),
+            ..Default::default()
+        });
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: Option::None,
+            },
+        );
+        let mut host = TestHost::new(state, state_builder);
+        let mut logger = TestLogger::init();
+        let result: ContractResult<MintResponse> =
+            mint(&ctx, &mut host, &mut logger, &TestCryptoPrimitives::new());
+
+        // The expiry is within the tolerance, the balance itself has already expired.
+        assert_eq!(
+            result,
+            Ok(MintResponse {
+                minted: 1,
+                replaced: 0,
+                burned: 0,
+            })
+        );
+        assert_eq!(
+            host.state().get_account_balance(
+                TOKEN_0,
+                ACCOUNT_2,
+                Timestamp::from_timestamp_millis(99)
+            ),
+            Ok(ContractTokenAmount::from(0))
+        );
+    }
+
     #[concordium_test]
     fn test_mint_no_token() {
         let mut ctx = TestReceiveContext::empty();
@@ -229,11 +689,14 @@ mod tests {
 
         let mint_params = MintParams {
             owner: ACCOUNT_2,
+            idempotency_key: None,
             tokens: collections::BTreeMap::from_iter(vec![(
                 TOKEN_0,
                 MintParam {
                     amount: ContractTokenAmount::from(100),
-                    expiry: Timestamp::from_timestamp_millis(100),
+                    expiry: Some(Timestamp::from_timestamp_millis(100)),
+                    valid_from: None,
+                    metadata_url: None,
                 },
             )]),
         };
@@ -243,7 +706,8 @@ mod tests {
         let state = State::empty(&mut state_builder);
         let mut host = TestHost::new(state, state_builder);
         let mut logger = TestLogger::init();
-        let result: ContractResult<()> = mint(&ctx, &mut host, &mut logger);
+        let result: ContractResult<MintResponse> =
+            mint(&ctx, &mut host, &mut logger, &TestCryptoPrimitives::new());
 
         assert!(result.is_err());
         assert_eq!(result.unwrap_err(), ContractError::InvalidTokenId);
@@ -258,11 +722,14 @@ mod tests {
 
         let mint_params = MintParams {
             owner: ACCOUNT_2,
+            idempotency_key: None,
             tokens: collections::BTreeMap::from_iter(vec![(
                 TOKEN_0,
                 MintParam {
                     amount: ContractTokenAmount::from(100),
-                    expiry: Timestamp::from_timestamp_millis(100),
+                    expiry: Some(Timestamp::from_timestamp_millis(100)),
+                    valid_from: None,
+                    metadata_url: None,
                 },
             )]),
         };
@@ -281,12 +748,71 @@ mod tests {
         );
         let mut host = TestHost::new(state, state_builder);
         let mut logger = TestLogger::init();
-        let result: ContractResult<()> = mint(&ctx, &mut host, &mut logger);
+        let result: ContractResult<MintResponse> =
+            mint(&ctx, &mut host, &mut logger, &TestCryptoPrimitives::new());
 
         assert!(result.is_err());
         assert_eq!(result.unwrap_err(), ContractError::Unauthorized);
     }
 
+    #[concordium_test]
+    fn test_mint_minter() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_2);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(99));
+
+        let mint_params = MintParams {
+            owner: ACCOUNT_2,
+            idempotency_key: None,
+            tokens: collections::BTreeMap::from_iter(vec![(
+                TOKEN_0,
+                MintParam {
+                    amount: ContractTokenAmount::from(100),
+                    expiry: Some(Timestamp::from_timestamp_millis(100)),
+                    valid_from: None,
+                    metadata_url: None,
+                },
+            )]),
+        };
+        let parameter_bytes = to_bytes(&mint_params);
+        ctx.set_parameter(&parameter_bytes);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        // Add the tokens to the state.
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: Option::None,
+            },
+        );
+        // Minters may mint tokens without an issuer.
+        state.update_minter(ADDRESS_0, true);
+        let mut host = TestHost::new(state, state_builder);
+        let mut logger = TestLogger::init();
+        let result: ContractResult<MintResponse> =
+            mint(&ctx, &mut host, &mut logger, &TestCryptoPrimitives::new());
+
+        assert_eq!(
+            result,
+            Ok(MintResponse {
+                minted: 1,
+                replaced: 0,
+                burned: 0,
+            })
+        );
+        assert_eq!(
+            host.state().get_account_balance(
+                TOKEN_0,
+                ACCOUNT_2,
+                Timestamp::from_timestamp_millis(99)
+            ),
+            Ok(100.into())
+        );
+    }
+
     #[concordium_test]
     fn test_burn_existing_token() {
         let mut ctx = TestReceiveContext::empty();
@@ -296,19 +822,24 @@ mod tests {
 
         let mint_params = MintParams {
             owner: ACCOUNT_2,
+            idempotency_key: None,
             tokens: collections::BTreeMap::from_iter(vec![
                 (
                     TOKEN_0,
                     MintParam {
                         amount: ContractTokenAmount::from(100),
-                        expiry: Timestamp::from_timestamp_millis(100),
+                        expiry: Some(Timestamp::from_timestamp_millis(100)),
+                        valid_from: None,
+                        metadata_url: None,
                     },
                 ),
                 (
                     TOKEN_1,
                     MintParam {
                         amount: ContractTokenAmount::from(200),
-                        expiry: Timestamp::from_timestamp_millis(200),
+                        expiry: Some(Timestamp::from_timestamp_millis(200)),
+                        valid_from: None,
+                        metadata_url: None,
                     },
                 ),
             ]),
@@ -342,6 +873,7 @@ mod tests {
                 ACCOUNT_2,
                 ContractTokenAmount::from(10),
                 Timestamp::from_timestamp_millis(90),
+                None
             )
             .is_ok());
         claim!(state
@@ -350,16 +882,28 @@ mod tests {
                 ACCOUNT_2,
                 ContractTokenAmount::from(20),
                 Timestamp::from_timestamp_millis(30),
+                None
             )
             .is_ok());
 
         let mut host = TestHost::new(state, state_builder);
         let mut logger = TestLogger::init();
-        let result: ContractResult<()> = mint(&ctx, &mut host, &mut logger);
+        let result: ContractResult<MintResponse> =
+            mint(&ctx, &mut host, &mut logger, &TestCryptoPrimitives::new());
 
-        assert!(result.is_ok());
+        assert_eq!(
+            result,
+            Ok(MintResponse {
+                minted: 2,
+                replaced: 2,
+                burned: 1,
+            })
+        );
+        // Only the valid replaced balance is counted as burned.
+        assert_eq!(host.state().get_token_totals(&TOKEN_0), Ok((110, 10)));
+        assert_eq!(host.state().get_token_totals(&TOKEN_1), Ok((220, 0)));
         let events = logger.logs;
-        assert_eq!(events.len(), 3);
+        assert_eq!(events.len(), 6);
         assert_eq!(
             events[0],
             to_bytes(&Cis2Event::Burn::<_, ContractTokenAmount>(BurnEvent {
@@ -370,6 +914,15 @@ mod tests {
         );
         assert_eq!(
             events[1],
+            to_bytes(&ContractEvent::DsidBurn(DsidBurnEvent {
+                token_id: TOKEN_0,
+                owner: Address::Account(ACCOUNT_2),
+                amount: ContractTokenAmount::from(10),
+                reason: SUPERSEDED_REASON,
+            }))
+        );
+        assert_eq!(
+            events[2],
             to_bytes(&Cis2Event::Mint::<_, ContractTokenAmount>(MintEvent {
                 token_id: TOKEN_0,
                 owner: Address::Account(ACCOUNT_2),
@@ -377,7 +930,7 @@ mod tests {
             }))
         );
         assert_eq!(
-            events[2],
+            events[4],
             to_bytes(&Cis2Event::Mint::<_, ContractTokenAmount>(MintEvent {
                 token_id: TOKEN_1,
                 owner: Address::Account(ACCOUNT_2),
@@ -385,4 +938,939 @@ mod tests {
             }))
         );
     }
+
+    #[concordium_test]
+    fn test_mint_notifies_subscribers() {
+        const CONTRACT_0: ContractAddress = ContractAddress {
+            index: 1,
+            subindex: 0,
+        };
+
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(50));
+
+        let mint_params = MintParams {
+            owner: ACCOUNT_2,
+            idempotency_key: None,
+            tokens: collections::BTreeMap::from_iter(vec![(
+                TOKEN_0,
+                MintParam {
+                    amount: ContractTokenAmount::from(100),
+                    expiry: Some(Timestamp::from_timestamp_millis(100)),
+                    valid_from: None,
+                    metadata_url: None,
+                },
+            )]),
+        };
+        let parameter_bytes = to_bytes(&mint_params);
+        ctx.set_parameter(&parameter_bytes);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: Option::None,
+            },
+        );
+        state
+            .add_subscriber(CONTRACT_0)
+            .expect("Failed to add subscriber");
+        let mut host = TestHost::new(state, state_builder);
+        host.setup_mock_entrypoint(
+            CONTRACT_0,
+            OwnedEntrypointName::new_unchecked("dsidNotify".to_string()),
+            MockFn::new_v1(|parameter, _, _, _| {
+                let params: DsidNotifyParams =
+                    from_bytes(parameter.as_ref()).map_err(|_| CallContractError::Trap)?;
+                claim_eq!(
+                    params.notifications,
+                    vec![DsidNotification {
+                        token_id: TOKEN_0,
+                        account: ACCOUNT_2,
+                        amount: ContractTokenAmount::from(100),
+                        expiry: Timestamp::from_timestamp_millis(100),
+                    }]
+                );
+                Ok((false, ()))
+            }),
+        );
+        let mut logger = TestLogger::init();
+        let result: ContractResult<MintResponse> =
+            mint(&ctx, &mut host, &mut logger, &TestCryptoPrimitives::new());
+        assert_eq!(
+            result,
+            Ok(MintResponse {
+                minted: 1,
+                replaced: 0,
+                burned: 0,
+            })
+        );
+        let failed = to_bytes(&ContractEvent::SubscriberNotifyFailed(
+            SubscriberNotifyFailedEvent {
+                subscriber: CONTRACT_0,
+            },
+        ));
+        assert!(!logger.logs.contains(&failed));
+
+        // A subscriber rejecting the notification is skipped and logged.
+        host.setup_mock_entrypoint(
+            CONTRACT_0,
+            OwnedEntrypointName::new_unchecked("dsidNotify".to_string()),
+            MockFn::returning_err::<()>(CallContractError::Trap),
+        );
+        let result: ContractResult<MintResponse> =
+            mint(&ctx, &mut host, &mut logger, &TestCryptoPrimitives::new());
+        assert!(result.is_ok());
+        assert_eq!(logger.logs.last(), Some(&failed));
+    }
+
+    #[concordium_test]
+    fn test_mint_valid_from() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(50));
+
+        let mint_params = MintParams {
+            owner: ACCOUNT_2,
+            idempotency_key: None,
+            tokens: collections::BTreeMap::from_iter(vec![(
+                TOKEN_0,
+                MintParam {
+                    amount: ContractTokenAmount::from(100),
+                    expiry: Some(Timestamp::from_timestamp_millis(200)),
+                    valid_from: Some(Timestamp::from_timestamp_millis(100)),
+                    metadata_url: None,
+                },
+            )]),
+        };
+        let parameter_bytes = to_bytes(&mint_params);
+        ctx.set_parameter(&parameter_bytes);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: Option::None,
+            },
+        );
+        let mut host = TestHost::new(state, state_builder);
+        let mut logger = TestLogger::init();
+        let result: ContractResult<MintResponse> =
+            mint(&ctx, &mut host, &mut logger, &TestCryptoPrimitives::new());
+        assert_eq!(
+            result,
+            Ok(MintResponse {
+                minted: 1,
+                replaced: 0,
+                burned: 0,
+            })
+        );
+
+        // The balance is 0 until the tokens become valid.
+        let state = host.state();
+        assert_eq!(
+            state.get_account_balance(TOKEN_0, ACCOUNT_2, Timestamp::from_timestamp_millis(99)),
+            Ok(ContractTokenAmount::from(0))
+        );
+        assert_eq!(
+            state.get_account_balance(TOKEN_0, ACCOUNT_2, Timestamp::from_timestamp_millis(100)),
+            Ok(ContractTokenAmount::from(100))
+        );
+    }
+
+    #[concordium_test]
+    fn test_mint_expiry_before_valid_from() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(50));
+
+        let mint_params = MintParams {
+            owner: ACCOUNT_2,
+            idempotency_key: None,
+            tokens: collections::BTreeMap::from_iter(vec![(
+                TOKEN_0,
+                MintParam {
+                    amount: ContractTokenAmount::from(100),
+                    expiry: Some(Timestamp::from_timestamp_millis(100)),
+                    valid_from: Some(Timestamp::from_timestamp_millis(100)),
+                    metadata_url: None,
+                },
+            )]),
+        };
+        let parameter_bytes = to_bytes(&mint_params);
+        ctx.set_parameter(&parameter_bytes);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: Option::None,
+            },
+        );
+        let mut host = TestHost::new(state, state_builder);
+        let mut logger = TestLogger::init();
+        let result: ContractResult<MintResponse> =
+            mint(&ctx, &mut host, &mut logger, &TestCryptoPrimitives::new());
+        assert_eq!(
+            result,
+            Err(ContractError::Custom(CustomError::InvalidExpiry))
+        );
+    }
+
+    #[concordium_test]
+    fn test_mint_duplicate_request() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(50));
+
+        let mint_params = MintParams {
+            owner: ACCOUNT_2,
+            idempotency_key: Some(HashSha2256([1u8; 32])),
+            tokens: collections::BTreeMap::from_iter(vec![(
+                TOKEN_0,
+                MintParam {
+                    amount: ContractTokenAmount::from(100),
+                    expiry: Some(Timestamp::from_timestamp_millis(200)),
+                    valid_from: None,
+                    metadata_url: None,
+                },
+            )]),
+        };
+        let parameter_bytes = to_bytes(&mint_params);
+        ctx.set_parameter(&parameter_bytes);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: Option::None,
+            },
+        );
+        let mut host = TestHost::new(state, state_builder);
+        let mut logger = TestLogger::init();
+        let result: ContractResult<MintResponse> =
+            mint(&ctx, &mut host, &mut logger, &TestCryptoPrimitives::new());
+        assert_eq!(
+            result,
+            Ok(MintResponse {
+                minted: 1,
+                replaced: 0,
+                burned: 0,
+            })
+        );
+
+        // Retrying the mint with the same key fails.
+        let result: ContractResult<MintResponse> =
+            mint(&ctx, &mut host, &mut logger, &TestCryptoPrimitives::new());
+        assert_eq!(
+            result,
+            Err(ContractError::Custom(CustomError::DuplicateMintRequest))
+        );
+    }
+
+    #[concordium_test]
+    fn test_mint_paused_token() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(50));
+
+        let mint_params = MintParams {
+            owner: ACCOUNT_2,
+            idempotency_key: None,
+            tokens: collections::BTreeMap::from_iter(vec![(
+                TOKEN_0,
+                MintParam {
+                    amount: ContractTokenAmount::from(100),
+                    expiry: Some(Timestamp::from_timestamp_millis(200)),
+                    valid_from: None,
+                    metadata_url: None,
+                },
+            )]),
+        };
+        let parameter_bytes = to_bytes(&mint_params);
+        ctx.set_parameter(&parameter_bytes);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: Option::None,
+            },
+        );
+        state
+            .set_token_paused(TOKEN_0, true)
+            .expect("Failed to pause token");
+        let mut host = TestHost::new(state, state_builder);
+        let mut logger = TestLogger::init();
+        let result: ContractResult<MintResponse> =
+            mint(&ctx, &mut host, &mut logger, &TestCryptoPrimitives::new());
+        assert_eq!(result, Err(ContractError::Custom(CustomError::TokenPaused)));
+    }
+
+    #[concordium_test]
+    fn test_mint_amount_rule() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(50));
+
+        let mint_params = MintParams {
+            owner: ACCOUNT_2,
+            idempotency_key: None,
+            tokens: collections::BTreeMap::from_iter(vec![(
+                TOKEN_0,
+                MintParam {
+                    amount: ContractTokenAmount::from(2),
+                    expiry: Some(Timestamp::from_timestamp_millis(200)),
+                    valid_from: None,
+                    metadata_url: None,
+                },
+            )]),
+        };
+        let parameter_bytes = to_bytes(&mint_params);
+        ctx.set_parameter(&parameter_bytes);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: Option::None,
+            },
+        );
+        // Membership tokens can only be minted with an amount of 1.
+        state
+            .set_token_amount_rule(TOKEN_0, AmountRule::Exact(1.into()))
+            .expect("Failed to set amount rule");
+        let mut host = TestHost::new(state, state_builder);
+        let mut logger = TestLogger::init();
+        let result: ContractResult<MintResponse> =
+            mint(&ctx, &mut host, &mut logger, &TestCryptoPrimitives::new());
+        assert_eq!(
+            result,
+            Err(ContractError::Custom(CustomError::AmountNotAllowed))
+        );
+    }
+
+    #[concordium_test]
+    fn test_mint_issuer() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(50));
+
+        let mint_params = MintParams {
+            owner: ACCOUNT_2,
+            idempotency_key: None,
+            tokens: collections::BTreeMap::from_iter(vec![(
+                TOKEN_0,
+                MintParam {
+                    amount: ContractTokenAmount::from(100),
+                    expiry: Some(Timestamp::from_timestamp_millis(200)),
+                    valid_from: None,
+                    metadata_url: None,
+                },
+            )]),
+        };
+        let parameter_bytes = to_bytes(&mint_params);
+        ctx.set_parameter(&parameter_bytes);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: Option::None,
+            },
+        );
+        state
+            .set_token_issuer(TOKEN_0, Some(Address::Account(ACCOUNT_2)))
+            .expect("Failed to set issuer");
+        let mut host = TestHost::new(state, state_builder);
+        let mut logger = TestLogger::init();
+
+        // The owner can not mint a token which has an issuer.
+        let result: ContractResult<MintResponse> =
+            mint(&ctx, &mut host, &mut logger, &TestCryptoPrimitives::new());
+        assert_eq!(result, Err(ContractError::Unauthorized));
+
+        // The issuer can mint the token.
+        ctx.set_sender(Address::Account(ACCOUNT_2));
+        let result: ContractResult<MintResponse> =
+            mint(&ctx, &mut host, &mut logger, &TestCryptoPrimitives::new());
+        assert_eq!(
+            result,
+            Ok(MintResponse {
+                minted: 1,
+                replaced: 0,
+                burned: 0,
+            })
+        );
+    }
+
+    #[concordium_test]
+    fn test_mint_default_validity() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(50));
+
+        let mint_params = MintParams {
+            owner: ACCOUNT_2,
+            idempotency_key: None,
+            tokens: collections::BTreeMap::from_iter(vec![(
+                TOKEN_0,
+                MintParam {
+                    amount: ContractTokenAmount::from(100),
+                    expiry: None,
+                    valid_from: None,
+                    metadata_url: None,
+                },
+            )]),
+        };
+        let parameter_bytes = to_bytes(&mint_params);
+        ctx.set_parameter(&parameter_bytes);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: Option::None,
+            },
+        );
+        let mut host = TestHost::new(state, state_builder);
+        let mut logger = TestLogger::init();
+
+        // Minting without an expiry fails if the token has no default validity.
+        let result: ContractResult<MintResponse> =
+            mint(&ctx, &mut host, &mut logger, &TestCryptoPrimitives::new());
+        assert_eq!(
+            result,
+            Err(ContractError::Custom(CustomError::ExpiryRequired))
+        );
+
+        host.state_mut()
+            .set_token_default_validity(TOKEN_0, Some(Duration::from_millis(100)))
+            .expect("Failed to set default validity");
+        let result: ContractResult<MintResponse> =
+            mint(&ctx, &mut host, &mut logger, &TestCryptoPrimitives::new());
+        assert_eq!(
+            result,
+            Ok(MintResponse {
+                minted: 1,
+                replaced: 0,
+                burned: 0,
+            })
+        );
+        assert_eq!(
+            host.state().get_account_balance_expiry(TOKEN_0, ACCOUNT_2),
+            Ok(Some(Timestamp::from_timestamp_millis(150)))
+        );
+    }
+
+    #[concordium_test]
+    fn test_mint_expiry_granularity() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(50));
+
+        let mint_params = MintParams {
+            owner: ACCOUNT_2,
+            idempotency_key: None,
+            tokens: collections::BTreeMap::from_iter(vec![(
+                TOKEN_0,
+                MintParam {
+                    amount: ContractTokenAmount::from(100),
+                    expiry: Some(Timestamp::from_timestamp_millis(150)),
+                    valid_from: None,
+                    metadata_url: None,
+                },
+            )]),
+        };
+        let parameter_bytes = to_bytes(&mint_params);
+        ctx.set_parameter(&parameter_bytes);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: Option::None,
+            },
+        );
+        state.set_config(ContractConfig {
+            expiry_granularity: Some(Duration::from_millis(100)),
+            ..Default::default()
+        });
+        let mut host = TestHost::new(state, state_builder);
+        let mut logger = TestLogger::init();
+
+        // The expiry is rounded up to the granularity.
+        let result: ContractResult<MintResponse> =
+            mint(&ctx, &mut host, &mut logger, &TestCryptoPrimitives::new());
+        assert_eq!(
+            result,
+            Ok(MintResponse {
+                minted: 1,
+                replaced: 0,
+                burned: 0,
+            })
+        );
+        assert_eq!(
+            host.state().get_account_balance_expiry(TOKEN_0, ACCOUNT_2),
+            Ok(Some(Timestamp::from_timestamp_millis(200)))
+        );
+    }
+
+    #[concordium_test]
+    fn test_mint_max_validity() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(50));
+
+        let mint_params = MintParams {
+            owner: ACCOUNT_2,
+            idempotency_key: None,
+            tokens: collections::BTreeMap::from_iter(vec![(
+                TOKEN_0,
+                MintParam {
+                    amount: ContractTokenAmount::from(100),
+                    expiry: Some(Timestamp::from_timestamp_millis(200)),
+                    valid_from: None,
+                    metadata_url: None,
+                },
+            )]),
+        };
+        let parameter_bytes = to_bytes(&mint_params);
+        ctx.set_parameter(&parameter_bytes);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: Option::None,
+            },
+        );
+        state.set_config(ContractConfig {
+            max_validity: Some(Duration::from_millis(100)),
+            ..Default::default()
+        });
+        let mut host = TestHost::new(state, state_builder);
+        let mut logger = TestLogger::init();
+
+        let result: ContractResult<MintResponse> =
+            mint(&ctx, &mut host, &mut logger, &TestCryptoPrimitives::new());
+        assert_eq!(
+            result,
+            Err(ContractError::Custom(CustomError::ValidityTooLong))
+        );
+    }
+
+    #[concordium_test]
+    fn test_mint_batch_too_large() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(50));
+
+        let mint_params = MintParams {
+            owner: ACCOUNT_2,
+            idempotency_key: None,
+            tokens: collections::BTreeMap::from_iter(vec![
+                (
+                    TOKEN_0,
+                    MintParam {
+                        amount: ContractTokenAmount::from(100),
+                        expiry: Some(Timestamp::from_timestamp_millis(200)),
+                        valid_from: None,
+                        metadata_url: None,
+                    },
+                ),
+                (
+                    TOKEN_1,
+                    MintParam {
+                        amount: ContractTokenAmount::from(200),
+                        expiry: Some(Timestamp::from_timestamp_millis(200)),
+                        valid_from: None,
+                        metadata_url: None,
+                    },
+                ),
+            ]),
+        };
+        let parameter_bytes = to_bytes(&mint_params);
+        ctx.set_parameter(&parameter_bytes);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.set_config(ContractConfig {
+            batch_limits: BatchLimits {
+                mint: Some(1),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        let mut host = TestHost::new(state, state_builder);
+        let mut logger = TestLogger::init();
+        let result: ContractResult<MintResponse> =
+            mint(&ctx, &mut host, &mut logger, &TestCryptoPrimitives::new());
+        assert_eq!(
+            result,
+            Err(ContractError::Custom(CustomError::BatchTooLarge))
+        );
+    }
+
+    #[concordium_test]
+    fn test_mint_deprecated_token() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(50));
+
+        let mint_params = MintParams {
+            owner: ACCOUNT_2,
+            idempotency_key: None,
+            tokens: collections::BTreeMap::from_iter(vec![(
+                TOKEN_0,
+                MintParam {
+                    amount: ContractTokenAmount::from(100),
+                    expiry: Some(Timestamp::from_timestamp_millis(200)),
+                    valid_from: None,
+                    metadata_url: None,
+                },
+            )]),
+        };
+        let parameter_bytes = to_bytes(&mint_params);
+        ctx.set_parameter(&parameter_bytes);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: Option::None,
+            },
+        );
+        state
+            .deprecate_token(TOKEN_0)
+            .expect("Failed to deprecate token");
+        let mut host = TestHost::new(state, state_builder);
+        let mut logger = TestLogger::init();
+        let result: ContractResult<MintResponse> =
+            mint(&ctx, &mut host, &mut logger, &TestCryptoPrimitives::new());
+        assert_eq!(
+            result,
+            Err(ContractError::Custom(CustomError::TokenDeprecated))
+        );
+    }
+
+    #[concordium_test]
+    fn test_mint_consent_required() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(50));
+
+        let mint_params = MintParams {
+            owner: ACCOUNT_2,
+            idempotency_key: None,
+            tokens: collections::BTreeMap::from_iter(vec![(
+                TOKEN_0,
+                MintParam {
+                    amount: ContractTokenAmount::from(100),
+                    expiry: Some(Timestamp::from_timestamp_millis(200)),
+                    valid_from: None,
+                    metadata_url: None,
+                },
+            )]),
+        };
+        let parameter_bytes = to_bytes(&mint_params);
+        ctx.set_parameter(&parameter_bytes);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: Option::None,
+            },
+        );
+        state
+            .set_token_consent_required(TOKEN_0, true)
+            .expect("Failed to require consent");
+        let mut host = TestHost::new(state, state_builder);
+        let mut logger = TestLogger::init();
+        let result: ContractResult<MintResponse> =
+            mint(&ctx, &mut host, &mut logger, &TestCryptoPrimitives::new());
+        assert_eq!(
+            result,
+            Err(ContractError::Custom(CustomError::ConsentRequired))
+        );
+
+        // Mint after the owner consented.
+        host.state_mut()
+            .set_issuance_consent(TOKEN_0, ACCOUNT_2, ADDRESS_0, true)
+            .expect("Failed to consent");
+        let result: ContractResult<MintResponse> =
+            mint(&ctx, &mut host, &mut logger, &TestCryptoPrimitives::new());
+        assert_eq!(
+            result,
+            Ok(MintResponse {
+                minted: 1,
+                replaced: 0,
+                burned: 0,
+            })
+        );
+    }
+
+    #[concordium_test]
+    fn test_mint_unique_person() {
+        const REGISTRY: ContractAddress = ContractAddress {
+            index: 1,
+            subindex: 0,
+        };
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(50));
+
+        let mint_params = MintParams {
+            owner: ACCOUNT_2,
+            idempotency_key: None,
+            tokens: collections::BTreeMap::from_iter(vec![(
+                TOKEN_0,
+                MintParam {
+                    amount: ContractTokenAmount::from(100),
+                    expiry: Some(Timestamp::from_timestamp_millis(100)),
+                    valid_from: None,
+                    metadata_url: None,
+                },
+            )]),
+        };
+        let parameter_bytes = to_bytes(&mint_params);
+        ctx.set_parameter(&parameter_bytes);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: Option::None,
+            },
+        );
+        state
+            .set_token_unique_person(TOKEN_0, true)
+            .expect("Failed to require unique persons");
+        let mut host = TestHost::new(state, state_builder);
+        let mut logger = TestLogger::init();
+
+        // Minting fails without an identity registry.
+        let result: ContractResult<MintResponse> =
+            mint(&ctx, &mut host, &mut logger, &TestCryptoPrimitives::new());
+        assert_eq!(
+            result,
+            Err(ContractError::Custom(CustomError::IdentityRegistryFailed))
+        );
+
+        host.state_mut().set_config(ContractConfig {
+            identity_registry: Some(REGISTRY),
+            ..Default::default()
+        });
+        host.setup_mock_entrypoint(
+            REGISTRY,
+            OwnedEntrypointName::new_unchecked(UNIQUE_PERSON_ENTRYPOINT.into()),
+            MockFn::returning_ok(false),
+        );
+        let result: ContractResult<MintResponse> =
+            mint(&ctx, &mut host, &mut logger, &TestCryptoPrimitives::new());
+        assert_eq!(
+            result,
+            Err(ContractError::Custom(CustomError::NotUniquePerson))
+        );
+
+        host.setup_mock_entrypoint(
+            REGISTRY,
+            OwnedEntrypointName::new_unchecked(UNIQUE_PERSON_ENTRYPOINT.into()),
+            MockFn::returning_ok(true),
+        );
+        let result: ContractResult<MintResponse> =
+            mint(&ctx, &mut host, &mut logger, &TestCryptoPrimitives::new());
+        assert_eq!(
+            result,
+            Ok(MintResponse {
+                minted: 1,
+                replaced: 0,
+                burned: 0,
+            })
+        );
+    }
+
+    #[concordium_test]
+    fn test_mint_summarized_events() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(50));
+
+        let mint_param = || MintParam {
+            amount: ContractTokenAmount::from(100),
+            expiry: Some(Timestamp::from_timestamp_millis(100)),
+            valid_from: None,
+            metadata_url: None,
+        };
+        let mint_params = MintParams {
+            owner: ACCOUNT_2,
+            idempotency_key: None,
+            tokens: collections::BTreeMap::from_iter(vec![
+                (TOKEN_0, mint_param()),
+                (TOKEN_1, mint_param()),
+            ]),
+        };
+        let parameter_bytes = to_bytes(&mint_params);
+        ctx.set_parameter(&parameter_bytes);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        for token_id in [TOKEN_0, TOKEN_1] {
+            state.add_token(
+                &mut state_builder,
+                token_id,
+                MetadataUrl {
+                    url: format!("https://example.com/{}", token_id.0),
+                    hash: Option::None,
+                },
+            );
+        }
+        state.set_config(ContractConfig {
+            summarize_mint_events: true,
+            ..Default::default()
+        });
+        let mut host = TestHost::new(state, state_builder);
+        let mut logger = TestLogger::init();
+        let crypto_primitives = TestCryptoPrimitives::new();
+        crypto_primitives.setup_hash_sha2_256_mock(|_| HashSha2256([1; 32]));
+
+        let result: ContractResult<MintResponse> =
+            mint(&ctx, &mut host, &mut logger, &crypto_primitives);
+        assert_eq!(
+            result,
+            Ok(MintResponse {
+                minted: 2,
+                replaced: 0,
+                burned: 0,
+            })
+        );
+        assert_eq!(
+            logger.logs,
+            vec![to_bytes(&ContractEvent::DsidBatchMint(
+                DsidBatchMintEvent {
+                    owner: Address::Account(ACCOUNT_2),
+                    issuer: ADDRESS_0,
+                    entries: 2,
+                    batch_hash: HashSha2256([1; 32]),
+                }
+            ))]
+        );
+        assert_eq!(
+            host.state().get_account_balance(
+                TOKEN_1,
+                ACCOUNT_2,
+                Timestamp::from_timestamp_millis(50)
+            ),
+            Ok(100.into())
+        );
+    }
+
+    #[concordium_test]
+    fn test_mint_volume_limit() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(50));
+
+        let mint_param = || MintParam {
+            amount: ContractTokenAmount::from(100),
+            expiry: Some(Timestamp::from_timestamp_millis(100)),
+            valid_from: None,
+            metadata_url: None,
+        };
+        let mint_params = MintParams {
+            owner: ACCOUNT_2,
+            idempotency_key: None,
+            tokens: collections::BTreeMap::from_iter(vec![
+                (TOKEN_0, mint_param()),
+                (TOKEN_1, mint_param()),
+            ]),
+        };
+        let parameter_bytes = to_bytes(&mint_params);
+        ctx.set_parameter(&parameter_bytes);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        for token_id in [TOKEN_0, TOKEN_1] {
+            state.add_token(
+                &mut state_builder,
+                token_id,
+                MetadataUrl {
+                    url: format!("https://example.com/{}", token_id.0),
+                    hash: Option::None,
+                },
+            );
+        }
+        state.set_config(ContractConfig {
+            mint_volume_limit: Some(MintVolumeLimit {
+                window: Duration::from_millis(1000),
+                max_count: None,
+                max_amount: Some(150),
+            }),
+            ..Default::default()
+        });
+        let mut host = TestHost::new(state, state_builder);
+        let mut logger = TestLogger::init();
+
+        // The mint exceeding the limit succeeds and pauses minting.
+        let result: ContractResult<MintResponse> =
+            mint(&ctx, &mut host, &mut logger, &TestCryptoPrimitives::new());
+        assert_eq!(result.map(|response| response.minted), Ok(2));
+        assert!(host.state().is_minting_paused());
+        assert_eq!(
+            logger.logs.last(),
+            Some(&to_bytes(&ContractEvent::MintingPaused(MintVolume {
+                window_start: Timestamp::from_timestamp_millis(50),
+                count: 2,
+                amount: 200,
+            })))
+        );
+
+        let result: ContractResult<MintResponse> =
+            mint(&ctx, &mut host, &mut logger, &TestCryptoPrimitives::new());
+        assert_eq!(
+            result,
+            Err(ContractError::Custom(CustomError::MintingPaused))
+        );
+    }
 }
@@ -0,0 +1,210 @@
+use concordium_cis2::Cis2Error;
+use concordium_std::*;
+
+use crate::{
+    contract::{
+        mint::{ensure_unique_person, mint_balance, record_mint_volume, MintParam, MintResponse},
+        update_subscribers::notify_subscribers,
+    },
+    errors::CustomError,
+    state::State,
+    types::{BundleId, ContractError, ContractResult},
+};
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct MintBundleParams {
+    /// Owner of the newly minted tokens.
+    pub owner: AccountAddress,
+    pub bundle_id: BundleId,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "mintBundle",
+    parameter = "MintBundleParams",
+    return_value = "MintResponse",
+    error = "ContractError",
+    enable_logger,
+    mutable
+)]
+/// Mints all tokens of a bundle, see `createBundle`. Either all tokens are minted or none.
+/// Returns the number of minted, replaced and burned balances.
+/// - This function fails if the bundle does not exist.
+/// - This function fails if any of the tokens can not be minted by the sender, see `mint`.
+pub fn mint_bundle<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<MintResponse> {
+    let params: MintBundleParams = ctx.parameter_cursor().get()?;
+    // Minters may mint tokens without an issuer, like the admin.
+    let is_minter = host.state().is_contract_admin(&ctx.sender(), ctx.owner())
+        || host.state().is_minter(&ctx.sender());
+    let components = host.state().get_bundle(&params.bundle_id)?;
+    ensure_unique_person(
+        host,
+        components.iter().map(|component| &component.token_id),
+        params.owner,
+    )?;
+    let state = host.state_mut();
+    let now = ctx.metadata().slot_time();
+    let mut notifications = Vec::new();
+    let mut response = MintResponse::default();
+    let mut amount = 0u64;
+    for component in components {
+        // Check that the sender is allowed to mint the token.
+        ensure!(
+            state.can_issue(&component.token_id, &ctx.sender(), is_minter)?,
+            ContractError::Unauthorized
+        );
+        // Ensure the owner consents to the issuance, if required.
+        ensure!(
+            state.has_issuance_consent(&component.token_id, params.owner, ctx.sender())?,
+            Cis2Error::Custom(CustomError::ConsentRequired)
+        );
+        let expiry = match component.validity {
+            Some(validity) => Some(
+                now.checked_add(validity)
+                    .ok_or(Cis2Error::Custom(CustomError::ValidityTooLong))?,
+            ),
+            None => None,
+        };
+        amount = amount.saturating_add(u64::from(component.amount.0));
+        let minted = mint_balance(
+            state,
+            logger,
+            component.token_id,
+            params.owner,
+            MintParam {
+                amount: component.amount,
+                expiry,
+                valid_from: None,
+                metadata_url: None,
+            },
+            ctx.sender(),
+            now,
+        )?;
+        response.minted += 1;
+        response.replaced += u32::from(minted.replaced);
+        response.burned += u32::from(minted.burned);
+        notifications.extend(minted.notifications);
+    }
+    record_mint_volume(state, logger, response.minted, amount, now)?;
+
+    // Notify the subscribers about the new balances.
+    notify_subscribers(host, logger, notifications)?;
+
+    Ok(response)
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::types::{BundleComponent, ContractTokenId};
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const ADDRESS_0: Address = Address::Account(ACCOUNT_0);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+    const TOKEN_1: ContractTokenId = TokenIdU8(3);
+
+    fn mint_bundle_with(
+        host: &mut TestHost<State<TestStateApi>>,
+        sender: Address,
+        bundle_id: BundleId,
+    ) -> ContractResult<MintResponse> {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(sender);
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(10));
+        let params = MintBundleParams {
+            owner: ACCOUNT_1,
+            bundle_id,
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        mint_bundle(&ctx, host, &mut TestLogger::init())
+    }
+
+    fn host_with_bundle() -> TestHost<State<TestStateApi>> {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        for token_id in [TOKEN_0, TOKEN_1] {
+            state.add_token(
+                &mut state_builder,
+                token_id,
+                MetadataUrl {
+                    url: format!("https://example.com/{}", token_id.0),
+                    hash: None,
+                },
+            );
+        }
+        state
+            .set_bundle(
+                1,
+                vec![
+                    BundleComponent {
+                        token_id: TOKEN_0,
+                        amount: 1.into(),
+                        validity: Some(Duration::from_millis(100)),
+                    },
+                    BundleComponent {
+                        token_id: TOKEN_1,
+                        amount: 2.into(),
+                        validity: Some(Duration::from_millis(200)),
+                    },
+                ],
+            )
+            .expect("Failed to create bundle");
+        TestHost::new(state, state_builder)
+    }
+
+    #[concordium_test]
+    fn test_mint_bundle() {
+        let mut host = host_with_bundle();
+        let result = mint_bundle_with(&mut host, ADDRESS_0, 1);
+        assert_eq!(
+            result,
+            Ok(MintResponse {
+                minted: 2,
+                replaced: 0,
+                burned: 0,
+            })
+        );
+        for (token_id, expiry) in [(TOKEN_0, 110), (TOKEN_1, 210)] {
+            assert_eq!(
+                host.state().get_account_balance_expiry(token_id, ACCOUNT_1),
+                Ok(Some(Timestamp::from_timestamp_millis(expiry)))
+            );
+        }
+    }
+
+    #[concordium_test]
+    fn test_mint_bundle_paused_component() {
+        let mut host = host_with_bundle();
+        host.state_mut()
+            .set_token_paused(TOKEN_1, true)
+            .expect("Failed to pause token");
+        let result = mint_bundle_with(&mut host, ADDRESS_0, 1);
+        assert_eq!(result, Err(ContractError::Custom(CustomError::TokenPaused)));
+    }
+
+    #[concordium_test]
+    fn test_mint_bundle_unknown_bundle() {
+        let mut host = host_with_bundle();
+        let result = mint_bundle_with(&mut host, ADDRESS_0, 2);
+        assert_eq!(
+            result,
+            Err(ContractError::Custom(CustomError::UnknownBundle))
+        );
+    }
+
+    #[concordium_test]
+    fn test_mint_bundle_unauthorized() {
+        let mut host = host_with_bundle();
+        let result = mint_bundle_with(&mut host, Address::Account(ACCOUNT_1), 1);
+        assert_eq!(result, Err(ContractError::Unauthorized));
+    }
+}
@@ -0,0 +1,149 @@
+use concordium_std::*;
+
+use crate::{
+    contract::mint::{mint_tokens, MintParams, MintResponse},
+    state::State,
+    types::{ContractError, ContractResult},
+};
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct MintFromContractParams {
+    /// The next nonce of the sending contract, see `viewConfig`.
+    pub nonce: u64,
+    pub mint: MintParams,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "mintFromContract",
+    parameter = "MintFromContractParams",
+    return_value = "MintResponse",
+    error = "ContractError",
+    enable_logger,
+    crypto_primitives,
+    mutable
+)]
+/// Mints tokens on behalf of a registered issuer contract, e.g. a contract verifying a proof.
+/// Issuer contracts mint like minters, each call has to use the next nonce of the contract.
+/// - This function fails if the sender is not a registered issuer contract.
+/// - This function fails if the nonce is not the next nonce of the sender.
+/// - This function fails if the tokens can not be minted, see `mint`.
+pub fn mint_from_contract<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    crypto_primitives: &impl HasCryptoPrimitives,
+) -> ContractResult<MintResponse> {
+    let contract = match ctx.sender() {
+        Address::Contract(contract) => contract,
+        Address::Account(_) => bail!(ContractError::Unauthorized),
+    };
+    let params: MintFromContractParams = ctx.parameter_cursor().get()?;
+    // Reject replays of an earlier call of the contract.
+    host.state_mut()
+        .use_issuer_contract_nonce(&contract, params.nonce)?;
+    mint_tokens(ctx, host, logger, crypto_primitives, params.mint, true)
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::{errors::CustomError, types::ContractTokenId};
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const CONTRACT_0: ContractAddress = ContractAddress {
+        index: 2,
+        subindex: 0,
+    };
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+
+    fn mint_from_contract_with(
+        host: &mut TestHost<State<TestStateApi>>,
+        sender: Address,
+        nonce: u64,
+    ) -> ContractResult<MintResponse> {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(sender);
+        ctx.set_owner(AccountAddress([0u8; 32]));
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(10));
+        let params = MintFromContractParams {
+            nonce,
+            mint: MintParams {
+                owner: ACCOUNT_1,
+                idempotency_key: None,
+                tokens: collections::BTreeMap::from_iter(vec![(
+                    TOKEN_0,
+                    crate::contract::mint::MintParam {
+                        amount: 1.into(),
+                        expiry: Some(Timestamp::from_timestamp_millis(100)),
+                        valid_from: None,
+                        metadata_url: None,
+                    },
+                )]),
+            },
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        mint_from_contract(
+            &ctx,
+            host,
+            &mut TestLogger::init(),
+            &TestCryptoPrimitives::new(),
+        )
+    }
+
+    fn host_with_issuer_contract() -> TestHost<State<TestStateApi>> {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        state.add_issuer_contract(CONTRACT_0);
+        TestHost::new(state, state_builder)
+    }
+
+    #[concordium_test]
+    fn test_mint_from_contract() {
+        let mut host = host_with_issuer_contract();
+        let sender = Address::Contract(CONTRACT_0);
+
+        let result = mint_from_contract_with(&mut host, sender, 0);
+        assert_eq!(result.map(|response| response.minted), Ok(1));
+        assert_eq!(
+            host.state().get_account_balance(
+                TOKEN_0,
+                ACCOUNT_1,
+                Timestamp::from_timestamp_millis(10)
+            ),
+            Ok(1.into())
+        );
+        assert_eq!(host.state().issuer_contracts(), vec![(CONTRACT_0, 1)]);
+
+        // The same call can not be replayed.
+        let result = mint_from_contract_with(&mut host, sender, 0);
+        assert_eq!(
+            result,
+            Err(ContractError::Custom(CustomError::NonceMismatch))
+        );
+    }
+
+    #[concordium_test]
+    fn test_mint_from_contract_unregistered() {
+        let mut host = host_with_issuer_contract();
+        let sender = Address::Contract(ContractAddress {
+            index: 3,
+            subindex: 0,
+        });
+        let result = mint_from_contract_with(&mut host, sender, 0);
+        assert_eq!(result, Err(ContractError::Unauthorized));
+        let result = mint_from_contract_with(&mut host, Address::Account(ACCOUNT_1), 0);
+        assert_eq!(result, Err(ContractError::Unauthorized));
+    }
+}
@@ -0,0 +1,178 @@
+use concordium_cis2::Cis2Error;
+use concordium_std::*;
+
+use crate::{
+    contract::mint::{check_mint_param, MintParams},
+    errors::CustomError,
+    state::State,
+    types::{ContractError, ContractResult, PendingMint},
+};
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "mintPending",
+    parameter = "MintParams",
+    error = "ContractError",
+    mutable
+)]
+/// Mints tokens which only become balances once the owner activates them using `activate`.
+/// A pending mint replaces an earlier pending mint of the token for the owner.
+/// It can be activated until the activation period of the contract configuration has passed.
+/// - This function fails if the token has an issuer and the sender is not the issuer.
+/// - This function fails if the token has no issuer and the sender is neither the admin of the contract nor a minter.
+/// - This function fails if the number of tokens exceeds the configured batch limit.
+/// - This function fails if the token does not exist.
+/// - This function fails if the token requires consent and the owner has not consented to be issued the token by the sender.
+/// - This function fails if the token requires an allowlist and the owner is not on it.
+/// - This function fails if the balance can not be minted, e.g. minting of the token is paused.
+/// - This function fails if the idempotency key has recently been used.
+pub fn mint_pending<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    let params: MintParams = ctx.parameter_cursor().get()?;
+    // Minters may mint tokens without an issuer, like the admin.
+    let is_minter = host.state().is_contract_admin(&ctx.sender(), ctx.owner())
+        || host.state().is_minter(&ctx.sender());
+    // Only the admin and minters can mint without minting any tokens.
+    ensure!(
+        is_minter || !params.tokens.is_empty(),
+        ContractError::Unauthorized
+    );
+    let now = ctx.metadata().slot_time();
+    let state = host.state_mut();
+    state.ensure_batch_size(|limits| limits.mint, params.tokens.len())?;
+    if let Some(key) = params.idempotency_key {
+        // Reject retries of an already executed mint.
+        state.use_mint_request_key(key)?;
+    }
+    let deadline = state
+        .config()
+        .activation_period
+        .and_then(|period| now.checked_add(period));
+    for (token_id, mint_param) in params.tokens {
+        // Check that the sender is allowed to mint the token.
+        ensure!(
+            state.can_issue(&token_id, &ctx.sender(), is_minter)?,
+            ContractError::Unauthorized
+        );
+        // Ensure the owner consents to the issuance, if required.
+        ensure!(
+            state.has_issuance_consent(&token_id, params.owner, ctx.sender())?,
+            Cis2Error::Custom(CustomError::ConsentRequired)
+        );
+        let expiry = check_mint_param(state, &token_id, &mint_param, now)?;
+        // Ensure the owner has been pre-approved, if required.
+        ensure!(
+            state.is_allowlisted(&token_id, params.owner)?,
+            Cis2Error::Custom(CustomError::NotAllowlisted)
+        );
+        state.add_pending_mint(
+            token_id,
+            params.owner,
+            PendingMint {
+                amount: mint_param.amount,
+                expiry,
+                valid_from: mint_param.valid_from,
+                metadata_url: mint_param.metadata_url,
+                issuer: ctx.sender(),
+                deadline,
+            },
+        )?;
+    }
+
+    Ok(())
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::{
+        contract::mint::MintParam,
+        types::{ContractConfig, ContractTokenAmount, ContractTokenId},
+    };
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const ADDRESS_0: Address = Address::Account(ACCOUNT_0);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+
+    fn mint_pending_with(
+        host: &mut TestHost<State<TestStateApi>>,
+        sender: Address,
+    ) -> ContractResult<()> {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(sender);
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(10));
+        let params = MintParams {
+            owner: ACCOUNT_1,
+            idempotency_key: None,
+            tokens: collections::BTreeMap::from_iter(vec![(
+                TOKEN_0,
+                MintParam {
+                    amount: ContractTokenAmount::from(10),
+                    expiry: Some(Timestamp::from_timestamp_millis(100)),
+                    valid_from: None,
+                    metadata_url: None,
+                },
+            )]),
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        mint_pending(&ctx, host)
+    }
+
+    fn host_with_token() -> TestHost<State<TestStateApi>> {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        state.set_config(ContractConfig {
+            activation_period: Some(Duration::from_millis(50)),
+            ..Default::default()
+        });
+        TestHost::new(state, state_builder)
+    }
+
+    #[concordium_test]
+    fn test_mint_pending() {
+        let mut host = host_with_token();
+        assert_eq!(mint_pending_with(&mut host, ADDRESS_0), Ok(()));
+
+        // Nothing is minted until the holder activates the mint.
+        let now = Timestamp::from_timestamp_millis(10);
+        assert_eq!(
+            host.state().get_account_balance(TOKEN_0, ACCOUNT_1, now),
+            Ok(0.into())
+        );
+        assert_eq!(
+            host.state_mut().take_pending_mint(TOKEN_0, ACCOUNT_1),
+            Ok(PendingMint {
+                amount: 10.into(),
+                expiry: Timestamp::from_timestamp_millis(100),
+                valid_from: None,
+                metadata_url: None,
+                issuer: ADDRESS_0,
+                deadline: Some(Timestamp::from_timestamp_millis(60)),
+            })
+        );
+    }
+
+    #[concordium_test]
+    fn test_mint_pending_unauthorized() {
+        let mut host = host_with_token();
+        assert_eq!(
+            mint_pending_with(&mut host, Address::Account(ACCOUNT_1)),
+            Err(ContractError::Unauthorized)
+        );
+    }
+}
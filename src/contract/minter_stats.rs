@@ -0,0 +1,108 @@
+use concordium_std::*;
+
+use crate::{
+    state::State,
+    types::{ContractError, ContractResult, MinterStats},
+};
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct MinterStatsParams {
+    #[concordium(size_length = 2)]
+    pub minters: Vec<Address>,
+}
+
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct MinterStatsResponse(#[concordium(size_length = 2)] pub Vec<MinterStats>);
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "minterStats",
+    parameter = "MinterStatsParams",
+    return_value = "MinterStatsResponse",
+    error = "ContractError"
+)]
+/// Gets the number of minted balances, the sum of the minted amounts and the number of revoked
+/// balances of each of the queried issuing addresses.
+/// - Addresses which never minted or revoked have all zeros.
+/// - This function fails if the number of addresses exceeds the configured batch limit.
+pub fn minter_stats<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<MinterStatsResponse> {
+    let params: MinterStatsParams = ctx.parameter_cursor().get()?;
+    let state = host.state();
+    state.ensure_batch_size(|limits| limits.query, params.minters.len())?;
+    Ok(MinterStatsResponse(
+        params
+            .minters
+            .iter()
+            .map(|minter| state.get_minter_stats(minter))
+            .collect(),
+    ))
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::{
+        contract::mint::{mint_balance, MintParam},
+        types::ContractTokenId,
+    };
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const ADDRESS_0: Address = Address::Account(ACCOUNT_0);
+    const ADDRESS_1: Address = Address::Account(ACCOUNT_1);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+
+    #[concordium_test]
+    fn test_minter_stats() {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        let now = Timestamp::from_timestamp_millis(10);
+        mint_balance(
+            &mut state,
+            &mut TestLogger::init(),
+            TOKEN_0,
+            ACCOUNT_1,
+            MintParam {
+                amount: 3.into(),
+                expiry: Some(Timestamp::from_timestamp_millis(100)),
+                valid_from: None,
+                metadata_url: None,
+            },
+            ADDRESS_0,
+            now,
+        )
+        .expect("Failed to mint token");
+        let host = TestHost::new(state, state_builder);
+
+        let mut ctx = TestReceiveContext::empty();
+        let parameter = to_bytes(&MinterStatsParams {
+            minters: vec![ADDRESS_0, ADDRESS_1],
+        });
+        ctx.set_parameter(&parameter);
+        let result = minter_stats(&ctx, &host);
+        assert_eq!(
+            result,
+            Ok(MinterStatsResponse(vec![
+                MinterStats {
+                    mints: 1,
+                    amount: 3,
+                    revokes: 0,
+                },
+                MinterStats::default(),
+            ]))
+        );
+    }
+}
@@ -1,13 +1,122 @@
+pub mod account_profile;
+pub mod activate;
 pub mod add;
+pub mod adjust_amount;
+pub mod approve_action;
+pub mod approve_token;
+pub mod authorize_issuance;
 pub mod balance_of;
+pub mod balance_of_deduplicated;
+pub mod balance_of_or_zero;
+pub mod batch_admin;
+pub mod cancel_action;
+pub mod cancel_revoke;
+pub mod category_score_of;
+pub mod claim;
+pub mod claim_from_root;
+pub mod commit;
+pub mod create_bundle;
+pub mod credential_entry;
+pub mod credential_status;
+pub mod decimals_of;
+pub mod deprecate;
+pub mod execute_revoke;
+pub mod expiring_between;
 pub mod expiry_of;
+pub mod export_balances;
+pub mod extend_expiry;
+pub mod federated_score_of;
+pub mod fee_ledger;
+pub mod force_remove;
+pub mod freeze_balance;
+pub mod history_of;
+pub mod holder_token_metadata;
+pub mod import_balances;
 pub mod init;
+pub mod init_admin;
+pub mod inspect_balance;
+pub mod list_errors;
+pub mod list_tokens;
+pub mod migrate;
 pub mod mint;
+pub mod mint_bundle;
+pub mod mint_from_contract;
+pub mod mint_pending;
+pub mod minter_stats;
+pub mod nonce_of;
+pub mod on_receiving_cis2;
 pub mod operator_of;
+pub mod pause_category;
+pub mod pause_token;
+pub mod pending_revocation_of;
+pub mod permit;
+pub mod propose_token;
+pub mod publish_issuance_root;
+pub mod reminder_preference_of;
 pub mod remove;
+pub mod remove_chunk;
+pub mod renew;
+pub mod renew_with_proof;
+pub mod renew_with_token;
+pub mod renounce;
+pub mod request_renewal;
+pub mod resume_minting;
+pub mod revocation_info;
+pub mod revocation_keys;
+pub mod revoke;
+pub mod revoke_with_signature;
+pub mod schedule_action;
+pub mod schedule_revoke;
+pub mod set_admins;
+pub mod set_allowlist;
+pub mod set_allowlist_required;
+pub mod set_config;
+pub mod set_consent_required;
+pub mod set_dependency;
+pub mod set_exclusive_group;
+pub mod set_fee_token;
+pub mod set_id_range;
+pub mod set_issuer_key;
+pub mod set_localized_metadata;
+pub mod set_max_lifetime;
+pub mod set_min_amount;
+pub mod set_monotonic_expiry;
+pub mod set_permit_key;
+pub mod set_reminder_preference;
+pub mod set_renewal_fee;
+pub mod set_schema_ref;
+pub mod set_timelock_delay;
+pub mod set_visibility;
+pub mod stats;
+pub mod status_of;
+pub mod supports_permit;
+pub mod sweep_expired;
+pub mod take_snapshot;
+pub mod token_info;
 pub mod token_metadata;
+pub mod token_metadata_localized;
+pub mod tokens_by_category;
 pub mod transfer;
+pub mod transfer_admin;
+pub mod unfreeze_balance;
+pub mod unpause_category;
+pub mod unpause_token;
+pub mod update_issuer_contracts;
+pub mod update_minters;
 pub mod update_operator;
+pub mod update_revocation_keys;
+pub mod update_roles;
+pub mod update_subscribers;
+pub mod upgrade;
+pub mod valid_from_of;
+pub mod validate_mint;
+pub mod verify;
+pub mod verify_commitment;
+pub mod verify_for;
+pub mod view_config;
+pub mod view_snapshot;
+pub mod view_version;
+pub mod withdraw_fees;
 use concordium_std::concordium_cfg_test;
 
 #[concordium_cfg_test]
@@ -36,7 +145,12 @@ mod tests {
         // It is not meant to be run on the CI, but rather locally.
 
         // Initialize the contract.
-        let init_ctx = TestInitContext::empty();
+        let mut init_ctx = TestInitContext::empty();
+        let init_parameter = to_bytes(&InitParams {
+            admin: ACCOUNT_OWNER,
+            config: ContractConfig::default(),
+        });
+        init_ctx.set_parameter(&init_parameter);
         let mut state_builder = TestStateBuilder::new();
         let init_result: InitResult<State<TestStateApi>> = init(&init_ctx, &mut state_builder);
         claim!(init_result.is_ok(), "Expected Ok");
@@ -59,6 +173,14 @@ mod tests {
                         url: "https://example.com".to_string(),
                         hash: None,
                     },
+                    category: 0,
+                    issuer: None,
+                    default_validity: None,
+                    renewal_policy: RenewalPolicy::IssuerOnly,
+                    private: false,
+                    unique_person: false,
+                    amount_unit: AmountUnit::default(),
+                    amount_rule: AmountRule::default(),
                 },
                 AddTokenParams {
                     token_id: TOKEN_1,
@@ -66,13 +188,21 @@ mod tests {
                         url: "https://example.com/1".to_string(),
                         hash: None,
                     },
+                    category: 0,
+                    issuer: None,
+                    default_validity: None,
+                    renewal_policy: RenewalPolicy::IssuerOnly,
+                    private: false,
+                    unique_person: false,
+                    amount_unit: AmountUnit::default(),
+                    amount_rule: AmountRule::default(),
                 },
             ],
         };
         let add_parameter = &to_bytes(&params);
         add_ctx.set_parameter(add_parameter);
         let mut logger = TestLogger::init();
-        let add_result: ContractResult<()> = add(&add_ctx, &mut host, &mut logger);
+        let add_result: ContractResult<AddResponse> = add(&add_ctx, &mut host, &mut logger);
         claim!(add_result.is_ok(), "Expected Ok");
 
         // Check token metadata.
@@ -106,19 +236,24 @@ mod tests {
 
         let mint_params = MintParams {
             owner: ACCOUNT_1,
+            idempotency_key: None,
             tokens: collections::BTreeMap::from_iter(vec![
                 (
                     TOKEN_0,
                     MintParam {
                         amount: 100.into(),
-                        expiry: Timestamp::from_timestamp_millis(100),
+                        expiry: Some(Timestamp::from_timestamp_millis(100)),
+                        valid_from: None,
+                        metadata_url: None,
                     },
                 ),
                 (
                     TOKEN_1,
                     MintParam {
                         amount: 200.into(),
-                        expiry: Timestamp::from_timestamp_millis(200),
+                        expiry: Some(Timestamp::from_timestamp_millis(200)),
+                        valid_from: None,
+                        metadata_url: None,
                     },
                 ),
             ]),
@@ -126,7 +261,12 @@ mod tests {
         let mint_parameter = &to_bytes(&mint_params);
         mint_ctx.set_parameter(mint_parameter);
         let mut mint_logger = TestLogger::init();
-        let mint_result = mint(&mint_ctx, &mut host, &mut mint_logger);
+        let mint_result = mint(
+            &mint_ctx,
+            &mut host,
+            &mut mint_logger,
+            &TestCryptoPrimitives::new(),
+        );
         claim!(mint_result.is_ok(), "Expected Ok");
 
         // Check balances.
@@ -187,6 +327,7 @@ mod tests {
 
         // Check Expiry.
         let mut expiry_ctx = TestReceiveContext::empty();
+        expiry_ctx.set_metadata_slot_time(now);
         let expiry_params = ContractExpiryOfQueryParams {
             queries: vec![
                 ContractExpiryOfQuery {
@@ -215,23 +356,23 @@ mod tests {
         claim_eq!(expiry_response.0.len(), 4, "Expected four expiry queries");
         claim_eq!(
             expiry_response.0[0],
-            Option::Some(Timestamp::from_timestamp_millis(100)),
+            ExpiryOfResult::Expiry(Timestamp::from_timestamp_millis(100)),
             "Expected expiry to be 100"
         );
         claim_eq!(
             expiry_response.0[1],
-            Option::Some(Timestamp::from_timestamp_millis(200)),
+            ExpiryOfResult::Expiry(Timestamp::from_timestamp_millis(200)),
             "Expected expiry to be 200"
         );
         claim_eq!(
             expiry_response.0[2],
-            Option::None,
-            "Expected expiry to be None"
+            ExpiryOfResult::NoBalance,
+            "Expected no balance"
         );
         claim_eq!(
             expiry_response.0[3],
-            Option::None,
-            "Expected expiry to be None"
+            ExpiryOfResult::NoBalance,
+            "Expected no balance"
         );
 
         // After some time has passed
@@ -244,18 +385,26 @@ mod tests {
         mint_ctx.set_metadata_slot_time(now);
         let mint_params = MintParams {
             owner: ACCOUNT_1,
+            idempotency_key: None,
             tokens: collections::BTreeMap::from_iter(vec![(
                 TOKEN_0,
                 MintParam {
                     amount: 200.into(),
-                    expiry: Timestamp::from_timestamp_millis(300),
+                    expiry: Some(Timestamp::from_timestamp_millis(300)),
+                    valid_from: None,
+                    metadata_url: None,
                 },
             )]),
         };
         let mint_parameter = &to_bytes(&mint_params);
         mint_ctx.set_parameter(mint_parameter);
         let mut mint_logger = TestLogger::init();
-        let mint_result = mint(&mint_ctx, &mut host, &mut mint_logger);
+        let mint_result = mint(
+            &mint_ctx,
+            &mut host,
+            &mut mint_logger,
+            &TestCryptoPrimitives::new(),
+        );
         claim!(mint_result.is_ok(), "Expected Ok");
 
         // Check that the balance has been updated.
@@ -282,6 +431,7 @@ mod tests {
 
         // Check that the expiry has been updated.
         let mut expiry_ctx = TestReceiveContext::empty();
+        expiry_ctx.set_metadata_slot_time(now);
         let expiry_params = ContractExpiryOfQueryParams {
             queries: vec![ContractExpiryOfQuery {
                 token_id: TOKEN_0,
@@ -296,7 +446,7 @@ mod tests {
         claim_eq!(expiry_response.0.len(), 1, "Expected one expiry query");
         claim_eq!(
             expiry_response.0[0],
-            Option::Some(Timestamp::from_timestamp_millis(300)),
+            ExpiryOfResult::Expiry(Timestamp::from_timestamp_millis(300)),
             "Expected expiry to be 300"
         );
 
@@ -307,11 +457,17 @@ mod tests {
         remove_ctx.set_metadata_slot_time(now);
         let remove_params = RemoveParams {
             tokens: vec![TOKEN_1],
+            ignore_missing: false,
         };
         let remove_parameter = &to_bytes(&remove_params);
         remove_ctx.set_parameter(remove_parameter);
         let mut remove_logger = TestLogger::init();
-        let remove_result = remove(&remove_ctx, &mut host, &mut remove_logger);
+        let remove_result = remove(
+            &remove_ctx,
+            &mut host,
+            &mut remove_logger,
+            &TestCryptoPrimitives::new(),
+        );
         claim!(remove_result.is_err(), "Expected Err");
 
         // After some time has passed
@@ -324,11 +480,17 @@ mod tests {
         remove_ctx.set_metadata_slot_time(now);
         let remove_params = RemoveParams {
             tokens: vec![TOKEN_1],
+            ignore_missing: false,
         };
         let remove_parameter = &to_bytes(&remove_params);
         remove_ctx.set_parameter(remove_parameter);
         let mut remove_logger = TestLogger::init();
-        let remove_result = remove(&remove_ctx, &mut host, &mut remove_logger);
+        let remove_result = remove(
+            &remove_ctx,
+            &mut host,
+            &mut remove_logger,
+            &TestCryptoPrimitives::new(),
+        );
         claim!(remove_result.is_ok(), "Expected Ok");
     }
 }
@@ -0,0 +1,69 @@
+use concordium_std::*;
+
+use crate::{
+    state::State,
+    types::{ContractError, ContractResult},
+};
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct NonceOfQueryParams {
+    #[concordium(size_length = 2)]
+    pub queries: Vec<AccountAddress>,
+}
+
+#[derive(Debug, Serialize, SchemaType)]
+pub struct NonceOfQueryResponse(#[concordium(size_length = 2)] pub Vec<u64>);
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "nonceOf",
+    parameter = "NonceOfQueryParams",
+    return_value = "NonceOfQueryResponse",
+    error = "ContractError"
+)]
+/// Gets the nonce the next `permit` message of each of the queried accounts has to be signed with,
+/// following CIS-3.
+/// - This function fails if the number of queries exceeds the configured batch limit.
+pub fn nonce_of<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<NonceOfQueryResponse> {
+    // Parse the parameter.
+    let params: NonceOfQueryParams = ctx.parameter_cursor().get()?;
+    let state = host.state();
+    state.ensure_batch_size(|limits| limits.query, params.queries.len())?;
+    let response = params
+        .queries
+        .iter()
+        .map(|account| state.permit_nonce(*account))
+        .collect();
+
+    Ok(NonceOfQueryResponse(response))
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+
+    #[concordium_test]
+    fn test_nonce_of() {
+        let mut ctx = TestReceiveContext::empty();
+        let parameter = to_bytes(&NonceOfQueryParams {
+            queries: vec![ACCOUNT_0, ACCOUNT_1],
+        });
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state
+            .use_permit_nonce(ACCOUNT_1, 0)
+            .expect("Failed to use nonce");
+        let host = TestHost::new(state, state_builder);
+
+        let result = nonce_of(&ctx, &host).expect("Failed to query nonces");
+        assert_eq!(result.0, vec![0, 1]);
+    }
+}
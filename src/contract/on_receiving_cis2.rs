@@ -0,0 +1,53 @@
+use concordium_std::*;
+
+use crate::{
+    state::State,
+    types::{ContractError, ContractResult},
+};
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "onReceivingCIS2",
+    error = "ContractError"
+)]
+/// Called by other CIS-2 contracts when tokens are transferred to this contract.
+/// - This function always fails so that tokens can not be stranded at the contract address.
+pub fn on_receiving_cis2<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    _host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    // Receiving tokens is not allowed.
+    Err(ContractError::Unauthorized)
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const CONTRACT_0: ContractAddress = ContractAddress {
+        index: 1,
+        subindex: 0,
+    };
+
+    #[concordium_test]
+    fn test_on_receiving_cis2() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Contract(CONTRACT_0));
+        let parameter = OnReceivingCis2Params {
+            token_id: TokenIdU8(1),
+            amount: TokenAmountU64::from(100),
+            from: Address::Account(ACCOUNT_0),
+            data: AdditionalData::empty(),
+        };
+        let parameter_bytes = to_bytes(&parameter);
+        ctx.set_parameter(&parameter_bytes);
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let host = TestHost::new(state, state_builder);
+        let result: ContractResult<()> = on_receiving_cis2(&ctx, &host);
+        assert_eq!(result, Err(ContractError::Unauthorized));
+    }
+}
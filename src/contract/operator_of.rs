@@ -9,12 +9,20 @@ use crate::{state::State, types::ContractResult};
     parameter = "OperatorOfQueryParams",
     return_value = "OperatorOfQueryResponse"
 )]
+/// Checks for each query whether the address is an operator of the owner, in the order of the queries.
+/// Operators can not be added using `updateOperator`, so this currently returns false for every query.
 pub fn contract_operator_of<S: HasStateApi>(
     ctx: &impl HasReceiveContext,
-    _host: &impl HasHost<State<S>, StateApiType = S>,
+    host: &impl HasHost<State<S>, StateApiType = S>,
 ) -> ContractResult<OperatorOfQueryResponse> {
     let params: OperatorOfQueryParams = ctx.parameter_cursor().get()?;
-    let response = params.queries.iter().map(|_| false).collect();
+    let state = host.state();
+    state.ensure_batch_size(|limits| limits.query, params.queries.len())?;
+    let response = params
+        .queries
+        .iter()
+        .map(|query| state.is_operator(&query.owner, &query.address))
+        .collect();
     Ok(OperatorOfQueryResponse(response))
 }
 
@@ -31,10 +39,16 @@ mod tests {
     fn test_operator_of() {
         let mut ctx = TestReceiveContext::empty();
         let operator_of_param = OperatorOfQueryParams {
-            queries: vec![OperatorOfQuery {
-                address: Address::Account(ACCOUNT_0),
-                owner: Address::Account(ACCOUNT_1),
-            }],
+            queries: vec![
+                OperatorOfQuery {
+                    address: Address::Account(ACCOUNT_0),
+                    owner: Address::Account(ACCOUNT_1),
+                },
+                OperatorOfQuery {
+                    address: Address::Account(ACCOUNT_1),
+                    owner: Address::Account(ACCOUNT_0),
+                },
+            ],
         };
         let parameter_bytes = to_bytes(&operator_of_param);
         ctx.set_parameter(&parameter_bytes);
@@ -44,7 +58,6 @@ mod tests {
         let result: ContractResult<OperatorOfQueryResponse> = contract_operator_of(&ctx, &host);
         claim!(result.is_ok(), "Expected Ok(_), got {:?}", result);
         let response = result.unwrap();
-        assert_eq!(response.0.len(), 1);
-        assert!(!response.0[0]);
+        assert_eq!(response.0, vec![false, false]);
     }
 }
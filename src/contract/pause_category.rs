@@ -0,0 +1,110 @@
+use concordium_std::*;
+
+use crate::{
+    state::State,
+    types::{ContractError, ContractResult, Role, TokenCategory},
+};
+
+#[derive(SchemaType, Deserial, Serial)]
+pub struct PauseCategoryParams {
+    pub category: TokenCategory,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "pauseCategory",
+    parameter = "PauseCategoryParams",
+    return_value = "u32",
+    error = "ContractError",
+    mutable
+)]
+/// Pauses minting of every token of a category and returns the number of paused tokens.
+/// Tokens added to the category later are not paused. Queries of the tokens keep working.
+/// - This function fails if the sender is neither the admin of the contract nor a pauser.
+pub fn pause_category<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<u32> {
+    // Pausers may pause categories like the admin, only the admin may unpause them.
+    let state = host.state();
+    ensure!(
+        state.is_contract_admin(&ctx.sender(), ctx.owner())
+            || state.has_role(Role::Pauser, &ctx.sender()),
+        ContractError::Unauthorized
+    );
+
+    let params: PauseCategoryParams = ctx.parameter_cursor().get()?;
+    Ok(host.state_mut().set_category_paused(params.category, true))
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::types::ContractTokenId;
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const ADDRESS_0: Address = Address::Account(ACCOUNT_0);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+    const TOKEN_1: ContractTokenId = TokenIdU8(3);
+    const TOKEN_2: ContractTokenId = TokenIdU8(4);
+
+    fn state_with_categories(state_builder: &mut TestStateBuilder) -> State<TestStateApi> {
+        let mut state = State::empty(state_builder);
+        for (token_id, category) in [(TOKEN_0, 1), (TOKEN_1, 1), (TOKEN_2, 2)] {
+            state.add_token(
+                state_builder,
+                token_id,
+                MetadataUrl {
+                    url: format!("https://example.com/{}", category),
+                    hash: None,
+                },
+            );
+            state
+                .set_token_category(token_id, category)
+                .expect("Failed to set category");
+        }
+        state
+    }
+
+    fn pause_category_with(
+        host: &mut TestHost<State<TestStateApi>>,
+        owner: AccountAddress,
+        category: TokenCategory,
+    ) -> ContractResult<u32> {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(owner);
+        let parameter = to_bytes(&PauseCategoryParams { category });
+        ctx.set_parameter(&parameter);
+        pause_category(&ctx, host)
+    }
+
+    #[concordium_test]
+    fn test_pause_category() {
+        let mut state_builder = TestStateBuilder::new();
+        let state = state_with_categories(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let result = pause_category_with(&mut host, ACCOUNT_0, 1);
+        assert_eq!(result, Ok(2));
+        assert_eq!(host.state().is_token_paused(&TOKEN_0), Ok(true));
+        assert_eq!(host.state().is_token_paused(&TOKEN_1), Ok(true));
+        assert_eq!(host.state().is_token_paused(&TOKEN_2), Ok(false));
+
+        // Categories without tokens pause nothing.
+        let result = pause_category_with(&mut host, ACCOUNT_0, 3);
+        assert_eq!(result, Ok(0));
+    }
+
+    #[concordium_test]
+    fn test_pause_category_not_owner() {
+        let mut state_builder = TestStateBuilder::new();
+        let state = state_with_categories(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let result = pause_category_with(&mut host, ACCOUNT_1, 1);
+        assert_eq!(result, Err(ContractError::Unauthorized));
+        assert_eq!(host.state().is_token_paused(&TOKEN_0), Ok(false));
+    }
+}
@@ -0,0 +1,112 @@
+use concordium_std::*;
+
+use crate::{
+    state::State,
+    types::{ContractError, ContractResult, ContractTokenId, Role},
+};
+
+#[derive(SchemaType, Deserial, Serial)]
+pub struct PauseTokensParams {
+    pub tokens: Vec<ContractTokenId>,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "pauseToken",
+    parameter = "PauseTokensParams",
+    error = "ContractError",
+    mutable
+)]
+/// Pauses minting of tokens. Queries of the tokens keep working.
+/// - This function fails if the token does not exist.
+/// - This function fails if the sender is neither the admin of the contract nor a pauser.
+pub fn pause_token<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    // Pausers may pause tokens like the admin, only the admin may unpause them.
+    let state = host.state();
+    ensure!(
+        state.is_contract_admin(&ctx.sender(), ctx.owner())
+            || state.has_role(Role::Pauser, &ctx.sender()),
+        ContractError::Unauthorized
+    );
+
+    let params: PauseTokensParams = ctx.parameter_cursor().get()?;
+    let state = host.state_mut();
+    state.ensure_batch_size(|limits| limits.admin, params.tokens.len())?;
+    for token_id in params.tokens {
+        state.set_token_paused(token_id, true)?;
+    }
+
+    Ok(())
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ADDRESS_0: Address = Address::Account(ACCOUNT_0);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+    const TOKEN_1: ContractTokenId = TokenIdU8(3);
+
+    fn state_with_tokens(state_builder: &mut TestStateBuilder) -> State<TestStateApi> {
+        let mut state = State::empty(state_builder);
+        state.add_token(
+            state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        state.add_token(
+            state_builder,
+            TOKEN_1,
+            MetadataUrl {
+                url: "https://example.com/1".to_string(),
+                hash: None,
+            },
+        );
+        state
+    }
+
+    #[concordium_test]
+    fn test_pause_token() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        let params = PauseTokensParams {
+            tokens: vec![TOKEN_0],
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let state = state_with_tokens(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let result: ContractResult<()> = pause_token(&ctx, &mut host);
+        assert_eq!(result, Ok(()));
+        assert_eq!(host.state().is_token_paused(&TOKEN_0), Ok(true));
+        assert_eq!(host.state().is_token_paused(&TOKEN_1), Ok(false));
+    }
+
+    #[concordium_test]
+    fn test_pause_token_not_owner() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(AccountAddress([1u8; 32]));
+        let params = PauseTokensParams {
+            tokens: vec![TOKEN_0],
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let state = state_with_tokens(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let result: ContractResult<()> = pause_token(&ctx, &mut host);
+        assert_eq!(result, Err(ContractError::Unauthorized));
+    }
+}
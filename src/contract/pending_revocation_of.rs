@@ -0,0 +1,130 @@
+use concordium_std::*;
+
+use crate::{
+    state::State,
+    types::{ContractError, ContractResult, CredentialQueryParams, ScheduledRevocation},
+};
+
+#[derive(Debug, Serialize, SchemaType)]
+pub struct PendingRevocationQueryResponse(
+    #[concordium(size_length = 2)] pub Vec<Option<ScheduledRevocation>>,
+);
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "pendingRevocationOf",
+    parameter = "CredentialQueryParams",
+    return_value = "PendingRevocationQueryResponse",
+    error = "ContractError"
+)]
+/// Gets the reason and effective time of the scheduled revocation for each of the queried balances.
+/// - Returns None for balances without a scheduled revocation.
+/// - This function fails if any of the tokens does not exist.
+pub fn pending_revocation_of<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<PendingRevocationQueryResponse> {
+    // Parse the parameter.
+    let params: CredentialQueryParams = ctx.parameter_cursor().get()?;
+    let state = host.state();
+    state.ensure_batch_size(|limits| limits.query, params.queries.len())?;
+    let response: Vec<Option<ScheduledRevocation>> = params
+        .queries
+        .iter()
+        .map(|q| state.get_scheduled_revocation(q.token_id, q.account))
+        .collect::<Result<Vec<Option<ScheduledRevocation>>, ContractError>>()?;
+
+    Ok(PendingRevocationQueryResponse(response))
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::types::{ContractTokenId, CredentialQuery};
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const ACCOUNT_2: AccountAddress = AccountAddress([2u8; 32]);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+    const TOKEN_1: ContractTokenId = TokenIdU8(3);
+    const REVOCATION: ScheduledRevocation = ScheduledRevocation {
+        reason: 2,
+        effective_at: Timestamp::from_timestamp_millis(50),
+    };
+
+    fn host_with_revocation() -> TestHost<State<TestStateApi>> {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        for account in [ACCOUNT_1, ACCOUNT_2] {
+            state
+                .mint(
+                    TOKEN_0,
+                    account,
+                    1.into(),
+                    Timestamp::from_timestamp_millis(100),
+                    None,
+                )
+                .expect("Failed to mint token");
+        }
+        state
+            .schedule_revocation(TOKEN_0, ACCOUNT_1, REVOCATION)
+            .expect("Failed to schedule revocation");
+        TestHost::new(state, state_builder)
+    }
+
+    fn query_with(
+        host: &TestHost<State<TestStateApi>>,
+        queries: Vec<CredentialQuery>,
+    ) -> ContractResult<PendingRevocationQueryResponse> {
+        let mut ctx = TestReceiveContext::empty();
+        let parameter = to_bytes(&CredentialQueryParams { queries });
+        ctx.set_parameter(&parameter);
+        pending_revocation_of(&ctx, host)
+    }
+
+    #[concordium_test]
+    fn test_pending_revocation_of() {
+        let host = host_with_revocation();
+        let result = query_with(
+            &host,
+            vec![
+                CredentialQuery {
+                    token_id: TOKEN_0,
+                    account: ACCOUNT_1,
+                },
+                CredentialQuery {
+                    token_id: TOKEN_0,
+                    account: ACCOUNT_2,
+                },
+            ],
+        )
+        .expect("Failed to query pending revocations");
+        // Only the balance with a scheduled revocation has one.
+        assert_eq!(result.0, vec![Some(REVOCATION), None]);
+    }
+
+    #[concordium_test]
+    fn test_pending_revocation_of_unknown_token() {
+        let host = host_with_revocation();
+        let result = query_with(
+            &host,
+            vec![CredentialQuery {
+                token_id: TOKEN_1,
+                account: ACCOUNT_1,
+            }],
+        );
+        assert_eq!(
+            result.map(|response| response.0),
+            Err(ContractError::InvalidTokenId)
+        );
+    }
+}
@@ -0,0 +1,276 @@
+use concordium_std::*;
+
+use crate::{
+    contract::{
+        renounce::{renounce_balance, RenounceParams},
+        request_renewal::{request_renewal_for, RequestRenewalParams},
+    },
+    errors::CustomError,
+    state::State,
+    types::{ContractError, ContractResult},
+};
+
+/// Name of the `renounce` entrypoint, which can be invoked through `permit`.
+pub(crate) const RENOUNCE_ENTRYPOINT: &str = "renounce";
+/// Name of the `requestRenewal` entrypoint, which can be invoked through `permit`.
+pub(crate) const REQUEST_RENEWAL_ENTRYPOINT: &str = "requestRenewal";
+/// Entrypoints which can be invoked through `permit`, reported by `supportsPermit`.
+pub(crate) const PERMIT_ENTRYPOINTS: [&str; 2] = [RENOUNCE_ENTRYPOINT, REQUEST_RENEWAL_ENTRYPOINT];
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct PermitMessage {
+    /// Address of the contract the message is meant for.
+    pub contract_address: ContractAddress,
+    /// Nonce of the signer, prevents replaying the message.
+    pub nonce: u64,
+    /// Time after which the message can no longer be used.
+    pub timestamp: Timestamp,
+    /// Entrypoint invoked on behalf of the signer.
+    pub entry_point: OwnedEntrypointName,
+    /// Serialized parameter of the entrypoint.
+    #[concordium(size_length = 2)]
+    pub payload: Vec<u8>,
+}
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct PermitParams {
+    /// Signature of the serialized message with the permit key of the signer.
+    pub signature: SignatureEd25519,
+    /// Account on whose behalf the entrypoint is invoked.
+    pub signer: AccountAddress,
+    pub message: PermitMessage,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "permit",
+    parameter = "PermitParams",
+    error = "ContractError",
+    crypto_primitives,
+    enable_logger,
+    mutable
+)]
+/// Invokes `renounce` or `requestRenewal` on behalf of a holder who signed the message with the
+/// key set using `setPermitKey`, following CIS-3. Anyone can submit the signed message and pay
+/// for the transaction.
+/// - This function fails if the message is meant for another contract.
+/// - This function fails if the message has expired.
+/// - This function fails if the signer has not set a permit key.
+/// - This function fails if the signature is not valid.
+/// - This function fails if the nonce is not the next nonce of the signer.
+/// - This function fails if the entrypoint can not be invoked through `permit`.
+/// - This function fails if the invoked entrypoint fails.
+pub fn permit<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    crypto_primitives: &impl HasCryptoPrimitives,
+) -> ContractResult<()> {
+    let params: PermitParams = ctx.parameter_cursor().get()?;
+    let message = params.message;
+    let now = ctx.metadata().slot_time();
+    ensure_eq!(
+        message.contract_address,
+        ctx.self_address(),
+        ContractError::Custom(CustomError::WrongContract)
+    );
+    ensure!(
+        message.timestamp > now,
+        ContractError::Custom(CustomError::MessageExpired)
+    );
+
+    // Check the signature of the message.
+    let key = host
+        .state()
+        .permit_key(params.signer)
+        .ok_or(ContractError::Custom(CustomError::UnknownPermitKey))?;
+    let message_bytes = to_bytes(&message);
+    ensure!(
+        crypto_primitives.verify_ed25519_signature(key, params.signature, &message_bytes),
+        ContractError::Custom(CustomError::WrongSignature)
+    );
+    host.state_mut()
+        .use_permit_nonce(params.signer, message.nonce)?;
+
+    let entry_point = message.entry_point.as_entrypoint_name();
+    if entry_point == EntrypointName::new_unchecked(RENOUNCE_ENTRYPOINT) {
+        let payload: RenounceParams = from_bytes(&message.payload)?;
+        renounce_balance(host, logger, params.signer, payload, now)
+    } else if entry_point == EntrypointName::new_unchecked(REQUEST_RENEWAL_ENTRYPOINT) {
+        let payload: RequestRenewalParams = from_bytes(&message.payload)?;
+        request_renewal_for(host.state_mut(), logger, params.signer, payload, now)
+    } else {
+        bail!(ContractError::Custom(CustomError::EntrypointNotPermitted))
+    }
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::{
+        contract::renounce::RENOUNCED_REASON,
+        types::{ContractEvent, ContractTokenId, RenewalRequestedEvent, RevocationInfo},
+    };
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const ACCOUNT_2: AccountAddress = AccountAddress([2u8; 32]);
+    const SELF_ADDRESS: ContractAddress = ContractAddress {
+        index: 1,
+        subindex: 0,
+    };
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+    const KEY_0: PublicKeyEd25519 = PublicKeyEd25519([1u8; 32]);
+    const SIGNATURE_0: SignatureEd25519 = SignatureEd25519([2u8; 64]);
+
+    fn host_with_balance() -> TestHost<State<TestStateApi>> {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_1,
+                10.into(),
+                Timestamp::from_timestamp_millis(200),
+                None,
+            )
+            .expect("Failed to mint token");
+        state.set_permit_key(ACCOUNT_1, Some(KEY_0));
+        TestHost::new(state, state_builder)
+    }
+
+    fn params(entry_point: &str, nonce: u64) -> PermitParams {
+        PermitParams {
+            signature: SIGNATURE_0,
+            signer: ACCOUNT_1,
+            message: PermitMessage {
+                contract_address: SELF_ADDRESS,
+                nonce,
+                timestamp: Timestamp::from_timestamp_millis(150),
+                entry_point: OwnedEntrypointName::new_unchecked(entry_point.to_string()),
+                payload: to_bytes(&TOKEN_0),
+            },
+        }
+    }
+
+    fn permit_with(
+        host: &mut TestHost<State<TestStateApi>>,
+        logger: &mut TestLogger,
+        params: &PermitParams,
+        now: u64,
+    ) -> ContractResult<()> {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(ACCOUNT_2));
+        ctx.set_self_address(SELF_ADDRESS);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(now));
+        let parameter = to_bytes(params);
+        ctx.set_parameter(&parameter);
+        let crypto_primitives = TestCryptoPrimitives::new();
+        crypto_primitives.setup_verify_ed25519_signature_mock(|key, signature, _| {
+            key == KEY_0 && signature == SIGNATURE_0
+        });
+        permit(&ctx, host, logger, &crypto_primitives)
+    }
+
+    #[concordium_test]
+    fn test_permit_request_renewal() {
+        let mut host = host_with_balance();
+        let mut logger = TestLogger::init();
+        let params = params(REQUEST_RENEWAL_ENTRYPOINT, 0);
+        let result = permit_with(&mut host, &mut logger, &params, 100);
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            logger.logs,
+            vec![to_bytes(&ContractEvent::RenewalRequested(
+                RenewalRequestedEvent {
+                    token_id: TOKEN_0,
+                    owner: Address::Account(ACCOUNT_1),
+                    expiry: Timestamp::from_timestamp_millis(200),
+                }
+            ))]
+        );
+        assert_eq!(host.state().permit_nonce(ACCOUNT_1), 1);
+
+        // Replaying the message fails.
+        let result = permit_with(&mut host, &mut logger, &params, 100);
+        assert_eq!(
+            result,
+            Err(ContractError::Custom(CustomError::NonceMismatch))
+        );
+    }
+
+    #[concordium_test]
+    fn test_permit_renounce() {
+        let mut host = host_with_balance();
+        let mut logger = TestLogger::init();
+        let result = permit_with(&mut host, &mut logger, &params(RENOUNCE_ENTRYPOINT, 0), 100);
+        assert_eq!(result, Ok(()));
+        // The balance of the signer is revoked, not the one of the sponsor.
+        assert_eq!(
+            host.state().get_revocation(TOKEN_0, ACCOUNT_1),
+            Ok(Some(RevocationInfo {
+                reason: RENOUNCED_REASON,
+                timestamp: Timestamp::from_timestamp_millis(100),
+            }))
+        );
+    }
+
+    #[concordium_test]
+    fn test_permit_expired() {
+        let mut host = host_with_balance();
+        let mut logger = TestLogger::init();
+        let result = permit_with(
+            &mut host,
+            &mut logger,
+            &params(REQUEST_RENEWAL_ENTRYPOINT, 0),
+            150,
+        );
+        assert_eq!(
+            result,
+            Err(ContractError::Custom(CustomError::MessageExpired))
+        );
+    }
+
+    #[concordium_test]
+    fn test_permit_wrong_signature() {
+        let mut host = host_with_balance();
+        let mut logger = TestLogger::init();
+        let mut params = params(REQUEST_RENEWAL_ENTRYPOINT, 0);
+        params.signature = SignatureEd25519([3u8; 64]);
+        let result = permit_with(&mut host, &mut logger, &params, 100);
+        assert_eq!(
+            result,
+            Err(ContractError::Custom(CustomError::WrongSignature))
+        );
+
+        // Accounts without a permit key can not sign messages.
+        params.signature = SIGNATURE_0;
+        params.signer = ACCOUNT_2;
+        let result = permit_with(&mut host, &mut logger, &params, 100);
+        assert_eq!(
+            result,
+            Err(ContractError::Custom(CustomError::UnknownPermitKey))
+        );
+        assert_eq!(host.state().permit_nonce(ACCOUNT_1), 0);
+    }
+
+    #[concordium_test]
+    fn test_permit_not_permitted() {
+        let mut host = host_with_balance();
+        let mut logger = TestLogger::init();
+        let result = permit_with(&mut host, &mut logger, &params("mint", 0), 100);
+        assert_eq!(
+            result,
+            Err(ContractError::Custom(CustomError::EntrypointNotPermitted))
+        );
+    }
+}
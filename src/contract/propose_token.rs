@@ -0,0 +1,172 @@
+use concordium_std::*;
+
+use crate::{
+    state::State,
+    types::{AddTokenParams, ContractError, ContractResult, TokenProposal},
+};
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "proposeToken",
+    parameter = "AddTokenParams",
+    error = "ContractError",
+    mutable
+)]
+/// Proposes a token, which is added once a different admin approves it using `approveToken`.
+/// Proposing a token again replaces the earlier proposal.
+/// - This function fails if the sender is neither the admin of the contract nor an admin.
+/// - This function fails if the token already exists.
+pub fn propose_token<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    let state = host.state_mut();
+    // Check that the sender is an admin.
+    let proposer = match ctx.sender() {
+        Address::Account(account)
+            if state.is_contract_admin(&ctx.sender(), ctx.owner()) || state.is_admin(&account) =>
+        {
+            account
+        }
+        _ => bail!(ContractError::Unauthorized),
+    };
+
+    let token: AddTokenParams = ctx.parameter_cursor().get()?;
+    state.propose_token(TokenProposal { proposer, token })
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::{
+        contract::approve_token::{approve_token, ApproveTokenParams},
+        errors::CustomError,
+        types::{AmountRule, AmountUnit, ContractConfig, ContractTokenId, RenewalPolicy},
+    };
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const ACCOUNT_2: AccountAddress = AccountAddress([2u8; 32]);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+
+    fn token_params(url: &str) -> AddTokenParams {
+        AddTokenParams {
+            token_id: TOKEN_0,
+            metadata_url: MetadataUrl {
+                url: url.to_string(),
+                hash: None,
+            },
+            category: 0,
+            issuer: None,
+            default_validity: None,
+            renewal_policy: RenewalPolicy::IssuerOnly,
+            private: false,
+            unique_person: false,
+            amount_unit: AmountUnit::default(),
+            amount_rule: AmountRule::default(),
+        }
+    }
+
+    fn host_with_admins() -> TestHost<State<TestStateApi>> {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.set_config(ContractConfig {
+            token_approval_required: true,
+            ..Default::default()
+        });
+        state
+            .set_admins(vec![ACCOUNT_0, ACCOUNT_1], 0)
+            .expect("Failed to set admins");
+        TestHost::new(state, state_builder)
+    }
+
+    fn propose_with(
+        host: &mut TestHost<State<TestStateApi>>,
+        sender: AccountAddress,
+        url: &str,
+    ) -> ContractResult<()> {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(sender));
+        ctx.set_owner(ACCOUNT_0);
+        let parameter = to_bytes(&token_params(url));
+        ctx.set_parameter(&parameter);
+        propose_token(&ctx, host)
+    }
+
+    fn approve_with(
+        host: &mut TestHost<State<TestStateApi>>,
+        sender: AccountAddress,
+    ) -> ContractResult<()> {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(sender));
+        ctx.set_owner(ACCOUNT_0);
+        let parameter = to_bytes(&ApproveTokenParams {
+            token_id: TOKEN_0,
+            approve: true,
+        });
+        ctx.set_parameter(&parameter);
+        approve_token(&ctx, host, &mut TestLogger::init())
+    }
+
+    #[concordium_test]
+    fn test_propose_token() {
+        let mut host = host_with_admins();
+        let result = propose_with(&mut host, ACCOUNT_1, "https://example.com");
+        assert_eq!(result, Ok(()));
+        // The token is only added once approved.
+        assert!(!host.state().has_token(TOKEN_0));
+
+        assert_eq!(approve_with(&mut host, ACCOUNT_0), Ok(()));
+        assert!(host.state().has_token(TOKEN_0));
+        // Existing tokens can not be proposed.
+        let result = propose_with(&mut host, ACCOUNT_1, "https://example.com");
+        assert_eq!(result, Err(ContractError::InvalidTokenId));
+    }
+
+    #[concordium_test]
+    fn test_propose_token_not_admin() {
+        let mut host = host_with_admins();
+        let result = propose_with(&mut host, ACCOUNT_2, "https://example.com");
+        assert_eq!(result, Err(ContractError::Unauthorized));
+        assert_eq!(
+            approve_with(&mut host, ACCOUNT_1),
+            Err(ContractError::Custom(CustomError::ProposalNotFound))
+        );
+    }
+
+    #[concordium_test]
+    fn test_propose_token_self_approval() {
+        let mut host = host_with_admins();
+        assert_eq!(
+            propose_with(&mut host, ACCOUNT_1, "https://example.com"),
+            Ok(())
+        );
+        // The proposer can not approve their own proposal.
+        assert_eq!(
+            approve_with(&mut host, ACCOUNT_1),
+            Err(ContractError::Unauthorized)
+        );
+        assert!(!host.state().has_token(TOKEN_0));
+    }
+
+    #[concordium_test]
+    fn test_propose_token_duplicate() {
+        let mut host = host_with_admins();
+        assert_eq!(
+            propose_with(&mut host, ACCOUNT_0, "https://example.com/a"),
+            Ok(())
+        );
+        // A second proposal replaces the first one, including its proposer.
+        assert_eq!(
+            propose_with(&mut host, ACCOUNT_1, "https://example.com/b"),
+            Ok(())
+        );
+        assert_eq!(approve_with(&mut host, ACCOUNT_0), Ok(()));
+        assert_eq!(
+            host.state().get_token_metadata(&TOKEN_0).map(|url| url.url),
+            Ok("https://example.com/b".to_string())
+        );
+    }
+}
@@ -0,0 +1,77 @@
+use concordium_std::*;
+
+use crate::{
+    state::State,
+    types::{ContractError, ContractResult, ContractTokenId},
+};
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct PublishIssuanceRootParams {
+    pub token_id: ContractTokenId,
+    /// Merkle root of the balances which can be claimed using `claimFromRoot`.
+    pub root: HashSha2256,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "publishIssuanceRoot",
+    parameter = "PublishIssuanceRootParams",
+    error = "ContractError",
+    mutable
+)]
+/// Publishes a Merkle root of balances of a token, which each holder claims using `claimFromRoot`.
+/// - This function fails if the token has an issuer and the sender is not the issuer.
+/// - This function fails if the token has no issuer and the sender is not the admin of the contract.
+/// - This function fails if the token does not exist.
+pub fn publish_issuance_root<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    let params: PublishIssuanceRootParams = ctx.parameter_cursor().get()?;
+    let is_owner = host.state().is_contract_admin(&ctx.sender(), ctx.owner());
+    let state = host.state_mut();
+    // Check that the sender is allowed to mint the token.
+    ensure!(
+        state.can_issue(&params.token_id, &ctx.sender(), is_owner)?,
+        ContractError::Unauthorized
+    );
+
+    state.add_issuance_root(params.token_id, params.root, ctx.sender())
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ADDRESS_0: Address = Address::Account(ACCOUNT_0);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+
+    #[concordium_test]
+    fn test_publish_issuance_root_not_owner() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(AccountAddress([1u8; 32]));
+        let params = PublishIssuanceRootParams {
+            token_id: TOKEN_0,
+            root: HashSha2256([1u8; 32]),
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        let mut host = TestHost::new(state, state_builder);
+        let result: ContractResult<()> = publish_issuance_root(&ctx, &mut host);
+        assert_eq!(result, Err(ContractError::Unauthorized));
+    }
+}
@@ -0,0 +1,72 @@
+use concordium_std::*;
+
+use crate::{
+    state::State,
+    types::{ContractError, ContractResult},
+};
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct ReminderPreferenceQueryParams {
+    #[concordium(size_length = 2)]
+    pub queries: Vec<AccountAddress>,
+}
+
+#[derive(Debug, Serialize, SchemaType)]
+pub struct ReminderPreferenceQueryResponse(
+    #[concordium(size_length = 2)] pub Vec<Option<Timestamp>>,
+);
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "reminderPreferenceOf",
+    parameter = "ReminderPreferenceQueryParams",
+    return_value = "ReminderPreferenceQueryResponse",
+    error = "ContractError"
+)]
+/// Gets the time each of the queried accounts consented to off-chain renewal reminders,
+/// None for accounts which have not consented, see `setReminderPreference`.
+pub fn reminder_preference_of<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<ReminderPreferenceQueryResponse> {
+    // Parse the parameter.
+    let params: ReminderPreferenceQueryParams = ctx.parameter_cursor().get()?;
+    let state = host.state();
+    state.ensure_batch_size(|limits| limits.query, params.queries.len())?;
+    let response = params
+        .queries
+        .iter()
+        .map(|account| state.get_reminder_consent(*account))
+        .collect();
+
+    Ok(ReminderPreferenceQueryResponse(response))
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+
+    #[concordium_test]
+    fn test_reminder_preference_of() {
+        let mut ctx = TestReceiveContext::empty();
+        let params = ReminderPreferenceQueryParams {
+            queries: vec![ACCOUNT_0, ACCOUNT_1],
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.set_reminder_consent(ACCOUNT_0, true, Timestamp::from_timestamp_millis(10));
+        let host = TestHost::new(state, state_builder);
+
+        let result = reminder_preference_of(&ctx, &host).unwrap();
+        assert_eq!(
+            result.0,
+            vec![Some(Timestamp::from_timestamp_millis(10)), None]
+        );
+    }
+}
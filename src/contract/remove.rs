@@ -2,6 +2,7 @@ use concordium_cis2::{Cis2Event, MetadataUrl, TokenMetadataEvent};
 use concordium_std::*;
 
 use crate::{
+    contract::approve_action::ensure_approved,
     errors::CustomError,
     state::State,
     types::{ContractError, ContractResult, ContractTokenAmount, ContractTokenId},
@@ -10,63 +11,97 @@ use crate::{
 #[derive(SchemaType, Deserial, Serial)]
 pub struct RemoveParams {
     pub tokens: Vec<ContractTokenId>,
+    /// Skip tokens which do not exist instead of failing, e.g. for idempotent cleanup scripts.
+    pub ignore_missing: bool,
+}
+
+#[derive(Debug, Serialize, SchemaType, PartialEq)]
+pub struct RemoveResponse {
+    /// Tokens which have been removed, in the order of the parameter.
+    pub removed: Vec<ContractTokenId>,
 }
 
 #[receive(
     contract = "cis2_dsid",
     name = "remove",
     parameter = "RemoveParams",
+    return_value = "RemoveResponse",
     error = "ContractError",
     enable_logger,
+    crypto_primitives,
     mutable
 )]
-/// Removes a token from the contract.
-/// - This function does not fail if the token does not exist.
+/// Removes tokens from the contract and returns the removed tokens.
+/// - This function fails if a token does not exist, unless `ignore_missing` is set.
 /// - This function fails if the token has valid balances.
-/// - This function fails if the sender is not the owner of the contract.
+/// - This function fails if the sender is not the admin of the contract.
+/// - This function fails if approvals are required and the invocation has not been approved by enough admins.
 pub fn remove<S: HasStateApi>(
     ctx: &impl HasReceiveContext,
     host: &mut impl HasHost<State<S>, StateApiType = S>,
     logger: &mut impl HasLogger,
-) -> ContractResult<()> {
-    // Check that the sender is the owner of the contract.
+    crypto_primitives: &impl HasCryptoPrimitives,
+) -> ContractResult<RemoveResponse> {
+    // Check that the sender is the admin of the contract.
     ensure!(
-        ctx.sender().matches_account(&ctx.owner()),
+        host.state().is_contract_admin(&ctx.sender(), ctx.owner()),
         ContractError::Unauthorized
     );
 
     let params: RemoveParams = ctx.parameter_cursor().get()?;
+    let now = ctx.metadata().slot_time();
     let state = host.state_mut();
+    state.ensure_batch_size(|limits| limits.admin, params.tokens.len())?;
+    ensure_approved(ctx, state, crypto_primitives, "remove")?;
+    let mut removed = Vec::new();
     for token_id in params.tokens {
-        // Ensure that the token exists.
-        ensure!(state.has_token(token_id), ContractError::InvalidTokenId);
-        // Ensure that tokens does not have valid balances.
-        ensure!(
-            !state.has_balances(token_id, ctx.metadata().slot_time()),
-            ContractError::Custom(CustomError::TokenHasValidBalances)
-        );
+        if params.ignore_missing && !state.has_token(token_id) {
+            continue;
+        }
+        remove_token(state, logger, token_id, now)?;
+        removed.push(token_id);
+    }
+    Ok(RemoveResponse { removed })
+}
 
-        // Remove the token from the state.
-        state.remove_token(token_id);
-
-        // Log the empty token metadata.
-        // This is done to ensure that the token metadata is removed from any off-chain listeners.
-        logger.log(&Cis2Event::TokenMetadata::<_, ContractTokenAmount>(
-            TokenMetadataEvent {
-                token_id,
-                metadata_url: MetadataUrl {
-                    url: String::new(),
-                    hash: None,
-                },
+/// Removes a single token from the state and logs empty metadata for it.
+/// - This function does not check the sender.
+pub(crate) fn remove_token<S: HasStateApi>(
+    state: &mut State<S>,
+    logger: &mut impl HasLogger,
+    token_id: ContractTokenId,
+    now: Timestamp,
+) -> ContractResult<()> {
+    // Ensure that the token exists.
+    ensure!(state.has_token(token_id), ContractError::InvalidTokenId);
+    // Ensure that tokens does not have valid balances.
+    ensure!(
+        !state.has_balances(token_id, now),
+        ContractError::Custom(CustomError::TokenHasValidBalances)
+    );
+
+    // Remove the token from the state.
+    state.remove_token(token_id);
+
+    // Log the empty token metadata.
+    // This is done to ensure that the token metadata is removed from any off-chain listeners.
+    logger.log(&Cis2Event::TokenMetadata::<_, ContractTokenAmount>(
+        TokenMetadataEvent {
+            token_id,
+            metadata_url: MetadataUrl {
+                url: String::new(),
+                hash: None,
             },
-        ))?;
-    }
+        },
+    ))?;
+
     Ok(())
 }
 
 #[concordium_cfg_test]
 mod tests {
     use super::*;
+    use crate::types::RevocationInfo;
     use concordium_cis2::*;
     use concordium_std::test_infrastructure::*;
 
@@ -85,6 +120,7 @@ mod tests {
 
         let remove_token_params = RemoveParams {
             tokens: vec![TOKEN_0, TOKEN_1],
+            ignore_missing: false,
         };
         let parameter = to_bytes(&remove_token_params);
         ctx.set_parameter(&parameter);
@@ -115,12 +151,19 @@ mod tests {
                 ACCOUNT_1,
                 ContractTokenAmount::from(1),
                 Timestamp::from_timestamp_millis(90),
+                None
             )
             .is_ok());
         let mut host = TestHost::new(state, state_builder);
         let mut logger = TestLogger::init();
-        let result: ContractResult<()> = remove(&ctx, &mut host, &mut logger);
-        assert_eq!(result, Ok(()));
+        let result: ContractResult<RemoveResponse> =
+            remove(&ctx, &mut host, &mut logger, &TestCryptoPrimitives::new());
+        assert_eq!(
+            result,
+            Ok(RemoveResponse {
+                removed: vec![TOKEN_0, TOKEN_1]
+            })
+        );
 
         // Ensure that the tokens are removed from the state.
         assert!(!host.state().has_token(TOKEN_0));
@@ -160,6 +203,7 @@ mod tests {
 
         let remove_token_params = RemoveParams {
             tokens: vec![TOKEN_0, TOKEN_1],
+            ignore_missing: false,
         };
         let parameter = to_bytes(&remove_token_params);
         ctx.set_parameter(&parameter);
@@ -184,7 +228,8 @@ mod tests {
         );
         let mut host = TestHost::new(state, state_builder);
         let mut logger = TestLogger::init();
-        let result: ContractResult<()> = remove(&ctx, &mut host, &mut logger);
+        let result: ContractResult<RemoveResponse> =
+            remove(&ctx, &mut host, &mut logger, &TestCryptoPrimitives::new());
         assert_eq!(result, Err(ContractError::Unauthorized));
     }
 
@@ -197,6 +242,7 @@ mod tests {
 
         let remove_token_params = RemoveParams {
             tokens: vec![TOKEN_0, TOKEN_1],
+            ignore_missing: false,
         };
         let parameter = to_bytes(&remove_token_params);
         ctx.set_parameter(&parameter);
@@ -214,10 +260,49 @@ mod tests {
 
         let mut host = TestHost::new(state, state_builder);
         let mut logger = TestLogger::init();
-        let result: ContractResult<()> = remove(&ctx, &mut host, &mut logger);
+        let result: ContractResult<RemoveResponse> =
+            remove(&ctx, &mut host, &mut logger, &TestCryptoPrimitives::new());
         assert_eq!(result, Err(ContractError::InvalidTokenId));
     }
 
+    #[concordium_test]
+    fn test_remove_ignore_missing() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(99));
+
+        let remove_token_params = RemoveParams {
+            tokens: vec![TOKEN_0, TOKEN_1],
+            ignore_missing: true,
+        };
+        let parameter = to_bytes(&remove_token_params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        // Add tokens to the state.
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: String::new(),
+                hash: None,
+            },
+        );
+
+        let mut host = TestHost::new(state, state_builder);
+        let mut logger = TestLogger::init();
+        let result: ContractResult<RemoveResponse> =
+            remove(&ctx, &mut host, &mut logger, &TestCryptoPrimitives::new());
+        assert_eq!(
+            result,
+            Ok(RemoveResponse {
+                removed: vec![TOKEN_0]
+            })
+        );
+        assert!(!host.state().has_token(TOKEN_0));
+    }
+
     #[concordium_test]
     fn test_remove_token_has_valid_balances() {
         let mut ctx = TestReceiveContext::empty();
@@ -227,6 +312,7 @@ mod tests {
 
         let remove_token_params = RemoveParams {
             tokens: vec![TOKEN_0, TOKEN_1],
+            ignore_missing: false,
         };
         let parameter = to_bytes(&remove_token_params);
         ctx.set_parameter(&parameter);
@@ -256,14 +342,190 @@ mod tests {
                 ACCOUNT_1,
                 ContractTokenAmount::from(1),
                 Timestamp::from_timestamp_millis(100),
+                None
             )
             .is_ok());
         let mut host = TestHost::new(state, state_builder);
         let mut logger = TestLogger::init();
-        let result: ContractResult<()> = remove(&ctx, &mut host, &mut logger);
+        let result: ContractResult<RemoveResponse> =
+            remove(&ctx, &mut host, &mut logger, &TestCryptoPrimitives::new());
         assert_eq!(
             result,
             Err(ContractError::Custom(CustomError::TokenHasValidBalances))
         );
     }
+
+    #[concordium_test]
+    fn test_remove_token_has_renewed_balances() {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: String::new(),
+                hash: None,
+            },
+        );
+        for account in [ACCOUNT_0, ACCOUNT_1] {
+            claim!(state
+                .mint(
+                    TOKEN_0,
+                    account,
+                    ContractTokenAmount::from(1),
+                    Timestamp::from_timestamp_millis(100),
+                    None
+                )
+                .is_ok());
+        }
+        // Renewing, shrinking to 0 and growing again keeps the balance valid.
+        claim!(state
+            .renew(TOKEN_0, ACCOUNT_1, Timestamp::from_timestamp_millis(200))
+            .is_ok());
+        claim!(state
+            .adjust_amount(TOKEN_0, ACCOUNT_1, ContractTokenAmount::from(0))
+            .is_ok());
+        claim!(state
+            .adjust_amount(TOKEN_0, ACCOUNT_1, ContractTokenAmount::from(2))
+            .is_ok());
+
+        let now = Timestamp::from_timestamp_millis(150);
+        assert!(state.has_balances(TOKEN_0, now));
+        // Without holders left the token has no valid balances.
+        claim!(state
+            .revoke(
+                TOKEN_0,
+                ACCOUNT_1,
+                RevocationInfo {
+                    reason: 0,
+                    timestamp: now,
+                },
+            )
+            .is_ok());
+        claim!(state
+            .revoke(
+                TOKEN_0,
+                ACCOUNT_0,
+                RevocationInfo {
+                    reason: 0,
+                    timestamp: now,
+                },
+            )
+            .is_ok());
+        assert!(!state.has_balances(TOKEN_0, now));
+    }
+
+    #[concordium_test]
+    fn test_remove_token_with_revoked_balance() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(50));
+
+        let remove_token_params = RemoveParams {
+            tokens: vec![TOKEN_0],
+            ignore_missing: false,
+        };
+        let parameter = to_bytes(&remove_token_params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: String::new(),
+                hash: None,
+            },
+        );
+        // Replace a balance and revoke it, leaving no holders.
+        for amount in [1, 2] {
+            claim!(state
+                .mint(
+                    TOKEN_0,
+                    ACCOUNT_1,
+                    ContractTokenAmount::from(amount),
+                    Timestamp::from_timestamp_millis(100),
+                    None
+                )
+                .is_ok());
+        }
+        claim!(state
+            .revoke(
+                TOKEN_0,
+                ACCOUNT_1,
+                RevocationInfo {
+                    reason: 0,
+                    timestamp: Timestamp::from_timestamp_millis(50),
+                },
+            )
+            .is_ok());
+        let mut host = TestHost::new(state, state_builder);
+        let mut logger = TestLogger::init();
+        let result: ContractResult<RemoveResponse> =
+            remove(&ctx, &mut host, &mut logger, &TestCryptoPrimitives::new());
+        assert_eq!(
+            result,
+            Ok(RemoveResponse {
+                removed: vec![TOKEN_0]
+            })
+        );
+        assert!(!host.state().has_token(TOKEN_0));
+    }
+
+    #[concordium_test]
+    fn test_remove_requires_approvals() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(50));
+
+        let remove_token_params = RemoveParams {
+            tokens: vec![TOKEN_0],
+            ignore_missing: false,
+        };
+        let parameter = to_bytes(&remove_token_params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: String::new(),
+                hash: None,
+            },
+        );
+        state
+            .set_admins(vec![ACCOUNT_0, ACCOUNT_1], 2)
+            .expect("Failed to set admins");
+        let mut host = TestHost::new(state, state_builder);
+        let mut logger = TestLogger::init();
+        let crypto_primitives = TestCryptoPrimitives::new();
+        crypto_primitives.setup_hash_sha2_256_mock(|_| HashSha2256([1u8; 32]));
+
+        let result: ContractResult<RemoveResponse> =
+            remove(&ctx, &mut host, &mut logger, &crypto_primitives);
+        assert_eq!(
+            result,
+            Err(ContractError::Custom(CustomError::ActionNotApproved))
+        );
+
+        for admin in [ACCOUNT_0, ACCOUNT_1] {
+            host.state_mut().approve_action(
+                HashSha2256([1u8; 32]),
+                admin,
+                Timestamp::from_timestamp_millis(100),
+            );
+        }
+        let result: ContractResult<RemoveResponse> =
+            remove(&ctx, &mut host, &mut logger, &crypto_primitives);
+        assert_eq!(
+            result,
+            Ok(RemoveResponse {
+                removed: vec![TOKEN_0]
+            })
+        );
+        assert!(!host.state().has_token(TOKEN_0));
+    }
 }
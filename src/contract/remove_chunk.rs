@@ -0,0 +1,155 @@
+use concordium_std::*;
+
+use crate::{
+    contract::{
+        approve_action::ensure_approved,
+        remove::remove_token,
+        update_subscribers::{notify_subscribers, DsidNotification},
+    },
+    errors::CustomError,
+    state::State,
+    types::{ContractError, ContractResult, ContractTokenAmount, ContractTokenId},
+};
+
+#[derive(SchemaType, Deserial, Serial)]
+pub struct RemoveChunkParams {
+    pub token_id: ContractTokenId,
+    /// Maximum number of balances which are deleted in this call.
+    pub limit: u32,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "removeChunk",
+    parameter = "RemoveChunkParams",
+    return_value = "bool",
+    error = "ContractError",
+    enable_logger,
+    crypto_primitives,
+    mutable
+)]
+/// Deletes up to `limit` expired balances of a token without valid balances and removes the
+/// token once no balances are left, like `remove` but spread over several calls for tokens with
+/// many holders. Returns true if the token has been removed, otherwise the call has to be repeated.
+/// - This function fails if the token does not exist.
+/// - This function fails if the token has valid balances.
+/// - This function fails if the sender is not the admin of the contract.
+/// - This function fails if approvals are required and the invocation has not been approved by enough admins.
+pub fn remove_chunk<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    crypto_primitives: &impl HasCryptoPrimitives,
+) -> ContractResult<bool> {
+    // Check that the sender is the admin of the contract.
+    ensure!(
+        host.state().is_contract_admin(&ctx.sender(), ctx.owner()),
+        ContractError::Unauthorized
+    );
+
+    let params: RemoveChunkParams = ctx.parameter_cursor().get()?;
+    let now = ctx.metadata().slot_time();
+    let state = host.state_mut();
+    ensure_approved(ctx, state, crypto_primitives, "removeChunk")?;
+    ensure!(
+        state.has_token(params.token_id),
+        ContractError::InvalidTokenId
+    );
+    // Ensure that no valid balances are deleted, these are burned by `forceRemove`.
+    ensure!(
+        !state.has_balances(params.token_id, now),
+        ContractError::Custom(CustomError::TokenHasValidBalances)
+    );
+
+    let (removed, done) = state.clear_balances(params.token_id, params.limit, now)?;
+    if done {
+        // No balances are left, remove the token.
+        remove_token(state, logger, params.token_id, now)?;
+    }
+
+    // Notify the subscribers about the removed balances.
+    let notifications = removed
+        .into_iter()
+        .map(|(account, _)| DsidNotification {
+            token_id: params.token_id,
+            account,
+            amount: ContractTokenAmount::from(0),
+            expiry: now,
+        })
+        .collect();
+    notify_subscribers(host, logger, notifications)?;
+
+    Ok(done)
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const ACCOUNT_2: AccountAddress = AccountAddress([2u8; 32]);
+    const ADDRESS_0: Address = Address::Account(ACCOUNT_0);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+
+    fn remove_chunk_with(
+        host: &mut TestHost<State<TestStateApi>>,
+        now: u64,
+    ) -> ContractResult<bool> {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(now));
+        let params = RemoveChunkParams {
+            token_id: TOKEN_0,
+            limit: 1,
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        remove_chunk(
+            &ctx,
+            host,
+            &mut TestLogger::init(),
+            &TestCryptoPrimitives::new(),
+        )
+    }
+
+    #[concordium_test]
+    fn test_remove_chunk() {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        for account in [ACCOUNT_1, ACCOUNT_2] {
+            state
+                .mint(
+                    TOKEN_0,
+                    account,
+                    1.into(),
+                    Timestamp::from_timestamp_millis(100),
+                    None,
+                )
+                .expect("Failed to mint token");
+        }
+        let mut host = TestHost::new(state, state_builder);
+
+        // Valid balances are not deleted.
+        assert_eq!(
+            remove_chunk_with(&mut host, 50),
+            Err(ContractError::Custom(CustomError::TokenHasValidBalances))
+        );
+        // Once the balances have expired, one balance is deleted per call.
+        assert_eq!(remove_chunk_with(&mut host, 150), Ok(false));
+        assert!(host.state().has_token(TOKEN_0));
+        assert_eq!(remove_chunk_with(&mut host, 150), Ok(true));
+        assert!(!host.state().has_token(TOKEN_0));
+    }
+}
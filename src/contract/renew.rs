@@ -0,0 +1,368 @@
+use concordium_cis2::Cis2Error;
+use concordium_std::*;
+
+use crate::{
+    contract::update_subscribers::{notify_subscribers, DsidNotification},
+    errors::CustomError,
+    state::State,
+    types::{
+        BalanceChange, ContractError, ContractEvent, ContractResult, ContractTokenId,
+        DsidRenewEvent, FeeCollectedEvent, HistoryEntry, RenewalPolicy,
+    },
+};
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct RenewParams {
+    pub token_id: ContractTokenId,
+    /// Owner of the renewed balance.
+    pub account: AccountAddress,
+    /// The new expiry of the balance.
+    /// If omitted, the expiry is the current time plus the default validity of the token.
+    pub expiry: Option<Timestamp>,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "renew",
+    parameter = "RenewParams",
+    error = "ContractError",
+    payable,
+    enable_logger,
+    mutable
+)]
+/// Extends the expiry of an existing balance according to the renewal policy of the token.
+/// Holders renewing their own balance pay the renewal fee in CCD, see `setRenewalFee`.
+/// The fee is credited to the collected renewal fees, which can be withdrawn using `withdrawFees`.
+/// CCD sent above the fee is returned to the sender. A `FeeCollected` event logs the CCD sent,
+/// the credited fee and the refund.
+/// - This function fails if the CCD sent is less than the renewal fee.
+/// - This function fails if CCD has to be returned to a contract.
+/// - This function fails if the sender is not allowed to renew by the renewal policy.
+/// - This function fails if the holder renews their own balance with an expiry.
+/// - This function fails if the token does not exist.
+/// - This function fails if the account does not have a balance of the token.
+/// - This function fails if minting of the token is paused or the token has been deprecated.
+/// - This function fails if no expiry is given and the token has no default validity.
+/// - This function fails if the expiry exceeds the maximum validity of the contract configuration.
+/// - This function fails if the expiry is not after the start of the validity of the balance.
+/// - This function fails if the token has monotonic expiries and the expiry shortens the unexpired balance.
+/// - This function fails if the expiry is after the hard expiry of the balance, whatever the renewal policy.
+pub fn renew<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    amount: Amount,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    let params: RenewParams = ctx.parameter_cursor().get()?;
+    // Only holders pay the renewal fee, issuers renew for free.
+    let fee = if ctx.sender().matches_account(&params.account) {
+        host.state().renewal_fee()
+    } else {
+        Amount::zero()
+    };
+    ensure!(
+        amount >= fee,
+        Cis2Error::Custom(CustomError::InsufficientFee)
+    );
+    renew_balance(ctx, host, logger, params)?;
+    host.state_mut().credit_renewal_fee(fee);
+
+    if amount > Amount::zero() {
+        // Return the CCD sent above the fee instead of keeping it.
+        let refund = amount - fee;
+        if refund > Amount::zero() {
+            let sender = match ctx.sender() {
+                Address::Account(account) => account,
+                Address::Contract(_) => bail!(ContractError::Custom(CustomError::AccountsOnly)),
+            };
+            host.invoke_transfer(&sender, refund)
+                .map_err(|_| ContractError::Custom(CustomError::FeePaymentFailed))?;
+        }
+        logger.log(&ContractEvent::FeeCollected(FeeCollectedEvent {
+            amount,
+            fee,
+            refund,
+        }))?;
+    }
+    Ok(())
+}
+
+/// Renews a balance like `renew`, checking the sender against the renewal policy of the token.
+pub(crate) fn renew_balance<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    params: RenewParams,
+) -> ContractResult<()> {
+    let now = ctx.metadata().slot_time();
+    let is_owner = host.state().is_contract_admin(&ctx.sender(), ctx.owner());
+    let state = host.state_mut();
+
+    // Check that the sender is allowed to renew the balance.
+    let is_issuer = state.can_issue(&params.token_id, &ctx.sender(), is_owner)?;
+    let is_holder = ctx.sender().matches_account(&params.account);
+    let authorized = match state.get_token_renewal_policy(&params.token_id)? {
+        // Holders renew tokens with proofs using `renewWithProof`.
+        RenewalPolicy::IssuerOnly | RenewalPolicy::AutoOnProof => is_issuer,
+        // Holders can not choose the expiry of their own balance.
+        RenewalPolicy::HolderPaid => is_issuer || (is_holder && params.expiry.is_none()),
+    };
+    ensure!(authorized, ContractError::Unauthorized);
+    renew_authorized(host, logger, params, ctx.sender(), now)
+}
+
+/// Renews a balance on behalf of `renewer`, who has already been authorized by the caller.
+pub(crate) fn renew_authorized<S: HasStateApi>(
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    params: RenewParams,
+    renewer: Address,
+    now: Timestamp,
+) -> ContractResult<()> {
+    let state = host.state_mut();
+    ensure!(
+        !state.is_token_paused(&params.token_id)?,
+        Cis2Error::Custom(CustomError::TokenPaused)
+    );
+    ensure!(
+        !state.is_token_deprecated(&params.token_id)?,
+        Cis2Error::Custom(CustomError::TokenDeprecated)
+    );
+
+    let expiry = match params.expiry {
+        Some(expiry) => expiry,
+        None => state
+            .get_token_default_validity(&params.token_id)?
+            .and_then(|validity| now.checked_add(validity))
+            .ok_or(Cis2Error::Custom(CustomError::ExpiryRequired))?,
+    };
+    let expiry = state.config().round_expiry(expiry);
+    ensure!(expiry > now, Cis2Error::Custom(CustomError::TokenExpired));
+    ensure!(
+        !state.config().exceeds_max_validity(now, expiry),
+        Cis2Error::Custom(CustomError::ValidityTooLong)
+    );
+    let valid_from = state.get_account_balance_valid_from(params.token_id, params.account)?;
+    state.ensure_valid_expiry(&params.token_id, params.account, expiry, valid_from, now)?;
+    let amount = state.renew(params.token_id, params.account, expiry)?;
+    state.record_history(
+        params.token_id,
+        params.account,
+        HistoryEntry {
+            timestamp: now,
+            change: BalanceChange::Renew,
+            amount,
+            expiry,
+            actor: renewer,
+        },
+    )?;
+
+    // Log the new expiry for indexers.
+    logger.log(&ContractEvent::DsidRenew(DsidRenewEvent {
+        token_id: params.token_id,
+        owner: Address::Account(params.account),
+        expiry,
+        renewer,
+    }))?;
+
+    // Notify the subscribers about the new expiry.
+    notify_subscribers(
+        host,
+        logger,
+        vec![DsidNotification {
+            token_id: params.token_id,
+            account: params.account,
+            amount,
+            expiry,
+        }],
+    )?;
+
+    Ok(())
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+
+    fn host_with_balance(policy: RenewalPolicy) -> TestHost<State<TestStateApi>> {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        state
+            .set_token_default_validity(TOKEN_0, Some(Duration::from_millis(100)))
+            .expect("Failed to set default validity");
+        state
+            .set_token_renewal_policy(TOKEN_0, policy)
+            .expect("Failed to set renewal policy");
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_1,
+                1.into(),
+                Timestamp::from_timestamp_millis(20),
+                None,
+            )
+            .expect("Failed to mint token");
+        TestHost::new(state, state_builder)
+    }
+
+    fn renew_with(
+        host: &mut TestHost<State<TestStateApi>>,
+        logger: &mut TestLogger,
+        sender: AccountAddress,
+        expiry: Option<u64>,
+    ) -> ContractResult<()> {
+        renew_paying_with(host, logger, sender, expiry, Amount::zero())
+    }
+
+    fn renew_paying_with(
+        host: &mut TestHost<State<TestStateApi>>,
+        logger: &mut TestLogger,
+        sender: AccountAddress,
+        expiry: Option<u64>,
+        amount: Amount,
+    ) -> ContractResult<()> {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(sender));
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(10));
+        let parameter = to_bytes(&RenewParams {
+            token_id: TOKEN_0,
+            account: ACCOUNT_1,
+            expiry: expiry.map(Timestamp::from_timestamp_millis),
+        });
+        ctx.set_parameter(&parameter);
+        // The received CCD are part of the balance of the contract.
+        host.set_self_balance(amount);
+        renew(&ctx, host, amount, logger)
+    }
+
+    #[concordium_test]
+    fn test_renew() {
+        let mut host = host_with_balance(RenewalPolicy::IssuerOnly);
+        let mut logger = TestLogger::init();
+        let result = renew_with(&mut host, &mut logger, ACCOUNT_0, Some(500));
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            host.state().get_account_balance_expiry(TOKEN_0, ACCOUNT_1),
+            Ok(Some(Timestamp::from_timestamp_millis(500)))
+        );
+        assert_eq!(
+            logger.logs,
+            vec![to_bytes(&ContractEvent::DsidRenew(DsidRenewEvent {
+                token_id: TOKEN_0,
+                owner: Address::Account(ACCOUNT_1),
+                expiry: Timestamp::from_timestamp_millis(500),
+                renewer: Address::Account(ACCOUNT_0),
+            }))]
+        );
+    }
+
+    #[concordium_test]
+    fn test_renew_by_holder() {
+        let mut logger = TestLogger::init();
+
+        // The holder can only renew if the policy allows it.
+        let mut host = host_with_balance(RenewalPolicy::IssuerOnly);
+        let result = renew_with(&mut host, &mut logger, ACCOUNT_1, None);
+        assert_eq!(result, Err(ContractError::Unauthorized));
+
+        let mut host = host_with_balance(RenewalPolicy::HolderPaid);
+        let result = renew_with(&mut host, &mut logger, ACCOUNT_1, None);
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            host.state().get_account_balance_expiry(TOKEN_0, ACCOUNT_1),
+            Ok(Some(Timestamp::from_timestamp_millis(110)))
+        );
+
+        // The holder can not choose the expiry.
+        let result = renew_with(&mut host, &mut logger, ACCOUNT_1, Some(500));
+        assert_eq!(result, Err(ContractError::Unauthorized));
+    }
+
+    #[concordium_test]
+    fn test_renew_refund() {
+        let mut host = host_with_balance(RenewalPolicy::HolderPaid);
+        host.state_mut().set_renewal_fee(Amount::from_micro_ccd(5));
+
+        // The holder has to pay the fee.
+        let mut logger = TestLogger::init();
+        let result = renew_paying_with(
+            &mut host,
+            &mut logger,
+            ACCOUNT_1,
+            None,
+            Amount::from_micro_ccd(4),
+        );
+        assert_eq!(
+            result,
+            Err(ContractError::Custom(CustomError::InsufficientFee))
+        );
+
+        // CCD above the fee is returned to the holder.
+        let mut logger = TestLogger::init();
+        let result = renew_paying_with(
+            &mut host,
+            &mut logger,
+            ACCOUNT_1,
+            None,
+            Amount::from_micro_ccd(8),
+        );
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            host.get_transfers(),
+            vec![(ACCOUNT_1, Amount::from_micro_ccd(3))]
+        );
+        assert_eq!(
+            host.state().collected_renewal_fees(),
+            Amount::from_micro_ccd(5)
+        );
+        assert_eq!(
+            logger.logs.last(),
+            Some(&to_bytes(&ContractEvent::FeeCollected(FeeCollectedEvent {
+                amount: Amount::from_micro_ccd(8),
+                fee: Amount::from_micro_ccd(5),
+                refund: Amount::from_micro_ccd(3),
+            })))
+        );
+
+        // Issuers pay no fee, so all CCD is returned.
+        let mut host = host_with_balance(RenewalPolicy::IssuerOnly);
+        host.state_mut().set_renewal_fee(Amount::from_micro_ccd(5));
+        let mut logger = TestLogger::init();
+        let result = renew_paying_with(
+            &mut host,
+            &mut logger,
+            ACCOUNT_0,
+            Some(500),
+            Amount::from_micro_ccd(2),
+        );
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            host.get_transfers(),
+            vec![(ACCOUNT_0, Amount::from_micro_ccd(2))]
+        );
+        assert_eq!(host.state().collected_renewal_fees(), Amount::zero());
+        assert_eq!(
+            logger.logs.last(),
+            Some(&to_bytes(&ContractEvent::FeeCollected(FeeCollectedEvent {
+                amount: Amount::from_micro_ccd(2),
+                fee: Amount::zero(),
+                refund: Amount::from_micro_ccd(2),
+            })))
+        );
+    }
+}
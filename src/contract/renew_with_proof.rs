@@ -0,0 +1,246 @@
+use concordium_std::*;
+
+use crate::{
+    contract::renew::{renew_authorized, RenewParams},
+    errors::CustomError,
+    state::State,
+    types::{ContractError, ContractResult, ContractTokenId, RenewalPolicy},
+};
+
+/// Renewal an issuer allows for the balance of an account, signed with an issuer key of the token.
+#[derive(Serial, Deserial, SchemaType)]
+pub struct RenewalVoucher {
+    /// Address of the contract the voucher is meant for.
+    pub contract_address: ContractAddress,
+    /// Id chosen by the issuer, shared with the vouchers of `claim`.
+    /// Each voucher of a token can only be used once.
+    pub voucher_id: u64,
+    pub token_id: ContractTokenId,
+    /// Owner of the renewed balance.
+    pub account: AccountAddress,
+    /// The new expiry of the balance.
+    /// If omitted, the expiry is the current time plus the default validity of the token.
+    pub expiry: Option<Timestamp>,
+    /// Time at which the voucher was signed, used to select the valid issuer key.
+    pub issued_at: Timestamp,
+    /// Time until which the voucher can be used.
+    pub valid_until: Timestamp,
+}
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct RenewWithProofParams {
+    /// Issuer key which signed the voucher.
+    pub signer: PublicKeyEd25519,
+    /// Signature of the serialized voucher.
+    pub signature: SignatureEd25519,
+    pub voucher: RenewalVoucher,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "renewWithProof",
+    parameter = "RenewWithProofParams",
+    error = "ContractError",
+    enable_logger,
+    crypto_primitives,
+    mutable
+)]
+/// Renews a balance of a token with the `AutoOnProof` renewal policy using a voucher signed by an
+/// issuer key of the token, like `renew` by the issuer. Anyone can submit the voucher, no renewal fee is charged.
+/// - This function fails if the voucher is meant for another contract.
+/// - This function fails if the voucher is no longer valid or has already been used.
+/// - This function fails if the signature is not valid.
+/// - This function fails if the renewal policy of the token is not `AutoOnProof`.
+/// - This function fails if the signer was not an issuer key of the token when the voucher was issued.
+/// - This function fails if the account of the voucher has called a self-service entrypoint within the configured cooldown.
+/// - This function fails if the balance can not be renewed by `renew`, e.g. minting of the token is paused.
+pub fn renew_with_proof<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    crypto_primitives: &impl HasCryptoPrimitives,
+) -> ContractResult<()> {
+    let params: RenewWithProofParams = ctx.parameter_cursor().get()?;
+    let voucher = params.voucher;
+    ensure_eq!(
+        voucher.contract_address,
+        ctx.self_address(),
+        ContractError::Custom(CustomError::WrongContract)
+    );
+    let now = ctx.metadata().slot_time();
+    ensure!(
+        now <= voucher.valid_until,
+        ContractError::Custom(CustomError::VoucherExpired)
+    );
+
+    // Check the signature of the voucher.
+    let voucher_bytes = to_bytes(&voucher);
+    ensure!(
+        crypto_primitives.verify_ed25519_signature(params.signer, params.signature, &voucher_bytes),
+        ContractError::Custom(CustomError::WrongSignature)
+    );
+
+    let state = host.state_mut();
+    ensure!(
+        state.get_token_renewal_policy(&voucher.token_id)? == RenewalPolicy::AutoOnProof,
+        ContractError::Unauthorized
+    );
+    state.ensure_issuer_key(&voucher.token_id, &params.signer, voucher.issued_at)?;
+    state.use_voucher(voucher.token_id, voucher.voucher_id)?;
+    state.record_self_service_action(voucher.account, now)?;
+    // The issuer signed the voucher, so the renewal is recorded as theirs.
+    let issuer = state
+        .get_token_issuer(&voucher.token_id)?
+        .unwrap_or_else(|| Address::Account(state.contract_admin(ctx.owner())));
+
+    renew_authorized(
+        host,
+        logger,
+        RenewParams {
+            token_id: voucher.token_id,
+            account: voucher.account,
+            expiry: voucher.expiry,
+        },
+        issuer,
+        now,
+    )
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::types::{ContractEvent, DsidRenewEvent, IssuerKeyValidity};
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const ACCOUNT_2: AccountAddress = AccountAddress([2u8; 32]);
+    const SELF_ADDRESS: ContractAddress = ContractAddress {
+        index: 1,
+        subindex: 0,
+    };
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+    const KEY_0: PublicKeyEd25519 = PublicKeyEd25519([1u8; 32]);
+    const KEY_1: PublicKeyEd25519 = PublicKeyEd25519([2u8; 32]);
+    const SIGNATURE_0: SignatureEd25519 = SignatureEd25519([3u8; 64]);
+
+    fn host_with_balance(policy: RenewalPolicy) -> TestHost<State<TestStateApi>> {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        state
+            .set_token_renewal_policy(TOKEN_0, policy)
+            .expect("Failed to set renewal policy");
+        state
+            .set_issuer_key(
+                TOKEN_0,
+                KEY_0,
+                IssuerKeyValidity {
+                    active_from: Timestamp::from_timestamp_millis(0),
+                    retired_at: None,
+                },
+            )
+            .expect("Failed to set issuer key");
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_1,
+                1.into(),
+                Timestamp::from_timestamp_millis(100),
+                None,
+            )
+            .expect("Failed to mint token");
+        TestHost::new(state, state_builder)
+    }
+
+    fn renew_with_proof_with(
+        host: &mut TestHost<State<TestStateApi>>,
+        logger: &mut TestLogger,
+        signer: PublicKeyEd25519,
+        voucher_id: u64,
+    ) -> ContractResult<()> {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_self_address(SELF_ADDRESS);
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_sender(Address::Account(ACCOUNT_2));
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(80));
+        let parameter = to_bytes(&RenewWithProofParams {
+            signer,
+            signature: SIGNATURE_0,
+            voucher: RenewalVoucher {
+                contract_address: SELF_ADDRESS,
+                voucher_id,
+                token_id: TOKEN_0,
+                account: ACCOUNT_1,
+                expiry: Some(Timestamp::from_timestamp_millis(500)),
+                issued_at: Timestamp::from_timestamp_millis(50),
+                valid_until: Timestamp::from_timestamp_millis(100),
+            },
+        });
+        ctx.set_parameter(&parameter);
+        let crypto_primitives = TestCryptoPrimitives::new();
+        crypto_primitives
+            .setup_verify_ed25519_signature_mock(|_, signature, _| signature == SIGNATURE_0);
+        renew_with_proof(&ctx, host, logger, &crypto_primitives)
+    }
+
+    #[concordium_test]
+    fn test_renew_with_proof() {
+        let mut host = host_with_balance(RenewalPolicy::AutoOnProof);
+        let mut logger = TestLogger::init();
+        let result = renew_with_proof_with(&mut host, &mut logger, KEY_0, 1);
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            host.state().get_account_balance_expiry(TOKEN_0, ACCOUNT_1),
+            Ok(Some(Timestamp::from_timestamp_millis(500)))
+        );
+        // The renewal is recorded as the issuer's, not the one of the sender.
+        assert_eq!(
+            logger.logs,
+            vec![to_bytes(&ContractEvent::DsidRenew(DsidRenewEvent {
+                token_id: TOKEN_0,
+                owner: Address::Account(ACCOUNT_1),
+                expiry: Timestamp::from_timestamp_millis(500),
+                renewer: Address::Account(ACCOUNT_0),
+            }))]
+        );
+
+        // Vouchers can only be used once.
+        let result = renew_with_proof_with(&mut host, &mut logger, KEY_0, 1);
+        assert_eq!(
+            result,
+            Err(ContractError::Custom(CustomError::VoucherAlreadyUsed))
+        );
+    }
+
+    #[concordium_test]
+    fn test_renew_with_proof_unknown_key() {
+        let mut host = host_with_balance(RenewalPolicy::AutoOnProof);
+        let mut logger = TestLogger::init();
+        let result = renew_with_proof_with(&mut host, &mut logger, KEY_1, 1);
+        assert_eq!(
+            result,
+            Err(ContractError::Custom(CustomError::UnknownIssuerKey))
+        );
+        assert_eq!(
+            host.state().get_account_balance_expiry(TOKEN_0, ACCOUNT_1),
+            Ok(Some(Timestamp::from_timestamp_millis(100)))
+        );
+    }
+
+    #[concordium_test]
+    fn test_renew_with_proof_wrong_policy() {
+        let mut host = host_with_balance(RenewalPolicy::IssuerOnly);
+        let mut logger = TestLogger::init();
+        let result = renew_with_proof_with(&mut host, &mut logger, KEY_0, 1);
+        assert_eq!(result, Err(ContractError::Unauthorized));
+    }
+}
@@ -0,0 +1,188 @@
+use concordium_cis2::{AdditionalData, Receiver, Transfer, TransferParams};
+use concordium_std::*;
+
+use crate::{
+    contract::renew::{renew_balance, RenewParams},
+    errors::CustomError,
+    state::State,
+    types::{ContractError, ContractResult},
+};
+
+/// Entrypoint of the fee token contract which transfers the fee.
+const TRANSFER_ENTRYPOINT: &str = "transfer";
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "renewWithToken",
+    parameter = "RenewParams",
+    error = "ContractError",
+    enable_logger,
+    mutable
+)]
+/// Renews a balance like `renew` and pays the fee in the configured CIS-2 fee token, see `setFeeToken`.
+/// Holders renewing their own balance pay the fee, issuers renew for free like with `renew`.
+/// The fee is transferred from the holder to the fee recipient by this contract, which the holder
+/// has to add as an operator on the fee token contract beforehand. The fee is credited to the fee ledger.
+/// - This function fails if the holder renews and no fee token is configured.
+/// - This function fails if the transfer of the fee fails.
+/// - This function fails if the balance can not be renewed by the sender, see `renew`.
+pub fn renew_with_token<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    let params: RenewParams = ctx.parameter_cursor().get()?;
+    // Only holders pay the renewal fee, issuers renew for free.
+    if ctx.sender().matches_account(&params.account) {
+        let fee_token = host
+            .state()
+            .fee_token()
+            .ok_or(ContractError::Custom(CustomError::FeeNotConfigured))?;
+
+        // Pull the fee from the holder, this contract is an operator of the holder.
+        let transfer = Transfer {
+            token_id: fee_token.token_id.clone(),
+            amount: fee_token.amount,
+            from: Address::Account(params.account),
+            to: Receiver::from_account(fee_token.recipient),
+            data: AdditionalData::empty(),
+        };
+        host.invoke_contract(
+            &fee_token.contract,
+            &TransferParams(vec![transfer]),
+            EntrypointName::new_unchecked(TRANSFER_ENTRYPOINT),
+            Amount::zero(),
+        )
+        .map_err(|_| ContractError::Custom(CustomError::FeePaymentFailed))?;
+        host.state_mut().credit_fee(&fee_token);
+    }
+
+    renew_balance(ctx, host, logger, params)
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::types::{ContractTokenId, FeeToken, RenewalPolicy};
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const ACCOUNT_2: AccountAddress = AccountAddress([2u8; 32]);
+    const FEE_CONTRACT: ContractAddress = ContractAddress {
+        index: 1,
+        subindex: 0,
+    };
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+
+    fn fee_token() -> FeeToken {
+        FeeToken {
+            contract: FEE_CONTRACT,
+            token_id: TokenIdVec(vec![]),
+            amount: TokenAmountU64(5),
+            recipient: ACCOUNT_2,
+        }
+    }
+
+    fn host_with_balance(fee_token: Option<FeeToken>) -> TestHost<State<TestStateApi>> {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        state
+            .set_token_default_validity(TOKEN_0, Some(Duration::from_millis(100)))
+            .expect("Failed to set default validity");
+        state
+            .set_token_renewal_policy(TOKEN_0, RenewalPolicy::HolderPaid)
+            .expect("Failed to set renewal policy");
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_1,
+                1.into(),
+                Timestamp::from_timestamp_millis(20),
+                None,
+            )
+            .expect("Failed to mint token");
+        state.set_fee_token(fee_token);
+        TestHost::new(state, state_builder)
+    }
+
+    fn renew_with_token_with(
+        host: &mut TestHost<State<TestStateApi>>,
+        sender: AccountAddress,
+    ) -> ContractResult<()> {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(sender));
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(10));
+        let parameter = to_bytes(&RenewParams {
+            token_id: TOKEN_0,
+            account: ACCOUNT_1,
+            expiry: None,
+        });
+        ctx.set_parameter(&parameter);
+        renew_with_token(&ctx, host, &mut TestLogger::init())
+    }
+
+    #[concordium_test]
+    fn test_renew_with_token() {
+        let mut host = host_with_balance(Some(fee_token()));
+        host.setup_mock_entrypoint(
+            FEE_CONTRACT,
+            OwnedEntrypointName::new_unchecked(TRANSFER_ENTRYPOINT.into()),
+            MockFn::returning_ok(()),
+        );
+        assert_eq!(renew_with_token_with(&mut host, ACCOUNT_1), Ok(()));
+        assert_eq!(
+            host.state().get_account_balance_expiry(TOKEN_0, ACCOUNT_1),
+            Ok(Some(Timestamp::from_timestamp_millis(110)))
+        );
+        assert_eq!(
+            host.state().fee_ledger(),
+            vec![(FEE_CONTRACT, TokenIdVec(vec![]), TokenAmountU64(5))]
+        );
+    }
+
+    #[concordium_test]
+    fn test_renew_with_token_payment_failed() {
+        let mut host = host_with_balance(Some(fee_token()));
+        host.setup_mock_entrypoint(
+            FEE_CONTRACT,
+            OwnedEntrypointName::new_unchecked(TRANSFER_ENTRYPOINT.into()),
+            MockFn::returning_err::<()>(CallContractError::Trap),
+        );
+        assert_eq!(
+            renew_with_token_with(&mut host, ACCOUNT_1),
+            Err(ContractError::Custom(CustomError::FeePaymentFailed))
+        );
+    }
+
+    #[concordium_test]
+    fn test_renew_with_token_not_configured() {
+        let mut host = host_with_balance(None);
+        assert_eq!(
+            renew_with_token_with(&mut host, ACCOUNT_1),
+            Err(ContractError::Custom(CustomError::FeeNotConfigured))
+        );
+    }
+
+    #[concordium_test]
+    fn test_renew_with_token_by_issuer() {
+        // No fee is transferred, so the fee token contract is not invoked.
+        let mut host = host_with_balance(Some(fee_token()));
+        assert_eq!(renew_with_token_with(&mut host, ACCOUNT_0), Ok(()));
+        assert_eq!(
+            host.state().get_account_balance_expiry(TOKEN_0, ACCOUNT_1),
+            Ok(Some(Timestamp::from_timestamp_millis(110)))
+        );
+        assert_eq!(host.state().fee_ledger(), vec![]);
+    }
+}
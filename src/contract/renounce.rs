@@ -0,0 +1,178 @@
+use concordium_std::*;
+
+use crate::{
+    contract::{
+        revoke::{revoke_balance, RevokeParam},
+        update_subscribers::notify_subscribers,
+    },
+    errors::CustomError,
+    state::State,
+    types::{ContractError, ContractResult, ContractTokenId},
+};
+
+/// Reason code recorded for balances revoked by their holder.
+pub const RENOUNCED_REASON: u8 = u8::MAX;
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct RenounceParams {
+    pub token_id: ContractTokenId,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "renounce",
+    parameter = "RenounceParams",
+    error = "ContractError",
+    enable_logger,
+    mutable
+)]
+/// Revokes the balance of the sender, recording the reason code `RENOUNCED_REASON`.
+/// - This function fails if the sender is not an account.
+/// - This function fails if the sender has called a self-service entrypoint within the configured cooldown.
+/// - This function fails if the token does not exist.
+/// - This function fails if the sender does not have a balance of the token.
+pub fn renounce<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    let account = match ctx.sender() {
+        Address::Account(account) => account,
+        Address::Contract(_) => bail!(ContractError::Custom(CustomError::AccountsOnly)),
+    };
+    let params: RenounceParams = ctx.parameter_cursor().get()?;
+    renounce_balance(host, logger, account, params, ctx.metadata().slot_time())
+}
+
+/// Revokes the balance of a holder like `renounce`, also invoked by `permit` on behalf of the holder.
+pub(crate) fn renounce_balance<S: HasStateApi>(
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    account: AccountAddress,
+    params: RenounceParams,
+    now: Timestamp,
+) -> ContractResult<()> {
+    let state = host.state_mut();
+    state.record_self_service_action(account, now)?;
+    let notification = revoke_balance(
+        state,
+        logger,
+        RevokeParam {
+            token_id: params.token_id,
+            account,
+            reason: RENOUNCED_REASON,
+        },
+        Address::Account(account),
+        now,
+    )?;
+
+    // Notify the subscribers about the revoked balance.
+    notify_subscribers(host, logger, vec![notification])?;
+
+    Ok(())
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::types::{ContractConfig, RevocationInfo};
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+    const TOKEN_1: ContractTokenId = TokenIdU8(3);
+
+    #[concordium_test]
+    fn test_renounce() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(ACCOUNT_1));
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(10));
+        let parameter = to_bytes(&RenounceParams { token_id: TOKEN_0 });
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_1,
+                10.into(),
+                Timestamp::from_timestamp_millis(100),
+                None,
+            )
+            .expect("Failed to mint token");
+        let mut host = TestHost::new(state, state_builder);
+        let mut logger = TestLogger::init();
+
+        let result = renounce(&ctx, &mut host, &mut logger);
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            host.state().get_revocation(TOKEN_0, ACCOUNT_1),
+            Ok(Some(RevocationInfo {
+                reason: RENOUNCED_REASON,
+                timestamp: Timestamp::from_timestamp_millis(10),
+            }))
+        );
+
+        // The balance can only be renounced once.
+        let result = renounce(&ctx, &mut host, &mut logger);
+        assert_eq!(
+            result,
+            Err(ContractError::Custom(CustomError::CredentialNotFound))
+        );
+    }
+
+    #[concordium_test]
+    fn test_renounce_cooldown() {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.set_config(ContractConfig {
+            self_service_cooldown: Some(Duration::from_millis(100)),
+            ..Default::default()
+        });
+        for token_id in [TOKEN_0, TOKEN_1] {
+            state.add_token(
+                &mut state_builder,
+                token_id,
+                MetadataUrl {
+                    url: format!("https://example.com/{}", token_id.0),
+                    hash: None,
+                },
+            );
+            state
+                .mint(
+                    token_id,
+                    ACCOUNT_1,
+                    10.into(),
+                    Timestamp::from_timestamp_millis(1000),
+                    None,
+                )
+                .expect("Failed to mint token");
+        }
+        let mut host = TestHost::new(state, state_builder);
+        let mut logger = TestLogger::init();
+        let mut renounce_at = |token_id, time| {
+            let mut ctx = TestReceiveContext::empty();
+            ctx.set_sender(Address::Account(ACCOUNT_1));
+            ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(time));
+            let parameter = to_bytes(&RenounceParams { token_id });
+            ctx.set_parameter(&parameter);
+            renounce(&ctx, &mut host, &mut logger)
+        };
+
+        assert_eq!(renounce_at(TOKEN_0, 10), Ok(()));
+        assert_eq!(
+            renounce_at(TOKEN_1, 50),
+            Err(ContractError::Custom(CustomError::TooManyRequests))
+        );
+        assert_eq!(renounce_at(TOKEN_1, 110), Ok(()));
+    }
+}
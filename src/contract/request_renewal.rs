@@ -0,0 +1,149 @@
+use concordium_std::*;
+
+use crate::{
+    errors::CustomError,
+    state::State,
+    types::{ContractError, ContractEvent, ContractResult, ContractTokenId, RenewalRequestedEvent},
+};
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct RequestRenewalParams {
+    pub token_id: ContractTokenId,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "requestRenewal",
+    parameter = "RequestRenewalParams",
+    error = "ContractError",
+    enable_logger,
+    mutable
+)]
+/// Asks the issuer to renew the balance of the sender by logging a `RenewalRequested` event.
+/// The balance is renewed by the issuer using `renew` or `extendExpiry`.
+/// - This function fails if the sender is not an account.
+/// - This function fails if the sender has called a self-service entrypoint within the configured cooldown.
+/// - This function fails if the token does not exist.
+/// - This function fails if the sender does not have a balance of the token.
+pub fn request_renewal<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    let account = match ctx.sender() {
+        Address::Account(account) => account,
+        Address::Contract(_) => bail!(ContractError::Custom(CustomError::AccountsOnly)),
+    };
+    let params: RequestRenewalParams = ctx.parameter_cursor().get()?;
+    request_renewal_for(
+        host.state_mut(),
+        logger,
+        account,
+        params,
+        ctx.metadata().slot_time(),
+    )
+}
+
+/// Logs a renewal request of a holder like `requestRenewal`, also invoked by `permit` on behalf of the holder.
+pub(crate) fn request_renewal_for<S: HasStateApi>(
+    state: &mut State<S>,
+    logger: &mut impl HasLogger,
+    account: AccountAddress,
+    params: RequestRenewalParams,
+    now: Timestamp,
+) -> ContractResult<()> {
+    state.record_self_service_action(account, now)?;
+    let expiry = state
+        .get_account_balance_expiry(params.token_id, account)?
+        .ok_or(ContractError::Custom(CustomError::CredentialNotFound))?;
+
+    logger.log(&ContractEvent::RenewalRequested(RenewalRequestedEvent {
+        token_id: params.token_id,
+        owner: Address::Account(account),
+        expiry,
+    }))?;
+
+    Ok(())
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const ACCOUNT_2: AccountAddress = AccountAddress([2u8; 32]);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+
+    fn host_with_balance() -> TestHost<State<TestStateApi>> {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_1,
+                10.into(),
+                Timestamp::from_timestamp_millis(100),
+                None,
+            )
+            .expect("Failed to mint token");
+        TestHost::new(state, state_builder)
+    }
+
+    fn request_with(
+        host: &mut TestHost<State<TestStateApi>>,
+        logger: &mut TestLogger,
+        sender: AccountAddress,
+    ) -> ContractResult<()> {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(sender));
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(10));
+        let parameter = to_bytes(&RequestRenewalParams { token_id: TOKEN_0 });
+        ctx.set_parameter(&parameter);
+        request_renewal(&ctx, host, logger)
+    }
+
+    #[concordium_test]
+    fn test_request_renewal() {
+        let mut host = host_with_balance();
+        let mut logger = TestLogger::init();
+        let result = request_with(&mut host, &mut logger, ACCOUNT_1);
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            logger.logs,
+            vec![to_bytes(&ContractEvent::RenewalRequested(
+                RenewalRequestedEvent {
+                    token_id: TOKEN_0,
+                    owner: Address::Account(ACCOUNT_1),
+                    expiry: Timestamp::from_timestamp_millis(100),
+                }
+            ))]
+        );
+        // Requesting does not change the balance.
+        assert_eq!(
+            host.state().get_account_balance_expiry(TOKEN_0, ACCOUNT_1),
+            Ok(Some(Timestamp::from_timestamp_millis(100)))
+        );
+    }
+
+    #[concordium_test]
+    fn test_request_renewal_without_balance() {
+        let mut host = host_with_balance();
+        let mut logger = TestLogger::init();
+        let result = request_with(&mut host, &mut logger, ACCOUNT_2);
+        assert_eq!(
+            result,
+            Err(ContractError::Custom(CustomError::CredentialNotFound))
+        );
+        assert!(logger.logs.is_empty());
+    }
+}
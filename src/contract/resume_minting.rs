@@ -0,0 +1,84 @@
+use concordium_std::*;
+
+use crate::{
+    state::State,
+    types::{ContractError, ContractResult},
+};
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "resumeMinting",
+    error = "ContractError",
+    mutable
+)]
+/// Resumes minting after it has been paused because the mint volume limit was exceeded.
+/// The volume is counted from zero in a new window.
+/// - This function fails if the sender is not the admin of the contract.
+pub fn resume_minting<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    // Check that the sender is the admin of the contract.
+    ensure!(
+        host.state().is_contract_admin(&ctx.sender(), ctx.owner()),
+        ContractError::Unauthorized
+    );
+
+    host.state_mut().resume_minting(ctx.metadata().slot_time());
+    Ok(())
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::types::{ContractConfig, MintVolumeLimit};
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ADDRESS_0: Address = Address::Account(ACCOUNT_0);
+
+    #[concordium_test]
+    fn test_resume_minting() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(20));
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.set_config(ContractConfig {
+            mint_volume_limit: Some(MintVolumeLimit {
+                window: Duration::from_millis(100),
+                max_count: Some(1),
+                max_amount: None,
+            }),
+            ..Default::default()
+        });
+        assert!(state
+            .record_mint_volume(2, 2, Timestamp::from_timestamp_millis(10))
+            .is_some());
+        assert!(state.is_minting_paused());
+        let mut host = TestHost::new(state, state_builder);
+
+        let result = resume_minting(&ctx, &mut host);
+        assert_eq!(result, Ok(()));
+        assert!(!host.state().is_minting_paused());
+        // The volume before resuming is not counted in the new window.
+        assert_eq!(
+            host.state_mut()
+                .record_mint_volume(1, 1, Timestamp::from_timestamp_millis(30)),
+            None
+        );
+    }
+
+    #[concordium_test]
+    fn test_resume_minting_not_owner() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(AccountAddress([1u8; 32]));
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let result = resume_minting(&ctx, &mut host);
+        assert_eq!(result, Err(ContractError::Unauthorized));
+    }
+}
@@ -0,0 +1,101 @@
+use concordium_std::*;
+
+use crate::{
+    state::State,
+    types::{ContractError, ContractResult, CredentialQueryParams, RevocationInfo},
+};
+
+#[derive(Debug, Serialize, SchemaType)]
+pub struct RevocationInfoQueryResponse(
+    #[concordium(size_length = 2)] pub Vec<Option<RevocationInfo>>,
+);
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "revocationInfo",
+    parameter = "CredentialQueryParams",
+    return_value = "RevocationInfoQueryResponse",
+    error = "ContractError"
+)]
+/// Gets the reason and time of the revocation for each of the queried balances.
+/// - Returns None for balances which have not been revoked.
+/// - This function fails if any of the tokens does not exist.
+pub fn revocation_info<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<RevocationInfoQueryResponse> {
+    // Parse the parameter.
+    let params: CredentialQueryParams = ctx.parameter_cursor().get()?;
+    let state = host.state();
+    state.ensure_batch_size(|limits| limits.query, params.queries.len())?;
+    let response: Vec<Option<RevocationInfo>> = params
+        .queries
+        .iter()
+        .map(|q| state.get_revocation(q.token_id, q.account))
+        .collect::<Result<Vec<Option<RevocationInfo>>, ContractError>>()?;
+
+    Ok(RevocationInfoQueryResponse(response))
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::types::*;
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+
+    #[concordium_test]
+    fn test_revocation_info() {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        for account in [ACCOUNT_0, ACCOUNT_1] {
+            state
+                .mint(
+                    TOKEN_0,
+                    account,
+                    10.into(),
+                    Timestamp::from_timestamp_millis(200),
+                    None,
+                )
+                .expect("Failed to mint token");
+        }
+        let revocation = RevocationInfo {
+            reason: 2,
+            timestamp: Timestamp::from_timestamp_millis(100),
+        };
+        state
+            .revoke(TOKEN_0, ACCOUNT_0, revocation.clone())
+            .expect("Failed to revoke token");
+        let host = TestHost::new(state, state_builder);
+
+        let mut ctx = TestReceiveContext::empty();
+        let params = CredentialQueryParams {
+            queries: vec![
+                CredentialQuery {
+                    token_id: TOKEN_0,
+                    account: ACCOUNT_0,
+                },
+                CredentialQuery {
+                    token_id: TOKEN_0,
+                    account: ACCOUNT_1,
+                },
+            ],
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let result = revocation_info(&ctx, &host).unwrap();
+        assert_eq!(result.0, vec![Some(revocation), None]);
+    }
+}
@@ -0,0 +1,65 @@
+use concordium_std::*;
+
+use crate::{
+    state::State,
+    types::{ContractError, ContractResult},
+};
+
+#[derive(Debug, Serialize, SchemaType, PartialEq)]
+pub struct RevocationKey {
+    pub key: PublicKeyEd25519,
+    /// The nonce the next message signed with the key has to use.
+    pub nonce: u64,
+}
+
+#[derive(Debug, Serialize, SchemaType)]
+pub struct RevocationKeysResponse(#[concordium(size_length = 2)] pub Vec<RevocationKey>);
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "revocationKeys",
+    return_value = "RevocationKeysResponse",
+    error = "ContractError"
+)]
+/// Gets the registered revocation keys with their next nonce.
+pub fn revocation_keys<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<RevocationKeysResponse> {
+    let response = host
+        .state()
+        .revocation_keys()
+        .into_iter()
+        .map(|(key, nonce)| RevocationKey { key, nonce })
+        .collect();
+
+    Ok(RevocationKeysResponse(response))
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use concordium_std::test_infrastructure::*;
+
+    const KEY_0: PublicKeyEd25519 = PublicKeyEd25519([1u8; 32]);
+
+    #[concordium_test]
+    fn test_revocation_keys() {
+        let ctx = TestReceiveContext::empty();
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_revocation_key(KEY_0);
+        state
+            .use_revocation_nonce(&KEY_0, 0)
+            .expect("Failed to use nonce");
+        let host = TestHost::new(state, state_builder);
+        let result = revocation_keys(&ctx, &host).unwrap();
+        assert_eq!(
+            result.0,
+            vec![RevocationKey {
+                key: KEY_0,
+                nonce: 1,
+            }]
+        );
+    }
+}
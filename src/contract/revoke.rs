@@ -0,0 +1,292 @@
+use concordium_cis2::{BurnEvent, Cis2Event};
+use concordium_std::*;
+
+use crate::{
+    contract::update_subscribers::{notify_subscribers, DsidNotification},
+    state::State,
+    types::{
+        BalanceChange, ContractError, ContractEvent, ContractResult, ContractTokenAmount,
+        ContractTokenId, DsidBurnEvent, HistoryEntry, RevocationInfo,
+    },
+};
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct RevokeParam {
+    pub token_id: ContractTokenId,
+    pub account: AccountAddress,
+    /// Reason code of the revocation, e.g. fraud or user request.
+    pub reason: u8,
+}
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct RevokeParams {
+    pub revocations: Vec<RevokeParam>,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "revoke",
+    parameter = "RevokeParams",
+    error = "ContractError",
+    enable_logger,
+    mutable
+)]
+/// Revokes token balances and records the reason of each revocation.
+/// - This function fails if the token has an issuer and the sender is not the issuer.
+/// - This function fails if the token has no issuer and the sender is not the admin of the contract.
+/// - This function fails if the token does not exist.
+/// - This function fails if the account does not have a balance of the token.
+pub fn revoke<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    let params: RevokeParams = ctx.parameter_cursor().get()?;
+    let now = ctx.metadata().slot_time();
+    let is_owner = host.state().is_contract_admin(&ctx.sender(), ctx.owner());
+    let state = host.state_mut();
+    state.ensure_batch_size(|limits| limits.revoke, params.revocations.len())?;
+    let mut notifications = Vec::new();
+    for param in params.revocations {
+        // Check that the sender is allowed to revoke the token.
+        ensure!(
+            state.can_issue(&param.token_id, &ctx.sender(), is_owner)?,
+            ContractError::Unauthorized
+        );
+        let notification = revoke_balance(state, logger, param, ctx.sender(), now)?;
+        state.record_minter_revoke(ctx.sender());
+        notifications.push(notification);
+    }
+
+    // Notify the subscribers about the revoked balances.
+    notify_subscribers(host, logger, notifications)?;
+
+    Ok(())
+}
+
+/// Revokes a single balance, logs the burned amount and records the revocation by the actor in the history.
+/// - This function does not check the sender.
+/// - Returns the notification for the subscribers.
+pub(crate) fn revoke_balance<S: HasStateApi>(
+    state: &mut State<S>,
+    logger: &mut impl HasLogger,
+    param: RevokeParam,
+    actor: Address,
+    now: Timestamp,
+) -> ContractResult<DsidNotification> {
+    let balance = state.revoke(
+        param.token_id,
+        param.account,
+        RevocationInfo {
+            reason: param.reason,
+            timestamp: now,
+        },
+    )?;
+
+    state.record_history(
+        param.token_id,
+        param.account,
+        HistoryEntry {
+            timestamp: now,
+            change: BalanceChange::Revoke,
+            amount: ContractTokenAmount::from(0),
+            expiry: balance.expiry,
+            actor,
+        },
+    )?;
+
+    let amount = balance.get_balance(now);
+    if amount > ContractTokenAmount::from(0) {
+        // The revoked balance had a valid amount.
+        // Log the burned tokens.
+        state.record_burn(param.token_id, amount)?;
+        log_burn(logger, param.token_id, param.account, amount, param.reason)?;
+    }
+
+    Ok(DsidNotification {
+        token_id: param.token_id,
+        account: param.account,
+        amount: ContractTokenAmount::from(0),
+        expiry: now,
+    })
+}
+
+/// Logs the CIS-2 `Burn` event followed by a `DsidBurn` event with the reason of the burn.
+pub(crate) fn log_burn(
+    logger: &mut impl HasLogger,
+    token_id: ContractTokenId,
+    owner: AccountAddress,
+    amount: ContractTokenAmount,
+    reason: u8,
+) -> ContractResult<()> {
+    logger.log(&Cis2Event::Burn::<_, ContractTokenAmount>(BurnEvent {
+        token_id,
+        owner: Address::Account(owner),
+        amount,
+    }))?;
+    logger.log(&ContractEvent::DsidBurn(DsidBurnEvent {
+        token_id,
+        owner: Address::Account(owner),
+        amount,
+        reason,
+    }))?;
+    Ok(())
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::{
+        errors::CustomError,
+        types::{CredentialStatus, DsidBurnEvent},
+    };
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const ADDRESS_0: Address = Address::Account(ACCOUNT_0);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+
+    fn state_with_balance(state_builder: &mut TestStateBuilder) -> State<TestStateApi> {
+        let mut state = State::empty(state_builder);
+        state.add_token(
+            state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_1,
+                10.into(),
+                Timestamp::from_timestamp_millis(200),
+                None,
+            )
+            .expect("Failed to mint token");
+        state
+    }
+
+    #[concordium_test]
+    fn test_revoke() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(100));
+        let params = RevokeParams {
+            revocations: vec![RevokeParam {
+                token_id: TOKEN_0,
+                account: ACCOUNT_1,
+                reason: 1,
+            }],
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let state = state_with_balance(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let mut logger = TestLogger::init();
+        let result: ContractResult<()> = revoke(&ctx, &mut host, &mut logger);
+        assert_eq!(result, Ok(()));
+
+        let state = host.state();
+        let now = Timestamp::from_timestamp_millis(100);
+        assert_eq!(
+            state.get_account_balance(TOKEN_0, ACCOUNT_1, now),
+            Ok(0.into())
+        );
+        assert_eq!(
+            state.get_revocation(TOKEN_0, ACCOUNT_1),
+            Ok(Some(RevocationInfo {
+                reason: 1,
+                timestamp: now,
+            }))
+        );
+        assert_eq!(
+            state.get_credential_status(TOKEN_0, ACCOUNT_1, now),
+            Ok(CredentialStatus::Revoked)
+        );
+        assert_eq!(
+            logger.logs,
+            vec![
+                to_bytes(&Cis2Event::Burn::<_, ContractTokenAmount>(BurnEvent {
+                    token_id: TOKEN_0,
+                    owner: Address::Account(ACCOUNT_1),
+                    amount: 10.into(),
+                })),
+                to_bytes(&ContractEvent::DsidBurn(DsidBurnEvent {
+                    token_id: TOKEN_0,
+                    owner: Address::Account(ACCOUNT_1),
+                    amount: 10.into(),
+                    reason: 1,
+                })),
+            ]
+        );
+
+        // Revoking again fails since there is no balance.
+        let result: ContractResult<()> = revoke(&ctx, &mut host, &mut logger);
+        assert_eq!(
+            result,
+            Err(ContractError::Custom(CustomError::CredentialNotFound))
+        );
+    }
+
+    #[concordium_test]
+    fn test_revoke_not_owner() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_1);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(100));
+        let params = RevokeParams {
+            revocations: vec![RevokeParam {
+                token_id: TOKEN_0,
+                account: ACCOUNT_1,
+                reason: 1,
+            }],
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let state = state_with_balance(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let mut logger = TestLogger::init();
+        let result: ContractResult<()> = revoke(&ctx, &mut host, &mut logger);
+        assert_eq!(result, Err(ContractError::Unauthorized));
+    }
+
+    #[concordium_test]
+    fn test_revoke_issuer() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(100));
+        let params = RevokeParams {
+            revocations: vec![RevokeParam {
+                token_id: TOKEN_0,
+                account: ACCOUNT_1,
+                reason: 1,
+            }],
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = state_with_balance(&mut state_builder);
+        state
+            .set_token_issuer(TOKEN_0, Some(Address::Account(ACCOUNT_1)))
+            .expect("Failed to set issuer");
+        let mut host = TestHost::new(state, state_builder);
+        let mut logger = TestLogger::init();
+
+        // The owner can not revoke a token which has an issuer.
+        let result: ContractResult<()> = revoke(&ctx, &mut host, &mut logger);
+        assert_eq!(result, Err(ContractError::Unauthorized));
+
+        // The issuer can revoke the token.
+        ctx.set_sender(Address::Account(ACCOUNT_1));
+        let result: ContractResult<()> = revoke(&ctx, &mut host, &mut logger);
+        assert_eq!(result, Ok(()));
+    }
+}
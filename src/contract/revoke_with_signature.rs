@@ -0,0 +1,244 @@
+use concordium_std::*;
+
+use crate::{
+    contract::{
+        revoke::{revoke_balance, RevokeParam},
+        update_subscribers::notify_subscribers,
+    },
+    errors::CustomError,
+    state::State,
+    types::{ContractError, ContractResult, ContractTokenId},
+};
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct RevocationMessage {
+    /// Address of the contract the message is meant for.
+    pub contract_address: ContractAddress,
+    pub token_id: ContractTokenId,
+    pub account: AccountAddress,
+    /// Reason code of the revocation, e.g. fraud or user request.
+    pub reason: u8,
+    /// Nonce of the signing key, prevents replaying the message.
+    pub nonce: u64,
+    /// Time after which the message can no longer be used.
+    pub expiry: Timestamp,
+}
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct RevokeWithSignatureParams {
+    /// Registered revocation key which signed the message.
+    pub signer: PublicKeyEd25519,
+    /// Signature of the serialized message.
+    pub signature: SignatureEd25519,
+    pub message: RevocationMessage,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "revokeWithSignature",
+    parameter = "RevokeWithSignatureParams",
+    error = "ContractError",
+    crypto_primitives,
+    enable_logger,
+    mutable
+)]
+/// Revokes a token balance on behalf of a registered revocation key.
+/// Anyone can submit the signed message.
+/// - This function fails if the token has an issuer, only the issuer can revoke it.
+/// - This function fails if the message is meant for another contract.
+/// - This function fails if the message has expired.
+/// - This function fails if the signer is not a registered revocation key.
+/// - This function fails if the nonce is not the next nonce of the signer.
+/// - This function fails if the signature is not valid.
+/// - This function fails if the account does not have a balance of the token.
+pub fn revoke_with_signature<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    crypto_primitives: &impl HasCryptoPrimitives,
+) -> ContractResult<()> {
+    let params: RevokeWithSignatureParams = ctx.parameter_cursor().get()?;
+    let message = params.message;
+    let now = ctx.metadata().slot_time();
+    ensure_eq!(
+        message.contract_address,
+        ctx.self_address(),
+        ContractError::Custom(CustomError::WrongContract)
+    );
+    ensure!(
+        message.expiry > now,
+        ContractError::Custom(CustomError::MessageExpired)
+    );
+
+    // Check the signature of the message.
+    let message_bytes = to_bytes(&message);
+    ensure!(
+        crypto_primitives.verify_ed25519_signature(params.signer, params.signature, &message_bytes),
+        ContractError::Custom(CustomError::WrongSignature)
+    );
+
+    let state = host.state_mut();
+    // Tokens with an issuer can only be revoked by the issuer.
+    ensure!(
+        state.get_token_issuer(&message.token_id)?.is_none(),
+        ContractError::Unauthorized
+    );
+    state.use_revocation_nonce(&params.signer, message.nonce)?;
+
+    let notification = revoke_balance(
+        state,
+        logger,
+        RevokeParam {
+            token_id: message.token_id,
+            account: message.account,
+            reason: message.reason,
+        },
+        ctx.sender(),
+        now,
+    )?;
+
+    // Notify the subscribers about the revoked balance.
+    notify_subscribers(host, logger, vec![notification])?;
+
+    Ok(())
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::types::{CredentialStatus, RevocationInfo};
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const SELF_ADDRESS: ContractAddress = ContractAddress {
+        index: 1,
+        subindex: 0,
+    };
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+    const KEY_0: PublicKeyEd25519 = PublicKeyEd25519([1u8; 32]);
+    const SIGNATURE_0: SignatureEd25519 = SignatureEd25519([2u8; 64]);
+
+    fn state_with_balance(state_builder: &mut TestStateBuilder) -> State<TestStateApi> {
+        let mut state = State::empty(state_builder);
+        state.add_token(
+            state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_1,
+                10.into(),
+                Timestamp::from_timestamp_millis(200),
+                None,
+            )
+            .expect("Failed to mint token");
+        state.add_revocation_key(KEY_0);
+        state
+    }
+
+    fn params(nonce: u64) -> RevokeWithSignatureParams {
+        RevokeWithSignatureParams {
+            signer: KEY_0,
+            signature: SIGNATURE_0,
+            message: RevocationMessage {
+                contract_address: SELF_ADDRESS,
+                token_id: TOKEN_0,
+                account: ACCOUNT_1,
+                reason: 2,
+                nonce,
+                expiry: Timestamp::from_timestamp_millis(150),
+            },
+        }
+    }
+
+    #[concordium_test]
+    fn test_revoke_with_signature() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_self_address(SELF_ADDRESS);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(100));
+        let parameter = to_bytes(&params(0));
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let state = state_with_balance(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let mut logger = TestLogger::init();
+        let crypto_primitives = TestCryptoPrimitives::new();
+        crypto_primitives.setup_verify_ed25519_signature_mock(|key, signature, _| {
+            key == KEY_0 && signature == SIGNATURE_0
+        });
+        let result: ContractResult<()> =
+            revoke_with_signature(&ctx, &mut host, &mut logger, &crypto_primitives);
+        assert_eq!(result, Ok(()));
+
+        let state = host.state();
+        let now = Timestamp::from_timestamp_millis(100);
+        assert_eq!(
+            state.get_revocation(TOKEN_0, ACCOUNT_1),
+            Ok(Some(RevocationInfo {
+                reason: 2,
+                timestamp: now,
+            }))
+        );
+        assert_eq!(
+            state.get_credential_status(TOKEN_0, ACCOUNT_1, now),
+            Ok(CredentialStatus::Revoked)
+        );
+        assert_eq!(state.revocation_keys(), vec![(KEY_0, 1)]);
+
+        // Replaying the message fails.
+        let result: ContractResult<()> =
+            revoke_with_signature(&ctx, &mut host, &mut logger, &crypto_primitives);
+        assert_eq!(
+            result,
+            Err(ContractError::Custom(CustomError::NonceMismatch))
+        );
+    }
+
+    #[concordium_test]
+    fn test_revoke_with_signature_wrong_signature() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_self_address(SELF_ADDRESS);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(100));
+        let parameter = to_bytes(&params(0));
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let state = state_with_balance(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let mut logger = TestLogger::init();
+        let crypto_primitives = TestCryptoPrimitives::new();
+        crypto_primitives.setup_verify_ed25519_signature_mock(|_, _, _| false);
+        let result: ContractResult<()> =
+            revoke_with_signature(&ctx, &mut host, &mut logger, &crypto_primitives);
+        assert_eq!(
+            result,
+            Err(ContractError::Custom(CustomError::WrongSignature))
+        );
+    }
+
+    #[concordium_test]
+    fn test_revoke_with_signature_expired() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_self_address(SELF_ADDRESS);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(150));
+        let parameter = to_bytes(&params(0));
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let state = state_with_balance(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let mut logger = TestLogger::init();
+        let crypto_primitives = TestCryptoPrimitives::new();
+        crypto_primitives.setup_verify_ed25519_signature_mock(|_, _, _| true);
+        let result: ContractResult<()> =
+            revoke_with_signature(&ctx, &mut host, &mut logger, &crypto_primitives);
+        assert_eq!(
+            result,
+            Err(ContractError::Custom(CustomError::MessageExpired))
+        );
+    }
+}
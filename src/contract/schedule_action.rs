@@ -0,0 +1,127 @@
+use concordium_std::*;
+
+use crate::{
+    contract::approve_action::action_hash,
+    state::State,
+    types::{ActionScheduledEvent, ContractError, ContractEvent, ContractResult},
+};
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "scheduleAction",
+    parameter = "HashSha2256",
+    return_value = "Timestamp",
+    error = "ContractError",
+    enable_logger,
+    mutable
+)]
+/// Schedules a timelocked action, which can be executed once the timelock delay has elapsed.
+/// Returns the time from which the action can be executed.
+/// - This function fails if the sender is not the admin of the contract.
+pub fn schedule_action<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<Timestamp> {
+    // Check that the sender is the admin of the contract.
+    ensure!(
+        host.state().is_contract_admin(&ctx.sender(), ctx.owner()),
+        ContractError::Unauthorized
+    );
+
+    let action_hash: HashSha2256 = ctx.parameter_cursor().get()?;
+    let ready_at = host
+        .state_mut()
+        .schedule_action(action_hash, ctx.metadata().slot_time());
+
+    // Log the scheduled action to give notice to the token holders.
+    logger.log(&ContractEvent::ActionScheduled(ActionScheduledEvent {
+        action_hash,
+        ready_at,
+    }))?;
+
+    Ok(ready_at)
+}
+
+/// Ensures that the current invocation of an entrypoint has been scheduled and its timelock delay has elapsed.
+/// - This function does nothing if no timelock delay is set.
+pub(crate) fn ensure_scheduled<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    state: &mut State<S>,
+    crypto_primitives: &impl HasCryptoPrimitives,
+    entrypoint: &str,
+) -> ContractResult<()> {
+    if state.timelock_delay().millis() == 0 {
+        return Ok(());
+    }
+
+    let action_hash = action_hash(ctx, crypto_primitives, entrypoint)?;
+    state.use_scheduled_action(&action_hash, ctx.metadata().slot_time())
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::errors::CustomError;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ADDRESS_0: Address = Address::Account(ACCOUNT_0);
+    const ACTION_0: HashSha2256 = HashSha2256([1u8; 32]);
+
+    #[concordium_test]
+    fn test_schedule_action() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(10));
+        let parameter = to_bytes(&ACTION_0);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.set_timelock_delay(Duration::from_millis(100));
+        let mut host = TestHost::new(state, state_builder);
+        let mut logger = TestLogger::init();
+
+        let result = schedule_action(&ctx, &mut host, &mut logger);
+        assert_eq!(result, Ok(Timestamp::from_timestamp_millis(110)));
+        assert_eq!(
+            logger.logs,
+            vec![to_bytes(&ContractEvent::ActionScheduled(
+                ActionScheduledEvent {
+                    action_hash: ACTION_0,
+                    ready_at: Timestamp::from_timestamp_millis(110),
+                }
+            ))]
+        );
+
+        // The action can only be executed after the delay.
+        assert_eq!(
+            host.state_mut()
+                .use_scheduled_action(&ACTION_0, Timestamp::from_timestamp_millis(109)),
+            Err(ContractError::Custom(CustomError::TimelockNotElapsed))
+        );
+        host.state_mut()
+            .schedule_action(ACTION_0, Timestamp::from_timestamp_millis(10));
+        assert_eq!(
+            host.state_mut()
+                .use_scheduled_action(&ACTION_0, Timestamp::from_timestamp_millis(110)),
+            Ok(())
+        );
+    }
+
+    #[concordium_test]
+    fn test_schedule_action_not_owner() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(AccountAddress([1u8; 32]));
+        let parameter = to_bytes(&ACTION_0);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let mut logger = TestLogger::init();
+        let result = schedule_action(&ctx, &mut host, &mut logger);
+        assert_eq!(result, Err(ContractError::Unauthorized));
+    }
+}
@@ -0,0 +1,144 @@
+use concordium_std::*;
+
+use crate::{
+    state::State,
+    types::{ContractError, ContractResult, ContractTokenId, ScheduledRevocation},
+};
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct ScheduleRevokeParams {
+    pub token_id: ContractTokenId,
+    pub account: AccountAddress,
+    /// Reason code of the revocation, e.g. fraud or user request.
+    pub reason: u8,
+    /// End of the dispute window, from which the revocation can be executed.
+    pub effective_at: Timestamp,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "scheduleRevoke",
+    parameter = "ScheduleRevokeParams",
+    error = "ContractError",
+    mutable
+)]
+/// Schedules the revocation of a balance, which stays valid until the revocation is executed
+/// using `executeRevoke` after `effective_at`. Replaces a revocation scheduled before.
+/// - This function fails if the token has an issuer and the sender is not the issuer.
+/// - This function fails if the token has no issuer and the sender is not the admin of the contract.
+/// - This function fails if the token does not exist.
+/// - This function fails if the account does not have a balance of the token.
+pub fn schedule_revoke<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    let params: ScheduleRevokeParams = ctx.parameter_cursor().get()?;
+    let is_owner = host.state().is_contract_admin(&ctx.sender(), ctx.owner());
+    let state = host.state_mut();
+    // Check that the sender is allowed to revoke the token.
+    ensure!(
+        state.can_issue(&params.token_id, &ctx.sender(), is_owner)?,
+        ContractError::Unauthorized
+    );
+    state.schedule_revocation(
+        params.token_id,
+        params.account,
+        ScheduledRevocation {
+            reason: params.reason,
+            effective_at: params.effective_at,
+        },
+    )
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::errors::CustomError;
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+
+    fn schedule_revoke_with(
+        host: &mut TestHost<State<TestStateApi>>,
+        sender: AccountAddress,
+    ) -> ContractResult<()> {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(sender));
+        ctx.set_owner(ACCOUNT_0);
+        let params = ScheduleRevokeParams {
+            token_id: TOKEN_0,
+            account: ACCOUNT_1,
+            reason: 1,
+            effective_at: Timestamp::from_timestamp_millis(50),
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        schedule_revoke(&ctx, host)
+    }
+
+    fn host_with_token() -> TestHost<State<TestStateApi>> {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        TestHost::new(state, state_builder)
+    }
+
+    #[concordium_test]
+    fn test_schedule_revoke() {
+        let mut host = host_with_token();
+        host.state_mut()
+            .mint(
+                TOKEN_0,
+                ACCOUNT_1,
+                1.into(),
+                Timestamp::from_timestamp_millis(100),
+                None,
+            )
+            .expect("Failed to mint token");
+
+        assert_eq!(schedule_revoke_with(&mut host, ACCOUNT_0), Ok(()));
+        assert_eq!(
+            host.state().get_scheduled_revocation(TOKEN_0, ACCOUNT_1),
+            Ok(Some(ScheduledRevocation {
+                reason: 1,
+                effective_at: Timestamp::from_timestamp_millis(50),
+            }))
+        );
+        // The balance stays valid until the revocation is executed.
+        assert_eq!(
+            host.state().get_account_balance(
+                TOKEN_0,
+                ACCOUNT_1,
+                Timestamp::from_timestamp_millis(60)
+            ),
+            Ok(1.into())
+        );
+    }
+
+    #[concordium_test]
+    fn test_schedule_revoke_no_balance() {
+        let mut host = host_with_token();
+        let result = schedule_revoke_with(&mut host, ACCOUNT_0);
+        assert_eq!(
+            result,
+            Err(ContractError::Custom(CustomError::CredentialNotFound))
+        );
+    }
+
+    #[concordium_test]
+    fn test_schedule_revoke_unauthorized() {
+        let mut host = host_with_token();
+        let result = schedule_revoke_with(&mut host, ACCOUNT_1);
+        assert_eq!(result, Err(ContractError::Unauthorized));
+    }
+}
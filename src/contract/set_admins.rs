@@ -0,0 +1,132 @@
+use concordium_std::*;
+
+use crate::{
+    contract::approve_action::ensure_approved,
+    state::State,
+    types::{ContractError, ContractResult},
+};
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct SetAdminsParams {
+    /// Accounts which approve destructive admin actions.
+    pub admins: Vec<AccountAddress>,
+    /// Number of admin approvals required for destructive admin actions, 0 disables approvals.
+    pub threshold: u8,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "setAdmins",
+    parameter = "SetAdminsParams",
+    error = "ContractError",
+    crypto_primitives,
+    mutable
+)]
+/// Replaces the admins and the number of admin approvals required for `remove`, `forceRemove`, `upgrade` and `setAdmins`.
+/// - This function fails if the sender is not the admin of the contract.
+/// - This function fails if approvals are required and the invocation has not been approved by enough admins.
+/// - This function fails if the threshold is larger than the number of admins.
+pub fn set_admins<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    crypto_primitives: &impl HasCryptoPrimitives,
+) -> ContractResult<()> {
+    // Check that the sender is the admin of the contract.
+    ensure!(
+        host.state().is_contract_admin(&ctx.sender(), ctx.owner()),
+        ContractError::Unauthorized
+    );
+
+    let params: SetAdminsParams = ctx.parameter_cursor().get()?;
+    let state = host.state_mut();
+    ensure_approved(ctx, state, crypto_primitives, "setAdmins")?;
+    state.set_admins(params.admins, params.threshold)
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::errors::CustomError;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const ADDRESS_0: Address = Address::Account(ACCOUNT_0);
+    const ACTION_0: HashSha2256 = HashSha2256([1u8; 32]);
+
+    #[concordium_test]
+    fn test_set_admins() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(10));
+        let params = SetAdminsParams {
+            admins: vec![ACCOUNT_1],
+            threshold: 1,
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let crypto_primitives = TestCryptoPrimitives::new();
+        crypto_primitives.setup_hash_sha2_256_mock(|_| ACTION_0);
+
+        let result: ContractResult<()> = set_admins(&ctx, &mut host, &crypto_primitives);
+        assert_eq!(result, Ok(()));
+        assert!(host.state().is_admin(&ACCOUNT_1));
+        assert_eq!(host.state().approval_threshold(), 1);
+
+        // Replacing the admins now requires an approval.
+        let result: ContractResult<()> = set_admins(&ctx, &mut host, &crypto_primitives);
+        assert_eq!(
+            result,
+            Err(ContractError::Custom(CustomError::ActionNotApproved))
+        );
+        host.state_mut()
+            .approve_action(ACTION_0, ACCOUNT_1, Timestamp::from_timestamp_millis(100));
+        let result: ContractResult<()> = set_admins(&ctx, &mut host, &crypto_primitives);
+        assert_eq!(result, Ok(()));
+    }
+
+    #[concordium_test]
+    fn test_set_admins_invalid_threshold() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        let params = SetAdminsParams {
+            admins: vec![ACCOUNT_1, ACCOUNT_1],
+            threshold: 2,
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let crypto_primitives = TestCryptoPrimitives::new();
+        let result: ContractResult<()> = set_admins(&ctx, &mut host, &crypto_primitives);
+        assert_eq!(
+            result,
+            Err(ContractError::Custom(CustomError::InvalidThreshold))
+        );
+    }
+
+    #[concordium_test]
+    fn test_set_admins_not_owner() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_1);
+        let params = SetAdminsParams {
+            admins: vec![ACCOUNT_1],
+            threshold: 1,
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let crypto_primitives = TestCryptoPrimitives::new();
+        let result: ContractResult<()> = set_admins(&ctx, &mut host, &crypto_primitives);
+        assert_eq!(result, Err(ContractError::Unauthorized));
+    }
+}
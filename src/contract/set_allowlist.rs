@@ -0,0 +1,169 @@
+use concordium_std::*;
+
+use crate::{
+    state::State,
+    types::{ContractError, ContractResult, ContractTokenId},
+};
+
+#[derive(Serial, Deserial, SchemaType)]
+pub enum AllowlistUpdate {
+    /// Adds the accounts to the allowlist.
+    Add,
+    /// Removes the accounts from the allowlist.
+    Remove,
+}
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct SetAllowlistParams {
+    pub token_id: ContractTokenId,
+    pub update: AllowlistUpdate,
+    pub accounts: Vec<AccountAddress>,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "setAllowlist",
+    parameter = "SetAllowlistParams",
+    error = "ContractError",
+    mutable
+)]
+/// Adds accounts to or removes them from the allowlist of a token.
+/// The allowlist is only enforced once it is required using `setAllowlistRequired`.
+/// - This function fails if the token does not exist.
+/// - This function fails if the token has an issuer and the sender is not the issuer.
+/// - This function fails if the token has no issuer and the sender is neither the admin of the contract nor a minter.
+/// - This function fails if the number of accounts exceeds the configured batch limit.
+pub fn set_allowlist<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    let params: SetAllowlistParams = ctx.parameter_cursor().get()?;
+    let state = host.state_mut();
+    // The allowlist is managed by whoever may mint the token.
+    let is_minter =
+        state.is_contract_admin(&ctx.sender(), ctx.owner()) || state.is_minter(&ctx.sender());
+    ensure!(
+        state.can_issue(&params.token_id, &ctx.sender(), is_minter)?,
+        ContractError::Unauthorized
+    );
+    state.ensure_batch_size(|limits| limits.admin, params.accounts.len())?;
+    let allowed = matches!(params.update, AllowlistUpdate::Add);
+    for account in params.accounts {
+        state.update_allowlist(params.token_id, account, allowed)?;
+    }
+
+    Ok(())
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::{
+        contract::mint::{mint_balance, MintParam},
+        errors::CustomError,
+    };
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const ACCOUNT_2: AccountAddress = AccountAddress([2u8; 32]);
+    const ADDRESS_0: Address = Address::Account(ACCOUNT_0);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+
+    fn set_allowlist_with(
+        host: &mut TestHost<State<TestStateApi>>,
+        sender: Address,
+        update: AllowlistUpdate,
+    ) -> ContractResult<()> {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(sender);
+        ctx.set_owner(ACCOUNT_0);
+        let params = SetAllowlistParams {
+            token_id: TOKEN_0,
+            update,
+            accounts: vec![ACCOUNT_1],
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        set_allowlist(&ctx, host)
+    }
+
+    fn mint_to(
+        host: &mut TestHost<State<TestStateApi>>,
+        owner: AccountAddress,
+    ) -> ContractResult<()> {
+        mint_balance(
+            host.state_mut(),
+            &mut TestLogger::init(),
+            TOKEN_0,
+            owner,
+            MintParam {
+                amount: 1.into(),
+                expiry: Some(Timestamp::from_timestamp_millis(100)),
+                valid_from: None,
+                metadata_url: None,
+            },
+            ADDRESS_0,
+            Timestamp::from_timestamp_millis(10),
+        )
+        .map(|_| ())
+    }
+
+    #[concordium_test]
+    fn test_set_allowlist() {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        state
+            .set_token_allowlist_required(TOKEN_0, true)
+            .expect("Failed to require the allowlist");
+        let mut host = TestHost::new(state, state_builder);
+
+        assert_eq!(
+            set_allowlist_with(&mut host, ADDRESS_0, AllowlistUpdate::Add),
+            Ok(())
+        );
+        assert_eq!(host.state().is_allowlisted(&TOKEN_0, ACCOUNT_1), Ok(true));
+        assert_eq!(mint_to(&mut host, ACCOUNT_1), Ok(()));
+        assert_eq!(
+            mint_to(&mut host, ACCOUNT_2),
+            Err(ContractError::Custom(CustomError::NotAllowlisted))
+        );
+
+        assert_eq!(
+            set_allowlist_with(&mut host, ADDRESS_0, AllowlistUpdate::Remove),
+            Ok(())
+        );
+        assert_eq!(host.state().is_allowlisted(&TOKEN_0, ACCOUNT_1), Ok(false));
+        assert_eq!(
+            mint_to(&mut host, ACCOUNT_1),
+            Err(ContractError::Custom(CustomError::NotAllowlisted))
+        );
+    }
+
+    #[concordium_test]
+    fn test_set_allowlist_unauthorized() {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        let mut host = TestHost::new(state, state_builder);
+        let result =
+            set_allowlist_with(&mut host, Address::Account(ACCOUNT_2), AllowlistUpdate::Add);
+        assert_eq!(result, Err(ContractError::Unauthorized));
+    }
+}
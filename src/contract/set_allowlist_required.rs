@@ -0,0 +1,66 @@
+use concordium_std::*;
+
+use crate::{
+    state::State,
+    types::{ContractError, ContractResult, ContractTokenId},
+};
+
+#[derive(SchemaType, Deserial, Serial)]
+pub struct SetAllowlistRequiredParams {
+    pub token_id: ContractTokenId,
+    /// Whether balances of the token can only be minted to accounts on its allowlist.
+    pub required: bool,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "setAllowlistRequired",
+    parameter = "SetAllowlistRequiredParams",
+    error = "ContractError",
+    mutable
+)]
+/// Sets whether balances of a token can only be minted to accounts added using `setAllowlist`.
+/// - This function fails if the token does not exist.
+/// - This function fails if the sender is not the admin of the contract.
+pub fn set_allowlist_required<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    // Check that the sender is the admin of the contract.
+    ensure!(
+        host.state().is_contract_admin(&ctx.sender(), ctx.owner()),
+        ContractError::Unauthorized
+    );
+
+    let params: SetAllowlistRequiredParams = ctx.parameter_cursor().get()?;
+    host.state_mut()
+        .set_token_allowlist_required(params.token_id, params.required)
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ADDRESS_0: Address = Address::Account(ACCOUNT_0);
+    const TOKEN_0: ContractTokenId = concordium_cis2::TokenIdU8(2);
+
+    #[concordium_test]
+    fn test_set_allowlist_required_not_owner() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(AccountAddress([1u8; 32]));
+        let params = SetAllowlistRequiredParams {
+            token_id: TOKEN_0,
+            required: true,
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let result: ContractResult<()> = set_allowlist_required(&ctx, &mut host);
+        assert_eq!(result, Err(ContractError::Unauthorized));
+    }
+}
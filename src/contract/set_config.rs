@@ -0,0 +1,164 @@
+use concordium_std::*;
+
+use crate::{
+    contract::schedule_action::ensure_scheduled,
+    state::State,
+    types::{ContractConfig, ContractError, ContractResult},
+};
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "setConfig",
+    parameter = "ContractConfig",
+    error = "ContractError",
+    crypto_primitives,
+    mutable
+)]
+/// Replaces the contract wide configuration.
+/// - This function fails if the sender is not the admin of the contract.
+/// - This function fails if a timelock delay is set and the invocation has not been scheduled or the delay has not elapsed.
+pub fn set_config<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    crypto_primitives: &impl HasCryptoPrimitives,
+) -> ContractResult<()> {
+    // Check that the sender is the admin of the contract.
+    ensure!(
+        host.state().is_contract_admin(&ctx.sender(), ctx.owner()),
+        ContractError::Unauthorized
+    );
+
+    let config: ContractConfig = ctx.parameter_cursor().get()?;
+    let state = host.state_mut();
+    ensure_scheduled(ctx, state, crypto_primitives, "setConfig")?;
+    state.set_config(config);
+
+    Ok(())
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::{
+        errors::CustomError,
+        types::{BatchLimits, MintVolumeLimit},
+    };
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ADDRESS_0: Address = Address::Account(ACCOUNT_0);
+    const ACTION_0: HashSha2256 = HashSha2256([1u8; 32]);
+
+    #[concordium_test]
+    fn test_set_config() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        let config = ContractConfig {
+            unique_metadata_urls: true,
+            metadata_hash_required: true,
+            batch_limits: BatchLimits {
+                mint: Some(10),
+                ..Default::default()
+            },
+            expiry_granularity: Some(Duration::from_days(1)),
+            self_service_cooldown: Some(Duration::from_seconds(60)),
+            max_validity: Some(Duration::from_days(365)),
+            new_tokens_paused: true,
+            identity_registry: Some(ContractAddress {
+                index: 1,
+                subindex: 0,
+            }),
+            summarize_mint_events: true,
+            activation_period: Some(Duration::from_days(30)),
+            mint_volume_limit: Some(MintVolumeLimit {
+                window: Duration::from_hours(1),
+                max_count: Some(1000),
+                max_amount: None,
+            }),
+            hide_expired_after: Some(Duration::from_days(90)),
+            log_transfer_attempts: true,
+            federation: vec![ContractAddress {
+                index: 2,
+                subindex: 0,
+            }],
+            soon_window: Some(Duration::from_days(30)),
+            token_approval_required: true,
+            clock_skew_tolerance: Some(Duration::from_seconds(30)),
+        };
+        let parameter = to_bytes(&config);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let result: ContractResult<()> = set_config(&ctx, &mut host, &TestCryptoPrimitives::new());
+        assert_eq!(result, Ok(()));
+        assert_eq!(host.state().config(), &config);
+    }
+
+    #[concordium_test]
+    fn test_set_config_not_owner() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(AccountAddress([1u8; 32]));
+        let config = ContractConfig {
+            unique_metadata_urls: true,
+            metadata_hash_required: true,
+            batch_limits: BatchLimits::default(),
+            expiry_granularity: None,
+            self_service_cooldown: None,
+            max_validity: None,
+            new_tokens_paused: false,
+            identity_registry: None,
+            summarize_mint_events: false,
+            activation_period: None,
+            mint_volume_limit: None,
+            hide_expired_after: None,
+            log_transfer_attempts: false,
+            federation: vec![],
+            soon_window: None,
+            token_approval_required: false,
+            clock_skew_tolerance: None,
+        };
+        let parameter = to_bytes(&config);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let result: ContractResult<()> = set_config(&ctx, &mut host, &TestCryptoPrimitives::new());
+        assert_eq!(result, Err(ContractError::Unauthorized));
+    }
+
+    #[concordium_test]
+    fn test_set_config_scheduled() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(10));
+        let config = ContractConfig {
+            unique_metadata_urls: true,
+            ..Default::default()
+        };
+        let parameter = to_bytes(&config);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.set_timelock_delay(Duration::from_millis(100));
+        let mut host = TestHost::new(state, state_builder);
+        let crypto_primitives = TestCryptoPrimitives::new();
+        crypto_primitives.setup_hash_sha2_256_mock(|_| ACTION_0);
+
+        let result: ContractResult<()> = set_config(&ctx, &mut host, &crypto_primitives);
+        assert_eq!(
+            result,
+            Err(ContractError::Custom(CustomError::ActionNotScheduled))
+        );
+
+        host.state_mut()
+            .schedule_action(ACTION_0, Timestamp::from_timestamp_millis(10));
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(110));
+        let result: ContractResult<()> = set_config(&ctx, &mut host, &crypto_primitives);
+        assert_eq!(result, Ok(()));
+        assert!(host.state().config().unique_metadata_urls);
+    }
+}
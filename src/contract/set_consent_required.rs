@@ -0,0 +1,66 @@
+use concordium_std::*;
+
+use crate::{
+    state::State,
+    types::{ContractError, ContractResult, ContractTokenId},
+};
+
+#[derive(SchemaType, Deserial, Serial)]
+pub struct SetConsentRequiredParams {
+    pub token_id: ContractTokenId,
+    /// Whether minting the token requires the consent of the holder.
+    pub required: bool,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "setConsentRequired",
+    parameter = "SetConsentRequiredParams",
+    error = "ContractError",
+    mutable
+)]
+/// Sets whether minting a token requires the holder to consent using `authorizeIssuance` first.
+/// - This function fails if the token does not exist.
+/// - This function fails if the sender is not the admin of the contract.
+pub fn set_consent_required<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    // Check that the sender is the admin of the contract.
+    ensure!(
+        host.state().is_contract_admin(&ctx.sender(), ctx.owner()),
+        ContractError::Unauthorized
+    );
+
+    let params: SetConsentRequiredParams = ctx.parameter_cursor().get()?;
+    host.state_mut()
+        .set_token_consent_required(params.token_id, params.required)
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ADDRESS_0: Address = Address::Account(ACCOUNT_0);
+    const TOKEN_0: ContractTokenId = concordium_cis2::TokenIdU8(2);
+
+    #[concordium_test]
+    fn test_set_consent_required_not_owner() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(AccountAddress([1u8; 32]));
+        let params = SetConsentRequiredParams {
+            token_id: TOKEN_0,
+            required: true,
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let result: ContractResult<()> = set_consent_required(&ctx, &mut host);
+        assert_eq!(result, Err(ContractError::Unauthorized));
+    }
+}
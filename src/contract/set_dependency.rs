@@ -0,0 +1,143 @@
+use concordium_std::*;
+
+use crate::{
+    state::State,
+    types::{ContractError, ContractResult, ContractTokenId},
+};
+
+#[derive(SchemaType, Deserial, Serial)]
+pub struct SetDependencyParams {
+    pub token_id: ContractTokenId,
+    /// Token whose balance of the same holder has to be valid, `None` removes the dependency.
+    pub depends_on: Option<ContractTokenId>,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "setDependency",
+    parameter = "SetDependencyParams",
+    error = "ContractError",
+    mutable
+)]
+/// Sets or removes the token a token depends on. A balance of the token is only valid while
+/// the holder also has a valid balance of the token it depends on.
+/// - This function fails if either token does not exist.
+/// - This function fails if the dependency would form a cycle.
+/// - This function fails if the sender is not the admin of the contract.
+pub fn set_dependency<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    // Check that the sender is the admin of the contract.
+    ensure!(
+        host.state().is_contract_admin(&ctx.sender(), ctx.owner()),
+        ContractError::Unauthorized
+    );
+
+    let params: SetDependencyParams = ctx.parameter_cursor().get()?;
+    host.state_mut()
+        .set_token_dependency(params.token_id, params.depends_on)
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::{errors::CustomError, types::CredentialStatus};
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const ADDRESS_0: Address = Address::Account(ACCOUNT_0);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+    const TOKEN_1: ContractTokenId = TokenIdU8(3);
+
+    fn set_dependency_with(
+        host: &mut TestHost<State<TestStateApi>>,
+        owner: AccountAddress,
+        token_id: ContractTokenId,
+        depends_on: ContractTokenId,
+    ) -> ContractResult<()> {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(owner);
+        let params = SetDependencyParams {
+            token_id,
+            depends_on: Some(depends_on),
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        set_dependency(&ctx, host)
+    }
+
+    fn host_with_tokens() -> TestHost<State<TestStateApi>> {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        for (token_id, expiry) in [(TOKEN_0, 100), (TOKEN_1, 200)] {
+            state.add_token(
+                &mut state_builder,
+                token_id,
+                MetadataUrl {
+                    url: format!("https://example.com/{}", token_id.0),
+                    hash: None,
+                },
+            );
+            state
+                .mint(
+                    token_id,
+                    ACCOUNT_1,
+                    1.into(),
+                    Timestamp::from_timestamp_millis(expiry),
+                    None,
+                )
+                .expect("Failed to mint token");
+        }
+        TestHost::new(state, state_builder)
+    }
+
+    #[concordium_test]
+    fn test_set_dependency() {
+        let mut host = host_with_tokens();
+        // TOKEN_1 is derived from TOKEN_0.
+        let result = set_dependency_with(&mut host, ACCOUNT_0, TOKEN_1, TOKEN_0);
+        assert_eq!(result, Ok(()));
+
+        let state = host.state();
+        let before = Timestamp::from_timestamp_millis(50);
+        assert_eq!(
+            state.get_account_balance(TOKEN_1, ACCOUNT_1, before),
+            Ok(1.into())
+        );
+        // Once TOKEN_0 has expired, TOKEN_1 is no longer valid.
+        let after = Timestamp::from_timestamp_millis(150);
+        assert_eq!(
+            state.get_account_balance(TOKEN_1, ACCOUNT_1, after),
+            Ok(0.into())
+        );
+        assert_eq!(
+            state.get_credential_status(TOKEN_1, ACCOUNT_1, after),
+            Ok(CredentialStatus::DependencyInvalid)
+        );
+    }
+
+    #[concordium_test]
+    fn test_set_dependency_cycle() {
+        let mut host = host_with_tokens();
+        assert_eq!(
+            set_dependency_with(&mut host, ACCOUNT_0, TOKEN_1, TOKEN_0),
+            Ok(())
+        );
+        let result = set_dependency_with(&mut host, ACCOUNT_0, TOKEN_0, TOKEN_1);
+        assert_eq!(
+            result,
+            Err(ContractError::Custom(CustomError::InvalidDependency))
+        );
+    }
+
+    #[concordium_test]
+    fn test_set_dependency_not_owner() {
+        let mut host = host_with_tokens();
+        let result = set_dependency_with(&mut host, ACCOUNT_1, TOKEN_1, TOKEN_0);
+        assert_eq!(result, Err(ContractError::Unauthorized));
+    }
+}
@@ -0,0 +1,172 @@
+use concordium_std::*;
+
+use crate::{
+    state::State,
+    types::{ContractError, ContractResult, ContractTokenId, ExclusiveGroup},
+};
+
+#[derive(SchemaType, Deserial, Serial)]
+pub struct SetExclusiveGroupParams {
+    pub token_id: ContractTokenId,
+    /// Group of mutually exclusive tokens, `None` removes the token from its group.
+    pub group: Option<ExclusiveGroup>,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "setExclusiveGroup",
+    parameter = "SetExclusiveGroupParams",
+    error = "ContractError",
+    mutable
+)]
+/// Sets or removes the exclusive group of a token. Minting a token of a group revokes the
+/// balances of the holder of the other tokens of the group, e.g. of other residency tiers.
+/// - This function fails if the token does not exist.
+/// - This function fails if the sender is not the admin of the contract.
+pub fn set_exclusive_group<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    // Check that the sender is the admin of the contract.
+    ensure!(
+        host.state().is_contract_admin(&ctx.sender(), ctx.owner()),
+        ContractError::Unauthorized
+    );
+
+    let params: SetExclusiveGroupParams = ctx.parameter_cursor().get()?;
+    host.state_mut()
+        .set_token_exclusive_group(params.token_id, params.group)
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::{
+        contract::mint::{mint_balance, MintParam},
+        types::{ContractTokenAmount, RevocationInfo, EXCLUDED_REASON},
+    };
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const ADDRESS_0: Address = Address::Account(ACCOUNT_0);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+    const TOKEN_1: ContractTokenId = TokenIdU8(3);
+
+    fn set_exclusive_group_with(
+        host: &mut TestHost<State<TestStateApi>>,
+        owner: AccountAddress,
+        token_id: ContractTokenId,
+    ) -> ContractResult<()> {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(owner);
+        let params = SetExclusiveGroupParams {
+            token_id,
+            group: Some(1),
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        set_exclusive_group(&ctx, host)
+    }
+
+    fn host_with_tokens() -> TestHost<State<TestStateApi>> {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        for token_id in [TOKEN_0, TOKEN_1] {
+            state.add_token(
+                &mut state_builder,
+                token_id,
+                MetadataUrl {
+                    url: format!("https://example.com/{}", token_id.0),
+                    hash: None,
+                },
+            );
+        }
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_1,
+                1.into(),
+                Timestamp::from_timestamp_millis(100),
+                None,
+            )
+            .expect("Failed to mint token");
+        TestHost::new(state, state_builder)
+    }
+
+    #[concordium_test]
+    fn test_set_exclusive_group() {
+        let mut host = host_with_tokens();
+        for token_id in [TOKEN_0, TOKEN_1] {
+            assert_eq!(
+                set_exclusive_group_with(&mut host, ACCOUNT_0, token_id),
+                Ok(())
+            );
+        }
+
+        // Minting TOKEN_1 revokes the balance of TOKEN_0 of the same group.
+        let now = Timestamp::from_timestamp_millis(10);
+        let mut logger = TestLogger::init();
+        let minted = mint_balance(
+            host.state_mut(),
+            &mut logger,
+            TOKEN_1,
+            ACCOUNT_1,
+            MintParam {
+                amount: 1.into(),
+                expiry: Some(Timestamp::from_timestamp_millis(100)),
+                valid_from: None,
+                metadata_url: None,
+            },
+            ADDRESS_0,
+            now,
+        )
+        .expect("Failed to mint token");
+        assert_eq!(minted.notifications.len(), 2);
+
+        let state = host.state();
+        assert_eq!(
+            state.get_account_balance(TOKEN_0, ACCOUNT_1, now),
+            Ok(0.into())
+        );
+        assert_eq!(
+            state.get_account_balance(TOKEN_1, ACCOUNT_1, now),
+            Ok(1.into())
+        );
+        assert_eq!(
+            state.get_revocation(TOKEN_0, ACCOUNT_1),
+            Ok(Some(RevocationInfo {
+                reason: EXCLUDED_REASON,
+                timestamp: now,
+            }))
+        );
+        // The mint of TOKEN_1 and the burn of TOKEN_0 are both logged.
+        assert!(logger
+            .logs
+            .contains(&to_bytes(&Cis2Event::Burn::<_, ContractTokenAmount>(
+                BurnEvent {
+                    token_id: TOKEN_0,
+                    owner: Address::Account(ACCOUNT_1),
+                    amount: 1.into(),
+                }
+            ))));
+        assert!(logger
+            .logs
+            .contains(&to_bytes(&Cis2Event::Mint::<_, ContractTokenAmount>(
+                MintEvent {
+                    token_id: TOKEN_1,
+                    owner: Address::Account(ACCOUNT_1),
+                    amount: 1.into(),
+                }
+            ))));
+    }
+
+    #[concordium_test]
+    fn test_set_exclusive_group_not_owner() {
+        let mut host = host_with_tokens();
+        let result = set_exclusive_group_with(&mut host, ACCOUNT_1, TOKEN_0);
+        assert_eq!(result, Err(ContractError::Unauthorized));
+    }
+}
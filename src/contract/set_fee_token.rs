@@ -0,0 +1,138 @@
+use concordium_std::*;
+
+use crate::{
+    contract::schedule_action::ensure_scheduled,
+    state::State,
+    types::{ContractError, ContractResult, FeeToken, Role},
+};
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "setFeeToken",
+    parameter = "Option<FeeToken>",
+    error = "ContractError",
+    crypto_primitives,
+    mutable
+)]
+/// Sets the CIS-2 token, fee and recipient of the fees paid by `renewWithToken`.
+/// None disables `renewWithToken`.
+/// - This function fails if the sender is neither the admin of the contract nor a fee manager.
+/// - This function fails if a timelock delay is set and the invocation has not been scheduled or the delay has not elapsed.
+pub fn set_fee_token<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    crypto_primitives: &impl HasCryptoPrimitives,
+) -> ContractResult<()> {
+    let state = host.state_mut();
+    // Check that the sender is the admin of the contract or a fee manager.
+    ensure!(
+        state.is_contract_admin(&ctx.sender(), ctx.owner())
+            || state.has_role(Role::FeeManager, &ctx.sender()),
+        ContractError::Unauthorized
+    );
+
+    let fee_token: Option<FeeToken> = ctx.parameter_cursor().get()?;
+    ensure_scheduled(ctx, state, crypto_primitives, "setFeeToken")?;
+    state.set_fee_token(fee_token);
+    Ok(())
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::errors::CustomError;
+    use concordium_cis2::{TokenAmountU64, TokenIdVec};
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const ACTION_0: HashSha2256 = HashSha2256([1u8; 32]);
+
+    fn fee_token() -> FeeToken {
+        FeeToken {
+            contract: ContractAddress {
+                index: 1,
+                subindex: 0,
+            },
+            token_id: TokenIdVec(vec![]),
+            amount: TokenAmountU64(5),
+            recipient: ACCOUNT_0,
+        }
+    }
+
+    fn ctx_with<'a>(sender: AccountAddress, parameter: &'a [u8]) -> TestReceiveContext<'a> {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(sender));
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(10));
+        ctx.set_parameter(parameter);
+        ctx
+    }
+
+    #[concordium_test]
+    fn test_set_fee_token() {
+        let parameter = to_bytes(&Some(fee_token()));
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let crypto_primitives = TestCryptoPrimitives::new();
+
+        let ctx = ctx_with(ACCOUNT_0, &parameter);
+        let result = set_fee_token(&ctx, &mut host, &crypto_primitives);
+        assert_eq!(result, Ok(()));
+        assert_eq!(host.state().fee_token(), Some(fee_token()));
+
+        // None disables the fee token.
+        let parameter = to_bytes(&None::<FeeToken>);
+        let ctx = ctx_with(ACCOUNT_0, &parameter);
+        let result = set_fee_token(&ctx, &mut host, &crypto_primitives);
+        assert_eq!(result, Ok(()));
+        assert_eq!(host.state().fee_token(), None);
+    }
+
+    #[concordium_test]
+    fn test_set_fee_token_by_fee_manager() {
+        let parameter = to_bytes(&Some(fee_token()));
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let crypto_primitives = TestCryptoPrimitives::new();
+
+        // Only the admin and fee managers can set the fee token.
+        let ctx = ctx_with(ACCOUNT_1, &parameter);
+        let result = set_fee_token(&ctx, &mut host, &crypto_primitives);
+        assert_eq!(result, Err(ContractError::Unauthorized));
+        assert_eq!(host.state().fee_token(), None);
+
+        host.state_mut()
+            .update_role(Role::FeeManager, Address::Account(ACCOUNT_1), true);
+        let result = set_fee_token(&ctx, &mut host, &crypto_primitives);
+        assert_eq!(result, Ok(()));
+        assert_eq!(host.state().fee_token(), Some(fee_token()));
+    }
+
+    #[concordium_test]
+    fn test_set_fee_token_scheduled() {
+        let parameter = to_bytes(&Some(fee_token()));
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.set_timelock_delay(Duration::from_millis(100));
+        let mut host = TestHost::new(state, state_builder);
+        let crypto_primitives = TestCryptoPrimitives::new();
+        crypto_primitives.setup_hash_sha2_256_mock(|_| ACTION_0);
+
+        let mut ctx = ctx_with(ACCOUNT_0, &parameter);
+        let result = set_fee_token(&ctx, &mut host, &crypto_primitives);
+        assert_eq!(
+            result,
+            Err(ContractError::Custom(CustomError::ActionNotScheduled))
+        );
+
+        host.state_mut()
+            .schedule_action(ACTION_0, Timestamp::from_timestamp_millis(10));
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(110));
+        let result = set_fee_token(&ctx, &mut host, &crypto_primitives);
+        assert_eq!(result, Ok(()));
+        assert_eq!(host.state().fee_token(), Some(fee_token()));
+    }
+}
@@ -0,0 +1,106 @@
+use concordium_std::*;
+
+use crate::{
+    state::State,
+    types::{ContractError, ContractResult, TokenIdRange},
+};
+
+#[derive(SchemaType, Deserial, Serial)]
+pub struct SetIdRangeParams {
+    /// Issuer the token ids are allocated to.
+    pub issuer: Address,
+    /// Token ids allocated to the issuer, `None` removes the allocation.
+    pub range: Option<TokenIdRange>,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "setIdRange",
+    parameter = "SetIdRangeParams",
+    error = "ContractError",
+    mutable
+)]
+/// Allocates a range of token ids to an issuer, which `add` enforces for new tokens.
+/// - This function fails if the range is empty or overlaps the range of another issuer.
+/// - This function fails if the sender is not the admin of the contract.
+pub fn set_id_range<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    // Check that the sender is the admin of the contract.
+    ensure!(
+        host.state().is_contract_admin(&ctx.sender(), ctx.owner()),
+        ContractError::Unauthorized
+    );
+
+    let params: SetIdRangeParams = ctx.parameter_cursor().get()?;
+    host.state_mut().set_id_range(params.issuer, params.range)
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::errors::CustomError;
+    use concordium_cis2::TokenIdU8;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ADDRESS_0: Address = Address::Account(ACCOUNT_0);
+    const ISSUER_0: Address = Address::Contract(ContractAddress {
+        index: 1,
+        subindex: 0,
+    });
+    const ISSUER_1: Address = Address::Contract(ContractAddress {
+        index: 2,
+        subindex: 0,
+    });
+
+    fn set_range_with(
+        host: &mut TestHost<State<TestStateApi>>,
+        issuer: Address,
+        first: u8,
+        last: u8,
+    ) -> ContractResult<()> {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        let parameter = to_bytes(&SetIdRangeParams {
+            issuer,
+            range: Some(TokenIdRange { first, last }),
+        });
+        ctx.set_parameter(&parameter);
+        set_id_range(&ctx, host)
+    }
+
+    #[concordium_test]
+    fn test_set_id_range() {
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+
+        assert_eq!(set_range_with(&mut host, ISSUER_0, 0, 9), Ok(()));
+        // Ranges of different issuers must not overlap.
+        assert_eq!(
+            set_range_with(&mut host, ISSUER_1, 9, 19),
+            Err(ContractError::Custom(CustomError::InvalidIdRange))
+        );
+        assert_eq!(set_range_with(&mut host, ISSUER_1, 10, 19), Ok(()));
+
+        let state = host.state();
+        assert_eq!(state.check_id_range(&TokenIdU8(5), Some(ISSUER_0)), Ok(()));
+        assert_eq!(
+            state.check_id_range(&TokenIdU8(5), Some(ISSUER_1)),
+            Err(ContractError::Custom(CustomError::TokenIdNotAllocated))
+        );
+        assert_eq!(
+            state.check_id_range(&TokenIdU8(5), None),
+            Err(ContractError::Custom(CustomError::TokenIdNotAllocated))
+        );
+        // Issuers with a range can only use token ids of their range.
+        assert_eq!(
+            state.check_id_range(&TokenIdU8(20), Some(ISSUER_0)),
+            Err(ContractError::Custom(CustomError::TokenIdNotAllocated))
+        );
+        assert_eq!(state.check_id_range(&TokenIdU8(20), None), Ok(()));
+    }
+}
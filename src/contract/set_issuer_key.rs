@@ -0,0 +1,83 @@
+use concordium_std::*;
+
+use crate::{
+    state::State,
+    types::{ContractError, ContractResult, ContractTokenId, IssuerKeyValidity},
+};
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct SetIssuerKeyParams {
+    pub token_id: ContractTokenId,
+    pub key: PublicKeyEd25519,
+    /// Period in which vouchers signed by the key may be issued.
+    pub validity: IssuerKeyValidity,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "setIssuerKey",
+    parameter = "SetIssuerKeyParams",
+    error = "ContractError",
+    mutable
+)]
+/// Registers a key signing vouchers of a token for `claim`, or replaces its validity.
+/// Keys are rotated by retiring the old key, vouchers issued before its retirement stay valid.
+/// - This function fails if the token has an issuer and the sender is not the issuer.
+/// - This function fails if the token has no issuer and the sender is not the admin of the contract.
+/// - This function fails if the token does not exist.
+pub fn set_issuer_key<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    let params: SetIssuerKeyParams = ctx.parameter_cursor().get()?;
+    let is_owner = host.state().is_contract_admin(&ctx.sender(), ctx.owner());
+    let state = host.state_mut();
+    // Check that the sender is allowed to mint the token.
+    ensure!(
+        state.can_issue(&params.token_id, &ctx.sender(), is_owner)?,
+        ContractError::Unauthorized
+    );
+
+    state.set_issuer_key(params.token_id, params.key, params.validity)
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ADDRESS_0: Address = Address::Account(ACCOUNT_0);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+
+    #[concordium_test]
+    fn test_set_issuer_key_not_owner() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(AccountAddress([1u8; 32]));
+        let params = SetIssuerKeyParams {
+            token_id: TOKEN_0,
+            key: PublicKeyEd25519([1u8; 32]),
+            validity: IssuerKeyValidity {
+                active_from: Timestamp::from_timestamp_millis(0),
+                retired_at: None,
+            },
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        let mut host = TestHost::new(state, state_builder);
+        let result = set_issuer_key(&ctx, &mut host);
+        assert_eq!(result, Err(ContractError::Unauthorized));
+    }
+}
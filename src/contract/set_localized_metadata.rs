@@ -0,0 +1,70 @@
+use concordium_cis2::MetadataUrl;
+use concordium_std::*;
+
+use crate::{
+    state::State,
+    types::{ContractError, ContractResult, ContractTokenId},
+};
+
+#[derive(SchemaType, Deserial, Serial)]
+pub struct SetLocalizedMetadataParams {
+    pub token_id: ContractTokenId,
+    /// Locale of the metadata, e.g. "en" or "fr".
+    pub locale: String,
+    /// Metadata url for the locale, `None` removes the metadata url of the locale.
+    pub metadata_url: Option<MetadataUrl>,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "setLocalizedMetadata",
+    parameter = "SetLocalizedMetadataParams",
+    error = "ContractError",
+    mutable
+)]
+/// Sets or removes the metadata url of a token for a locale.
+/// - This function fails if the token does not exist.
+/// - This function fails if the sender is not the admin of the contract.
+pub fn set_localized_metadata<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    // Check that the sender is the admin of the contract.
+    ensure!(
+        host.state().is_contract_admin(&ctx.sender(), ctx.owner()),
+        ContractError::Unauthorized
+    );
+
+    let params: SetLocalizedMetadataParams = ctx.parameter_cursor().get()?;
+    host.state_mut()
+        .set_localized_metadata(params.token_id, params.locale, params.metadata_url)
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ADDRESS_0: Address = Address::Account(ACCOUNT_0);
+    const TOKEN_0: ContractTokenId = concordium_cis2::TokenIdU8(2);
+
+    #[concordium_test]
+    fn test_set_localized_metadata_not_owner() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(AccountAddress([1u8; 32]));
+        let params = SetLocalizedMetadataParams {
+            token_id: TOKEN_0,
+            locale: "fr".to_string(),
+            metadata_url: None,
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let result = set_localized_metadata(&ctx, &mut host);
+        assert_eq!(result, Err(ContractError::Unauthorized));
+    }
+}
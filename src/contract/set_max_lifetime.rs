@@ -0,0 +1,136 @@
+use concordium_std::*;
+
+use crate::{
+    state::State,
+    types::{ContractError, ContractResult, ContractTokenId},
+};
+
+#[derive(SchemaType, Deserial, Serial)]
+pub struct SetMaxLifetimeParams {
+    pub token_id: ContractTokenId,
+    /// Maximum time between the first mint of a balance and its expiry, None removes the limit.
+    pub max_lifetime: Option<Duration>,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "setMaxLifetime",
+    parameter = "SetMaxLifetimeParams",
+    error = "ContractError",
+    mutable
+)]
+/// Sets or removes the maximum lifetime of the balances of a token. The first mint of a balance
+/// sets its hard expiry, after which it can not be renewed or extended by any renewal policy.
+/// Balances minted before keep their hard expiry.
+/// - This function fails if the token does not exist.
+/// - This function fails if the sender is not the admin of the contract.
+pub fn set_max_lifetime<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    // Check that the sender is the admin of the contract.
+    ensure!(
+        host.state().is_contract_admin(&ctx.sender(), ctx.owner()),
+        ContractError::Unauthorized
+    );
+
+    let params: SetMaxLifetimeParams = ctx.parameter_cursor().get()?;
+    host.state_mut()
+        .set_token_max_lifetime(params.token_id, params.max_lifetime)
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::{
+        contract::mint::{mint_balance, MintParam},
+        errors::CustomError,
+    };
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const ADDRESS_0: Address = Address::Account(ACCOUNT_0);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+
+    fn set_max_lifetime_with(
+        host: &mut TestHost<State<TestStateApi>>,
+        owner: AccountAddress,
+    ) -> ContractResult<()> {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(owner);
+        let params = SetMaxLifetimeParams {
+            token_id: TOKEN_0,
+            max_lifetime: Some(Duration::from_millis(150)),
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        set_max_lifetime(&ctx, host)
+    }
+
+    fn mint_balance_with(
+        host: &mut TestHost<State<TestStateApi>>,
+        expiry: u64,
+        now: u64,
+    ) -> ContractResult<()> {
+        mint_balance(
+            host.state_mut(),
+            &mut TestLogger::init(),
+            TOKEN_0,
+            ACCOUNT_1,
+            MintParam {
+                amount: 1.into(),
+                expiry: Some(Timestamp::from_timestamp_millis(expiry)),
+                valid_from: None,
+                metadata_url: None,
+            },
+            ADDRESS_0,
+            Timestamp::from_timestamp_millis(now),
+        )
+        .map(|_| ())
+    }
+
+    fn host_with_token() -> TestHost<State<TestStateApi>> {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        TestHost::new(state, state_builder)
+    }
+
+    #[concordium_test]
+    fn test_set_max_lifetime() {
+        let mut host = host_with_token();
+        assert_eq!(set_max_lifetime_with(&mut host, ACCOUNT_0), Ok(()));
+
+        // The first mint sets the hard expiry to 10 + 150.
+        assert_eq!(mint_balance_with(&mut host, 100, 10), Ok(()));
+        // Replacing the balance keeps the hard expiry of the first mint.
+        assert_eq!(
+            mint_balance_with(&mut host, 200, 50),
+            Err(ContractError::Custom(CustomError::LifetimeExceeded))
+        );
+        assert_eq!(mint_balance_with(&mut host, 160, 50), Ok(()));
+        assert_eq!(
+            host.state()
+                .get_account_balance_state(TOKEN_0, ACCOUNT_1)
+                .map(|balance| balance.and_then(|balance| balance.hard_expiry)),
+            Ok(Some(Timestamp::from_timestamp_millis(160)))
+        );
+    }
+
+    #[concordium_test]
+    fn test_set_max_lifetime_not_owner() {
+        let mut host = host_with_token();
+        let result = set_max_lifetime_with(&mut host, ACCOUNT_1);
+        assert_eq!(result, Err(ContractError::Unauthorized));
+    }
+}
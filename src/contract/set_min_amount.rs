@@ -0,0 +1,145 @@
+use concordium_std::*;
+
+use crate::{
+    state::State,
+    types::{ContractError, ContractResult, ContractTokenAmount, ContractTokenId},
+};
+
+#[derive(SchemaType, Deserial, Serial)]
+pub struct SetMinAmountParams {
+    pub token_id: ContractTokenId,
+    /// Smallest amount which can be minted, 0 allows mints which only replace the existing balance.
+    pub min_amount: ContractTokenAmount,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "setMinAmount",
+    parameter = "SetMinAmountParams",
+    error = "ContractError",
+    mutable
+)]
+/// Sets the smallest amount of a token which can be minted, 1 for new tokens.
+/// - This function fails if the token does not exist.
+/// - This function fails if the sender is not the admin of the contract.
+pub fn set_min_amount<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    // Check that the sender is the admin of the contract.
+    ensure!(
+        host.state().is_contract_admin(&ctx.sender(), ctx.owner()),
+        ContractError::Unauthorized
+    );
+
+    let params: SetMinAmountParams = ctx.parameter_cursor().get()?;
+    host.state_mut()
+        .set_token_min_amount(params.token_id, params.min_amount)
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::{
+        contract::mint::{mint_balance, MintParam},
+        errors::CustomError,
+    };
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const ADDRESS_0: Address = Address::Account(ACCOUNT_0);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+
+    fn mint_with(
+        host: &mut TestHost<State<TestStateApi>>,
+        logger: &mut TestLogger,
+        amount: ContractTokenAmount,
+    ) -> ContractResult<()> {
+        mint_balance(
+            host.state_mut(),
+            logger,
+            TOKEN_0,
+            ACCOUNT_1,
+            MintParam {
+                amount,
+                expiry: Some(Timestamp::from_timestamp_millis(100)),
+                valid_from: None,
+                metadata_url: None,
+            },
+            ADDRESS_0,
+            Timestamp::from_timestamp_millis(10),
+        )
+        .map(|_| ())
+    }
+
+    #[concordium_test]
+    fn test_set_min_amount() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        let params = SetMinAmountParams {
+            token_id: TOKEN_0,
+            min_amount: 0.into(),
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        let mut host = TestHost::new(state, state_builder);
+        let mut logger = TestLogger::init();
+
+        // Mints of 0 are rejected by default.
+        assert_eq!(
+            mint_with(&mut host, &mut logger, 0.into()),
+            Err(ContractError::Custom(CustomError::ZeroAmount))
+        );
+        assert_eq!(mint_with(&mut host, &mut logger, 5.into()), Ok(()));
+
+        let result: ContractResult<()> = set_min_amount(&ctx, &mut host);
+        assert_eq!(result, Ok(()));
+        assert_eq!(host.state().get_token_min_amount(&TOKEN_0), Ok(0.into()));
+
+        // A mint of 0 burns the existing balance without storing a new one.
+        logger.logs.clear();
+        assert_eq!(mint_with(&mut host, &mut logger, 0.into()), Ok(()));
+        assert_eq!(
+            host.state().get_account_balance_expiry(TOKEN_0, ACCOUNT_1),
+            Ok(None)
+        );
+        assert_eq!(
+            host.state()
+                .get_token_supply(&TOKEN_0, Timestamp::from_timestamp_millis(10)),
+            Ok((0, 0))
+        );
+        // Only the burn of the replaced balance is logged.
+        assert_eq!(logger.logs.len(), 2);
+    }
+
+    #[concordium_test]
+    fn test_set_min_amount_not_owner() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(AccountAddress([1u8; 32]));
+        let params = SetMinAmountParams {
+            token_id: TOKEN_0,
+            min_amount: 2.into(),
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let result: ContractResult<()> = set_min_amount(&ctx, &mut host);
+        assert_eq!(result, Err(ContractError::Unauthorized));
+    }
+}
@@ -0,0 +1,138 @@
+use concordium_std::*;
+
+use crate::{
+    state::State,
+    types::{ContractError, ContractResult, ContractTokenId},
+};
+
+#[derive(SchemaType, Deserial, Serial)]
+pub struct SetMonotonicExpiryParams {
+    pub token_id: ContractTokenId,
+    /// True if a mint or renewal may not shorten the expiry of an unexpired balance.
+    pub monotonic: bool,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "setMonotonicExpiry",
+    parameter = "SetMonotonicExpiryParams",
+    error = "ContractError",
+    mutable
+)]
+/// Sets whether the expiry of an unexpired balance of a token may only be extended.
+/// A mint or renewal with an earlier expiry than the unexpired balance then fails with `InvalidExpiry`.
+/// - This function fails if the token does not exist.
+/// - This function fails if the sender is not the admin of the contract.
+pub fn set_monotonic_expiry<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    // Check that the sender is the admin of the contract.
+    ensure!(
+        host.state().is_contract_admin(&ctx.sender(), ctx.owner()),
+        ContractError::Unauthorized
+    );
+
+    let params: SetMonotonicExpiryParams = ctx.parameter_cursor().get()?;
+    host.state_mut()
+        .set_token_monotonic_expiry(params.token_id, params.monotonic)
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::{
+        contract::mint::{mint_balance, MintParam},
+        errors::CustomError,
+    };
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const ADDRESS_0: Address = Address::Account(ACCOUNT_0);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+
+    fn set_monotonic_expiry_with(
+        host: &mut TestHost<State<TestStateApi>>,
+        owner: AccountAddress,
+    ) -> ContractResult<()> {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(owner);
+        let params = SetMonotonicExpiryParams {
+            token_id: TOKEN_0,
+            monotonic: true,
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        set_monotonic_expiry(&ctx, host)
+    }
+
+    fn mint_balance_with(
+        host: &mut TestHost<State<TestStateApi>>,
+        expiry: u64,
+        now: u64,
+    ) -> ContractResult<()> {
+        mint_balance(
+            host.state_mut(),
+            &mut TestLogger::init(),
+            TOKEN_0,
+            ACCOUNT_1,
+            MintParam {
+                amount: 1.into(),
+                expiry: Some(Timestamp::from_timestamp_millis(expiry)),
+                valid_from: None,
+                metadata_url: None,
+            },
+            ADDRESS_0,
+            Timestamp::from_timestamp_millis(now),
+        )
+        .map(|_| ())
+    }
+
+    fn host_with_token() -> TestHost<State<TestStateApi>> {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_1,
+                1.into(),
+                Timestamp::from_timestamp_millis(100),
+                None,
+            )
+            .expect("Failed to mint token");
+        TestHost::new(state, state_builder)
+    }
+
+    #[concordium_test]
+    fn test_set_monotonic_expiry() {
+        let mut host = host_with_token();
+        assert_eq!(set_monotonic_expiry_with(&mut host, ACCOUNT_0), Ok(()));
+
+        // The unexpired balance can not be shortened, only extended.
+        assert_eq!(
+            mint_balance_with(&mut host, 50, 10),
+            Err(ContractError::Custom(CustomError::InvalidExpiry))
+        );
+        assert_eq!(mint_balance_with(&mut host, 200, 10), Ok(()));
+        // Once expired, the balance can be replaced with any expiry.
+        assert_eq!(mint_balance_with(&mut host, 250, 210), Ok(()));
+    }
+
+    #[concordium_test]
+    fn test_set_monotonic_expiry_not_owner() {
+        let mut host = host_with_token();
+        let result = set_monotonic_expiry_with(&mut host, ACCOUNT_1);
+        assert_eq!(result, Err(ContractError::Unauthorized));
+    }
+}
@@ -0,0 +1,90 @@
+use concordium_std::*;
+
+use crate::{
+    errors::CustomError,
+    state::State,
+    types::{ContractError, ContractResult},
+};
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "setPermitKey",
+    parameter = "Option<PublicKeyEd25519>",
+    error = "ContractError",
+    mutable
+)]
+/// Sets the key with which the sender signs messages submitted by sponsors through `permit`,
+/// None removes the key. The nonce of the sender is kept, so replacing the key does not
+/// make messages signed with an earlier key valid again.
+/// - This function fails if the sender is not an account.
+pub fn set_permit_key<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    let account = match ctx.sender() {
+        Address::Account(account) => account,
+        Address::Contract(_) => bail!(ContractError::Custom(CustomError::AccountsOnly)),
+    };
+
+    let key: Option<PublicKeyEd25519> = ctx.parameter_cursor().get()?;
+    host.state_mut().set_permit_key(account, key);
+    Ok(())
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const KEY_0: PublicKeyEd25519 = PublicKeyEd25519([1u8; 32]);
+    const CONTRACT_0: ContractAddress = ContractAddress {
+        index: 2,
+        subindex: 0,
+    };
+
+    fn set_permit_key_with(
+        host: &mut TestHost<State<TestStateApi>>,
+        sender: Address,
+        key: Option<PublicKeyEd25519>,
+    ) -> ContractResult<()> {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(sender);
+        let parameter = to_bytes(&key);
+        ctx.set_parameter(&parameter);
+        set_permit_key(&ctx, host)
+    }
+
+    #[concordium_test]
+    fn test_set_permit_key() {
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+
+        let result = set_permit_key_with(&mut host, Address::Account(ACCOUNT_1), Some(KEY_0));
+        assert_eq!(result, Ok(()));
+        assert_eq!(host.state().permit_key(ACCOUNT_1), Some(KEY_0));
+        host.state_mut()
+            .use_permit_nonce(ACCOUNT_1, 0)
+            .expect("Failed to use nonce");
+
+        // Removing the key keeps the nonce.
+        let result = set_permit_key_with(&mut host, Address::Account(ACCOUNT_1), None);
+        assert_eq!(result, Ok(()));
+        assert_eq!(host.state().permit_key(ACCOUNT_1), None);
+        assert_eq!(host.state().permit_nonce(ACCOUNT_1), 1);
+    }
+
+    #[concordium_test]
+    fn test_set_permit_key_contract() {
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+
+        let result = set_permit_key_with(&mut host, Address::Contract(CONTRACT_0), Some(KEY_0));
+        assert_eq!(
+            result,
+            Err(ContractError::Custom(CustomError::AccountsOnly))
+        );
+    }
+}
@@ -0,0 +1,102 @@
+use concordium_std::*;
+
+use crate::{
+    errors::CustomError,
+    state::State,
+    types::{ContractError, ContractResult},
+};
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct SetReminderPreferenceParams {
+    /// True to consent to off-chain renewal reminders, false to withdraw the consent.
+    pub consent: bool,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "setReminderPreference",
+    parameter = "SetReminderPreferenceParams",
+    error = "ContractError",
+    mutable
+)]
+/// Gives or withdraws the consent of the sender to off-chain renewal reminders.
+/// The consent applies to all aliases of the account.
+/// - This function fails if the sender is not an account.
+pub fn set_reminder_preference<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    let holder = match ctx.sender() {
+        Address::Account(account) => account,
+        Address::Contract(_) => bail!(ContractError::Custom(CustomError::AccountsOnly)),
+    };
+
+    let params: SetReminderPreferenceParams = ctx.parameter_cursor().get()?;
+    let now = ctx.metadata().slot_time();
+    host.state_mut()
+        .set_reminder_consent(holder, params.consent, now);
+    Ok(())
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+
+    fn set_reminder_preference_with(
+        host: &mut TestHost<State<TestStateApi>>,
+        sender: Address,
+        consent: bool,
+        now: u64,
+    ) -> ContractResult<()> {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(sender);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(now));
+        let parameter = to_bytes(&SetReminderPreferenceParams { consent });
+        ctx.set_parameter(&parameter);
+        set_reminder_preference(&ctx, host)
+    }
+
+    #[concordium_test]
+    fn test_set_reminder_preference() {
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let sender = Address::Account(ACCOUNT_0);
+
+        assert_eq!(
+            set_reminder_preference_with(&mut host, sender, true, 10),
+            Ok(())
+        );
+        // Consenting again keeps the time of the original consent.
+        assert_eq!(
+            set_reminder_preference_with(&mut host, sender, true, 20),
+            Ok(())
+        );
+        assert_eq!(
+            host.state().get_reminder_consent(ACCOUNT_0),
+            Some(Timestamp::from_timestamp_millis(10))
+        );
+
+        assert_eq!(
+            set_reminder_preference_with(&mut host, sender, false, 30),
+            Ok(())
+        );
+        assert_eq!(host.state().get_reminder_consent(ACCOUNT_0), None);
+    }
+
+    #[concordium_test]
+    fn test_set_reminder_preference_contract_sender() {
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let sender = Address::Contract(ContractAddress::new(1, 0));
+
+        assert_eq!(
+            set_reminder_preference_with(&mut host, sender, true, 10),
+            Err(ContractError::Custom(CustomError::AccountsOnly))
+        );
+    }
+}
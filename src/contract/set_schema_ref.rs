@@ -0,0 +1,129 @@
+use concordium_std::*;
+
+use crate::{
+    state::State,
+    types::{ContractError, ContractEvent, ContractResult, ContractTokenId, DsidSchemaRefEvent},
+};
+
+#[derive(SchemaType, Deserial, Serial)]
+pub struct SetSchemaRefParams {
+    pub token_id: ContractTokenId,
+    /// SHA-256 checksum of the JSON schema the metadata conforms to, `None` removes it.
+    pub schema_ref: Option<HashSha2256>,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "setSchemaRef",
+    parameter = "SetSchemaRefParams",
+    error = "ContractError",
+    enable_logger,
+    mutable
+)]
+/// Sets or removes the checksum of the JSON schema the metadata of a token conforms to
+/// and logs a `DsidSchemaRef` event.
+/// - This function fails if the token does not exist.
+/// - This function fails if the token has an issuer and the sender is not the issuer.
+/// - This function fails if the token has no issuer and the sender is not the admin of the contract.
+pub fn set_schema_ref<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    let params: SetSchemaRefParams = ctx.parameter_cursor().get()?;
+    let is_owner = host.state().is_contract_admin(&ctx.sender(), ctx.owner());
+    let state = host.state_mut();
+    // Check that the sender is the issuer of the token.
+    ensure!(
+        state.can_issue(&params.token_id, &ctx.sender(), is_owner)?,
+        ContractError::Unauthorized
+    );
+
+    set_token_schema_ref(state, logger, params)
+}
+
+/// Sets or removes the metadata schema checksum of a single token and logs it.
+/// - This function does not check the sender.
+pub(crate) fn set_token_schema_ref<S: HasStateApi>(
+    state: &mut State<S>,
+    logger: &mut impl HasLogger,
+    params: SetSchemaRefParams,
+) -> ContractResult<()> {
+    state.set_token_schema_ref(params.token_id, params.schema_ref)?;
+    logger.log(&ContractEvent::DsidSchemaRef(DsidSchemaRefEvent {
+        token_id: params.token_id,
+        schema_ref: params.schema_ref,
+    }))?;
+    Ok(())
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ADDRESS_0: Address = Address::Account(ACCOUNT_0);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+
+    fn set_schema_ref_with(
+        host: &mut TestHost<State<TestStateApi>>,
+        logger: &mut TestLogger,
+        owner: AccountAddress,
+    ) -> ContractResult<()> {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(owner);
+        let params = SetSchemaRefParams {
+            token_id: TOKEN_0,
+            schema_ref: Some(HashSha2256([7; 32])),
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        set_schema_ref(&ctx, host, logger)
+    }
+
+    fn host_with_token() -> TestHost<State<TestStateApi>> {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        TestHost::new(state, state_builder)
+    }
+
+    #[concordium_test]
+    fn test_set_schema_ref() {
+        let mut host = host_with_token();
+        let mut logger = TestLogger::init();
+        let result = set_schema_ref_with(&mut host, &mut logger, ACCOUNT_0);
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            host.state().get_token_schema_ref(&TOKEN_0),
+            Ok(Some(HashSha2256([7; 32])))
+        );
+        assert_eq!(
+            logger.logs,
+            vec![to_bytes(&ContractEvent::DsidSchemaRef(
+                DsidSchemaRefEvent {
+                    token_id: TOKEN_0,
+                    schema_ref: Some(HashSha2256([7; 32])),
+                }
+            ))]
+        );
+    }
+
+    #[concordium_test]
+    fn test_set_schema_ref_not_owner() {
+        let mut host = host_with_token();
+        let mut logger = TestLogger::init();
+        let result = set_schema_ref_with(&mut host, &mut logger, AccountAddress([1u8; 32]));
+        assert_eq!(result, Err(ContractError::Unauthorized));
+    }
+}
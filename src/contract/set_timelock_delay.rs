@@ -0,0 +1,94 @@
+use concordium_std::*;
+
+use crate::{
+    contract::schedule_action::ensure_scheduled,
+    state::State,
+    types::{ContractError, ContractResult},
+};
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "setTimelockDelay",
+    parameter = "Duration",
+    error = "ContractError",
+    crypto_primitives,
+    mutable
+)]
+/// Replaces the delay between scheduling and executing `upgrade` and `setTimelockDelay`, 0 disables the timelock.
+/// - This function fails if the sender is not the admin of the contract.
+/// - This function fails if a timelock delay is set and the invocation has not been scheduled or the delay has not elapsed.
+pub fn set_timelock_delay<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    crypto_primitives: &impl HasCryptoPrimitives,
+) -> ContractResult<()> {
+    // Check that the sender is the admin of the contract.
+    ensure!(
+        host.state().is_contract_admin(&ctx.sender(), ctx.owner()),
+        ContractError::Unauthorized
+    );
+
+    let delay: Duration = ctx.parameter_cursor().get()?;
+    let state = host.state_mut();
+    ensure_scheduled(ctx, state, crypto_primitives, "setTimelockDelay")?;
+    state.set_timelock_delay(delay);
+
+    Ok(())
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::errors::CustomError;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ADDRESS_0: Address = Address::Account(ACCOUNT_0);
+    const ACTION_0: HashSha2256 = HashSha2256([1u8; 32]);
+
+    #[concordium_test]
+    fn test_set_timelock_delay() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(10));
+        let parameter = to_bytes(&Duration::from_millis(100));
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let crypto_primitives = TestCryptoPrimitives::new();
+        crypto_primitives.setup_hash_sha2_256_mock(|_| ACTION_0);
+
+        let result: ContractResult<()> = set_timelock_delay(&ctx, &mut host, &crypto_primitives);
+        assert_eq!(result, Ok(()));
+        assert_eq!(host.state().timelock_delay(), Duration::from_millis(100));
+
+        // Changing the delay now has to be scheduled.
+        let result: ContractResult<()> = set_timelock_delay(&ctx, &mut host, &crypto_primitives);
+        assert_eq!(
+            result,
+            Err(ContractError::Custom(CustomError::ActionNotScheduled))
+        );
+        host.state_mut()
+            .schedule_action(ACTION_0, Timestamp::from_timestamp_millis(0));
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(100));
+        let result: ContractResult<()> = set_timelock_delay(&ctx, &mut host, &crypto_primitives);
+        assert_eq!(result, Ok(()));
+    }
+
+    #[concordium_test]
+    fn test_set_timelock_delay_not_owner() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(AccountAddress([1u8; 32]));
+        let parameter = to_bytes(&Duration::from_millis(100));
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let crypto_primitives = TestCryptoPrimitives::new();
+        let result: ContractResult<()> = set_timelock_delay(&ctx, &mut host, &crypto_primitives);
+        assert_eq!(result, Err(ContractError::Unauthorized));
+    }
+}
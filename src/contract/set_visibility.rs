@@ -0,0 +1,126 @@
+use concordium_std::*;
+
+use crate::{
+    errors::CustomError,
+    state::State,
+    types::{ContractError, ContractResult, ContractTokenId},
+};
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct SetVisibilityParams {
+    pub token_id: ContractTokenId,
+    /// False to hide the balance of the sender from other callers of `balanceOf`.
+    pub public: bool,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "setVisibility",
+    parameter = "SetVisibilityParams",
+    error = "ContractError",
+    mutable
+)]
+/// Shows or hides the balance of the sender of a token.
+/// A hidden balance is 0 in `balanceOf` and `balanceOfOrZero`, except for the holder,
+/// the admin and verifiers. The setting applies to all aliases of the account.
+/// - This function fails if the sender is not an account.
+/// - This function fails if the token does not exist.
+pub fn set_visibility<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    let holder = match ctx.sender() {
+        Address::Account(account) => account,
+        Address::Contract(_) => bail!(ContractError::Custom(CustomError::AccountsOnly)),
+    };
+
+    let params: SetVisibilityParams = ctx.parameter_cursor().get()?;
+    host.state_mut()
+        .set_balance_hidden(params.token_id, holder, !params.public)
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::{contract::balance_of::balance_of, types::*};
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const ACCOUNT_2: AccountAddress = AccountAddress([2u8; 32]);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+
+    fn balance_of_with(host: &TestHost<State<TestStateApi>>, sender: AccountAddress) -> u8 {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(sender));
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(10));
+        let params = ContractBalanceOfQueryParams {
+            queries: vec![BalanceOfQuery {
+                address: Address::Account(ACCOUNT_1),
+                token_id: TOKEN_0,
+            }],
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        balance_of(&ctx, host).expect("Failed to query balance").0[0].0
+    }
+
+    #[concordium_test]
+    fn test_set_visibility() {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_1,
+                1.into(),
+                Timestamp::from_timestamp_millis(100),
+                None,
+            )
+            .expect("Failed to mint token");
+        let mut host = TestHost::new(state, state_builder);
+
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(ACCOUNT_1));
+        let parameter = to_bytes(&SetVisibilityParams {
+            token_id: TOKEN_0,
+            public: false,
+        });
+        ctx.set_parameter(&parameter);
+        assert_eq!(set_visibility(&ctx, &mut host), Ok(()));
+
+        // Only the holder, the admin and verifiers see the hidden balance.
+        assert_eq!(balance_of_with(&host, ACCOUNT_2), 0);
+        assert_eq!(balance_of_with(&host, ACCOUNT_1), 1);
+        assert_eq!(balance_of_with(&host, ACCOUNT_0), 1);
+        host.state_mut()
+            .update_role(Role::Verifier, Address::Account(ACCOUNT_2), true);
+        assert_eq!(balance_of_with(&host, ACCOUNT_2), 1);
+    }
+
+    #[concordium_test]
+    fn test_set_visibility_unknown_token() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(ACCOUNT_1));
+        let parameter = to_bytes(&SetVisibilityParams {
+            token_id: TOKEN_0,
+            public: false,
+        });
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let result = set_visibility(&ctx, &mut host);
+        assert_eq!(result, Err(ContractError::InvalidTokenId));
+    }
+}
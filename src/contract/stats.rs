@@ -0,0 +1,122 @@
+use concordium_std::*;
+
+use crate::{
+    state::State,
+    types::{ContractError, ContractResult},
+};
+
+#[derive(Debug, Serialize, SchemaType, PartialEq)]
+pub struct ContractStats {
+    /// Number of tokens.
+    pub tokens: u32,
+    /// Number of stored balances > 0 over all tokens. Balances which expired are counted
+    /// until `sweepExpired` removes them.
+    pub active_balances: u64,
+    /// Number of mints since the contract was initialized.
+    pub mints: u64,
+    /// Number of burns of valid amounts since the contract was initialized.
+    pub burns: u64,
+    /// Whether minting has been paused because the mint volume limit was exceeded.
+    pub minting_paused: bool,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "stats",
+    return_value = "ContractStats",
+    error = "ContractError"
+)]
+/// Gets statistics of the contract.
+/// Only counters are read, so the energy cost does not grow with the number of balances.
+pub fn stats<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<ContractStats> {
+    let state = host.state();
+    let (tokens, mints, burns) = state.get_counters();
+    Ok(ContractStats {
+        tokens,
+        active_balances: state.active_balances(),
+        mints,
+        burns,
+        minting_paused: state.is_minting_paused(),
+    })
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::types::ContractTokenId;
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+    const TOKEN_1: ContractTokenId = TokenIdU8(3);
+
+    #[concordium_test]
+    fn test_stats() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(150));
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        for token_id in [TOKEN_0, TOKEN_1] {
+            state.add_token(
+                &mut state_builder,
+                token_id,
+                MetadataUrl {
+                    url: format!("https://example.com/{}", token_id.0),
+                    hash: None,
+                },
+            );
+        }
+        for (account, expiry) in [(ACCOUNT_0, 100), (ACCOUNT_1, 200)] {
+            state
+                .mint(
+                    TOKEN_0,
+                    account,
+                    1.into(),
+                    Timestamp::from_timestamp_millis(expiry),
+                    None,
+                )
+                .expect("Failed to mint token");
+        }
+        state
+            .mint(
+                TOKEN_1,
+                ACCOUNT_0,
+                1.into(),
+                Timestamp::from_timestamp_millis(200),
+                None,
+            )
+            .expect("Failed to mint token");
+        state
+            .record_burn(TOKEN_0, 1.into())
+            .expect("Failed to record burn");
+        // Balances of removed tokens are no longer counted.
+        state.remove_token(TOKEN_1);
+        let mut host = TestHost::new(state, state_builder);
+
+        let result = stats(&ctx, &host);
+        assert_eq!(
+            result,
+            Ok(ContractStats {
+                tokens: 1,
+                // The expired balance of account 0 is counted until it is swept.
+                active_balances: 2,
+                mints: 3,
+                burns: 1,
+                minting_paused: false,
+            })
+        );
+
+        // Sweeping removes the expired balances from the count.
+        let (_, removed) = host
+            .state_mut()
+            .prune_expired(10, Timestamp::from_timestamp_millis(86_400_000));
+        assert_eq!(removed.len(), 2);
+        let result = stats(&ctx, &host).expect("Failed to read stats");
+        assert_eq!(result.active_balances, 0);
+    }
+}
@@ -0,0 +1,257 @@
+use concordium_std::*;
+
+use crate::{
+    contract::balance_of::is_hidden_from_sender, errors::CustomError, state::State, types::*,
+};
+
+#[derive(Debug, Serialize, SchemaType, PartialEq)]
+pub struct BalanceStatus {
+    /// The balance as returned by `balanceOf`.
+    pub amount: ContractTokenAmount,
+    /// The expiry of the balance, None if the account does not have a balance.
+    pub expiry: Option<Timestamp>,
+    /// The time from which the balance is valid, None if there is no balance or it is valid immediately.
+    pub valid_from: Option<Timestamp>,
+    /// Whether the balance is valid and expires within the soon window of the configuration.
+    pub expiring_soon: bool,
+}
+
+#[derive(Debug, Serialize, SchemaType)]
+pub struct StatusOfQueryResponse(#[concordium(size_length = 2)] pub Vec<BalanceStatus>);
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "statusOf",
+    parameter = "ContractBalanceOfQueryParams",
+    return_value = "StatusOfQueryResponse",
+    error = "ContractError"
+)]
+/// Gets the balance, expiry and valid from time of each of the queried balances in one call,
+/// and whether the balance is expiring soon.
+/// - Balances hidden by their holder are reported as no balance, see `balanceOf`.
+/// - This function fails if any of the tokens does not exist.
+/// - This function fails if any of the addresses is a contract.
+pub fn status_of<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<StatusOfQueryResponse> {
+    // Parse the parameter.
+    let params: ContractBalanceOfQueryParams = ctx.parameter_cursor().get()?;
+    let state = host.state();
+    state.ensure_batch_size(|limits| limits.query, params.queries.len())?;
+    let now = ctx.metadata().slot_time();
+    let response: Vec<BalanceStatus> = params
+        .queries
+        .iter()
+        .map(|q| match q.address {
+            Address::Account(address) => {
+                let balance = state.get_account_balance_state(q.token_id, address)?;
+                let balance = if is_hidden_from_sender(ctx, state, &q.token_id, address)? {
+                    None
+                } else {
+                    balance
+                };
+                Ok(match balance {
+                    Some(balance) => {
+                        let amount = state.get_account_balance(q.token_id, address, now)?;
+                        BalanceStatus {
+                            amount,
+                            expiry: Some(balance.expiry),
+                            valid_from: balance.valid_from,
+                            expiring_soon: amount > ContractTokenAmount::from(0)
+                                && state.config().is_expiring_soon(now, balance.expiry),
+                        }
+                    }
+                    None => BalanceStatus {
+                        amount: ContractTokenAmount::from(0),
+                        expiry: None,
+                        valid_from: None,
+                        expiring_soon: false,
+                    },
+                })
+            }
+            Address::Contract(_) => Err(ContractError::Custom(CustomError::AccountsOnly)),
+        })
+        .collect::<Result<Vec<BalanceStatus>, ContractError>>()?;
+
+    Ok(StatusOfQueryResponse(response))
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const ACCOUNT_2: AccountAddress = AccountAddress([2u8; 32]);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+
+    #[concordium_test]
+    fn test_status_of() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(150));
+        let params = ContractBalanceOfQueryParams {
+            queries: vec![
+                BalanceOfQuery {
+                    address: Address::Account(ACCOUNT_0),
+                    token_id: TOKEN_0,
+                },
+                BalanceOfQuery {
+                    address: Address::Account(ACCOUNT_1),
+                    token_id: TOKEN_0,
+                },
+                BalanceOfQuery {
+                    address: Address::Account(ACCOUNT_2),
+                    token_id: TOKEN_0,
+                },
+            ],
+        };
+        let parameter = &to_bytes(&params);
+        ctx.set_parameter(parameter);
+
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.set_config(ContractConfig {
+            soon_window: Some(Duration::from_millis(100)),
+            ..Default::default()
+        });
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_0,
+                10.into(),
+                Timestamp::from_timestamp_millis(200),
+                Some(Timestamp::from_timestamp_millis(100)),
+            )
+            .unwrap();
+        // Balances which are not valid yet are 0.
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_1,
+                20.into(),
+                Timestamp::from_timestamp_millis(300),
+                Some(Timestamp::from_timestamp_millis(200)),
+            )
+            .unwrap();
+
+        let host = TestHost::new(state, state_builder);
+        let result = status_of(&ctx, &host).unwrap();
+        assert_eq!(
+            result.0,
+            vec![
+                BalanceStatus {
+                    amount: 10.into(),
+                    expiry: Some(Timestamp::from_timestamp_millis(200)),
+                    valid_from: Some(Timestamp::from_timestamp_millis(100)),
+                    expiring_soon: true,
+                },
+                BalanceStatus {
+                    amount: 0.into(),
+                    expiry: Some(Timestamp::from_timestamp_millis(300)),
+                    valid_from: Some(Timestamp::from_timestamp_millis(200)),
+                    expiring_soon: false,
+                },
+                BalanceStatus {
+                    amount: 0.into(),
+                    expiry: None,
+                    valid_from: None,
+                    expiring_soon: false,
+                },
+            ]
+        );
+    }
+
+    #[concordium_test]
+    fn test_status_of_contract_address() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(150));
+        let params = ContractBalanceOfQueryParams {
+            queries: vec![BalanceOfQuery {
+                address: Address::Contract(ContractAddress {
+                    index: 1,
+                    subindex: 0,
+                }),
+                token_id: TOKEN_0,
+            }],
+        };
+        let parameter = &to_bytes(&params);
+        ctx.set_parameter(parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let host = TestHost::new(state, state_builder);
+        let result = status_of(&ctx, &host);
+        assert_eq!(
+            result.map(|r| r.0),
+            Err(ContractError::Custom(CustomError::AccountsOnly))
+        );
+    }
+
+    #[concordium_test]
+    fn test_status_of_hidden() {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_1,
+                10.into(),
+                Timestamp::from_timestamp_millis(200),
+                None,
+            )
+            .expect("Failed to mint token");
+        state
+            .set_balance_hidden(TOKEN_0, ACCOUNT_1, true)
+            .expect("Failed to hide balance");
+        let host = TestHost::new(state, state_builder);
+        let parameter = to_bytes(&ContractBalanceOfQueryParams {
+            queries: vec![BalanceOfQuery {
+                address: Address::Account(ACCOUNT_1),
+                token_id: TOKEN_0,
+            }],
+        });
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(ACCOUNT_2));
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(150));
+        ctx.set_parameter(&parameter);
+
+        assert_eq!(
+            status_of(&ctx, &host).map(|response| response.0),
+            Ok(vec![BalanceStatus {
+                amount: 0.into(),
+                expiry: None,
+                valid_from: None,
+                expiring_soon: false,
+            }])
+        );
+        ctx.set_sender(Address::Account(ACCOUNT_1));
+        assert_eq!(
+            status_of(&ctx, &host).map(|response| response.0),
+            Ok(vec![BalanceStatus {
+                amount: 10.into(),
+                expiry: Some(Timestamp::from_timestamp_millis(200)),
+                valid_from: None,
+                expiring_soon: false,
+            }])
+        );
+    }
+}
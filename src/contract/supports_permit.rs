@@ -0,0 +1,78 @@
+use concordium_cis2::{SupportResult, SupportsQueryResponse};
+use concordium_std::*;
+
+use crate::{
+    contract::permit::PERMIT_ENTRYPOINTS,
+    state::State,
+    types::{ContractError, ContractResult},
+};
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct SupportsPermitQueryParams {
+    /// Names of the entrypoints to query.
+    #[concordium(size_length = 2)]
+    pub queries: Vec<OwnedEntrypointName>,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "supportsPermit",
+    parameter = "SupportsPermitQueryParams",
+    return_value = "SupportsQueryResponse",
+    error = "ContractError"
+)]
+/// Gets whether each queried entrypoint accepts sponsored calls through `permit`, following CIS-3.
+/// - This function fails if the number of queries exceeds the configured batch limit.
+pub fn supports_permit<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<SupportsQueryResponse> {
+    let params: SupportsPermitQueryParams = ctx.parameter_cursor().get()?;
+    host.state()
+        .ensure_batch_size(|limits| limits.query, params.queries.len())?;
+    let results = params
+        .queries
+        .iter()
+        .map(|entrypoint| {
+            let permitted = PERMIT_ENTRYPOINTS
+                .iter()
+                .any(|name| EntrypointName::new_unchecked(name) == entrypoint.as_entrypoint_name());
+            if permitted {
+                SupportResult::Support
+            } else {
+                SupportResult::NoSupport
+            }
+        })
+        .collect();
+
+    Ok(SupportsQueryResponse { results })
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use concordium_std::test_infrastructure::*;
+
+    #[concordium_test]
+    fn test_supports_permit() {
+        let mut ctx = TestReceiveContext::empty();
+        let params = SupportsPermitQueryParams {
+            queries: vec![
+                OwnedEntrypointName::new_unchecked("renounce".to_string()),
+                OwnedEntrypointName::new_unchecked("requestRenewal".to_string()),
+                OwnedEntrypointName::new_unchecked("mint".to_string()),
+            ],
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let host = TestHost::new(state, state_builder);
+
+        let result = supports_permit(&ctx, &host).expect("Failed to query support");
+        assert_eq!(result.results.len(), 3);
+        assert!(matches!(result.results[0], SupportResult::Support));
+        assert!(matches!(result.results[1], SupportResult::Support));
+        assert!(matches!(result.results[2], SupportResult::NoSupport));
+    }
+}
@@ -0,0 +1,173 @@
+use concordium_std::*;
+
+use crate::{
+    contract::update_subscribers::{notify_subscribers, DsidNotification},
+    state::State,
+    types::{ContractError, ContractResult, ContractTokenAmount},
+};
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct SweepExpiredParams {
+    /// Maximum number of entries of the expiry index to process.
+    pub max_entries: u32,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "sweepExpired",
+    parameter = "SweepExpiredParams",
+    return_value = "u32",
+    error = "ContractError",
+    enable_logger,
+    mutable
+)]
+/// Removes balances which expired before the current day from the state and notifies the
+/// subscribers. Expired balances are already reported as 0, so no events are logged.
+/// Returns the number of processed entries, call again until 0 is returned.
+/// - This function fails if the sender is not the admin of the contract.
+pub fn sweep_expired<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<u32> {
+    // Check that the sender is the admin of the contract.
+    ensure!(
+        host.state().is_contract_admin(&ctx.sender(), ctx.owner()),
+        ContractError::Unauthorized
+    );
+
+    let params: SweepExpiredParams = ctx.parameter_cursor().get()?;
+    let now = ctx.metadata().slot_time();
+    let (processed, removed) = host.state_mut().prune_expired(params.max_entries, now);
+
+    // Notify the subscribers about the removed balances.
+    let notifications = removed
+        .into_iter()
+        .map(|(token_id, account, expiry)| DsidNotification {
+            token_id,
+            account,
+            amount: ContractTokenAmount::from(0),
+            expiry,
+        })
+        .collect();
+    notify_subscribers(host, logger, notifications)?;
+
+    Ok(processed)
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::{contract::update_subscribers::DsidNotifyParams, types::*};
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const ADDRESS_0: Address = Address::Account(ACCOUNT_0);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+    const DAY: u64 = 86_400_000;
+    const CONTRACT_0: ContractAddress = ContractAddress {
+        index: 2,
+        subindex: 0,
+    };
+
+    #[concordium_test]
+    fn test_sweep_expired() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(2 * DAY));
+        let params = SweepExpiredParams { max_entries: 10 };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_0,
+                1.into(),
+                Timestamp::from_timestamp_millis(DAY),
+                None,
+            )
+            .expect("Failed to mint token");
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_1,
+                1.into(),
+                Timestamp::from_timestamp_millis(3 * DAY),
+                None,
+            )
+            .expect("Failed to mint token");
+        state
+            .add_subscriber(CONTRACT_0)
+            .expect("Failed to add subscriber");
+        let mut host = TestHost::new(state, state_builder);
+        // Subscribers are notified about the removed balance only.
+        host.setup_mock_entrypoint(
+            CONTRACT_0,
+            OwnedEntrypointName::new_unchecked("dsidNotify".to_string()),
+            MockFn::new_v1(|parameter, _, _, _| {
+                let params: DsidNotifyParams =
+                    from_bytes(parameter.as_ref()).map_err(|_| CallContractError::Trap)?;
+                claim_eq!(
+                    params.notifications,
+                    vec![DsidNotification {
+                        token_id: TOKEN_0,
+                        account: ACCOUNT_0,
+                        amount: 0.into(),
+                        expiry: Timestamp::from_timestamp_millis(DAY),
+                    }]
+                );
+                Ok((false, ()))
+            }),
+        );
+
+        let mut logger = TestLogger::init();
+        let result = sweep_expired(&ctx, &mut host, &mut logger);
+        assert_eq!(result, Ok(1));
+        assert!(logger.logs.is_empty());
+        assert_eq!(
+            host.state().get_account_balance_expiry(TOKEN_0, ACCOUNT_0),
+            Ok(None)
+        );
+        assert_eq!(
+            host.state().get_account_balance_expiry(TOKEN_0, ACCOUNT_1),
+            Ok(Some(Timestamp::from_timestamp_millis(3 * DAY)))
+        );
+        assert_eq!(
+            host.state()
+                .get_token_supply(&TOKEN_0, ctx.metadata().slot_time()),
+            Ok((1, 1))
+        );
+
+        // Nothing is left to prune.
+        let result = sweep_expired(&ctx, &mut host, &mut logger);
+        assert_eq!(result, Ok(0));
+    }
+
+    #[concordium_test]
+    fn test_sweep_expired_not_owner() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(AccountAddress([1u8; 32]));
+        let params = SweepExpiredParams { max_entries: 10 };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let result = sweep_expired(&ctx, &mut host, &mut TestLogger::init());
+        assert_eq!(result, Err(ContractError::Unauthorized));
+    }
+}
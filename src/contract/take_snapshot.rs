@@ -0,0 +1,123 @@
+use concordium_std::*;
+
+use crate::{
+    state::State,
+    types::{ContractError, ContractResult, SnapshotId},
+};
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct TakeSnapshotParams {
+    /// Name of the checkpoint.
+    pub name: String,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "takeSnapshot",
+    parameter = "TakeSnapshotParams",
+    return_value = "SnapshotId",
+    error = "ContractError",
+    mutable
+)]
+/// Records the total supply and holder count of every token at the current time.
+/// - Returns the id of the new snapshot.
+/// - This function fails if the sender is not the admin of the contract.
+pub fn take_snapshot<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<SnapshotId> {
+    // Check that the sender is the admin of the contract.
+    ensure!(
+        host.state().is_contract_admin(&ctx.sender(), ctx.owner()),
+        ContractError::Unauthorized
+    );
+
+    let params: TakeSnapshotParams = ctx.parameter_cursor().get()?;
+    let snapshot_id = host
+        .state_mut()
+        .take_snapshot(params.name, ctx.metadata().slot_time());
+
+    Ok(snapshot_id)
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::types::{ContractTokenId, TokenSnapshot};
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const ADDRESS_0: Address = Address::Account(ACCOUNT_0);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+
+    #[concordium_test]
+    fn test_take_snapshot() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(50));
+        let params = TakeSnapshotParams {
+            name: "2023-Q1".to_string(),
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_1,
+                10.into(),
+                Timestamp::from_timestamp_millis(100),
+                None,
+            )
+            .expect("Failed to mint token");
+        let mut host = TestHost::new(state, state_builder);
+
+        // Snapshot ids are assigned incrementally.
+        assert_eq!(take_snapshot(&ctx, &mut host), Ok(0));
+        assert_eq!(take_snapshot(&ctx, &mut host), Ok(1));
+
+        let snapshot = host.state().get_snapshot(0).unwrap();
+        assert_eq!(snapshot.name, "2023-Q1");
+        assert_eq!(snapshot.timestamp, Timestamp::from_timestamp_millis(50));
+        assert_eq!(
+            snapshot.tokens,
+            vec![TokenSnapshot {
+                token_id: TOKEN_0,
+                total_supply: 10,
+                holders: 1,
+            }]
+        );
+    }
+
+    #[concordium_test]
+    fn test_take_snapshot_not_owner() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_1);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(50));
+        let params = TakeSnapshotParams {
+            name: "2023-Q1".to_string(),
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        assert_eq!(
+            take_snapshot(&ctx, &mut host),
+            Err(ContractError::Unauthorized)
+        );
+    }
+}
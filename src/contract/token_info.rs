@@ -0,0 +1,275 @@
+use concordium_cis2::MetadataUrl;
+use concordium_std::*;
+
+use crate::{
+    state::State,
+    types::{
+        AmountRule, AmountUnit, ContractError, ContractResult, ContractTokenAmount,
+        ContractTokenId, ContractTokenMetadataQueryParams, ExclusiveGroup, RenewalPolicy,
+        TokenCategory,
+    },
+};
+
+#[derive(Debug, Serialize, SchemaType, PartialEq)]
+pub struct TokenInfo {
+    /// The metadata url of the token.
+    pub metadata_url: MetadataUrl,
+    /// Sum of all balances which have not expired.
+    pub total_supply: u64,
+    /// Number of accounts with a balance which has not expired.
+    pub holders: u32,
+    /// Sum of all amounts ever minted.
+    pub total_minted: u64,
+    /// Sum of all valid amounts ever burned by replacing or revoking a balance.
+    pub total_burned: u64,
+    /// Whether the token can be transferred by its holders.
+    pub transferable: bool,
+    /// Category of the token.
+    pub category: TokenCategory,
+    /// Whether minting of the token is paused.
+    pub paused: bool,
+    /// Whether the token has been deprecated.
+    pub deprecated: bool,
+    /// Address with the exclusive right to mint and revoke the token.
+    pub issuer: Option<Address>,
+    /// Who may extend the expiry of existing balances.
+    pub renewal_policy: RenewalPolicy,
+    /// Whether minting requires the consent of the holder.
+    pub consent_required: bool,
+    /// Whether balances can only be minted to accounts on the allowlist.
+    pub allowlist_required: bool,
+    /// Whether only commitments of balances are stored.
+    pub private: bool,
+    /// Whether holders must be confirmed as unique persons by the identity registry.
+    pub unique_person: bool,
+    /// Decimals and unit of the amounts.
+    pub amount_unit: AmountUnit,
+    /// Amounts which may be minted.
+    pub amount_rule: AmountRule,
+    /// Smallest amount which can be minted.
+    pub min_amount: ContractTokenAmount,
+    /// SHA-256 checksum of the JSON schema the metadata conforms to.
+    pub schema_ref: Option<HashSha2256>,
+    /// Token whose balance the holder needs for a balance of this token to be valid.
+    pub depends_on: Option<ContractTokenId>,
+    /// Group of tokens of which a holder has at most one balance at a time.
+    pub exclusive_group: Option<ExclusiveGroup>,
+    /// Whether a mint or renewal may not shorten the expiry of an unexpired balance.
+    pub monotonic_expiry: bool,
+    /// Maximum time between the first mint of a balance and its expiry, None if unlimited.
+    pub max_lifetime: Option<Duration>,
+}
+
+#[derive(Debug, Serialize, SchemaType)]
+pub struct TokenInfoQueryResponse(#[concordium(size_length = 2)] pub Vec<TokenInfo>);
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "tokenInfo",
+    parameter = "ContractTokenMetadataQueryParams",
+    return_value = "TokenInfoQueryResponse",
+    error = "ContractError"
+)]
+/// Gets the metadata url, supply and holder count for each of the queried tokens.
+/// - This function fails if any of the tokens does not exist.
+pub fn token_info<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<TokenInfoQueryResponse> {
+    // Parse the parameter.
+    let params: ContractTokenMetadataQueryParams = ctx.parameter_cursor().get()?;
+    let state = host.state();
+    state.ensure_batch_size(|limits| limits.query, params.queries.len())?;
+    let now = ctx.metadata().slot_time();
+    let response: Vec<TokenInfo> = params
+        .queries
+        .iter()
+        .map(|token_id| {
+            let metadata_url = state.get_token_metadata(token_id)?;
+            let (total_supply, holders) = state.get_token_supply(token_id, now)?;
+            let (total_minted, total_burned) = state.get_token_totals(token_id)?;
+            Ok(TokenInfo {
+                metadata_url,
+                total_supply,
+                holders,
+                total_minted,
+                total_burned,
+                // Tokens of this contract can not be transferred.
+                transferable: false,
+                category: state.get_token_category(token_id)?,
+                paused: state.is_token_paused(token_id)?,
+                deprecated: state.is_token_deprecated(token_id)?,
+                issuer: state.get_token_issuer(token_id)?,
+                renewal_policy: state.get_token_renewal_policy(token_id)?,
+                consent_required: state.is_token_consent_required(token_id)?,
+                allowlist_required: state.is_token_allowlist_required(token_id)?,
+                private: state.is_token_private(token_id)?,
+                unique_person: state.is_token_unique_person(token_id)?,
+                amount_unit: state.get_token_amount_unit(token_id)?,
+                amount_rule: state.get_token_amount_rule(token_id)?,
+                min_amount: state.get_token_min_amount(token_id)?,
+                schema_ref: state.get_token_schema_ref(token_id)?,
+                depends_on: state.get_token_dependency(token_id)?,
+                exclusive_group: state.get_token_exclusive_group(token_id)?,
+                monotonic_expiry: state.is_token_monotonic_expiry(token_id)?,
+                max_lifetime: state.get_token_max_lifetime(token_id)?,
+            })
+        })
+        .collect::<Result<Vec<TokenInfo>, ContractError>>()?;
+
+    Ok(TokenInfoQueryResponse(response))
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::types::*;
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+    const TOKEN_1: ContractTokenId = TokenIdU8(3);
+
+    #[concordium_test]
+    fn test_token_info() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(150));
+        let params = ContractTokenMetadataQueryParams {
+            queries: vec![TOKEN_0, TOKEN_1],
+        };
+        let parameter = &to_bytes(&params);
+        ctx.set_parameter(parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+
+        // Add tokens to the state.
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        state.add_token(
+            &mut state_builder,
+            TOKEN_1,
+            MetadataUrl {
+                url: "https://example.com/1".to_string(),
+                hash: None,
+            },
+        );
+
+        // Add balances to the state.
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_0,
+                10.into(),
+                Timestamp::from_timestamp_millis(200),
+                None,
+            )
+            .expect("Failed to mint token");
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_1,
+                20.into(),
+                Timestamp::from_timestamp_millis(300),
+                None,
+            )
+            .expect("Failed to mint token");
+        // Expired balances are not counted.
+        state
+            .mint(
+                TOKEN_1,
+                ACCOUNT_0,
+                30.into(),
+                Timestamp::from_timestamp_millis(100),
+                None,
+            )
+            .expect("Failed to mint token");
+
+        let host = TestHost::new(state, state_builder);
+        let result = token_info(&ctx, &host).unwrap();
+        assert_eq!(
+            result.0,
+            vec![
+                TokenInfo {
+                    metadata_url: MetadataUrl {
+                        url: "https://example.com".to_string(),
+                        hash: None,
+                    },
+                    total_supply: 30,
+                    holders: 2,
+                    total_minted: 30,
+                    total_burned: 0,
+                    transferable: false,
+                    category: 0,
+                    paused: false,
+                    deprecated: false,
+                    issuer: None,
+                    renewal_policy: RenewalPolicy::IssuerOnly,
+                    consent_required: false,
+                    allowlist_required: false,
+                    private: false,
+                    unique_person: false,
+                    amount_unit: AmountUnit::default(),
+                    amount_rule: AmountRule::default(),
+                    min_amount: 1.into(),
+                    schema_ref: None,
+                    depends_on: None,
+                    exclusive_group: None,
+                    monotonic_expiry: false,
+                    max_lifetime: None,
+                },
+                TokenInfo {
+                    metadata_url: MetadataUrl {
+                        url: "https://example.com/1".to_string(),
+                        hash: None,
+                    },
+                    total_supply: 0,
+                    holders: 0,
+                    total_minted: 30,
+                    total_burned: 0,
+                    transferable: false,
+                    category: 0,
+                    paused: false,
+                    deprecated: false,
+                    issuer: None,
+                    renewal_policy: RenewalPolicy::IssuerOnly,
+                    consent_required: false,
+                    allowlist_required: false,
+                    private: false,
+                    unique_person: false,
+                    amount_unit: AmountUnit::default(),
+                    amount_rule: AmountRule::default(),
+                    min_amount: 1.into(),
+                    schema_ref: None,
+                    depends_on: None,
+                    exclusive_group: None,
+                    monotonic_expiry: false,
+                    max_lifetime: None,
+                },
+            ]
+        );
+    }
+
+    #[concordium_test]
+    fn test_token_info_invalid_token_id() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(150));
+        let params = ContractTokenMetadataQueryParams {
+            queries: vec![TOKEN_0],
+        };
+        let parameter = &to_bytes(&params);
+        ctx.set_parameter(parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let host = TestHost::new(state, state_builder);
+        let result = token_info(&ctx, &host);
+        assert_eq!(result.map(|r| r.0), Err(ContractError::InvalidTokenId));
+    }
+}
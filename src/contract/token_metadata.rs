@@ -1,4 +1,3 @@
-use concordium_cis2::{MetadataUrl, TokenMetadataQueryResponse};
 use concordium_std::*;
 
 use crate::{
@@ -10,23 +9,43 @@ use crate::{
     contract = "cis2_dsid",
     name = "tokenMetadata",
     parameter = "ContractTokenMetadataQueryParams",
-    return_value = "TokenMetadataQueryResponse",
+    return_value = "concordium_cis2::TokenMetadataQueryResponse",
     error = "ContractError"
 )]
+/// Gets the metadata url of each of the queried tokens.
+/// - This function fails if any of the tokens does not exist.
 pub fn token_metadata<S: HasStateApi>(
     ctx: &impl HasReceiveContext,
     host: &impl HasHost<State<S>, StateApiType = S>,
-) -> ContractResult<TokenMetadataQueryResponse> {
+) -> ContractResult<TokenMetadataResponseBytes> {
     // Parse the parameter.
     let params: ContractTokenMetadataQueryParams = ctx.parameter_cursor().get()?;
     let state = host.state();
-    let response: Vec<MetadataUrl> = params
-        .queries
-        .iter()
-        .map(|q| state.get_token_metadata(q))
-        .collect::<Result<Vec<MetadataUrl>, ContractError>>()?;
+    state.ensure_batch_size(|limits| limits.query, params.queries.len())?;
 
-    Ok(TokenMetadataQueryResponse::from(response))
+    // Serialize the response while reading the state, in the layout of `TokenMetadataQueryResponse`.
+    // The queries are parsed with a 2 byte length, so the length fits in a u16.
+    let mut bytes = Vec::new();
+    (params.queries.len() as u16)
+        .serial(&mut bytes)
+        .unwrap_abort();
+    for token_id in params.queries.iter() {
+        // Writing to a vector does not fail.
+        state
+            .with_token_metadata(token_id, |metadata| metadata.serial(&mut bytes))?
+            .unwrap_abort();
+    }
+
+    Ok(TokenMetadataResponseBytes(bytes))
+}
+
+/// Serialized `TokenMetadataQueryResponse`, built without cloning the metadata urls.
+pub struct TokenMetadataResponseBytes(Vec<u8>);
+
+impl Serial for TokenMetadataResponseBytes {
+    fn serial<W: Write>(&self, out: &mut W) -> Result<(), W::Err> {
+        out.write_all(&self.0)
+    }
 }
 
 #[concordium_cfg_test]
@@ -69,10 +88,29 @@ mod tests {
         );
 
         let host = TestHost::new(state, state_builder);
-        let result = token_metadata(&ctx, &host).unwrap();
+        let bytes = to_bytes(&token_metadata(&ctx, &host).unwrap());
+        let result: TokenMetadataQueryResponse =
+            from_bytes(&bytes).expect("Failed to parse response");
         assert_eq!(result.0.len(), 2);
         assert_eq!(result.0[0].url, "https://example.com");
         assert_eq!(result.0[0].hash, Some([1; 32]));
         assert_eq!(result.0[1].url, "https://example.com/1");
     }
+
+    #[concordium_test]
+    fn test_token_metadata_invalid_token_id() {
+        const TOKEN_0: ContractTokenId = TokenIdU8(2);
+
+        let mut ctx = TestReceiveContext::empty();
+        let params = ContractTokenMetadataQueryParams {
+            queries: vec![TOKEN_0],
+        };
+        let parameter = &to_bytes(&params);
+        ctx.set_parameter(parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let host = TestHost::new(state, state_builder);
+        let result = token_metadata(&ctx, &host);
+        assert_eq!(result.err(), Some(ContractError::InvalidTokenId));
+    }
 }
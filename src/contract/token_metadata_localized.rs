@@ -0,0 +1,115 @@
+use concordium_cis2::MetadataUrl;
+use concordium_std::*;
+
+use crate::{
+    state::State,
+    types::{ContractError, ContractResult, ContractTokenId},
+};
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct LocalizedMetadataQuery {
+    pub token_id: ContractTokenId,
+    /// Locale of the metadata, e.g. "en" or "fr".
+    pub locale: String,
+}
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct TokenMetadataLocalizedParams {
+    #[concordium(size_length = 2)]
+    pub queries: Vec<LocalizedMetadataQuery>,
+}
+
+#[derive(Debug, Serialize, SchemaType)]
+pub struct TokenMetadataLocalizedResponse(#[concordium(size_length = 2)] pub Vec<MetadataUrl>);
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "tokenMetadataLocalized",
+    parameter = "TokenMetadataLocalizedParams",
+    return_value = "TokenMetadataLocalizedResponse",
+    error = "ContractError"
+)]
+/// Gets the metadata url of each of the queried tokens for a locale.
+/// The default metadata url is returned for locales without a metadata url.
+/// - This function fails if any of the tokens does not exist.
+pub fn token_metadata_localized<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<TokenMetadataLocalizedResponse> {
+    // Parse the parameter.
+    let params: TokenMetadataLocalizedParams = ctx.parameter_cursor().get()?;
+    let state = host.state();
+    state.ensure_batch_size(|limits| limits.query, params.queries.len())?;
+    let response = params
+        .queries
+        .iter()
+        .map(|query| state.get_localized_metadata(&query.token_id, &query.locale))
+        .collect::<ContractResult<Vec<MetadataUrl>>>()?;
+
+    Ok(TokenMetadataLocalizedResponse(response))
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+
+    #[concordium_test]
+    fn test_token_metadata_localized() {
+        let mut ctx = TestReceiveContext::empty();
+        let params = TokenMetadataLocalizedParams {
+            queries: vec![
+                LocalizedMetadataQuery {
+                    token_id: TOKEN_0,
+                    locale: "fr".to_string(),
+                },
+                LocalizedMetadataQuery {
+                    token_id: TOKEN_0,
+                    locale: "de".to_string(),
+                },
+            ],
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        state
+            .set_localized_metadata(
+                TOKEN_0,
+                "fr".to_string(),
+                Some(MetadataUrl {
+                    url: "https://example.com/fr".to_string(),
+                    hash: None,
+                }),
+            )
+            .expect("Failed to set localized metadata");
+        let host = TestHost::new(state, state_builder);
+
+        let result = token_metadata_localized(&ctx, &host).unwrap();
+        // Locales without a metadata url fall back to the default metadata url.
+        assert_eq!(
+            result.0,
+            vec![
+                MetadataUrl {
+                    url: "https://example.com/fr".to_string(),
+                    hash: None,
+                },
+                MetadataUrl {
+                    url: "https://example.com".to_string(),
+                    hash: None,
+                },
+            ]
+        );
+    }
+}
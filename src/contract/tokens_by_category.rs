@@ -0,0 +1,64 @@
+use concordium_std::*;
+
+use crate::{
+    state::State,
+    types::{ContractError, ContractResult, ContractTokenId, TokenCategory},
+};
+
+#[derive(Debug, Serialize, SchemaType)]
+pub struct TokensByCategoryResponse(#[concordium(size_length = 2)] pub Vec<ContractTokenId>);
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "tokensByCategory",
+    parameter = "TokenCategory",
+    return_value = "TokensByCategoryResponse",
+    error = "ContractError"
+)]
+/// Gets all the tokens of a category.
+pub fn tokens_by_category<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<TokensByCategoryResponse> {
+    // Parse the parameter.
+    let category: TokenCategory = ctx.parameter_cursor().get()?;
+    let tokens = host.state().tokens_by_category(category);
+
+    Ok(TokensByCategoryResponse(tokens))
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+    const TOKEN_1: ContractTokenId = TokenIdU8(3);
+    const TOKEN_2: ContractTokenId = TokenIdU8(4);
+
+    #[concordium_test]
+    fn test_tokens_by_category() {
+        let mut ctx = TestReceiveContext::empty();
+        let parameter = to_bytes(&1u8);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        for (token_id, category) in [(TOKEN_0, 1), (TOKEN_1, 2), (TOKEN_2, 1)] {
+            state.add_token(
+                &mut state_builder,
+                token_id,
+                MetadataUrl {
+                    url: format!("https://example.com/{}", token_id.0),
+                    hash: None,
+                },
+            );
+            state
+                .set_token_category(token_id, category)
+                .expect("Failed to set category");
+        }
+        let host = TestHost::new(state, state_builder);
+        let result = tokens_by_category(&ctx, &host).unwrap();
+        assert_eq!(result.0, vec![TOKEN_0, TOKEN_2]);
+    }
+}
@@ -2,7 +2,10 @@ use concordium_std::*;
 
 use crate::{
     state::State,
-    types::{ContractError, ContractResult},
+    types::{
+        ContractError, ContractEvent, ContractResult, ContractTransferParams,
+        TransferAttemptedEvent,
+    },
 };
 
 #[receive(
@@ -10,12 +13,26 @@ use crate::{
     name = "transfer",
     parameter = "crate::types::ContractTransferParams",
     error = "ContractError",
+    enable_logger,
     mutable
 )]
+/// Rejects every transfer, the tokens are soulbound.
+/// Logs a `TransferAttempted` event for each transfer if `log_transfer_attempts` is configured.
 pub fn transfer<S: HasStateApi>(
-    _ctx: &impl HasReceiveContext,
-    _host: &mut impl HasHost<State<S>, StateApiType = S>,
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
 ) -> ContractResult<()> {
+    if host.state().config().log_transfer_attempts {
+        let params: ContractTransferParams = ctx.parameter_cursor().get()?;
+        for transfer in params.0 {
+            logger.log(&ContractEvent::TransferAttempted(TransferAttemptedEvent {
+                token_id: transfer.token_id,
+                sender: ctx.sender(),
+            }))?;
+        }
+    }
+
     // Transfer of tokens is not allowed.
     Err(ContractError::Unauthorized)
 }
@@ -32,9 +49,15 @@ mod tests {
     const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
     const TOKEN_0: ContractTokenId = TokenIdU8(2);
 
+    fn transfer_ctx(parameter_bytes: &[u8]) -> TestReceiveContext {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_parameter(parameter_bytes);
+        ctx
+    }
+
     #[concordium_test]
     fn test_transfer() {
-        let mut ctx = TestReceiveContext::empty();
         let transfer_param = concordium_cis2::Transfer {
             token_id: TOKEN_0,
             amount: crate::types::ContractTokenAmount::from(100),
@@ -44,11 +67,46 @@ mod tests {
         };
         let parameter = ContractTransferParams::from(vec![transfer_param]);
         let parameter_bytes = to_bytes(&parameter);
-        ctx.set_parameter(&parameter_bytes);
+        let ctx = transfer_ctx(&parameter_bytes);
         let mut state_builder = TestStateBuilder::new();
         let state = State::empty(&mut state_builder);
         let mut host = TestHost::new(state, state_builder);
-        let result: ContractResult<()> = transfer(&ctx, &mut host);
+        let mut logger = TestLogger::init();
+        let result: ContractResult<()> = transfer(&ctx, &mut host, &mut logger);
+        assert_eq!(result, Err(ContractError::Unauthorized));
+        assert!(logger.logs.is_empty());
+    }
+
+    #[concordium_test]
+    fn test_transfer_logs_attempt() {
+        let transfer_param = concordium_cis2::Transfer {
+            token_id: TOKEN_0,
+            amount: crate::types::ContractTokenAmount::from(1),
+            from: ADDRESS_0,
+            to: Receiver::from_account(ACCOUNT_1),
+            data: AdditionalData::empty(),
+        };
+        let parameter = ContractTransferParams::from(vec![transfer_param]);
+        let parameter_bytes = to_bytes(&parameter);
+        let ctx = transfer_ctx(&parameter_bytes);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.set_config(ContractConfig {
+            log_transfer_attempts: true,
+            ..Default::default()
+        });
+        let mut host = TestHost::new(state, state_builder);
+        let mut logger = TestLogger::init();
+        let result: ContractResult<()> = transfer(&ctx, &mut host, &mut logger);
         assert_eq!(result, Err(ContractError::Unauthorized));
+        assert_eq!(
+            logger.logs,
+            vec![to_bytes(&ContractEvent::TransferAttempted(
+                TransferAttemptedEvent {
+                    token_id: TOKEN_0,
+                    sender: ADDRESS_0,
+                }
+            ))]
+        );
     }
 }
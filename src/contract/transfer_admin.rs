@@ -0,0 +1,61 @@
+use concordium_std::*;
+
+use crate::{
+    state::State,
+    types::{ContractError, ContractResult},
+};
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "transferAdmin",
+    parameter = "AccountAddress",
+    error = "ContractError",
+    mutable
+)]
+/// Transfers the admin role to another account.
+/// - This function fails if the sender is not the admin of the contract.
+pub fn transfer_admin<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    // Check that the sender is the admin of the contract.
+    ensure!(
+        host.state().is_contract_admin(&ctx.sender(), ctx.owner()),
+        ContractError::Unauthorized
+    );
+
+    let admin: AccountAddress = ctx.parameter_cursor().get()?;
+    host.state_mut().set_contract_admin(admin);
+    Ok(())
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const ADDRESS_0: Address = Address::Account(ACCOUNT_0);
+
+    #[concordium_test]
+    fn test_transfer_admin() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_1);
+        let parameter = to_bytes(&ACCOUNT_1);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.set_contract_admin(ACCOUNT_0);
+        let mut host = TestHost::new(state, state_builder);
+
+        let result = transfer_admin(&ctx, &mut host);
+        assert_eq!(result, Ok(()));
+        assert_eq!(host.state().contract_admin(ACCOUNT_1), ACCOUNT_1);
+
+        // The previous admin can no longer transfer the role.
+        let result = transfer_admin(&ctx, &mut host);
+        assert_eq!(result, Err(ContractError::Unauthorized));
+    }
+}
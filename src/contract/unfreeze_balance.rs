@@ -0,0 +1,96 @@
+use concordium_std::*;
+
+use crate::{
+    contract::freeze_balance::{update_frozen, FreezeBalanceParams},
+    state::State,
+    types::{ContractError, ContractResult},
+};
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "unfreezeBalance",
+    parameter = "FreezeBalanceParams",
+    error = "ContractError",
+    enable_logger,
+    mutable
+)]
+/// Unfreezes a frozen balance, which is valid again until its expiry.
+/// - This function fails if the sender is not a freezer and the token has an issuer which is not the sender.
+/// - This function fails if the sender is not a freezer and the token has no issuer and the sender is neither the admin of the contract nor a minter.
+/// - This function fails if the token does not exist.
+/// - This function fails if the account does not have a balance of the token.
+pub fn unfreeze_balance<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    let params: FreezeBalanceParams = ctx.parameter_cursor().get()?;
+    update_frozen(ctx, host, logger, params, false)
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::types::{ContractEvent, ContractTokenId, DsidFreezeEvent};
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const ADDRESS_0: Address = Address::Account(ACCOUNT_0);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+
+    #[concordium_test]
+    fn test_unfreeze_balance() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        let now = Timestamp::from_timestamp_millis(100);
+        ctx.set_metadata_slot_time(now);
+        let params = FreezeBalanceParams {
+            token_id: TOKEN_0,
+            account: ACCOUNT_1,
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_1,
+                10.into(),
+                Timestamp::from_timestamp_millis(200),
+                None,
+            )
+            .expect("Failed to mint token");
+        state
+            .set_balance_frozen(TOKEN_0, ACCOUNT_1, true)
+            .expect("Failed to freeze balance");
+        let mut host = TestHost::new(state, state_builder);
+        let mut logger = TestLogger::init();
+        let result: ContractResult<()> = unfreeze_balance(&ctx, &mut host, &mut logger);
+        assert_eq!(result, Ok(()));
+
+        assert_eq!(
+            host.state().get_account_balance(TOKEN_0, ACCOUNT_1, now),
+            Ok(10.into())
+        );
+        assert_eq!(
+            logger.logs,
+            vec![to_bytes(&ContractEvent::DsidUnfreeze(DsidFreezeEvent {
+                token_id: TOKEN_0,
+                owner: Address::Account(ACCOUNT_1),
+                actor: ADDRESS_0,
+            }))]
+        );
+    }
+}
@@ -0,0 +1,98 @@
+use concordium_std::*;
+
+use crate::{
+    contract::pause_category::PauseCategoryParams,
+    state::State,
+    types::{ContractError, ContractResult},
+};
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "unpauseCategory",
+    parameter = "PauseCategoryParams",
+    return_value = "u32",
+    error = "ContractError",
+    mutable
+)]
+/// Resumes minting of every token of a category and returns the number of resumed tokens.
+/// - This function fails if the sender is not the admin of the contract.
+pub fn unpause_category<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<u32> {
+    // Check that the sender is the admin of the contract.
+    ensure!(
+        host.state().is_contract_admin(&ctx.sender(), ctx.owner()),
+        ContractError::Unauthorized
+    );
+
+    let params: PauseCategoryParams = ctx.parameter_cursor().get()?;
+    Ok(host.state_mut().set_category_paused(params.category, false))
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::types::{ContractTokenId, Role};
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+    const TOKEN_1: ContractTokenId = TokenIdU8(3);
+
+    fn host_with_paused_tokens() -> TestHost<State<TestStateApi>> {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        for (token_id, category) in [(TOKEN_0, 1), (TOKEN_1, 2)] {
+            state.add_token(
+                &mut state_builder,
+                token_id,
+                MetadataUrl {
+                    url: format!("https://example.com/{}", category),
+                    hash: None,
+                },
+            );
+            state
+                .set_token_category(token_id, category)
+                .expect("Failed to set category");
+            state
+                .set_token_paused(token_id, true)
+                .expect("Failed to pause token");
+        }
+        TestHost::new(state, state_builder)
+    }
+
+    fn unpause_category_with(
+        host: &mut TestHost<State<TestStateApi>>,
+        sender: AccountAddress,
+    ) -> ContractResult<u32> {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(sender));
+        ctx.set_owner(ACCOUNT_0);
+        let parameter = to_bytes(&PauseCategoryParams { category: 1 });
+        ctx.set_parameter(&parameter);
+        unpause_category(&ctx, host)
+    }
+
+    #[concordium_test]
+    fn test_unpause_category() {
+        let mut host = host_with_paused_tokens();
+        let result = unpause_category_with(&mut host, ACCOUNT_0);
+        assert_eq!(result, Ok(1));
+        assert_eq!(host.state().is_token_paused(&TOKEN_0), Ok(false));
+        assert_eq!(host.state().is_token_paused(&TOKEN_1), Ok(true));
+    }
+
+    #[concordium_test]
+    fn test_unpause_category_pauser() {
+        // Pausers may pause a category but not resume it.
+        let mut host = host_with_paused_tokens();
+        host.state_mut()
+            .update_role(Role::Pauser, Address::Account(ACCOUNT_1), true);
+        let result = unpause_category_with(&mut host, ACCOUNT_1);
+        assert_eq!(result, Err(ContractError::Unauthorized));
+        assert_eq!(host.state().is_token_paused(&TOKEN_0), Ok(true));
+    }
+}
@@ -0,0 +1,95 @@
+use concordium_std::*;
+
+use crate::{
+    contract::pause_token::PauseTokensParams,
+    state::State,
+    types::{ContractError, ContractResult},
+};
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "unpauseToken",
+    parameter = "PauseTokensParams",
+    error = "ContractError",
+    mutable
+)]
+/// Resumes minting of paused tokens.
+/// - This function fails if the token does not exist.
+/// - This function fails if the sender is not the admin of the contract.
+pub fn unpause_token<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    // Check that the sender is the admin of the contract.
+    ensure!(
+        host.state().is_contract_admin(&ctx.sender(), ctx.owner()),
+        ContractError::Unauthorized
+    );
+
+    let params: PauseTokensParams = ctx.parameter_cursor().get()?;
+    let state = host.state_mut();
+    state.ensure_batch_size(|limits| limits.admin, params.tokens.len())?;
+    for token_id in params.tokens {
+        state.set_token_paused(token_id, false)?;
+    }
+
+    Ok(())
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::types::ContractTokenId;
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ADDRESS_0: Address = Address::Account(ACCOUNT_0);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+
+    #[concordium_test]
+    fn test_unpause_token() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        let params = PauseTokensParams {
+            tokens: vec![TOKEN_0],
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        state
+            .set_token_paused(TOKEN_0, true)
+            .expect("Failed to pause token");
+        let mut host = TestHost::new(state, state_builder);
+        let result: ContractResult<()> = unpause_token(&ctx, &mut host);
+        assert_eq!(result, Ok(()));
+        assert_eq!(host.state().is_token_paused(&TOKEN_0), Ok(false));
+    }
+
+    #[concordium_test]
+    fn test_unpause_token_invalid_token_id() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        let params = PauseTokensParams {
+            tokens: vec![TOKEN_0],
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let result: ContractResult<()> = unpause_token(&ctx, &mut host);
+        assert_eq!(result, Err(ContractError::InvalidTokenId));
+    }
+}
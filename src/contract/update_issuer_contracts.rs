@@ -0,0 +1,99 @@
+use concordium_std::*;
+
+use crate::{
+    state::State,
+    types::{ContractError, ContractResult},
+};
+
+#[derive(Serial, Deserial, SchemaType)]
+pub enum IssuerContractUpdate {
+    /// Allows the contract to mint using `mintFromContract`.
+    Add,
+    /// Removes the contract from the issuer contracts.
+    Remove,
+}
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct UpdateIssuerContractParams {
+    pub update: IssuerContractUpdate,
+    pub contract: ContractAddress,
+}
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct UpdateIssuerContractsParams {
+    pub updates: Vec<UpdateIssuerContractParams>,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "updateIssuerContracts",
+    parameter = "UpdateIssuerContractsParams",
+    error = "ContractError",
+    mutable
+)]
+/// Adds or removes contracts which may mint using `mintFromContract`, e.g. a proof verifier.
+/// - This function fails if the sender is not the admin of the contract.
+pub fn update_issuer_contracts<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    // Check that the sender is the admin of the contract.
+    ensure!(
+        host.state().is_contract_admin(&ctx.sender(), ctx.owner()),
+        ContractError::Unauthorized
+    );
+
+    let params: UpdateIssuerContractsParams = ctx.parameter_cursor().get()?;
+    let state = host.state_mut();
+    state.ensure_batch_size(|limits| limits.admin, params.updates.len())?;
+    for param in params.updates {
+        update_issuer_contract(state, param);
+    }
+
+    Ok(())
+}
+
+/// Adds or removes a single issuer contract.
+/// - This function does not check the sender.
+pub(crate) fn update_issuer_contract<S: HasStateApi>(
+    state: &mut State<S>,
+    param: UpdateIssuerContractParams,
+) {
+    match param.update {
+        IssuerContractUpdate::Add => state.add_issuer_contract(param.contract),
+        IssuerContractUpdate::Remove => state.remove_issuer_contract(&param.contract),
+    }
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ADDRESS_0: Address = Address::Account(ACCOUNT_0);
+    const CONTRACT_0: ContractAddress = ContractAddress {
+        index: 2,
+        subindex: 0,
+    };
+
+    #[concordium_test]
+    fn test_update_issuer_contracts_not_owner() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(AccountAddress([1u8; 32]));
+        let params = UpdateIssuerContractsParams {
+            updates: vec![UpdateIssuerContractParams {
+                update: IssuerContractUpdate::Add,
+                contract: CONTRACT_0,
+            }],
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let result: ContractResult<()> = update_issuer_contracts(&ctx, &mut host);
+        assert_eq!(result, Err(ContractError::Unauthorized));
+    }
+}
@@ -0,0 +1,164 @@
+use concordium_std::*;
+
+use crate::{
+    contract::schedule_action::ensure_scheduled,
+    state::State,
+    types::{ContractError, ContractResult},
+};
+
+#[derive(Serial, Deserial, SchemaType)]
+pub enum MinterUpdate {
+    /// Grants the minter role to the address.
+    Add,
+    /// Revokes the minter role from the address.
+    Remove,
+}
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct UpdateMinterParams {
+    pub update: MinterUpdate,
+    pub address: Address,
+}
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct UpdateMintersParams {
+    pub updates: Vec<UpdateMinterParams>,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "updateMinters",
+    parameter = "UpdateMintersParams",
+    error = "ContractError",
+    crypto_primitives,
+    mutable
+)]
+/// Grants or revokes the minter role, which allows minting tokens without an issuer.
+/// - This function fails if the sender is not the admin of the contract.
+/// - This function fails if a timelock delay is set, the updates revoke a minter role and the invocation has not been scheduled or the delay has not elapsed.
+pub fn update_minters<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    crypto_primitives: &impl HasCryptoPrimitives,
+) -> ContractResult<()> {
+    // Check that the sender is the admin of the contract.
+    ensure!(
+        host.state().is_contract_admin(&ctx.sender(), ctx.owner()),
+        ContractError::Unauthorized
+    );
+
+    let params: UpdateMintersParams = ctx.parameter_cursor().get()?;
+    let state = host.state_mut();
+    state.ensure_batch_size(|limits| limits.admin, params.updates.len())?;
+    // Revoking a minter role takes effect only after the timelock, so minters get notice.
+    if params
+        .updates
+        .iter()
+        .any(|param| matches!(param.update, MinterUpdate::Remove))
+    {
+        ensure_scheduled(ctx, state, crypto_primitives, "updateMinters")?;
+    }
+    for param in params.updates {
+        update_minter(state, param);
+    }
+
+    Ok(())
+}
+
+/// Grants or revokes the minter role of a single address.
+/// - This function does not check the sender.
+pub(crate) fn update_minter<S: HasStateApi>(state: &mut State<S>, param: UpdateMinterParams) {
+    match param.update {
+        MinterUpdate::Add => state.update_minter(param.address, true),
+        MinterUpdate::Remove => state.update_minter(param.address, false),
+    }
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::errors::CustomError;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ADDRESS_0: Address = Address::Account(ACCOUNT_0);
+    const ADDRESS_1: Address = Address::Account(AccountAddress([1u8; 32]));
+    const ACTION_0: HashSha2256 = HashSha2256([1u8; 32]);
+
+    #[concordium_test]
+    fn test_update_minters() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        let params = UpdateMintersParams {
+            updates: vec![UpdateMinterParams {
+                update: MinterUpdate::Add,
+                address: ADDRESS_1,
+            }],
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+
+        let result = update_minters(&ctx, &mut host, &TestCryptoPrimitives::new());
+        assert_eq!(result, Ok(()));
+        assert!(host.state().is_minter(&ADDRESS_1));
+        assert_eq!(host.state().minters(), vec![ADDRESS_1]);
+    }
+
+    #[concordium_test]
+    fn test_update_minters_not_admin() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_1);
+        ctx.set_owner(ACCOUNT_0);
+        let params = UpdateMintersParams { updates: vec![] };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+
+        let result = update_minters(&ctx, &mut host, &TestCryptoPrimitives::new());
+        assert_eq!(result, Err(ContractError::Unauthorized));
+    }
+
+    #[concordium_test]
+    fn test_update_minters_remove_scheduled() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(10));
+        let params = UpdateMintersParams {
+            updates: vec![UpdateMinterParams {
+                update: MinterUpdate::Remove,
+                address: ADDRESS_1,
+            }],
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.update_minter(ADDRESS_1, true);
+        state.set_timelock_delay(Duration::from_millis(100));
+        let mut host = TestHost::new(state, state_builder);
+        let crypto_primitives = TestCryptoPrimitives::new();
+        crypto_primitives.setup_hash_sha2_256_mock(|_| ACTION_0);
+
+        // Revoking a minter role has to be scheduled.
+        let result = update_minters(&ctx, &mut host, &crypto_primitives);
+        assert_eq!(
+            result,
+            Err(ContractError::Custom(CustomError::ActionNotScheduled))
+        );
+        assert!(host.state().is_minter(&ADDRESS_1));
+
+        host.state_mut()
+            .schedule_action(ACTION_0, Timestamp::from_timestamp_millis(10));
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(110));
+        let result = update_minters(&ctx, &mut host, &crypto_primitives);
+        assert_eq!(result, Ok(()));
+        assert!(!host.state().is_minter(&ADDRESS_1));
+    }
+}
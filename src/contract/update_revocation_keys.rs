@@ -0,0 +1,128 @@
+use concordium_std::*;
+
+use crate::{
+    state::State,
+    types::{ContractError, ContractResult},
+};
+
+#[derive(Serial, Deserial, SchemaType)]
+pub enum RevocationKeyUpdate {
+    /// Registers the key as a revocation authority.
+    Add,
+    /// Removes the key from the revocation authorities.
+    Remove,
+}
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct UpdateRevocationKeyParams {
+    pub update: RevocationKeyUpdate,
+    pub key: PublicKeyEd25519,
+}
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct UpdateRevocationKeysParams {
+    pub updates: Vec<UpdateRevocationKeyParams>,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "updateRevocationKeys",
+    parameter = "UpdateRevocationKeysParams",
+    error = "ContractError",
+    mutable
+)]
+/// Adds or removes public keys of external revocation authorities.
+/// - This function fails if the sender is not the admin of the contract.
+pub fn update_revocation_keys<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    // Check that the sender is the admin of the contract.
+    ensure!(
+        host.state().is_contract_admin(&ctx.sender(), ctx.owner()),
+        ContractError::Unauthorized
+    );
+
+    let params: UpdateRevocationKeysParams = ctx.parameter_cursor().get()?;
+    let state = host.state_mut();
+    state.ensure_batch_size(|limits| limits.admin, params.updates.len())?;
+    for param in params.updates {
+        update_revocation_key(state, param);
+    }
+
+    Ok(())
+}
+
+/// Adds or removes a single revocation key.
+/// - This function does not check the sender.
+pub(crate) fn update_revocation_key<S: HasStateApi>(
+    state: &mut State<S>,
+    param: UpdateRevocationKeyParams,
+) {
+    match param.update {
+        RevocationKeyUpdate::Add => state.add_revocation_key(param.key),
+        RevocationKeyUpdate::Remove => state.remove_revocation_key(&param.key),
+    }
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ADDRESS_0: Address = Address::Account(ACCOUNT_0);
+    const KEY_0: PublicKeyEd25519 = PublicKeyEd25519([1u8; 32]);
+    const KEY_1: PublicKeyEd25519 = PublicKeyEd25519([2u8; 32]);
+
+    #[concordium_test]
+    fn test_update_revocation_keys() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        let params = UpdateRevocationKeysParams {
+            updates: vec![
+                UpdateRevocationKeyParams {
+                    update: RevocationKeyUpdate::Add,
+                    key: KEY_0,
+                },
+                UpdateRevocationKeyParams {
+                    update: RevocationKeyUpdate::Add,
+                    key: KEY_1,
+                },
+                UpdateRevocationKeyParams {
+                    update: RevocationKeyUpdate::Remove,
+                    key: KEY_0,
+                },
+            ],
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let result: ContractResult<()> = update_revocation_keys(&ctx, &mut host);
+        assert_eq!(result, Ok(()));
+        assert_eq!(host.state().revocation_keys(), vec![(KEY_1, 0)]);
+    }
+
+    #[concordium_test]
+    fn test_update_revocation_keys_not_owner() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(AccountAddress([1u8; 32]));
+        let params = UpdateRevocationKeysParams {
+            updates: vec![UpdateRevocationKeyParams {
+                update: RevocationKeyUpdate::Add,
+                key: KEY_0,
+            }],
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let result: ContractResult<()> = update_revocation_keys(&ctx, &mut host);
+        assert_eq!(result, Err(ContractError::Unauthorized));
+    }
+}
@@ -0,0 +1,124 @@
+use concordium_std::*;
+
+use crate::{
+    state::State,
+    types::{ContractError, ContractResult, Role},
+};
+
+#[derive(Serial, Deserial, SchemaType)]
+pub enum RoleUpdate {
+    /// Grants the role to the address.
+    Grant,
+    /// Revokes the role from the address.
+    Revoke,
+}
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct UpdateRoleParams {
+    pub update: RoleUpdate,
+    pub role: Role,
+    pub address: Address,
+}
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct UpdateRolesParams {
+    pub updates: Vec<UpdateRoleParams>,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "updateRoles",
+    parameter = "UpdateRolesParams",
+    error = "ContractError",
+    mutable
+)]
+/// Grants or revokes the pauser, freezer, fee manager and verifier roles.
+/// None of the roles allows minting tokens.
+/// - This function fails if the sender is not the admin of the contract.
+pub fn update_roles<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    // Check that the sender is the admin of the contract.
+    ensure!(
+        host.state().is_contract_admin(&ctx.sender(), ctx.owner()),
+        ContractError::Unauthorized
+    );
+
+    let params: UpdateRolesParams = ctx.parameter_cursor().get()?;
+    let state = host.state_mut();
+    state.ensure_batch_size(|limits| limits.admin, params.updates.len())?;
+    for param in params.updates {
+        update_role(state, param);
+    }
+
+    Ok(())
+}
+
+/// Grants or revokes a single role of an address.
+/// - This function does not check the sender.
+pub(crate) fn update_role<S: HasStateApi>(state: &mut State<S>, param: UpdateRoleParams) {
+    match param.update {
+        RoleUpdate::Grant => state.update_role(param.role, param.address, true),
+        RoleUpdate::Revoke => state.update_role(param.role, param.address, false),
+    }
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ADDRESS_0: Address = Address::Account(ACCOUNT_0);
+    const ADDRESS_1: Address = Address::Account(AccountAddress([1u8; 32]));
+
+    fn update_roles_with(
+        host: &mut TestHost<State<TestStateApi>>,
+        sender: Address,
+        update: RoleUpdate,
+    ) -> ContractResult<()> {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(sender);
+        ctx.set_owner(ACCOUNT_0);
+        let params = UpdateRolesParams {
+            updates: vec![UpdateRoleParams {
+                update,
+                role: Role::Pauser,
+                address: ADDRESS_1,
+            }],
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        update_roles(&ctx, host)
+    }
+
+    #[concordium_test]
+    fn test_update_roles() {
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+
+        let result = update_roles_with(&mut host, ADDRESS_0, RoleUpdate::Grant);
+        assert_eq!(result, Ok(()));
+        assert!(host.state().has_role(Role::Pauser, &ADDRESS_1));
+        // Roles are granted separately.
+        assert!(!host.state().has_role(Role::Freezer, &ADDRESS_1));
+        assert!(!host.state().is_minter(&ADDRESS_1));
+        assert_eq!(host.state().roles(), vec![(Role::Pauser, ADDRESS_1)]);
+
+        let result = update_roles_with(&mut host, ADDRESS_0, RoleUpdate::Revoke);
+        assert_eq!(result, Ok(()));
+        assert!(!host.state().has_role(Role::Pauser, &ADDRESS_1));
+    }
+
+    #[concordium_test]
+    fn test_update_roles_not_admin() {
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+
+        let result = update_roles_with(&mut host, ADDRESS_1, RoleUpdate::Grant);
+        assert_eq!(result, Err(ContractError::Unauthorized));
+    }
+}
@@ -0,0 +1,263 @@
+use concordium_std::*;
+
+use crate::{
+    state::State,
+    types::{
+        ContractError, ContractEvent, ContractResult, ContractTokenAmount, ContractTokenId,
+        SubscriberNotifyFailedEvent,
+    },
+};
+
+/// Name of the entrypoint invoked on subscribers.
+const NOTIFY_ENTRYPOINT: &str = "dsidNotify";
+
+#[derive(Serial, Deserial, SchemaType)]
+pub enum SubscriberUpdate {
+    /// Adds the contract to the subscribers.
+    Add,
+    /// Removes the contract from the subscribers.
+    Remove,
+}
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct UpdateSubscriberParams {
+    pub update: SubscriberUpdate,
+    pub address: ContractAddress,
+}
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct UpdateSubscribersParams {
+    pub updates: Vec<UpdateSubscriberParams>,
+}
+
+/// A balance change sent to subscribers.
+#[derive(Serial, Deserial, SchemaType, Clone, Debug, PartialEq)]
+pub struct DsidNotification {
+    pub token_id: ContractTokenId,
+    pub account: AccountAddress,
+    /// The new amount of the balance.
+    pub amount: ContractTokenAmount,
+    /// The new expiry of the balance.
+    pub expiry: Timestamp,
+}
+
+/// Parameter of the `dsidNotify` entrypoint which subscribers have to implement.
+#[derive(Serial, Deserial, SchemaType, Debug, PartialEq)]
+pub struct DsidNotifyParams {
+    pub notifications: Vec<DsidNotification>,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "updateSubscribers",
+    parameter = "UpdateSubscribersParams",
+    error = "ContractError",
+    mutable
+)]
+/// Adds or removes contracts which are notified about balance changes.
+/// - This function fails if the sender is not the admin of the contract.
+/// - This function fails if adding a contract exceeds the maximum number of subscribers.
+pub fn update_subscribers<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    // Check that the sender is the admin of the contract.
+    ensure!(
+        host.state().is_contract_admin(&ctx.sender(), ctx.owner()),
+        ContractError::Unauthorized
+    );
+
+    let params: UpdateSubscribersParams = ctx.parameter_cursor().get()?;
+    let state = host.state_mut();
+    state.ensure_batch_size(|limits| limits.admin, params.updates.len())?;
+    for param in params.updates {
+        update_subscriber(state, param)?;
+    }
+
+    Ok(())
+}
+
+/// Adds or removes a single subscriber.
+/// - This function does not check the sender.
+/// - This function fails if adding the contract exceeds the maximum number of subscribers.
+pub(crate) fn update_subscriber<S: HasStateApi>(
+    state: &mut State<S>,
+    param: UpdateSubscriberParams,
+) -> ContractResult<()> {
+    match param.update {
+        SubscriberUpdate::Add => state.add_subscriber(param.address)?,
+        SubscriberUpdate::Remove => state.remove_subscriber(&param.address),
+    }
+    Ok(())
+}
+
+/// Invokes `dsidNotify` on every subscriber with the given balance changes.
+/// - This function does nothing if there are no notifications.
+/// - A subscriber which fails is skipped and logged with a `SubscriberNotifyFailed` event,
+///   so a single subscriber can not block balance changes.
+pub(crate) fn notify_subscribers<S: HasStateApi>(
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    notifications: Vec<DsidNotification>,
+) -> ContractResult<()> {
+    if notifications.is_empty() {
+        return Ok(());
+    }
+
+    let params = DsidNotifyParams { notifications };
+    let subscribers = host.state().subscribers();
+    for subscriber in subscribers {
+        let result = host.invoke_contract(
+            &subscriber,
+            &params,
+            EntrypointName::new_unchecked(NOTIFY_ENTRYPOINT),
+            Amount::zero(),
+        );
+        if result.is_err() {
+            logger.log(&ContractEvent::SubscriberNotifyFailed(
+                SubscriberNotifyFailedEvent { subscriber },
+            ))?;
+        }
+    }
+
+    Ok(())
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::{errors::CustomError, state::MAX_SUBSCRIBERS};
+    use concordium_cis2::TokenIdU8;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ADDRESS_0: Address = Address::Account(ACCOUNT_0);
+    const CONTRACT_0: ContractAddress = ContractAddress {
+        index: 1,
+        subindex: 0,
+    };
+    const CONTRACT_1: ContractAddress = ContractAddress {
+        index: 2,
+        subindex: 0,
+    };
+
+    #[concordium_test]
+    fn test_update_subscribers() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        let params = UpdateSubscribersParams {
+            updates: vec![
+                UpdateSubscriberParams {
+                    update: SubscriberUpdate::Add,
+                    address: CONTRACT_0,
+                },
+                UpdateSubscriberParams {
+                    update: SubscriberUpdate::Add,
+                    address: CONTRACT_1,
+                },
+                UpdateSubscriberParams {
+                    update: SubscriberUpdate::Remove,
+                    address: CONTRACT_0,
+                },
+            ],
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let result: ContractResult<()> = update_subscribers(&ctx, &mut host);
+        assert_eq!(result, Ok(()));
+        assert_eq!(host.state().subscribers(), vec![CONTRACT_1]);
+    }
+
+    #[concordium_test]
+    fn test_update_subscribers_not_owner() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(AccountAddress([1u8; 32]));
+        let params = UpdateSubscribersParams {
+            updates: vec![UpdateSubscriberParams {
+                update: SubscriberUpdate::Add,
+                address: CONTRACT_0,
+            }],
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let result: ContractResult<()> = update_subscribers(&ctx, &mut host);
+        assert_eq!(result, Err(ContractError::Unauthorized));
+    }
+
+    #[concordium_test]
+    fn test_update_subscribers_limit() {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        for index in 0..MAX_SUBSCRIBERS as u64 {
+            state
+                .add_subscriber(ContractAddress { index, subindex: 0 })
+                .expect("Failed to add subscriber");
+        }
+        // Adding an existing subscriber again does not count towards the limit.
+        assert_eq!(state.add_subscriber(CONTRACT_0), Ok(()));
+
+        let result = update_subscriber(
+            &mut state,
+            UpdateSubscriberParams {
+                update: SubscriberUpdate::Add,
+                address: ContractAddress {
+                    index: MAX_SUBSCRIBERS as u64,
+                    subindex: 0,
+                },
+            },
+        );
+        assert_eq!(
+            result,
+            Err(ContractError::Custom(CustomError::TooManySubscribers))
+        );
+    }
+
+    #[concordium_test]
+    fn test_notify_subscribers_skips_failing() {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state
+            .add_subscriber(CONTRACT_0)
+            .expect("Failed to add subscriber");
+        state
+            .add_subscriber(CONTRACT_1)
+            .expect("Failed to add subscriber");
+        let mut host = TestHost::new(state, state_builder);
+        host.setup_mock_entrypoint(
+            CONTRACT_0,
+            OwnedEntrypointName::new_unchecked(NOTIFY_ENTRYPOINT.to_string()),
+            MockFn::returning_err::<()>(CallContractError::Trap),
+        );
+        host.setup_mock_entrypoint(
+            CONTRACT_1,
+            OwnedEntrypointName::new_unchecked(NOTIFY_ENTRYPOINT.to_string()),
+            MockFn::returning_ok(()),
+        );
+        let mut logger = TestLogger::init();
+
+        let notification = DsidNotification {
+            token_id: TokenIdU8(2),
+            account: ACCOUNT_0,
+            amount: ContractTokenAmount::from(1),
+            expiry: Timestamp::from_timestamp_millis(100),
+        };
+        let result = notify_subscribers(&mut host, &mut logger, vec![notification]);
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            logger.logs,
+            vec![to_bytes(&ContractEvent::SubscriberNotifyFailed(
+                SubscriberNotifyFailedEvent {
+                    subscriber: CONTRACT_0,
+                }
+            ))]
+        );
+    }
+}
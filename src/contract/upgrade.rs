@@ -0,0 +1,158 @@
+use concordium_std::*;
+
+use crate::{
+    contract::{approve_action::ensure_approved, schedule_action::ensure_scheduled},
+    errors::CustomError,
+    state::State,
+    types::{ContractError, ContractResult},
+};
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct UpgradeParams {
+    /// The new module of the contract.
+    pub module: ModuleReference,
+    /// Entrypoint of the new module which is invoked after the upgrade, usually `migrate`.
+    pub migrate: Option<(OwnedEntrypointName, OwnedParameter)>,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "upgrade",
+    parameter = "UpgradeParams",
+    error = "ContractError",
+    crypto_primitives,
+    mutable
+)]
+/// Upgrades the module of the contract and optionally invokes a migration entrypoint of the new module.
+/// - This function fails if the sender is not the admin of the contract.
+/// - This function fails if approvals are required and the invocation has not been approved by enough admins.
+/// - This function fails if a timelock delay is set and the invocation has not been scheduled or the delay has not elapsed.
+/// - This function fails if the module does not exist or does not contain the contract.
+/// - This function fails if the migration fails.
+pub fn upgrade<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    crypto_primitives: &impl HasCryptoPrimitives,
+) -> ContractResult<()> {
+    // Check that the sender is the admin of the contract.
+    ensure!(
+        host.state().is_contract_admin(&ctx.sender(), ctx.owner()),
+        ContractError::Unauthorized
+    );
+
+    let params: UpgradeParams = ctx.parameter_cursor().get()?;
+    ensure_approved(ctx, host.state_mut(), crypto_primitives, "upgrade")?;
+    ensure_scheduled(ctx, host.state_mut(), crypto_primitives, "upgrade")?;
+    host.upgrade(params.module)
+        .map_err(|_| ContractError::Custom(CustomError::UpgradeFailed))?;
+
+    // Migrate the state using the new module.
+    if let Some((entrypoint, parameter)) = params.migrate {
+        host.invoke_contract_raw(
+            &ctx.self_address(),
+            parameter.as_parameter(),
+            entrypoint.as_entrypoint_name(),
+            Amount::zero(),
+        )
+        .map_err(|_| ContractError::Custom(CustomError::MigrationFailed))?;
+    }
+
+    Ok(())
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ADDRESS_0: Address = Address::Account(ACCOUNT_0);
+    const MODULE_0: ModuleReference = ModuleReference::new([1u8; 32]);
+
+    #[concordium_test]
+    fn test_upgrade() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        let params = UpgradeParams {
+            module: MODULE_0,
+            migrate: None,
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        host.setup_mock_upgrade(MODULE_0, Ok(()));
+        let result: ContractResult<()> = upgrade(&ctx, &mut host, &TestCryptoPrimitives::new());
+        assert_eq!(result, Ok(()));
+    }
+
+    #[concordium_test]
+    fn test_upgrade_missing_module() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        let params = UpgradeParams {
+            module: MODULE_0,
+            migrate: None,
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        host.setup_mock_upgrade(MODULE_0, Err(UpgradeError::MissingModule));
+        let result: ContractResult<()> = upgrade(&ctx, &mut host, &TestCryptoPrimitives::new());
+        assert_eq!(
+            result,
+            Err(ContractError::Custom(CustomError::UpgradeFailed))
+        );
+    }
+
+    #[concordium_test]
+    fn test_upgrade_not_owner() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(AccountAddress([1u8; 32]));
+        let params = UpgradeParams {
+            module: MODULE_0,
+            migrate: None,
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let result: ContractResult<()> = upgrade(&ctx, &mut host, &TestCryptoPrimitives::new());
+        assert_eq!(result, Err(ContractError::Unauthorized));
+    }
+
+    #[concordium_test]
+    fn test_upgrade_timelocked() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(50));
+        let params = UpgradeParams {
+            module: MODULE_0,
+            migrate: None,
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.set_timelock_delay(Duration::from_millis(100));
+        state.schedule_action(HashSha2256([1u8; 32]), Timestamp::from_timestamp_millis(0));
+        let mut host = TestHost::new(state, state_builder);
+        host.setup_mock_upgrade(MODULE_0, Ok(()));
+        let crypto_primitives = TestCryptoPrimitives::new();
+        crypto_primitives.setup_hash_sha2_256_mock(|_| HashSha2256([1u8; 32]));
+
+        let result: ContractResult<()> = upgrade(&ctx, &mut host, &crypto_primitives);
+        assert_eq!(
+            result,
+            Err(ContractError::Custom(CustomError::TimelockNotElapsed))
+        );
+    }
+}
@@ -0,0 +1,160 @@
+use concordium_std::*;
+
+use crate::{
+    contract::balance_of::is_hidden_from_sender, errors::CustomError, state::State, types::*,
+};
+
+#[derive(Debug, Serialize, SchemaType)]
+pub struct ValidFromOfQueryResponse(#[concordium(size_length = 2)] pub Vec<Option<Timestamp>>);
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "validFromOf",
+    parameter = "ContractExpiryOfQueryParams",
+    return_value = "ValidFromOfQueryResponse",
+    error = "ContractError"
+)]
+/// Gets the time from which each of the queried balances is valid.
+/// - None is returned if there is no balance or the balance is valid immediately.
+/// - None is returned for balances hidden by their holder, see `balanceOf`.
+/// - This function fails if any of the tokens does not exist.
+pub fn valid_from_of<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<ValidFromOfQueryResponse> {
+    // Parse the parameter.
+    let params: ContractExpiryOfQueryParams = ctx.parameter_cursor().get()?;
+    let state = host.state();
+    state.ensure_batch_size(|limits| limits.query, params.queries.len())?;
+    let response: Vec<Option<Timestamp>> = params
+        .queries
+        .iter()
+        .map(|q| match q.address {
+            Address::Account(address) => {
+                let valid_from = state.get_account_balance_valid_from(q.token_id, address)?;
+                if is_hidden_from_sender(ctx, state, &q.token_id, address)? {
+                    return Ok(None);
+                }
+                Ok(valid_from)
+            }
+            Address::Contract(_) => Err(ContractError::Custom(CustomError::AccountsOnly)),
+        })
+        .collect::<Result<Vec<Option<Timestamp>>, ContractError>>()?;
+
+    Ok(ValidFromOfQueryResponse(response))
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const ACCOUNT_2: AccountAddress = AccountAddress([2u8; 32]);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+
+    #[concordium_test]
+    fn test_valid_from_of() {
+        let mut ctx = TestReceiveContext::empty();
+        let params = ContractExpiryOfQueryParams {
+            queries: vec![
+                ContractExpiryOfQuery {
+                    address: Address::Account(ACCOUNT_0),
+                    token_id: TOKEN_0,
+                },
+                ContractExpiryOfQuery {
+                    address: Address::Account(ACCOUNT_1),
+                    token_id: TOKEN_0,
+                },
+            ],
+        };
+        let parameter = &to_bytes(&params);
+        ctx.set_parameter(parameter);
+
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_0,
+                10.into(),
+                Timestamp::from_timestamp_millis(300),
+                Some(Timestamp::from_timestamp_millis(100)),
+            )
+            .unwrap();
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_1,
+                20.into(),
+                Timestamp::from_timestamp_millis(300),
+                None,
+            )
+            .unwrap();
+
+        let host = TestHost::new(state, state_builder);
+        let result = valid_from_of(&ctx, &host).unwrap();
+        assert_eq!(
+            result.0,
+            vec![Some(Timestamp::from_timestamp_millis(100)), None]
+        );
+    }
+
+    #[concordium_test]
+    fn test_valid_from_of_hidden() {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_1,
+                10.into(),
+                Timestamp::from_timestamp_millis(200),
+                Some(Timestamp::from_timestamp_millis(100)),
+            )
+            .expect("Failed to mint token");
+        state
+            .set_balance_hidden(TOKEN_0, ACCOUNT_1, true)
+            .expect("Failed to hide balance");
+        let host = TestHost::new(state, state_builder);
+        let parameter = to_bytes(&ContractExpiryOfQueryParams {
+            queries: vec![ContractExpiryOfQuery {
+                address: Address::Account(ACCOUNT_1),
+                token_id: TOKEN_0,
+            }],
+        });
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(ACCOUNT_2));
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(150));
+        ctx.set_parameter(&parameter);
+
+        assert_eq!(
+            valid_from_of(&ctx, &host).map(|response| response.0),
+            Ok(vec![None])
+        );
+        ctx.set_sender(Address::Account(ACCOUNT_1));
+        assert_eq!(
+            valid_from_of(&ctx, &host).map(|response| response.0),
+            Ok(vec![Some(Timestamp::from_timestamp_millis(100))])
+        );
+    }
+}
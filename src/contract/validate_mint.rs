@@ -0,0 +1,168 @@
+use concordium_cis2::Cis2Error;
+use concordium_std::*;
+
+use crate::{
+    contract::mint::{check_mint_param, MintParams},
+    errors::CustomError,
+    state::State,
+    types::{ContractError, ContractResult, ContractTokenId},
+};
+
+#[derive(Debug, Serialize, SchemaType, PartialEq)]
+pub struct MintValidation {
+    pub token_id: ContractTokenId,
+    /// The error minting the token would fail with, None if it would succeed.
+    pub error: Option<ContractError>,
+}
+
+#[derive(Debug, Serialize, SchemaType, PartialEq)]
+pub struct ValidateMintResponse {
+    /// The error the whole batch would fail with, e.g. a batch limit or a used idempotency key.
+    pub batch_error: Option<ContractError>,
+    /// Result of each token, in the order in which `mint` processes them.
+    #[concordium(size_length = 2)]
+    pub tokens: Vec<MintValidation>,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "validateMint",
+    parameter = "MintParams",
+    return_value = "ValidateMintResponse",
+    error = "ContractError"
+)]
+/// Checks a `mint` invocation by the sender without changing the state.
+/// Returns the error of each token which would make the invocation fail.
+/// Notifying the subscribers is not checked.
+pub fn validate_mint<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<ValidateMintResponse> {
+    let params: MintParams = ctx.parameter_cursor().get()?;
+    let now = ctx.metadata().slot_time();
+    let state = host.state();
+    let is_minter =
+        state.is_contract_admin(&ctx.sender(), ctx.owner()) || state.is_minter(&ctx.sender());
+
+    let batch_error = if !is_minter && params.tokens.is_empty() {
+        Some(ContractError::Unauthorized)
+    } else if params
+        .idempotency_key
+        .map_or(false, |key| state.has_mint_request_key(&key))
+    {
+        Some(ContractError::Custom(CustomError::DuplicateMintRequest))
+    } else {
+        state
+            .ensure_batch_size(|limits| limits.mint, params.tokens.len())
+            .err()
+    };
+
+    let tokens = params
+        .tokens
+        .iter()
+        .map(|(token_id, mint_param)| {
+            let result = state
+                .can_issue(token_id, &ctx.sender(), is_minter)
+                .and_then(|can_issue| {
+                    ensure!(can_issue, ContractError::Unauthorized);
+                    ensure!(
+                        state.has_issuance_consent(token_id, params.owner, ctx.sender())?,
+                        Cis2Error::Custom(CustomError::ConsentRequired)
+                    );
+                    check_mint_param(state, token_id, mint_param, now)
+                });
+            MintValidation {
+                token_id: *token_id,
+                error: result.err(),
+            }
+        })
+        .collect();
+
+    Ok(ValidateMintResponse {
+        batch_error,
+        tokens,
+    })
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::contract::mint::MintParam;
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ADDRESS_0: Address = Address::Account(ACCOUNT_0);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+    const TOKEN_1: ContractTokenId = TokenIdU8(3);
+    const TOKEN_2: ContractTokenId = TokenIdU8(4);
+
+    #[concordium_test]
+    fn test_validate_mint() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(10));
+        let mint_param = |expiry| MintParam {
+            amount: 1.into(),
+            expiry: Some(Timestamp::from_timestamp_millis(expiry)),
+            valid_from: None,
+            metadata_url: None,
+        };
+        let mut tokens = collections::BTreeMap::new();
+        tokens.insert(TOKEN_0, mint_param(100));
+        tokens.insert(TOKEN_1, mint_param(5));
+        tokens.insert(TOKEN_2, mint_param(100));
+        let params = MintParams {
+            owner: ACCOUNT_0,
+            idempotency_key: None,
+            tokens,
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        for token_id in [TOKEN_0, TOKEN_1] {
+            state.add_token(
+                &mut state_builder,
+                token_id,
+                MetadataUrl {
+                    url: format!("https://example.com/{}", token_id.0),
+                    hash: None,
+                },
+            );
+        }
+        let host = TestHost::new(state, state_builder);
+
+        let result = validate_mint(&ctx, &host);
+        assert_eq!(
+            result,
+            Ok(ValidateMintResponse {
+                batch_error: None,
+                tokens: vec![
+                    MintValidation {
+                        token_id: TOKEN_0,
+                        error: None,
+                    },
+                    MintValidation {
+                        token_id: TOKEN_1,
+                        error: Some(ContractError::Custom(CustomError::TokenExpired)),
+                    },
+                    MintValidation {
+                        token_id: TOKEN_2,
+                        error: Some(ContractError::InvalidTokenId),
+                    },
+                ],
+            })
+        );
+        // Nothing has been minted.
+        assert_eq!(
+            host.state().get_account_balance(
+                TOKEN_0,
+                ACCOUNT_0,
+                Timestamp::from_timestamp_millis(10)
+            ),
+            Ok(0.into())
+        );
+    }
+}
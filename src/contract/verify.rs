@@ -0,0 +1,199 @@
+use concordium_std::*;
+
+use crate::{
+    contract::balance_of::is_hidden_from_sender,
+    state::State,
+    types::{ContractError, ContractResult, ContractTokenAmount, ContractTokenId},
+};
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct VerifyParams {
+    pub token_id: ContractTokenId,
+    pub account: AccountAddress,
+}
+
+#[derive(Debug, Serialize, SchemaType, PartialEq)]
+pub struct VerifyResponse {
+    /// The stored amount, regardless of expiry.
+    pub amount: ContractTokenAmount,
+    /// The expiry of the balance, None if the account does not have a balance.
+    pub expiry: Option<Timestamp>,
+    /// Whether the account has a balance > 0 which has not expired and whose dependency is valid.
+    pub valid: bool,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "verify",
+    parameter = "VerifyParams",
+    return_value = "VerifyResponse",
+    error = "ContractError"
+)]
+/// Gets the balance, expiry and validity of a single token for a single account.
+/// - Balances hidden by their holder are reported as no balance, see `balanceOf`.
+/// - This function fails if the token does not exist.
+pub fn verify<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<VerifyResponse> {
+    // Parse the parameter.
+    let params: VerifyParams = ctx.parameter_cursor().get()?;
+    let state = host.state();
+    let balance = state.get_account_balance_state(params.token_id, params.account)?;
+    let balance = if is_hidden_from_sender(ctx, state, &params.token_id, params.account)? {
+        None
+    } else {
+        balance
+    };
+    let now = ctx.metadata().slot_time();
+
+    let response = match balance {
+        Some(balance) => VerifyResponse {
+            amount: balance.amount,
+            expiry: Some(balance.expiry),
+            valid: balance.has_balance(now)
+                && state.has_valid_dependency(&params.token_id, params.account, now),
+        },
+        None => VerifyResponse {
+            amount: ContractTokenAmount::from(0),
+            expiry: None,
+            valid: false,
+        },
+    };
+    Ok(response)
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const ACCOUNT_2: AccountAddress = AccountAddress([2u8; 32]);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+    const TOKEN_1: ContractTokenId = TokenIdU8(3);
+
+    fn verify_with(
+        host: &TestHost<State<TestStateApi>>,
+        token_id: ContractTokenId,
+        account: AccountAddress,
+    ) -> ContractResult<VerifyResponse> {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(150));
+        let parameter = to_bytes(&VerifyParams { token_id, account });
+        ctx.set_parameter(&parameter);
+        verify(&ctx, host)
+    }
+
+    #[concordium_test]
+    fn test_verify() {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_0,
+                10.into(),
+                Timestamp::from_timestamp_millis(200),
+                None,
+            )
+            .expect("Failed to mint token");
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_1,
+                20.into(),
+                Timestamp::from_timestamp_millis(100),
+                None,
+            )
+            .expect("Failed to mint token");
+        let host = TestHost::new(state, state_builder);
+
+        assert_eq!(
+            verify_with(&host, TOKEN_0, ACCOUNT_0),
+            Ok(VerifyResponse {
+                amount: 10.into(),
+                expiry: Some(Timestamp::from_timestamp_millis(200)),
+                valid: true,
+            })
+        );
+        // The balance has expired.
+        assert_eq!(
+            verify_with(&host, TOKEN_0, ACCOUNT_1),
+            Ok(VerifyResponse {
+                amount: 20.into(),
+                expiry: Some(Timestamp::from_timestamp_millis(100)),
+                valid: false,
+            })
+        );
+        assert_eq!(
+            verify_with(&host, TOKEN_1, ACCOUNT_0),
+            Err(ContractError::InvalidTokenId)
+        );
+    }
+
+    #[concordium_test]
+    fn test_verify_hidden() {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_1,
+                10.into(),
+                Timestamp::from_timestamp_millis(200),
+                None,
+            )
+            .expect("Failed to mint token");
+        state
+            .set_balance_hidden(TOKEN_0, ACCOUNT_1, true)
+            .expect("Failed to hide balance");
+        let host = TestHost::new(state, state_builder);
+        let parameter = to_bytes(&VerifyParams {
+            token_id: TOKEN_0,
+            account: ACCOUNT_1,
+        });
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(ACCOUNT_2));
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(150));
+        ctx.set_parameter(&parameter);
+
+        // The hidden balance reads as no balance for others.
+        assert_eq!(
+            verify(&ctx, &host),
+            Ok(VerifyResponse {
+                amount: 0.into(),
+                expiry: None,
+                valid: false,
+            })
+        );
+        ctx.set_sender(Address::Account(ACCOUNT_1));
+        assert_eq!(
+            verify(&ctx, &host),
+            Ok(VerifyResponse {
+                amount: 10.into(),
+                expiry: Some(Timestamp::from_timestamp_millis(200)),
+                valid: true,
+            })
+        );
+    }
+}
@@ -0,0 +1,125 @@
+use concordium_std::*;
+
+use crate::{
+    state::State,
+    types::{ContractError, ContractResult, ContractTokenAmount, ContractTokenId},
+};
+
+/// Opening of a commitment, hashed with SHA-256 in its serialized form.
+#[derive(Serial, Deserial, SchemaType)]
+pub struct CommitmentOpening {
+    pub amount: ContractTokenAmount,
+    pub expiry: Timestamp,
+    /// Random value chosen by the issuer so the commitment can not be guessed.
+    pub salt: [u8; 32],
+}
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct VerifyCommitmentParams {
+    pub token_id: ContractTokenId,
+    pub account: AccountAddress,
+    pub opening: CommitmentOpening,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "verifyCommitment",
+    parameter = "VerifyCommitmentParams",
+    return_value = "bool",
+    error = "ContractError",
+    crypto_primitives
+)]
+/// Checks an opened commitment of the balance of an account.
+/// Returns `true` if the opening matches the stored commitment and its expiry has not passed.
+/// - This function fails if the token does not exist.
+pub fn verify_commitment<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+    crypto_primitives: &impl HasCryptoPrimitives,
+) -> ContractResult<bool> {
+    let params: VerifyCommitmentParams = ctx.parameter_cursor().get()?;
+    let commitment = host
+        .state()
+        .get_commitment(&params.token_id, &params.account)?;
+    let opened = crypto_primitives.hash_sha2_256(&to_bytes(&params.opening));
+
+    Ok(commitment == Some(opened) && params.opening.expiry > ctx.metadata().slot_time())
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+
+    /// Deterministic stand-in for SHA-256.
+    fn test_hash(data: &[u8]) -> HashSha2256 {
+        let mut hash = [0u8; 32];
+        for (i, byte) in data.iter().enumerate() {
+            hash[i % 32] = hash[i % 32].rotate_left(3) ^ byte.wrapping_add(i as u8);
+        }
+        HashSha2256(hash)
+    }
+
+    fn verify_with(
+        host: &TestHost<State<TestStateApi>>,
+        account: AccountAddress,
+        amount: u64,
+        now: u64,
+    ) -> ContractResult<bool> {
+        let crypto_primitives = TestCryptoPrimitives::new();
+        crypto_primitives.setup_hash_sha2_256_mock(test_hash);
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(now));
+        let params = VerifyCommitmentParams {
+            token_id: TOKEN_0,
+            account,
+            opening: CommitmentOpening {
+                amount: amount.into(),
+                expiry: Timestamp::from_timestamp_millis(100),
+                salt: [7u8; 32],
+            },
+        };
+        let parameter = to_bytes(&params);
+        ctx.set_parameter(&parameter);
+        verify_commitment(&ctx, host, &crypto_primitives)
+    }
+
+    #[concordium_test]
+    fn test_verify_commitment() {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        state
+            .set_token_private(TOKEN_0, true)
+            .expect("Failed to set token private");
+        let commitment = test_hash(&to_bytes(&CommitmentOpening {
+            amount: 10.into(),
+            expiry: Timestamp::from_timestamp_millis(100),
+            salt: [7u8; 32],
+        }));
+        state
+            .set_commitment(TOKEN_0, ACCOUNT_0, Some(commitment))
+            .expect("Failed to set commitment");
+        let host = TestHost::new(state, state_builder);
+
+        assert_eq!(verify_with(&host, ACCOUNT_0, 10, 10), Ok(true));
+        // A different amount does not open the commitment.
+        assert_eq!(verify_with(&host, ACCOUNT_0, 11, 10), Ok(false));
+        // Expired commitments are not valid.
+        assert_eq!(verify_with(&host, ACCOUNT_0, 10, 100), Ok(false));
+        // Accounts without a commitment are not valid.
+        assert_eq!(verify_with(&host, ACCOUNT_1, 10, 10), Ok(false));
+    }
+}
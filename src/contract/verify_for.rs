@@ -0,0 +1,176 @@
+use concordium_std::*;
+
+use crate::{
+    contract::balance_of::is_hidden_from_sender,
+    state::State,
+    types::{ContractError, ContractResult, ContractTokenId},
+};
+
+/// Parameter of the `verifyFor` entrypoint.
+/// This is part of the stable interface used by other contracts and must not change.
+#[derive(Serial, Deserial, SchemaType)]
+pub struct VerifyForParams {
+    pub token_id: ContractTokenId,
+    pub address: Address,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "verifyFor",
+    parameter = "VerifyForParams",
+    return_value = "bool",
+    error = "ContractError"
+)]
+/// Checks if an address holds a valid balance of a token.
+/// Intended to be invoked by other contracts which gate actions on DSID tokens.
+/// - Returns true if the address has a balance > 0 which has not expired and whose dependency is valid.
+/// - Returns false for contract addresses, since only accounts can hold tokens.
+/// - Returns false for balances hidden by their holder unless the sender is the holder, the admin
+///   or a verifier, see `setVisibility`.
+/// - This function fails if the token does not exist.
+pub fn verify_for<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<bool> {
+    // Parse the parameter.
+    let params: VerifyForParams = ctx.parameter_cursor().get()?;
+    let state = host.state();
+    match params.address {
+        Address::Account(account) => {
+            let balance = state.get_account_balance_state(params.token_id, account)?;
+            if is_hidden_from_sender(ctx, state, &params.token_id, account)? {
+                return Ok(false);
+            }
+            let now = ctx.metadata().slot_time();
+            Ok(balance.map_or(false, |balance| {
+                balance.has_balance(now)
+                    && state.has_valid_dependency(&params.token_id, account, now)
+            }))
+        }
+        Address::Contract(_) => {
+            ensure!(
+                state.has_token(params.token_id),
+                ContractError::InvalidTokenId
+            );
+            Ok(false)
+        }
+    }
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::types::Role;
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const ACCOUNT_2: AccountAddress = AccountAddress([2u8; 32]);
+    const CONTRACT_0: ContractAddress = ContractAddress {
+        index: 0,
+        subindex: 0,
+    };
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+    const TOKEN_1: ContractTokenId = TokenIdU8(3);
+
+    fn verify_for_with(
+        host: &TestHost<State<TestStateApi>>,
+        token_id: ContractTokenId,
+        address: Address,
+    ) -> ContractResult<bool> {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(150));
+        let parameter = to_bytes(&VerifyForParams { token_id, address });
+        ctx.set_parameter(&parameter);
+        verify_for(&ctx, host)
+    }
+
+    #[concordium_test]
+    fn test_verify_for() {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_0,
+                10.into(),
+                Timestamp::from_timestamp_millis(200),
+                None,
+            )
+            .expect("Failed to mint token");
+        let host = TestHost::new(state, state_builder);
+
+        assert_eq!(
+            verify_for_with(&host, TOKEN_0, Address::Account(ACCOUNT_0)),
+            Ok(true)
+        );
+        assert_eq!(
+            verify_for_with(&host, TOKEN_0, Address::Account(ACCOUNT_1)),
+            Ok(false)
+        );
+        assert_eq!(
+            verify_for_with(&host, TOKEN_0, Address::Contract(CONTRACT_0)),
+            Ok(false)
+        );
+        assert_eq!(
+            verify_for_with(&host, TOKEN_1, Address::Account(ACCOUNT_0)),
+            Err(ContractError::InvalidTokenId)
+        );
+    }
+
+    #[concordium_test]
+    fn test_verify_for_hidden() {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.add_token(
+            &mut state_builder,
+            TOKEN_0,
+            MetadataUrl {
+                url: "https://example.com".to_string(),
+                hash: None,
+            },
+        );
+        state
+            .mint(
+                TOKEN_0,
+                ACCOUNT_1,
+                10.into(),
+                Timestamp::from_timestamp_millis(200),
+                None,
+            )
+            .expect("Failed to mint token");
+        state
+            .set_balance_hidden(TOKEN_0, ACCOUNT_1, true)
+            .expect("Failed to hide balance");
+        let mut host = TestHost::new(state, state_builder);
+        let parameter = to_bytes(&VerifyForParams {
+            token_id: TOKEN_0,
+            address: Address::Account(ACCOUNT_1),
+        });
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(ACCOUNT_2));
+        ctx.set_owner(ACCOUNT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(150));
+        ctx.set_parameter(&parameter);
+
+        assert_eq!(verify_for(&ctx, &host), Ok(false));
+        ctx.set_sender(Address::Account(ACCOUNT_1));
+        assert_eq!(verify_for(&ctx, &host), Ok(true));
+
+        // Contracts gating on the token can be granted the verifier role.
+        ctx.set_sender(Address::Contract(CONTRACT_0));
+        assert_eq!(verify_for(&ctx, &host), Ok(false));
+        host.state_mut()
+            .update_role(Role::Verifier, Address::Contract(CONTRACT_0), true);
+        assert_eq!(verify_for(&ctx, &host), Ok(true));
+    }
+}
@@ -0,0 +1,120 @@
+use concordium_std::*;
+
+use crate::{
+    state::State,
+    types::{ContractConfig, ContractError, ContractResult, ContractTokenId, Role, TokenIdRange},
+};
+
+#[derive(Debug, Serialize, SchemaType, PartialEq)]
+pub struct ConfigView {
+    /// Account with the admin role, the only account allowed to perform admin operations.
+    pub admin: AccountAddress,
+    /// Addresses which may mint tokens without an issuer, see `updateMinters`.
+    pub minters: Vec<Address>,
+    /// Roles delegated by the admin, see `updateRoles`.
+    pub roles: Vec<(Role, Address)>,
+    /// Contracts which may mint using `mintFromContract` and their next nonce.
+    pub issuer_contracts: Vec<(ContractAddress, u64)>,
+    /// Admins which approve destructive admin operations, see `setAdmins`.
+    pub admins: Vec<AccountAddress>,
+    /// Number of admin approvals required for destructive admin operations.
+    pub approval_threshold: u8,
+    /// Delay between scheduling and executing timelocked operations.
+    pub timelock_delay: Duration,
+    /// Contract configuration including the batch limits, see `setConfig`.
+    pub config: ContractConfig,
+    /// Tokens whose minting is paused.
+    pub paused_tokens: Vec<ContractTokenId>,
+    /// Contracts notified about balance changes.
+    pub subscribers: Vec<ContractAddress>,
+    /// Token ids allocated to issuers.
+    pub id_ranges: Vec<(Address, TokenIdRange)>,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "viewConfig",
+    return_value = "ConfigView",
+    error = "ContractError"
+)]
+/// Gets the governance parameters of the contract.
+pub fn view_config<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<ConfigView> {
+    let state = host.state();
+    Ok(ConfigView {
+        admin: state.contract_admin(ctx.owner()),
+        minters: state.minters(),
+        roles: state.roles(),
+        issuer_contracts: state.issuer_contracts(),
+        admins: state.admins(),
+        approval_threshold: state.approval_threshold(),
+        timelock_delay: state.timelock_delay(),
+        config: state.config().clone(),
+        paused_tokens: state.paused_tokens(),
+        subscribers: state.subscribers(),
+        id_ranges: state.id_ranges(),
+    })
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use concordium_cis2::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const TOKEN_0: ContractTokenId = TokenIdU8(2);
+    const TOKEN_1: ContractTokenId = TokenIdU8(3);
+
+    #[concordium_test]
+    fn test_view_config() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_owner(ACCOUNT_0);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        for token_id in [TOKEN_0, TOKEN_1] {
+            state.add_token(
+                &mut state_builder,
+                token_id,
+                MetadataUrl {
+                    url: format!("https://example.com/{}", token_id.0),
+                    hash: None,
+                },
+            );
+        }
+        state
+            .set_token_paused(TOKEN_1, true)
+            .expect("Failed to pause token");
+        state
+            .set_admins(vec![ACCOUNT_1], 1)
+            .expect("Failed to set admins");
+        state.set_timelock_delay(Duration::from_millis(100));
+        let config = ContractConfig {
+            unique_metadata_urls: true,
+            ..Default::default()
+        };
+        state.set_config(config.clone());
+        let host = TestHost::new(state, state_builder);
+
+        let result = view_config(&ctx, &host);
+        assert_eq!(
+            result,
+            Ok(ConfigView {
+                admin: ACCOUNT_0,
+                minters: vec![],
+                roles: vec![],
+                issuer_contracts: vec![],
+                admins: vec![ACCOUNT_1],
+                approval_threshold: 1,
+                timelock_delay: Duration::from_millis(100),
+                config,
+                paused_tokens: vec![TOKEN_1],
+                subscribers: vec![],
+                id_ranges: vec![],
+            })
+        );
+    }
+}
@@ -0,0 +1,68 @@
+use concordium_std::*;
+
+use crate::{
+    state::State,
+    types::{ContractError, ContractResult, Snapshot, SnapshotId},
+};
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "viewSnapshot",
+    parameter = "SnapshotId",
+    return_value = "Snapshot",
+    error = "ContractError"
+)]
+/// Gets a snapshot recorded by `takeSnapshot`.
+/// - This function fails if the snapshot does not exist.
+pub fn view_snapshot<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<Snapshot> {
+    // Parse the parameter.
+    let snapshot_id: SnapshotId = ctx.parameter_cursor().get()?;
+    host.state().get_snapshot(snapshot_id)
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use crate::errors::CustomError;
+    use concordium_std::test_infrastructure::*;
+
+    #[concordium_test]
+    fn test_view_snapshot() {
+        let mut ctx = TestReceiveContext::empty();
+        let parameter = to_bytes(&0u32);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.take_snapshot("2023-Q1".to_string(), Timestamp::from_timestamp_millis(10));
+        let host = TestHost::new(state, state_builder);
+
+        let result = view_snapshot(&ctx, &host);
+        assert_eq!(
+            result,
+            Ok(Snapshot {
+                name: "2023-Q1".to_string(),
+                timestamp: Timestamp::from_timestamp_millis(10),
+                tokens: vec![],
+            })
+        );
+    }
+
+    #[concordium_test]
+    fn test_view_snapshot_not_found() {
+        let mut ctx = TestReceiveContext::empty();
+        let parameter = to_bytes(&1u32);
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let host = TestHost::new(state, state_builder);
+
+        let result = view_snapshot(&ctx, &host);
+        assert_eq!(
+            result,
+            Err(ContractError::Custom(CustomError::SnapshotNotFound))
+        );
+    }
+}
@@ -0,0 +1,68 @@
+use concordium_std::*;
+
+use crate::{
+    state::{State, STATE_VERSION},
+    types::{ContractError, ContractResult},
+};
+
+/// Standards implemented by the contract.
+const STANDARDS: [&str; 1] = ["CIS-2"];
+
+#[derive(Debug, Serialize, SchemaType, PartialEq)]
+pub struct VersionInfo {
+    /// Version of the crate the module was built from.
+    pub crate_version: String,
+    /// Version of the state layout written by the module.
+    pub state_version: u8,
+    /// Version of the stored state, equal to `state_version` once the state can be read by the module.
+    pub stored_state_version: u8,
+    /// Standards implemented by the contract.
+    pub standards: Vec<String>,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "viewVersion",
+    return_value = "VersionInfo",
+    error = "ContractError"
+)]
+/// Gets the version of the module and state and the implemented standards.
+pub fn view_version<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<VersionInfo> {
+    Ok(VersionInfo {
+        crate_version: env!("CARGO_PKG_VERSION").to_string(),
+        state_version: STATE_VERSION,
+        stored_state_version: host.state().version(),
+        standards: STANDARDS
+            .iter()
+            .map(|standard| standard.to_string())
+            .collect(),
+    })
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use concordium_std::test_infrastructure::*;
+
+    #[concordium_test]
+    fn test_view_version() {
+        let ctx = TestReceiveContext::empty();
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder);
+        let host = TestHost::new(state, state_builder);
+
+        let result = view_version(&ctx, &host);
+        assert_eq!(
+            result,
+            Ok(VersionInfo {
+                crate_version: env!("CARGO_PKG_VERSION").to_string(),
+                state_version: STATE_VERSION,
+                stored_state_version: STATE_VERSION,
+                standards: vec!["CIS-2".to_string()],
+            })
+        );
+    }
+}
@@ -0,0 +1,131 @@
+use concordium_std::*;
+
+use crate::{
+    errors::CustomError,
+    state::State,
+    types::{ContractError, ContractEvent, ContractResult, FeesWithdrawnEvent, Role},
+};
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct WithdrawFeesParams {
+    /// Account receiving the fees.
+    pub to: AccountAddress,
+    pub amount: Amount,
+}
+
+#[receive(
+    contract = "cis2_dsid",
+    name = "withdrawFees",
+    parameter = "WithdrawFeesParams",
+    error = "ContractError",
+    enable_logger,
+    mutable
+)]
+/// Transfers renewal fees in CCD collected by `renew` to an account and logs a `FeesWithdrawn` event.
+/// - This function fails if the sender is neither the admin of the contract nor a fee manager.
+/// - This function fails if the amount exceeds the renewal fees not withdrawn yet.
+/// - This function fails if the transfer to the account fails.
+pub fn withdraw_fees<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    let state = host.state_mut();
+    // Check that the sender is the admin of the contract or a fee manager.
+    ensure!(
+        state.is_contract_admin(&ctx.sender(), ctx.owner())
+            || state.has_role(Role::FeeManager, &ctx.sender()),
+        ContractError::Unauthorized
+    );
+
+    let params: WithdrawFeesParams = ctx.parameter_cursor().get()?;
+    state.withdraw_renewal_fees(params.amount)?;
+    host.invoke_transfer(&params.to, params.amount)
+        .map_err(|_| ContractError::Custom(CustomError::FeePaymentFailed))?;
+
+    logger.log(&ContractEvent::FeesWithdrawn(FeesWithdrawnEvent {
+        to: params.to,
+        amount: params.amount,
+    }))?;
+
+    Ok(())
+}
+
+#[concordium_cfg_test]
+mod tests {
+    use super::*;
+    use concordium_std::test_infrastructure::*;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const ACCOUNT_2: AccountAddress = AccountAddress([2u8; 32]);
+
+    fn host_with_fees() -> TestHost<State<TestStateApi>> {
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder);
+        state.credit_renewal_fee(Amount::from_micro_ccd(5));
+        let mut host = TestHost::new(state, state_builder);
+        host.set_self_balance(Amount::from_micro_ccd(5));
+        host
+    }
+
+    fn withdraw_with(
+        host: &mut TestHost<State<TestStateApi>>,
+        logger: &mut TestLogger,
+        sender: AccountAddress,
+        amount: u64,
+    ) -> ContractResult<()> {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(Address::Account(sender));
+        ctx.set_owner(ACCOUNT_0);
+        let parameter = to_bytes(&WithdrawFeesParams {
+            to: ACCOUNT_2,
+            amount: Amount::from_micro_ccd(amount),
+        });
+        ctx.set_parameter(&parameter);
+        withdraw_fees(&ctx, host, logger)
+    }
+
+    #[concordium_test]
+    fn test_withdraw_fees() {
+        let mut host = host_with_fees();
+        let mut logger = TestLogger::init();
+        let result = withdraw_with(&mut host, &mut logger, ACCOUNT_0, 3);
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            host.get_transfers(),
+            vec![(ACCOUNT_2, Amount::from_micro_ccd(3))]
+        );
+        assert_eq!(
+            host.state().collected_renewal_fees(),
+            Amount::from_micro_ccd(2)
+        );
+        assert_eq!(
+            logger.logs,
+            vec![to_bytes(&ContractEvent::FeesWithdrawn(
+                FeesWithdrawnEvent {
+                    to: ACCOUNT_2,
+                    amount: Amount::from_micro_ccd(3),
+                }
+            ))]
+        );
+
+        // Only the fees not withdrawn yet can be withdrawn.
+        let result = withdraw_with(&mut host, &mut logger, ACCOUNT_0, 3);
+        assert_eq!(result, Err(ContractError::InsufficientFunds));
+    }
+
+    #[concordium_test]
+    fn test_withdraw_fees_by_fee_manager() {
+        let mut host = host_with_fees();
+        let mut logger = TestLogger::init();
+        let result = withdraw_with(&mut host, &mut logger, ACCOUNT_1, 5);
+        assert_eq!(result, Err(ContractError::Unauthorized));
+
+        host.state_mut()
+            .update_role(Role::FeeManager, Address::Account(ACCOUNT_1), true);
+        let result = withdraw_with(&mut host, &mut logger, ACCOUNT_1, 5);
+        assert_eq!(result, Ok(()));
+        assert_eq!(host.state().collected_renewal_fees(), Amount::zero());
+    }
+}
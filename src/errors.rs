@@ -1,19 +1,324 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 use concordium_std::*;
+use core::num::NonZeroI32;
 
-#[derive(SchemaType, Serial, Reject, Debug, PartialEq)]
+/// Errors of the contract, rejected with the explicit code of each variant.
+/// - Codes never change: new variants take the next unused code, regardless of their position.
+/// - Removed variants leave their code unused.
+#[derive(SchemaType, Serial, Deserial, Debug, PartialEq, Clone)]
 pub enum CustomError {
     /// Failed parsing the parameter.
-    ParseParams,
+    ParseParams = -1,
     /// Failed logging: Log is full.
-    LogFull,
+    LogFull = -2,
     /// Failed logging: Log is malformed.
-    LogMalformed,
-    AccountsOnly,
+    LogMalformed = -3,
+    AccountsOnly = -4,
     /// The token expiry is in the past.
-    TokenExpired,
+    TokenExpired = -5,
     /// The token has valid balances.
-    TokenHasValidBalances,
+    TokenHasValidBalances = -6,
+    /// Another token already uses the same metadata url.
+    DuplicateMetadataUrl = -7,
+    /// The metadata url is required to have a hash.
+    MetadataHashRequired = -8,
+    /// The snapshot does not exist.
+    SnapshotNotFound = -9,
+    /// Invoking `dsidNotify` on a subscriber failed.
+    /// No longer thrown, failing subscribers are skipped and logged, the code is kept reserved.
+    NotifySubscriberFailed = -10,
+    /// The account does not have a balance of the token.
+    CredentialNotFound = -11,
+    /// The public key is not a registered revocation key.
+    UnknownRevocationKey = -12,
+    /// The signature does not match the message and public key.
+    WrongSignature = -13,
+    /// The nonce of the message does not match the nonce of the public key.
+    NonceMismatch = -14,
+    /// The message is intended for another contract.
+    WrongContract = -15,
+    /// The message has expired.
+    MessageExpired = -16,
+    /// A mint with the same idempotency key has already been executed.
+    DuplicateMintRequest = -17,
+    /// Minting of the token is paused.
+    TokenPaused = -18,
+    /// No expiry is given and the token has no default validity.
+    ExpiryRequired = -19,
+    /// The number of items exceeds the configured batch limit.
+    BatchTooLarge = -20,
+    /// Upgrading the module of the contract failed.
+    UpgradeFailed = -21,
+    /// Invoking the migration entrypoint after an upgrade failed.
+    MigrationFailed = -22,
+    /// The state has been written by a newer version of the contract.
+    UnsupportedStateVersion = -23,
+    /// The token has been deprecated and can no longer be minted.
+    TokenDeprecated = -24,
+    /// The approval threshold is larger than the number of admins.
+    InvalidThreshold = -25,
+    /// The action has not been approved by enough admins.
+    ActionNotApproved = -26,
+    /// The approvals of the action have expired.
+    ActionExpired = -27,
+    /// The action has not been scheduled.
+    ActionNotScheduled = -28,
+    /// The timelock delay of the scheduled action has not elapsed.
+    TimelockNotElapsed = -29,
+    /// The holder has not consented to be issued the token by the sender.
+    ConsentRequired = -30,
+    /// The Merkle root has not been published for the token.
+    UnknownIssuanceRoot = -31,
+    /// The Merkle proof does not lead to the root.
+    InvalidMerkleProof = -32,
+    /// The balance has already been claimed.
+    AlreadyClaimed = -33,
+    /// The token only stores commitments of balances, balances can not be minted.
+    PrivateToken = -34,
+    /// The token does not store commitments of balances.
+    PublicToken = -35,
+    /// The first token id of the range is larger than the last or the range overlaps the range of another issuer.
+    InvalidIdRange = -36,
+    /// The token id is allocated to another issuer or outside the range allocated to the issuer.
+    TokenIdNotAllocated = -37,
+    /// The key is not an issuer key of the token or was not valid when the voucher was issued.
+    UnknownIssuerKey = -38,
+    /// The voucher is no longer valid.
+    VoucherExpired = -39,
+    /// The voucher has already been claimed.
+    VoucherAlreadyUsed = -40,
+    /// The account has called a self-service entrypoint too recently.
+    TooManyRequests = -41,
+    /// The admin of the contract has already been set.
+    AdminAlreadySet = -42,
+    /// The expiry is further in the future than the maximum validity allows.
+    ValidityTooLong = -43,
+    /// The identity registry is not configured, failed or returned an invalid response.
+    IdentityRegistryFailed = -44,
+    /// The identity registry did not confirm the account as a unique person.
+    NotUniquePerson = -45,
+    /// The account has no pending mint of the token.
+    PendingMintNotFound = -46,
+    /// The activation deadline of the pending mint has passed.
+    ActivationExpired = -47,
+    /// Minting has been paused because the mint volume limit was exceeded.
+    MintingPaused = -48,
+    /// The token requires an allowlist and the account is not on it.
+    NotAllowlisted = -49,
+    /// The amount is below the minimum amount of the token, which is 1 unless changed.
+    ZeroAmount = -50,
+    /// A sibling contract of the federation failed or returned an invalid response.
+    FederationFailed = -51,
+    /// The bundle does not exist.
+    UnknownBundle = -52,
+    /// The token would depend on itself.
+    InvalidDependency = -53,
+    /// The amount is not allowed by the amount rule of the token.
+    AmountNotAllowed = -54,
+    /// No revocation of the balance has been scheduled.
+    RevocationNotScheduled = -55,
+    /// The dispute window of the scheduled revocation has not ended yet.
+    RevocationNotDue = -56,
+    /// The expiry is not after the start of the validity or shortens the unexpired balance.
+    InvalidExpiry = -57,
+    /// Tokens have to be proposed and approved by a second admin instead of being added directly.
+    ProposalRequired = -58,
+    /// The token has not been proposed.
+    ProposalNotFound = -59,
+    /// The expiry is after the hard expiry of the balance, set by the maximum lifetime of the token.
+    LifetimeExceeded = -60,
+    /// No fee token is configured.
+    FeeNotConfigured = -61,
+    /// Transferring the fee on the fee token contract, or returning CCD sent above the fee, failed.
+    FeePaymentFailed = -62,
+    /// The CCD sent with the call is less than the renewal fee.
+    InsufficientFee = -63,
+    /// The signer of a permit message has not set a permit key.
+    UnknownPermitKey = -64,
+    /// The entrypoint can not be invoked through `permit`.
+    EntrypointNotPermitted = -65,
+    /// Adding the subscriber would exceed the maximum number of subscribers.
+    TooManySubscribers = -66,
+    /// The limit of a paginated query is 0, so the query would never make progress.
+    InvalidLimit = -67,
+}
+
+/// Category of a `CustomError`.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq, Clone, Copy)]
+pub enum ErrorCategory {
+    /// The parameter is invalid or inconsistent.
+    Validation,
+    /// The sender or the holder is not permitted to perform the call.
+    Authorization,
+    /// The call is not possible in the current state of the contract.
+    State,
+    /// A signature, key, voucher or proof is invalid.
+    Crypto,
+    /// Logging, or invoking another contract or the host, failed.
+    External,
+}
+
+impl CustomError {
+    /// All variants, in the order of their reject codes.
+    pub fn variants() -> Vec<CustomError> {
+        vec![
+            CustomError::ParseParams,
+            CustomError::LogFull,
+            CustomError::LogMalformed,
+            CustomError::AccountsOnly,
+            CustomError::TokenExpired,
+            CustomError::TokenHasValidBalances,
+            CustomError::DuplicateMetadataUrl,
+            CustomError::MetadataHashRequired,
+            CustomError::SnapshotNotFound,
+            CustomError::NotifySubscriberFailed,
+            CustomError::CredentialNotFound,
+            CustomError::UnknownRevocationKey,
+            CustomError::WrongSignature,
+            CustomError::NonceMismatch,
+            CustomError::WrongContract,
+            CustomError::MessageExpired,
+            CustomError::DuplicateMintRequest,
+            CustomError::TokenPaused,
+            CustomError::ExpiryRequired,
+            CustomError::BatchTooLarge,
+            CustomError::UpgradeFailed,
+            CustomError::MigrationFailed,
+            CustomError::UnsupportedStateVersion,
+            CustomError::TokenDeprecated,
+            CustomError::InvalidThreshold,
+            CustomError::ActionNotApproved,
+            CustomError::ActionExpired,
+            CustomError::ActionNotScheduled,
+            CustomError::TimelockNotElapsed,
+            CustomError::ConsentRequired,
+            CustomError::UnknownIssuanceRoot,
+            CustomError::InvalidMerkleProof,
+            CustomError::AlreadyClaimed,
+            CustomError::PrivateToken,
+            CustomError::PublicToken,
+            CustomError::InvalidIdRange,
+            CustomError::TokenIdNotAllocated,
+            CustomError::UnknownIssuerKey,
+            CustomError::VoucherExpired,
+            CustomError::VoucherAlreadyUsed,
+            CustomError::TooManyRequests,
+            CustomError::AdminAlreadySet,
+            CustomError::ValidityTooLong,
+            CustomError::IdentityRegistryFailed,
+            CustomError::NotUniquePerson,
+            CustomError::PendingMintNotFound,
+            CustomError::ActivationExpired,
+            CustomError::MintingPaused,
+            CustomError::NotAllowlisted,
+            CustomError::ZeroAmount,
+            CustomError::FederationFailed,
+            CustomError::UnknownBundle,
+            CustomError::InvalidDependency,
+            CustomError::AmountNotAllowed,
+            CustomError::RevocationNotScheduled,
+            CustomError::RevocationNotDue,
+            CustomError::InvalidExpiry,
+            CustomError::ProposalRequired,
+            CustomError::ProposalNotFound,
+            CustomError::LifetimeExceeded,
+            CustomError::FeeNotConfigured,
+            CustomError::FeePaymentFailed,
+            CustomError::InsufficientFee,
+            CustomError::UnknownPermitKey,
+            CustomError::EntrypointNotPermitted,
+            CustomError::TooManySubscribers,
+            CustomError::InvalidLimit,
+        ]
+    }
+
+    /// The reject code the contract fails with for this error.
+    pub fn reject_code(&self) -> i32 {
+        self.clone() as i32
+    }
+
+    /// The category of this error.
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            Self::ParseParams
+            | Self::AccountsOnly
+            | Self::TokenExpired
+            | Self::DuplicateMetadataUrl
+            | Self::MetadataHashRequired
+            | Self::ExpiryRequired
+            | Self::BatchTooLarge
+            | Self::InvalidThreshold
+            | Self::InvalidIdRange
+            | Self::ValidityTooLong
+            | Self::ZeroAmount
+            | Self::InvalidDependency
+            | Self::AmountNotAllowed
+            | Self::InvalidExpiry
+            | Self::InsufficientFee
+            | Self::EntrypointNotPermitted
+            | Self::TooManySubscribers
+            | Self::InvalidLimit => ErrorCategory::Validation,
+            Self::ActionNotApproved
+            | Self::ActionExpired
+            | Self::ConsentRequired
+            | Self::TokenIdNotAllocated
+            | Self::TooManyRequests
+            | Self::NotUniquePerson
+            | Self::NotAllowlisted
+            | Self::ProposalRequired => ErrorCategory::Authorization,
+            Self::TokenHasValidBalances
+            | Self::SnapshotNotFound
+            | Self::CredentialNotFound
+            | Self::DuplicateMintRequest
+            | Self::TokenPaused
+            | Self::UnsupportedStateVersion
+            | Self::TokenDeprecated
+            | Self::ActionNotScheduled
+            | Self::TimelockNotElapsed
+            | Self::AlreadyClaimed
+            | Self::PrivateToken
+            | Self::PublicToken
+            | Self::AdminAlreadySet
+            | Self::PendingMintNotFound
+            | Self::ActivationExpired
+            | Self::MintingPaused
+            | Self::UnknownBundle
+            | Self::RevocationNotScheduled
+            | Self::RevocationNotDue
+            | Self::ProposalNotFound
+            | Self::LifetimeExceeded
+            | Self::FeeNotConfigured => ErrorCategory::State,
+            Self::UnknownRevocationKey
+            | Self::WrongSignature
+            | Self::NonceMismatch
+            | Self::WrongContract
+            | Self::MessageExpired
+            | Self::UnknownIssuanceRoot
+            | Self::InvalidMerkleProof
+            | Self::UnknownIssuerKey
+            | Self::VoucherExpired
+            | Self::VoucherAlreadyUsed
+            | Self::UnknownPermitKey => ErrorCategory::Crypto,
+            Self::LogFull
+            | Self::LogMalformed
+            | Self::NotifySubscriberFailed
+            | Self::UpgradeFailed
+            | Self::MigrationFailed
+            | Self::IdentityRegistryFailed
+            | Self::FederationFailed
+            | Self::FeePaymentFailed => ErrorCategory::External,
+        }
+    }
+}
+
+/// Rejects with the explicit code of the error and the serialized error as return value.
+impl From<CustomError> for Reject {
+    fn from(error: CustomError) -> Self {
+        Reject {
+            error_code: NonZeroI32::new(error.reject_code()).unwrap_abort(),
+            return_value: Some(to_bytes(&error)),
+        }
+    }
 }
 
 /// Mapping the logging errors to ContractError.
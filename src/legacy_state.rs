@@ -0,0 +1,56 @@
+//! Layouts of the state written by previous modules, read by `migrate`.
+//! A layout is kept here as long as instances with it can be upgraded.
+use concordium_cis2::MetadataUrl;
+use concordium_std::*;
+
+use crate::types::{
+    BatchLimits, ContractTokenAmount, ContractTokenId, RevocationInfo, Snapshot, SnapshotId,
+    TokenCategory,
+};
+
+/// Balance of state version 1.
+#[derive(Serial, Deserial, Clone)]
+pub(crate) struct TokenBalanceStateV1 {
+    pub(crate) amount: ContractTokenAmount,
+    pub(crate) expiry: Timestamp,
+    pub(crate) valid_from: Option<Timestamp>,
+}
+
+/// Token of state version 1.
+#[derive(Serial, DeserialWithState, Deletable)]
+#[concordium(state_parameter = "S")]
+pub(crate) struct TokenStateV1<S> {
+    /// Balances by account, aliases of an account have separate balances.
+    pub(crate) balances: StateMap<AccountAddress, TokenBalanceStateV1, S>,
+    pub(crate) metadata: MetadataUrl,
+    pub(crate) revocations: StateMap<AccountAddress, RevocationInfo, S>,
+    pub(crate) category: TokenCategory,
+    pub(crate) paused: bool,
+    pub(crate) issuer: Option<Address>,
+    pub(crate) default_validity: Option<Duration>,
+}
+
+/// Contract configuration of state version 1.
+#[derive(Serial, Deserial)]
+pub(crate) struct ContractConfigV1 {
+    pub(crate) unique_metadata_urls: bool,
+    pub(crate) metadata_hash_required: bool,
+    pub(crate) batch_limits: BatchLimits,
+}
+
+/// State version 1, the first layout written by a module with `upgrade`.
+#[derive(Serial, DeserialWithState)]
+#[concordium(state_parameter = "S")]
+pub(crate) struct StateV1<S> {
+    pub(crate) version: u8,
+    pub(crate) tokens: StateMap<ContractTokenId, TokenStateV1<S>, S>,
+    pub(crate) metadata_urls: StateMap<MetadataUrl, ContractTokenId, S>,
+    pub(crate) config: ContractConfigV1,
+    pub(crate) snapshots: StateMap<SnapshotId, Snapshot, S>,
+    pub(crate) next_snapshot_id: SnapshotId,
+    pub(crate) subscribers: StateSet<ContractAddress, S>,
+    pub(crate) revocation_keys: StateMap<PublicKeyEd25519, u64, S>,
+    pub(crate) mint_request_keys: StateSet<HashSha2256, S>,
+    pub(crate) mint_request_slots: StateMap<u64, HashSha2256, S>,
+    pub(crate) next_mint_request: u64,
+}
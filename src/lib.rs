@@ -1,4 +1,9 @@
 pub mod contract;
 pub mod errors;
+mod legacy_state;
+#[concordium_cfg_test]
+mod simulation;
 mod state;
 pub mod types;
+
+use concordium_std::concordium_cfg_test;
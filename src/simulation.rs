@@ -0,0 +1,327 @@
+//! Scenarios which drive a contract instance through several entrypoints in a row,
+//! the way the backend, issuers and holders use a deployed contract.
+use concordium_cis2::*;
+use concordium_std::{test_infrastructure::*, *};
+
+use crate::{
+    contract::{
+        add::{add, AddParams},
+        balance_of::balance_of,
+        claim::{claim, ClaimParams, Voucher},
+        init::{init, InitParams},
+        mint::{mint, MintParam, MintParams},
+        pause_token::{pause_token, PauseTokensParams},
+        renew::{renew, RenewParams},
+        set_issuer_key::{set_issuer_key, SetIssuerKeyParams},
+        transfer_admin::transfer_admin,
+        unpause_token::unpause_token,
+        update_minters::{update_minters, MinterUpdate, UpdateMinterParams, UpdateMintersParams},
+        update_roles::{update_roles, RoleUpdate, UpdateRoleParams, UpdateRolesParams},
+    },
+    errors::CustomError,
+    state::State,
+    types::{
+        AddTokenParams, AmountRule, AmountUnit, ContractBalanceOfQueryParams, ContractConfig,
+        ContractError, ContractResult, ContractTokenAmount, ContractTokenId, IssuerKeyValidity,
+        RenewalPolicy, Role,
+    },
+};
+
+// Owner of the instance, which is not the admin once the admin has been set.
+const OWNER: AccountAddress = AccountAddress([0u8; 32]);
+const ADMIN: AccountAddress = AccountAddress([1u8; 32]);
+const NEW_ADMIN: AccountAddress = AccountAddress([2u8; 32]);
+const MINTER: AccountAddress = AccountAddress([3u8; 32]);
+const HOLDER: AccountAddress = AccountAddress([4u8; 32]);
+const PAUSER: AccountAddress = AccountAddress([5u8; 32]);
+const SELF_ADDRESS: ContractAddress = ContractAddress {
+    index: 1,
+    subindex: 0,
+};
+const TOKEN_0: ContractTokenId = TokenIdU8(2);
+const KEY_0: PublicKeyEd25519 = PublicKeyEd25519([1u8; 32]);
+const SIGNATURE_0: SignatureEd25519 = SignatureEd25519([3u8; 64]);
+
+/// Contract instance with a clock, on which entrypoints are invoked.
+struct Simulation {
+    host: TestHost<State<TestStateApi>>,
+    logger: TestLogger,
+    now: Timestamp,
+}
+
+impl Simulation {
+    /// Initializes the contract with an admin and the default configuration.
+    fn new(admin: AccountAddress) -> Self {
+        let parameter = to_bytes(&InitParams {
+            admin,
+            config: ContractConfig::default(),
+        });
+        let mut ctx = TestInitContext::empty();
+        ctx.set_parameter(&parameter);
+        let mut state_builder = TestStateBuilder::new();
+        let state = init(&ctx, &mut state_builder).expect_report("Failed to initialize");
+        Self {
+            host: TestHost::new(state, state_builder),
+            logger: TestLogger::init(),
+            now: Timestamp::from_timestamp_millis(0),
+        }
+    }
+
+    /// Moves the clock forward.
+    fn advance(&mut self, millis: u64) {
+        self.now = Timestamp::from_timestamp_millis(self.now.timestamp_millis() + millis);
+    }
+
+    /// Invokes an entrypoint as an account at the current time.
+    fn invoke<P: Serial, R>(
+        &mut self,
+        sender: AccountAddress,
+        params: &P,
+        entrypoint: impl FnOnce(
+            &TestReceiveContext,
+            &mut TestHost<State<TestStateApi>>,
+            &mut TestLogger,
+        ) -> ContractResult<R>,
+    ) -> ContractResult<R> {
+        let parameter = to_bytes(params);
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_self_address(SELF_ADDRESS);
+        ctx.set_owner(OWNER);
+        ctx.set_sender(Address::Account(sender));
+        ctx.set_metadata_slot_time(self.now);
+        ctx.set_parameter(&parameter);
+        entrypoint(&ctx, &mut self.host, &mut self.logger)
+    }
+
+    /// Adds TOKEN_0 as the given account.
+    fn add_token(&mut self, sender: AccountAddress) -> ContractResult<()> {
+        let params = AddParams {
+            tokens: vec![AddTokenParams {
+                token_id: TOKEN_0,
+                metadata_url: MetadataUrl {
+                    url: "https://example.com".to_string(),
+                    hash: None,
+                },
+                category: 0,
+                issuer: None,
+                default_validity: None,
+                renewal_policy: RenewalPolicy::IssuerOnly,
+                private: false,
+                unique_person: false,
+                amount_unit: AmountUnit::default(),
+                amount_rule: AmountRule::default(),
+            }],
+        };
+        self.invoke(sender, &params, |ctx, host, logger| add(ctx, host, logger))
+            .map(|_| ())
+    }
+
+    /// Mints 10 of TOKEN_0 to the holder as the given account.
+    fn mint_token(&mut self, sender: AccountAddress, expiry: u64) -> ContractResult<()> {
+        let params = MintParams {
+            owner: HOLDER,
+            idempotency_key: None,
+            tokens: collections::BTreeMap::from_iter(vec![(
+                TOKEN_0,
+                MintParam {
+                    amount: 10.into(),
+                    expiry: Some(Timestamp::from_timestamp_millis(expiry)),
+                    valid_from: None,
+                    metadata_url: None,
+                },
+            )]),
+        };
+        self.invoke(sender, &params, |ctx, host, logger| {
+            mint(ctx, host, logger, &TestCryptoPrimitives::new())
+        })
+        .map(|_| ())
+    }
+
+    /// Gets the balance of TOKEN_0 of the holder using `balanceOf`.
+    fn balance(&mut self) -> ContractTokenAmount {
+        let params = ContractBalanceOfQueryParams {
+            queries: vec![BalanceOfQuery {
+                token_id: TOKEN_0,
+                address: Address::Account(HOLDER),
+            }],
+        };
+        let response = self
+            .invoke(HOLDER, &params, |ctx, host, _| balance_of(ctx, &*host))
+            .expect_report("Failed to query the balance");
+        response.0[0]
+    }
+}
+
+#[concordium_test]
+fn test_admin_rotation() {
+    let mut simulation = Simulation::new(ADMIN);
+    assert_eq!(
+        simulation.invoke(ADMIN, &NEW_ADMIN, |ctx, host, _| transfer_admin(ctx, host)),
+        Ok(())
+    );
+
+    // Only the new admin can manage the contract.
+    assert_eq!(
+        simulation.add_token(ADMIN),
+        Err(ContractError::Unauthorized)
+    );
+    assert_eq!(simulation.add_token(NEW_ADMIN), Ok(()));
+    let params = UpdateMintersParams {
+        updates: vec![UpdateMinterParams {
+            update: MinterUpdate::Add,
+            address: Address::Account(MINTER),
+        }],
+    };
+    assert_eq!(
+        simulation.invoke(NEW_ADMIN, &params, |ctx, host, _| {
+            update_minters(ctx, host, &TestCryptoPrimitives::new())
+        }),
+        Ok(())
+    );
+
+    // The minter appointed by the new admin can mint, the previous admin can not.
+    assert_eq!(simulation.mint_token(MINTER, 100), Ok(()));
+    assert_eq!(simulation.balance(), 10.into());
+    assert_eq!(
+        simulation.mint_token(ADMIN, 100),
+        Err(ContractError::Unauthorized)
+    );
+}
+
+#[concordium_test]
+fn test_pause_and_resume() {
+    let mut simulation = Simulation::new(ADMIN);
+    assert_eq!(simulation.add_token(ADMIN), Ok(()));
+    let params = PauseTokensParams {
+        tokens: vec![TOKEN_0],
+    };
+    assert_eq!(
+        simulation.invoke(ADMIN, &params, |ctx, host, _| pause_token(ctx, host)),
+        Ok(())
+    );
+    assert_eq!(
+        simulation.mint_token(ADMIN, 100),
+        Err(ContractError::Custom(CustomError::TokenPaused))
+    );
+
+    assert_eq!(
+        simulation.invoke(ADMIN, &params, |ctx, host, _| unpause_token(ctx, host)),
+        Ok(())
+    );
+    assert_eq!(simulation.mint_token(ADMIN, 100), Ok(()));
+    assert_eq!(simulation.balance(), 10.into());
+}
+
+#[concordium_test]
+fn test_pauser_role() {
+    let mut simulation = Simulation::new(ADMIN);
+    assert_eq!(simulation.add_token(ADMIN), Ok(()));
+    let params = UpdateRolesParams {
+        updates: vec![UpdateRoleParams {
+            update: RoleUpdate::Grant,
+            role: Role::Pauser,
+            address: Address::Account(PAUSER),
+        }],
+    };
+    assert_eq!(
+        simulation.invoke(ADMIN, &params, |ctx, host, _| update_roles(ctx, host)),
+        Ok(())
+    );
+
+    // The pauser can pause the token, but neither mint nor unpause it.
+    let params = PauseTokensParams {
+        tokens: vec![TOKEN_0],
+    };
+    assert_eq!(
+        simulation.invoke(PAUSER, &params, |ctx, host, _| pause_token(ctx, host)),
+        Ok(())
+    );
+    assert_eq!(
+        simulation.invoke(PAUSER, &params, |ctx, host, _| unpause_token(ctx, host)),
+        Err(ContractError::Unauthorized)
+    );
+    assert_eq!(
+        simulation.mint_token(PAUSER, 100),
+        Err(ContractError::Unauthorized)
+    );
+}
+
+#[concordium_test]
+fn test_claim_voucher() {
+    let mut simulation = Simulation::new(ADMIN);
+    assert_eq!(simulation.add_token(ADMIN), Ok(()));
+    let params = SetIssuerKeyParams {
+        token_id: TOKEN_0,
+        key: KEY_0,
+        validity: IssuerKeyValidity {
+            active_from: Timestamp::from_timestamp_millis(0),
+            retired_at: None,
+        },
+    };
+    assert_eq!(
+        simulation.invoke(ADMIN, &params, |ctx, host, _| set_issuer_key(ctx, host)),
+        Ok(())
+    );
+
+    // The holder submits the voucher signed by the backend.
+    simulation.advance(10);
+    let params = ClaimParams {
+        signer: KEY_0,
+        signature: SIGNATURE_0,
+        voucher: Voucher {
+            contract_address: SELF_ADDRESS,
+            voucher_id: 0,
+            token_id: TOKEN_0,
+            account: HOLDER,
+            amount: 10.into(),
+            expiry: Timestamp::from_timestamp_millis(100),
+            issued_at: Timestamp::from_timestamp_millis(5),
+            valid_until: Timestamp::from_timestamp_millis(50),
+        },
+    };
+    let crypto_primitives = TestCryptoPrimitives::new();
+    crypto_primitives
+        .setup_verify_ed25519_signature_mock(|_, signature, _| signature == SIGNATURE_0);
+    assert_eq!(
+        simulation.invoke(HOLDER, &params, |ctx, host, logger| {
+            claim(ctx, host, logger, &crypto_primitives)
+        }),
+        Ok(())
+    );
+    assert_eq!(simulation.balance(), 10.into());
+
+    // The voucher can not be claimed again.
+    assert_eq!(
+        simulation.invoke(HOLDER, &params, |ctx, host, logger| {
+            claim(ctx, host, logger, &crypto_primitives)
+        }),
+        Err(ContractError::Custom(CustomError::VoucherAlreadyUsed))
+    );
+}
+
+#[concordium_test]
+fn test_renewal() {
+    let mut simulation = Simulation::new(ADMIN);
+    assert_eq!(simulation.add_token(ADMIN), Ok(()));
+    assert_eq!(simulation.mint_token(ADMIN, 100), Ok(()));
+
+    // The balance is renewed before it expires and stays valid after the original expiry.
+    simulation.advance(50);
+    let params = RenewParams {
+        token_id: TOKEN_0,
+        account: HOLDER,
+        expiry: Some(Timestamp::from_timestamp_millis(300)),
+    };
+    assert_eq!(
+        simulation.invoke(ADMIN, &params, |ctx, host, logger| {
+            renew(ctx, host, Amount::zero(), logger)
+        }),
+        Ok(())
+    );
+    simulation.advance(100);
+    assert_eq!(simulation.balance(), 10.into());
+
+    // The balance expires at the new expiry.
+    simulation.advance(200);
+    assert_eq!(simulation.balance(), 0.into());
+}
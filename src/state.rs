@@ -1,12 +1,117 @@
-use concordium_cis2::MetadataUrl;
+use concordium_cis2::{MetadataUrl, TokenAmountU64, TokenIdVec};
 use concordium_std::*;
 
-use crate::types::{ContractError, ContractResult, ContractTokenAmount, ContractTokenId};
+use crate::errors::CustomError;
+use crate::legacy_state::StateV1;
+use crate::types::{
+    AmountRule, AmountUnit, BatchLimits, BundleComponent, BundleId, ContractConfig, ContractError,
+    ContractResult, ContractTokenAmount, ContractTokenId, CredentialStatus, ExclusiveGroup,
+    FeeToken, HistoryEntry, IssuerKeyValidity, MintVolume, MinterStats, PendingMint, RenewalPolicy,
+    RevocationInfo, Role, ScheduledRevocation, Snapshot, SnapshotId, TokenCategory, TokenIdRange,
+    TokenProposal, TokenSnapshot,
+};
 
-#[derive(Serial, Deserial)]
+/// Version of the state layout written by this module.
+/// Every change of the layout of the state, its tokens or balances bumps the version and adds a
+/// migration step from the previous layout to `migrate_state`.
+pub(crate) const STATE_VERSION: u8 = 2;
+
+/// Maximum number of mint idempotency keys which are remembered.
+const MAX_MINT_REQUEST_KEYS: u64 = 1024;
+
+/// Maximum number of entries kept in the history of a balance.
+const MAX_HISTORY_ENTRIES: usize = 16;
+
+/// Maximum number of subscribers, every balance change invokes each of them.
+pub(crate) const MAX_SUBSCRIBERS: usize = 16;
+
+/// Number of leading bytes of an account address which identify the account,
+/// the remaining bytes distinguish the aliases of the account.
+const ACCOUNT_IDENTITY_BYTES: usize = 29;
+
+/// Length of an expiry bucket of the expiry index.
+const MILLIS_PER_DAY: u64 = 86_400_000;
+
+/// Entry of the expiry index.
+/// The day is stored big endian so the entries are ordered by the day.
+#[derive(Serial, Deserial, Clone, Copy)]
+struct ExpiryIndexKey {
+    day: [u8; 8],
+    token_id: ContractTokenId,
+    account: AccountAddress,
+}
+
+impl ExpiryIndexKey {
+    fn new(expiry: Timestamp, token_id: ContractTokenId, account: AccountAddress) -> Self {
+        Self {
+            day: day_of(expiry).to_be_bytes(),
+            token_id,
+            account,
+        }
+    }
+
+    fn day(&self) -> u64 {
+        u64::from_be_bytes(self.day)
+    }
+}
+
+/// Gets the address which identifies all aliases of an account, used as key of the balances.
+pub(crate) fn canonical_account(account: AccountAddress) -> AccountAddress {
+    let mut address = account.0;
+    address[ACCOUNT_IDENTITY_BYTES..].fill(0);
+    AccountAddress(address)
+}
+
+/// Gets the number of the day of a timestamp.
+fn day_of(timestamp: Timestamp) -> u64 {
+    timestamp.timestamp_millis() / MILLIS_PER_DAY
+}
+
+/// Reads the version of the layout of the stored state.
+/// Every layout since version 1 starts with the version.
+pub(crate) fn stored_state_version<S: HasStateApi>(state_api: &S) -> ContractResult<u8> {
+    Ok(state_api.read_root()?)
+}
+
+/// Migrates the stored state to the layout of STATE_VERSION, one version at a time.
+/// - If the state has been written by a newer module or predates versioning, UnsupportedStateVersion is thrown.
+pub(crate) fn migrate_state<S: HasStateApi + Clone>(
+    state_api: &mut S,
+    state_builder: &mut StateBuilder<S>,
+) -> ContractResult<()> {
+    let version = stored_state_version(state_api)?;
+    ensure!(
+        (1..=STATE_VERSION).contains(&version),
+        ContractError::Custom(CustomError::UnsupportedStateVersion)
+    );
+    if version == 1 {
+        let old: StateV1<S> = state_api.read_root()?;
+        let state = State::from_v1(old, state_builder)?;
+        state_api.write_root(&state);
+    }
+    Ok(())
+}
+
+/// Approvals of an admin action which has not been executed yet.
+#[derive(Serial, Deserial, Clone)]
+pub struct PendingAction {
+    /// Time after which the approvals can no longer be used.
+    pub expiry: Timestamp,
+    /// Admins which approved the action.
+    pub approvals: Vec<AccountAddress>,
+}
+
+#[derive(Serial, Deserial, SchemaType, Clone, Debug, PartialEq, Eq)]
 pub struct TokenBalanceState {
     pub amount: ContractTokenAmount,
     pub expiry: Timestamp,
+    /// The time from which the balance is valid, None if it is valid immediately.
+    pub valid_from: Option<Timestamp>,
+    /// Whether the balance is frozen, in which case it is 0 until it is unfrozen.
+    pub frozen: bool,
+    /// Time after which the balance can not be renewed or extended, regardless of the renewal
+    /// policy. Set by the first mint of a token with a maximum lifetime.
+    pub hard_expiry: Option<Timestamp>,
 }
 
 impl TokenBalanceState {
@@ -16,15 +121,35 @@ impl TokenBalanceState {
         balance > ContractTokenAmount::from(0)
     }
 
+    /// Checks if the balance has become valid at the given time.
+    pub fn is_activated(&self, now: Timestamp) -> bool {
+        self.valid_from.map_or(true, |valid_from| valid_from <= now)
+    }
+
     /// Gets the balance of the token.
+    /// - If the balance is not valid yet, the balance is 0.
     /// - If the balance has expired, the balance is 0.
+    /// - If the balance is frozen, the balance is 0.
     pub fn get_balance(&self, now: Timestamp) -> ContractTokenAmount {
-        if self.expiry > now {
+        if self.is_activated(now) && self.expiry > now && !self.frozen {
             self.amount
         } else {
             ContractTokenAmount::from(0)
         }
     }
+
+    /// Gets the credential status of the balance at the given time.
+    pub fn get_status(&self, now: Timestamp) -> CredentialStatus {
+        if !self.is_activated(now) {
+            CredentialStatus::NotActivated
+        } else if self.expiry <= now {
+            CredentialStatus::Expired
+        } else if self.frozen {
+            CredentialStatus::Frozen
+        } else {
+            CredentialStatus::Active
+        }
+    }
 }
 
 #[derive(Serial, DeserialWithState, Deletable)]
@@ -32,12 +157,154 @@ impl TokenBalanceState {
 pub struct TokenState<S> {
     balances: StateMap<AccountAddress, TokenBalanceState, S>,
     metadata: MetadataUrl,
+    /// Revocations of balances which have not been replaced by a new mint.
+    revocations: StateMap<AccountAddress, RevocationInfo, S>,
+    /// Category used to manage tokens as a group, e.g. KYC or membership.
+    category: TokenCategory,
+    /// Whether minting of the token is paused.
+    paused: bool,
+    /// Whether the token is being phased out, new balances can not be minted.
+    deprecated: bool,
+    /// Address which has the exclusive right to mint and revoke the token.
+    issuer: Option<Address>,
+    /// Validity of minted balances which do not have an expiry.
+    default_validity: Option<Duration>,
+    /// Who may extend the expiry of existing balances.
+    renewal_policy: RenewalPolicy,
+    /// Whether minting requires the consent of the holder.
+    consent_required: bool,
+    /// Holders and the issuers they consent to be issued the token by.
+    consents: StateSet<(AccountAddress, Address), S>,
+    /// Whether balances can only be minted to accounts on the allowlist.
+    allowlist_required: bool,
+    /// Accounts pre-approved to be minted the token.
+    allowlist: StateSet<AccountAddress, S>,
+    /// Published Merkle roots of balances which holders can claim, and their publisher.
+    issuance_roots: StateMap<HashSha2256, Address, S>,
+    /// Hashes of the leaves which have been claimed.
+    claimed_leaves: StateSet<HashSha2256, S>,
+    /// Latest changes of the balance of each account, oldest first.
+    history: StateMap<AccountAddress, Vec<HistoryEntry>, S>,
+    /// Metadata urls of the token by locale, e.g. "en" or "fr".
+    localized_metadata: StateMap<String, MetadataUrl, S>,
+    /// Metadata urls attached to the balance of an account, e.g. a personalized certificate.
+    holder_metadata: StateMap<AccountAddress, MetadataUrl, S>,
+    /// Keys signing vouchers for `claim` with the period in which they were valid.
+    issuer_keys: StateMap<PublicKeyEd25519, IssuerKeyValidity, S>,
+    /// Ids of the vouchers which have been claimed.
+    used_vouchers: StateSet<u64, S>,
+    /// Mints which become balances once the holder activates them, see `mintPending`.
+    pending_mints: StateMap<AccountAddress, PendingMint, S>,
+    /// Revocations waiting for their dispute window to end, see `scheduleRevoke`.
+    scheduled_revocations: StateMap<AccountAddress, ScheduledRevocation, S>,
+    /// Holders who hide their balance from `balanceOf`, see `setVisibility`.
+    hidden_holders: StateSet<AccountAddress, S>,
+    /// Whether only commitments of balances are stored, see `commitments`.
+    private: bool,
+    /// Whether holders must be confirmed as unique persons by the identity registry.
+    unique_person: bool,
+    /// Decimals and unit of the amounts of the token.
+    amount_unit: AmountUnit,
+    /// Amounts which may be minted.
+    amount_rule: AmountRule,
+    /// Smallest amount which can be minted.
+    min_amount: ContractTokenAmount,
+    /// SHA-256 checksum of the JSON schema the metadata conforms to.
+    schema_ref: Option<HashSha2256>,
+    /// Token whose balance of the same holder has to be valid for a balance of this token to be valid.
+    depends_on: Option<ContractTokenId>,
+    /// Group of tokens of which a holder has at most one balance at a time.
+    exclusive_group: Option<ExclusiveGroup>,
+    /// Whether a new expiry may not shorten the expiry of an unexpired balance.
+    monotonic_expiry: bool,
+    /// Maximum time between the first mint of a balance and its expiry, see `hard_expiry`.
+    max_lifetime: Option<Duration>,
+    /// Hash commitments of the balances of a private token.
+    commitments: StateMap<AccountAddress, HashSha2256, S>,
+    /// Sum of all amounts ever minted.
+    total_minted: u64,
+    /// Sum of all valid amounts ever burned by replacing or revoking a balance.
+    total_burned: u64,
+    /// Number of stored balances with an amount > 0, including expired ones.
+    holder_count: u32,
+    /// Latest expiry of the balances counted in `holder_count` since the token last had none.
+    /// Never earlier than the expiry of any counted balance, see `has_balances`.
+    latest_expiry: Timestamp,
 }
 
 impl<S> TokenState<S>
 where
     S: HasStateApi,
 {
+    /// Creates a token without balances and with the default settings.
+    fn new(state_builder: &mut StateBuilder<S>, metadata: MetadataUrl) -> Self {
+        Self {
+            balances: state_builder.new_map(),
+            metadata,
+            revocations: state_builder.new_map(),
+            category: 0,
+            paused: false,
+            deprecated: false,
+            issuer: None,
+            default_validity: None,
+            renewal_policy: RenewalPolicy::default(),
+            consent_required: false,
+            consents: state_builder.new_set(),
+            allowlist_required: false,
+            allowlist: state_builder.new_set(),
+            issuance_roots: state_builder.new_map(),
+            claimed_leaves: state_builder.new_set(),
+            history: state_builder.new_map(),
+            localized_metadata: state_builder.new_map(),
+            holder_metadata: state_builder.new_map(),
+            issuer_keys: state_builder.new_map(),
+            used_vouchers: state_builder.new_set(),
+            pending_mints: state_builder.new_map(),
+            scheduled_revocations: state_builder.new_map(),
+            hidden_holders: state_builder.new_set(),
+            private: false,
+            unique_person: false,
+            amount_unit: AmountUnit::default(),
+            amount_rule: AmountRule::default(),
+            min_amount: ContractTokenAmount::from(1),
+            schema_ref: None,
+            depends_on: None,
+            exclusive_group: None,
+            monotonic_expiry: false,
+            max_lifetime: None,
+            commitments: state_builder.new_map(),
+            total_minted: 0,
+            total_burned: 0,
+            holder_count: 0,
+            latest_expiry: Timestamp::from_timestamp_millis(0),
+        }
+    }
+
+    /// Counts a stored balance with an amount > 0 which expires at `expiry`,
+    /// in the token and in the `active_balances` of the contract.
+    fn add_holder(&mut self, expiry: Timestamp, active_balances: &mut u64) {
+        self.holder_count = self.holder_count.saturating_add(1);
+        *active_balances = active_balances.saturating_add(1);
+        self.extend_latest_expiry(expiry);
+    }
+
+    /// Stops counting a stored balance with an amount > 0.
+    /// - Without holders left, the latest expiry starts over.
+    fn remove_holder(&mut self, active_balances: &mut u64) {
+        debug_assert!(self.holder_count > 0, "Holder count underflow");
+        debug_assert!(*active_balances > 0, "Active balance count underflow");
+        self.holder_count = self.holder_count.saturating_sub(1);
+        *active_balances = active_balances.saturating_sub(1);
+        if self.holder_count == 0 {
+            self.latest_expiry = Timestamp::from_timestamp_millis(0);
+        }
+    }
+
+    /// Moves the latest expiry forward to the new expiry of a counted balance.
+    fn extend_latest_expiry(&mut self, expiry: Timestamp) {
+        self.latest_expiry = self.latest_expiry.max(expiry);
+    }
+
     /// Gets Account Balance for a given token and account.
     /// - If the state has no entry for the given account and token, the balance is 0.
     /// - If the balance has expired, the balance is 0.
@@ -46,6 +313,7 @@ where
         account: AccountAddress,
         now: Timestamp,
     ) -> ContractTokenAmount {
+        let account = canonical_account(account);
         self.balances
             .get(&account)
             .map_or(ContractTokenAmount::from(0), |balance| {
@@ -53,17 +321,119 @@ where
             })
     }
 
+    /// Gets the total supply and the number of holders of the token.
+    /// - Expired balances are not included.
+    pub(crate) fn get_supply(&self, now: Timestamp) -> (u64, u32) {
+        self.balances
+            .iter()
+            .map(|(_, balance)| balance.get_balance(now))
+            .filter(|amount| *amount > ContractTokenAmount::from(0))
+            .fold((0, 0), |(supply, holders), amount| {
+                (supply + u64::from(amount.0), holders + 1)
+            })
+    }
+
     /// Get Account Balance Expiry for a given token and account.
     /// - If the state has no entry for the given account and token, the expiry is None.
     pub(crate) fn get_account_balance_expiry(&self, account: AccountAddress) -> Option<Timestamp> {
+        let account = canonical_account(account);
         self.balances.get(&account).map(|balance| balance.expiry)
     }
+
+    /// Get the time from which the balance of an account is valid.
+    /// - If the state has no entry for the given account and token, None is returned.
+    /// - If the balance is valid immediately, None is returned.
+    pub(crate) fn get_account_balance_valid_from(
+        &self,
+        account: AccountAddress,
+    ) -> Option<Timestamp> {
+        let account = canonical_account(account);
+        self.balances
+            .get(&account)
+            .and_then(|balance| balance.valid_from)
+    }
 }
 
 #[derive(Serial, DeserialWithState, StateClone)]
 #[concordium(state_parameter = "S")]
 pub struct State<S> {
+    /// Version of the state layout, see STATE_VERSION.
+    version: u8,
     tokens: StateMap<ContractTokenId, TokenState<S>, S>,
+    /// Reverse lookup of the token using a metadata url.
+    metadata_urls: StateMap<MetadataUrl, ContractTokenId, S>,
+    config: ContractConfig,
+    snapshots: StateMap<SnapshotId, Snapshot, S>,
+    next_snapshot_id: SnapshotId,
+    /// Contracts which are notified about balance changes.
+    subscribers: StateSet<ContractAddress, S>,
+    /// Keys of external revocation authorities and their next nonce.
+    revocation_keys: StateMap<PublicKeyEd25519, u64, S>,
+    /// Contracts which may mint using `mintFromContract` and their next nonce.
+    issuer_contracts: StateMap<ContractAddress, u64, S>,
+    /// Issuance activity of each issuing address.
+    minter_stats: StateMap<Address, MinterStats, S>,
+    /// Recently used mint idempotency keys.
+    mint_request_keys: StateSet<HashSha2256, S>,
+    /// Recently used mint idempotency keys by insertion slot, used to evict the oldest key.
+    mint_request_slots: StateMap<u64, HashSha2256, S>,
+    next_mint_request: u64,
+    /// Stored balances bucketed by the day of their expiry.
+    expiry_index: StateSet<ExpiryIndexKey, S>,
+    /// Accounts which approve destructive admin actions.
+    admins: StateSet<AccountAddress, S>,
+    /// Number of admin approvals required for destructive admin actions, 0 disables approvals.
+    approval_threshold: u8,
+    /// Approvals collected for hashes of admin actions.
+    pending_actions: StateMap<HashSha2256, PendingAction, S>,
+    /// Delay between scheduling and executing timelocked actions, 0 disables the timelock.
+    timelock_delay: Duration,
+    /// Hashes of scheduled actions and the time from which they can be executed.
+    scheduled_actions: StateMap<HashSha2256, Timestamp, S>,
+    /// Token ids allocated to issuers.
+    id_ranges: StateMap<Address, TokenIdRange, S>,
+    /// Bundles of tokens minted together, see `createBundle`.
+    bundles: StateMap<BundleId, Vec<BundleComponent>, S>,
+    /// Tokens proposed by an admin and awaiting the approval of another admin.
+    token_proposals: StateMap<ContractTokenId, TokenProposal, S>,
+    /// Number of tokens.
+    token_count: u32,
+    /// Number of mints since the contract was initialized.
+    mint_count: u64,
+    /// Number of burns of valid amounts since the contract was initialized.
+    burn_count: u64,
+    /// Number of stored balances with an amount > 0 over all tokens, the sum of the holder counts.
+    /// Expired balances are counted until `sweepExpired` removes them.
+    active_balances: u64,
+    /// Time of the last self-service call of each account, used for the cooldown.
+    last_actions: StateMap<AccountAddress, Timestamp, S>,
+    /// Account with the admin role, None for instances migrated from state version 1, which did
+    /// not store roles, in which case the owner of the instance is the admin until `initAdmin` is called.
+    contract_admin: Option<AccountAddress>,
+    /// Addresses which may mint tokens without an issuer.
+    minters: StateSet<Address, S>,
+    /// Roles delegated by the admin as (role, address).
+    roles: StateSet<(Role, Address), S>,
+    /// Operators of owners as (owner, operator), empty until restricted operators can be added.
+    operators: StateSet<(Address, Address), S>,
+    /// Accounts which consent to off-chain renewal reminders and the time they consented.
+    reminder_consents: StateMap<AccountAddress, Timestamp, S>,
+    /// Whether minting has been paused because the mint volume limit was exceeded.
+    minting_paused: bool,
+    /// Volume minted within the current window of the mint volume limit.
+    mint_volume: MintVolume,
+    /// CIS-2 token in which renewal fees are paid, None if `renewWithToken` is disabled.
+    fee_token: Option<FeeToken>,
+    /// Fees collected by `renewWithToken` for each fee token as (contract, token id).
+    fee_ledger: StateMap<(ContractAddress, TokenIdVec), TokenAmountU64, S>,
+    /// Fee in CCD paid by holders renewing their own balance using `renew`.
+    renewal_fee: Amount,
+    /// Renewal fees in CCD collected by `renew` and not withdrawn yet.
+    collected_renewal_fees: Amount,
+    /// Keys with which accounts sign `permit` messages, see `setPermitKey`.
+    permit_keys: StateMap<AccountAddress, PublicKeyEd25519, S>,
+    /// Next nonce of the `permit` messages of each account, kept when the key is replaced.
+    permit_nonces: StateMap<AccountAddress, u64, S>,
 }
 impl<S> State<S>
 where
@@ -72,8 +442,240 @@ where
 {
     pub(crate) fn empty(state_builder: &mut StateBuilder<S>) -> Self {
         Self {
+            version: STATE_VERSION,
             tokens: state_builder.new_map(),
+            metadata_urls: state_builder.new_map(),
+            config: ContractConfig::default(),
+            snapshots: state_builder.new_map(),
+            next_snapshot_id: 0,
+            subscribers: state_builder.new_set(),
+            revocation_keys: state_builder.new_map(),
+            issuer_contracts: state_builder.new_map(),
+            minter_stats: state_builder.new_map(),
+            mint_request_keys: state_builder.new_set(),
+            mint_request_slots: state_builder.new_map(),
+            next_mint_request: 0,
+            expiry_index: state_builder.new_set(),
+            admins: state_builder.new_set(),
+            approval_threshold: 0,
+            pending_actions: state_builder.new_map(),
+            timelock_delay: Duration::from_millis(0),
+            scheduled_actions: state_builder.new_map(),
+            id_ranges: state_builder.new_map(),
+            bundles: state_builder.new_map(),
+            token_proposals: state_builder.new_map(),
+            token_count: 0,
+            mint_count: 0,
+            burn_count: 0,
+            active_balances: 0,
+            last_actions: state_builder.new_map(),
+            contract_admin: None,
+            minters: state_builder.new_set(),
+            roles: state_builder.new_set(),
+            operators: state_builder.new_set(),
+            reminder_consents: state_builder.new_map(),
+            minting_paused: false,
+            mint_volume: MintVolume {
+                window_start: Timestamp::from_timestamp_millis(0),
+                count: 0,
+                amount: 0,
+            },
+            fee_token: None,
+            fee_ledger: state_builder.new_map(),
+            renewal_fee: Amount::zero(),
+            collected_renewal_fees: Amount::zero(),
+            permit_keys: state_builder.new_map(),
+            permit_nonces: state_builder.new_map(),
+        }
+    }
+
+    /// Gets the version of the state layout.
+    pub(crate) fn version(&self) -> u8 {
+        self.version
+    }
+
+    /// Converts a state of layout version 1, moving the maps whose layout did not change.
+    /// - Balances are stored under the canonical alias of their account, of several aliases
+    ///   the balance with the latest expiry is kept.
+    /// - Counters which version 1 did not track start from the stored balances.
+    /// - The owner of the instance stays the admin until `initAdmin` is called.
+    pub(crate) fn from_v1(
+        old: StateV1<S>,
+        state_builder: &mut StateBuilder<S>,
+    ) -> ContractResult<Self> {
+        debug_assert_eq!(old.version, 1);
+        let mut state = Self::empty(state_builder);
+        state.metadata_urls = old.metadata_urls;
+        state.config = ContractConfig {
+            unique_metadata_urls: old.config.unique_metadata_urls,
+            metadata_hash_required: old.config.metadata_hash_required,
+            batch_limits: old.config.batch_limits,
+            ..ContractConfig::default()
+        };
+        state.snapshots = old.snapshots;
+        state.next_snapshot_id = old.next_snapshot_id;
+        state.subscribers = old.subscribers;
+        state.revocation_keys = old.revocation_keys;
+        state.mint_request_keys = old.mint_request_keys;
+        state.mint_request_slots = old.mint_request_slots;
+        state.next_mint_request = old.next_mint_request;
+
+        let mut tokens = old.tokens;
+        let token_ids: Vec<ContractTokenId> =
+            tokens.iter().map(|(token_id, _)| *token_id).collect();
+        for token_id in token_ids {
+            let old_token = match tokens.remove_and_get(&token_id) {
+                Some(token) => token,
+                None => continue,
+            };
+            // The reverse lookup of the metadata urls has been moved with the other maps.
+            if let Entry::Vacant(entry) = state.tokens.entry(token_id) {
+                entry.insert(TokenState::new(state_builder, old_token.metadata.clone()));
+                state.token_count += 1;
+            }
+            if let Some(mut token) = state.tokens.get_mut(&token_id) {
+                token.category = old_token.category;
+                token.paused = old_token.paused;
+                token.issuer = old_token.issuer;
+                token.default_validity = old_token.default_validity;
+                for (account, revocation) in old_token.revocations.iter() {
+                    token
+                        .revocations
+                        .insert(canonical_account(*account), revocation.clone());
+                }
+            }
+            let balances: Vec<_> = old_token
+                .balances
+                .iter()
+                .map(|(account, balance)| (*account, balance.clone()))
+                .collect();
+            for (account, balance) in balances {
+                if balance.amount == ContractTokenAmount::from(0) {
+                    continue;
+                }
+                let stored_expiry = state.get_account_balance_expiry(token_id, account)?;
+                if stored_expiry.map_or(true, |expiry| expiry < balance.expiry) {
+                    // Minting keeps the holder count and the expiry index up to date.
+                    state.mint(
+                        token_id,
+                        account,
+                        balance.amount,
+                        balance.expiry,
+                        balance.valid_from,
+                    )?;
+                }
+            }
+            old_token.balances.delete();
+            old_token.revocations.delete();
         }
+        Ok(state)
+    }
+
+    /// Gets the contract wide configuration.
+    pub(crate) fn config(&self) -> &ContractConfig {
+        &self.config
+    }
+
+    /// Replaces the contract wide configuration.
+    pub(crate) fn set_config(&mut self, config: ContractConfig) {
+        self.config = config;
+    }
+
+    /// Records a self-service call of an account, if a cooldown is configured.
+    /// - If the last call of the account is within the cooldown, TooManyRequests is thrown.
+    pub(crate) fn record_self_service_action(
+        &mut self,
+        account: AccountAddress,
+        now: Timestamp,
+    ) -> ContractResult<()> {
+        let cooldown = match self.config.self_service_cooldown {
+            Some(cooldown) => cooldown,
+            None => return Ok(()),
+        };
+        let account = canonical_account(account);
+        if let Some(last_action) = self.last_actions.get(&account) {
+            let available_at = last_action
+                .checked_add(cooldown)
+                .unwrap_or(Timestamp::from_timestamp_millis(u64::MAX));
+            ensure!(
+                now >= available_at,
+                ContractError::Custom(CustomError::TooManyRequests)
+            );
+        }
+        self.last_actions.insert(account, now);
+        Ok(())
+    }
+
+    /// Checks if minting has been paused because the mint volume limit was exceeded.
+    pub(crate) fn is_minting_paused(&self) -> bool {
+        self.minting_paused
+    }
+
+    /// Adds minted balances to the volume of the current window, if a mint volume limit is configured.
+    /// - A new window starts with the first mint after the previous window ended.
+    /// - If the volume exceeds the limit, minting is paused and the volume is returned.
+    pub(crate) fn record_mint_volume(
+        &mut self,
+        count: u32,
+        amount: u64,
+        now: Timestamp,
+    ) -> Option<MintVolume> {
+        let limit = self.config.mint_volume_limit.as_ref()?;
+        let window_end = self
+            .mint_volume
+            .window_start
+            .checked_add(limit.window)
+            .unwrap_or(Timestamp::from_timestamp_millis(u64::MAX));
+        if now >= window_end {
+            self.mint_volume = MintVolume {
+                window_start: now,
+                count: 0,
+                amount: 0,
+            };
+        }
+        self.mint_volume.count = self.mint_volume.count.saturating_add(count);
+        self.mint_volume.amount = self.mint_volume.amount.saturating_add(amount);
+        let exceeded = limit
+            .max_count
+            .map_or(false, |max_count| self.mint_volume.count > max_count)
+            || limit
+                .max_amount
+                .map_or(false, |max_amount| self.mint_volume.amount > max_amount);
+        if !exceeded || self.minting_paused {
+            return None;
+        }
+        self.minting_paused = true;
+        Some(self.mint_volume.clone())
+    }
+
+    /// Resumes minting after it has been paused and starts a new window of the mint volume limit.
+    pub(crate) fn resume_minting(&mut self, now: Timestamp) {
+        self.minting_paused = false;
+        self.mint_volume = MintVolume {
+            window_start: now,
+            count: 0,
+            amount: 0,
+        };
+    }
+
+    /// Checks the number of items of a call against the configured batch limit.
+    /// - If the number exceeds the limit, BatchTooLarge is thrown.
+    pub(crate) fn ensure_batch_size(
+        &self,
+        limit: fn(&BatchLimits) -> Option<u16>,
+        size: usize,
+    ) -> ContractResult<()> {
+        match limit(&self.config.batch_limits) {
+            Some(max) if size > usize::from(max) => {
+                bail!(ContractError::Custom(CustomError::BatchTooLarge))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks if a token with the given metadata url (including the hash) exists.
+    pub(crate) fn has_metadata_url(&self, metadata_url: &MetadataUrl) -> bool {
+        self.metadata_urls.get(metadata_url).is_some()
     }
 
     /// Checks if a token exists.
@@ -91,89 +693,2524 @@ where
     ) {
         // Add the token to the state.
         // This is safe because it does not overwrite an existing token.
-        self.tokens.entry(token_id).or_insert(TokenState {
-            balances: state_builder.new_map(),
-            metadata: token_metadata,
-        });
+        if let Entry::Vacant(entry) = self.tokens.entry(token_id) {
+            self.metadata_urls
+                .entry(token_metadata.clone())
+                .or_insert(token_id);
+            entry.insert(TokenState::new(state_builder, token_metadata));
+            self.token_count += 1;
+        }
     }
 
     /// Removes a token from the state.
     /// - This function does not fail if the token does not exist.
     pub(crate) fn remove_token(&mut self, token_id: ContractTokenId) {
+        let token = self
+            .tokens
+            .get(&token_id)
+            .map(|token| (token.metadata.clone(), token.holder_count));
+        if let Some((metadata, holder_count)) = token {
+            // The balances of the token are no longer counted.
+            self.active_balances = self.active_balances.saturating_sub(u64::from(holder_count));
+            // Only remove the reverse lookup if it points to this token.
+            let is_token_url = self
+                .metadata_urls
+                .get(&metadata)
+                .map_or(false, |id| *id == token_id);
+            if is_token_url {
+                self.metadata_urls.remove(&metadata);
+            }
+            self.token_count -= 1;
+        }
         self.tokens.remove(&token_id);
     }
 
-    /// Checks if a token has valid balances.
-    /// - A tokens has valid balances if there is a balance > 0 which has not expired.
+    /// Checks if a token may have valid balances, without iterating the balances.
+    /// - A token has valid balances if there is a balance > 0 which has not expired.
+    /// - Returns true while any stored balance > 0 has not expired, including frozen balances,
+    ///   balances which are not valid yet, and until the latest expiry of balances revoked before
+    ///   they expired. A valid balance is never missed.
     pub(crate) fn has_balances(&self, token_id: ContractTokenId, now: Timestamp) -> bool {
         self.tokens.get(&token_id).map_or(false, |token| {
-            token
-                .balances
-                .iter()
-                .any(|(_, balance)| balance.has_balance(now))
+            let has_balances = token.holder_count > 0 && token.latest_expiry > now;
+            debug_assert!(
+                has_balances
+                    || !token
+                        .balances
+                        .iter()
+                        .any(|(_, balance)| balance.has_balance(now))
+            );
+            has_balances
         })
     }
 
     /// Mints a new token balance.
+    /// - The balance is stored under the canonical alias of the account, so all aliases share it.
     /// - If the token does not exist, an error is returned.
     /// - If the token balance already exists, the old balance is returned.
+    /// - Any revocation of a previous balance is cleared, as is a revocation scheduled for it.
+    /// - A frozen balance stays frozen when it is replaced.
+    /// - The amount is added to the total minted amount of the token.
     pub(crate) fn mint(
         &mut self,
         token_id: ContractTokenId,
         account: AccountAddress,
         amount: ContractTokenAmount,
         expiry: Timestamp,
+        valid_from: Option<Timestamp>,
     ) -> ContractResult<Option<TokenBalanceState>> {
+        let account = canonical_account(account);
         match self.tokens.get_mut(&token_id) {
-            Some(mut token) => Ok(token
-                .balances
-                .insert(account, TokenBalanceState { amount, expiry })),
+            Some(mut token) => {
+                self.mint_count += 1;
+                token.revocations.remove(&account);
+                // A scheduled revocation was meant for the previous balance.
+                token.scheduled_revocations.remove(&account);
+                token.total_minted = token.total_minted.saturating_add(u64::from(amount.0));
+                // Replacing a balance keeps it frozen and keeps its hard expiry.
+                let (frozen, hard_expiry) = token
+                    .balances
+                    .get(&account)
+                    .map_or((false, None), |balance| {
+                        (balance.frozen, balance.hard_expiry)
+                    });
+                // A mint of 0 only replaces the existing balance, no entry is stored.
+                let existing = if amount > ContractTokenAmount::from(0) {
+                    token.balances.insert(
+                        account,
+                        TokenBalanceState {
+                            amount,
+                            expiry,
+                            valid_from,
+                            frozen,
+                            hard_expiry,
+                        },
+                    )
+                } else {
+                    token.balances.remove_and_get(&account)
+                };
+                if let Some(balance) = existing.as_ref() {
+                    if balance.amount > ContractTokenAmount::from(0) {
+                        token.remove_holder(&mut self.active_balances);
+                    }
+                    self.expiry_index.remove(&ExpiryIndexKey::new(
+                        balance.expiry,
+                        token_id,
+                        account,
+                    ));
+                }
+                if amount > ContractTokenAmount::from(0) {
+                    token.add_holder(expiry, &mut self.active_balances);
+                    self.expiry_index
+                        .insert(ExpiryIndexKey::new(expiry, token_id, account));
+                }
+                Ok(existing)
+            }
             None => bail!(ContractError::InvalidTokenId),
         }
     }
 
-    /// Get Account balance for a token.
+    /// Revokes a token balance and records the reason of the revocation.
     /// - If the token does not exist, InvalidTokenId is thrown.
-    /// - If the account does not have a balance, 0 balance is returned.
-    /// - If the balance has expired, 0 balance is returned.
-    pub(crate) fn get_account_balance(
+    /// - If the account does not have a balance, CredentialNotFound is thrown.
+    /// - Returns the revoked balance.
+    pub(crate) fn revoke(
+        &mut self,
+        token_id: ContractTokenId,
+        account: AccountAddress,
+        revocation: RevocationInfo,
+    ) -> ContractResult<TokenBalanceState> {
+        let account = canonical_account(account);
+        let mut token = self
+            .tokens
+            .get_mut(&token_id)
+            .ok_or(ContractError::InvalidTokenId)?;
+        let balance = token
+            .balances
+            .remove_and_get(&account)
+            .ok_or(ContractError::Custom(CustomError::CredentialNotFound))?;
+        if balance.amount > ContractTokenAmount::from(0) {
+            token.remove_holder(&mut self.active_balances);
+        }
+        self.expiry_index
+            .remove(&ExpiryIndexKey::new(balance.expiry, token_id, account));
+        token.revocations.insert(account, revocation);
+        token.scheduled_revocations.remove(&account);
+        Ok(balance)
+    }
+
+    /// Schedules or reschedules the revocation of the balance of an account.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    /// - If the account does not have a balance, CredentialNotFound is thrown.
+    pub(crate) fn schedule_revocation(
+        &mut self,
+        token_id: ContractTokenId,
+        account: AccountAddress,
+        revocation: ScheduledRevocation,
+    ) -> ContractResult<()> {
+        let account = canonical_account(account);
+        let mut token = self
+            .tokens
+            .get_mut(&token_id)
+            .ok_or(ContractError::InvalidTokenId)?;
+        ensure!(
+            token.balances.get(&account).is_some(),
+            ContractError::Custom(CustomError::CredentialNotFound)
+        );
+        token.scheduled_revocations.insert(account, revocation);
+        Ok(())
+    }
+
+    /// Removes the scheduled revocation of the balance of an account and returns it.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    /// - If no revocation is scheduled, RevocationNotScheduled is thrown.
+    pub(crate) fn take_scheduled_revocation(
+        &mut self,
+        token_id: ContractTokenId,
+        account: AccountAddress,
+    ) -> ContractResult<ScheduledRevocation> {
+        let account = canonical_account(account);
+        let mut token = self
+            .tokens
+            .get_mut(&token_id)
+            .ok_or(ContractError::InvalidTokenId)?;
+        token
+            .scheduled_revocations
+            .remove_and_get(&account)
+            .ok_or(ContractError::Custom(CustomError::RevocationNotScheduled))
+    }
+
+    /// Gets the scheduled revocation of the balance of an account.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    /// - If no revocation is scheduled, None is returned.
+    pub(crate) fn get_scheduled_revocation(
         &self,
         token_id: ContractTokenId,
         account: AccountAddress,
-        now: Timestamp,
-    ) -> ContractResult<ContractTokenAmount> {
+    ) -> ContractResult<Option<ScheduledRevocation>> {
+        let account = canonical_account(account);
         self.tokens
             .get(&token_id)
             .map_or(Err(ContractError::InvalidTokenId), |token| {
-                Ok(token.get_account_balance(account, now))
+                Ok(token
+                    .scheduled_revocations
+                    .get(&account)
+                    .map(|revocation| *revocation))
             })
     }
 
-    /// Get the Account Balance Expiry for a token.
+    /// Freezes or unfreezes the balance of an account, keeping its amount and expiry.
     /// - If the token does not exist, InvalidTokenId is thrown.
-    /// - If the account does not have a balance, None is returned.
-    pub(crate) fn get_account_balance_expiry(
+    /// - If the account does not have a balance, CredentialNotFound is thrown.
+    pub(crate) fn set_balance_frozen(
+        &mut self,
+        token_id: ContractTokenId,
+        account: AccountAddress,
+        frozen: bool,
+    ) -> ContractResult<()> {
+        let account = canonical_account(account);
+        let mut token = self
+            .tokens
+            .get_mut(&token_id)
+            .ok_or(ContractError::InvalidTokenId)?;
+        let mut balance = token
+            .balances
+            .get_mut(&account)
+            .ok_or(ContractError::Custom(CustomError::CredentialNotFound))?;
+        balance.frozen = frozen;
+        Ok(())
+    }
+
+    /// Hides or shows the balance of an account of a token, see `setVisibility`.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    pub(crate) fn set_balance_hidden(
+        &mut self,
+        token_id: ContractTokenId,
+        account: AccountAddress,
+        hidden: bool,
+    ) -> ContractResult<()> {
+        let account = canonical_account(account);
+        let mut token = self
+            .tokens
+            .get_mut(&token_id)
+            .ok_or(ContractError::InvalidTokenId)?;
+        if hidden {
+            token.hidden_holders.insert(account);
+        } else {
+            token.hidden_holders.remove(&account);
+        }
+        Ok(())
+    }
+
+    /// Checks if an account hides its balance of a token.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    pub(crate) fn is_balance_hidden(
+        &self,
+        token_id: &ContractTokenId,
+        account: AccountAddress,
+    ) -> ContractResult<bool> {
+        let account = canonical_account(account);
+        self.tokens
+            .get(token_id)
+            .map_or(Err(ContractError::InvalidTokenId), |token| {
+                Ok(token.hidden_holders.contains(&account))
+            })
+    }
+
+    /// Checks if the reader may see balances of the account hidden by it, i.e. the reader is
+    /// the account itself, the admin or has the verifier role.
+    pub(crate) fn can_read_hidden_balance(
+        &self,
+        reader: &Address,
+        account: AccountAddress,
+        owner: AccountAddress,
+    ) -> bool {
+        let is_holder = matches!(reader, Address::Account(reader) if canonical_account(*reader) == canonical_account(account));
+        is_holder || self.is_contract_admin(reader, owner) || self.has_role(Role::Verifier, reader)
+    }
+
+    /// Removes up to max_entries balances of a token.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    /// - Revocations scheduled for the removed balances are dropped.
+    /// - Returns the accounts of the removed balances with their amount at `now`, 0 for balances
+    ///   which were not valid, and whether the token has no balances left.
+    pub(crate) fn clear_balances(
+        &mut self,
+        token_id: ContractTokenId,
+        max_entries: u32,
+        now: Timestamp,
+    ) -> ContractResult<(Vec<(AccountAddress, ContractTokenAmount)>, bool)> {
+        let mut token = self
+            .tokens
+            .get_mut(&token_id)
+            .ok_or(ContractError::InvalidTokenId)?;
+        let accounts: Vec<AccountAddress> = token
+            .balances
+            .iter()
+            .take(max_entries as usize)
+            .map(|(account, _)| *account)
+            .collect();
+
+        let mut removed = Vec::new();
+        for account in accounts {
+            if let Some(balance) = token.balances.remove_and_get(&account) {
+                if balance.amount > ContractTokenAmount::from(0) {
+                    token.remove_holder(&mut self.active_balances);
+                }
+                token.scheduled_revocations.remove(&account);
+                self.expiry_index
+                    .remove(&ExpiryIndexKey::new(balance.expiry, token_id, account));
+                removed.push((account, balance.get_balance(now)));
+            }
+        }
+        let done = token.balances.iter().next().is_none();
+        Ok((removed, done))
+    }
+
+    /// Removes balances which expired before the current day, using the expiry index.
+    /// - At most max_entries entries of the index are processed.
+    /// - Entries of removed tokens and replaced balances are dropped from the index.
+    /// - Revocations scheduled for the removed balances are dropped.
+    /// - Returns the number of processed entries, 0 if nothing is left to prune,
+    ///   and the tokens, accounts and expiries of the removed balances.
+    pub(crate) fn prune_expired(
+        &mut self,
+        max_entries: u32,
+        now: Timestamp,
+    ) -> (u32, Vec<(ContractTokenId, AccountAddress, Timestamp)>) {
+        let today = day_of(now);
+        let keys: Vec<ExpiryIndexKey> = self
+            .expiry_index
+            .iter()
+            .take(max_entries as usize)
+            .map(|key| *key)
+            .take_while(|key| key.day() < today)
+            .collect();
+
+        let mut removed = Vec::new();
+        for key in keys.iter() {
+            self.expiry_index.remove(key);
+            if let Some(mut token) = self.tokens.get_mut(&key.token_id) {
+                // Only remove the balance if it still is the indexed one.
+                let is_indexed = token
+                    .balances
+                    .get(&key.account)
+                    .map_or(false, |balance| day_of(balance.expiry) == key.day());
+                if is_indexed {
+                    if let Some(balance) = token.balances.remove_and_get(&key.account) {
+                        if balance.amount > ContractTokenAmount::from(0) {
+                            token.remove_holder(&mut self.active_balances);
+                        }
+                        token.scheduled_revocations.remove(&key.account);
+                        removed.push((key.token_id, key.account, balance.expiry));
+                    }
+                }
+            }
+        }
+        (keys.len() as u32, removed)
+    }
+
+    /// Gets the balances expiring at or after `from` and before `to`, ordered by the day of their expiry.
+    /// - The first `skip` balances are skipped and at most `limit` balances are returned.
+    /// - Returns whether more balances are left after the returned ones.
+    /// - Balances for which `visible` returns false are left out before skipping.
+    /// - Index entries before the day of `from` are still visited, sweeping expired balances keeps this cheap.
+    pub(crate) fn expiring_between(
         &self,
+        from: Timestamp,
+        to: Timestamp,
+        skip: u32,
+        limit: u16,
+        visible: impl Fn(&ContractTokenId, AccountAddress) -> bool,
+    ) -> (Vec<(ContractTokenId, AccountAddress, Timestamp)>, bool) {
+        let (from_day, to_day) = (day_of(from), day_of(to));
+        let mut balances = self
+            .expiry_index
+            .iter()
+            .skip_while(|key| key.day() < from_day)
+            .take_while(|key| key.day() <= to_day)
+            .filter_map(|key| {
+                // Skip entries of removed tokens and replaced balances.
+                let expiry = self
+                    .tokens
+                    .get(&key.token_id)?
+                    .get_account_balance_expiry(key.account)?;
+                (day_of(expiry) == key.day() && from <= expiry && expiry < to).then_some((
+                    key.token_id,
+                    key.account,
+                    expiry,
+                ))
+            })
+            .filter(|(token_id, account, _)| visible(token_id, *account))
+            .skip(skip as usize);
+        let page = balances.by_ref().take(usize::from(limit)).collect();
+        (page, balances.next().is_some())
+    }
+
+    /// Replaces the expiry of an existing balance and returns the amount of the balance.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    /// - If the account does not have a balance, CredentialNotFound is thrown.
+    pub(crate) fn renew(
+        &mut self,
         token_id: ContractTokenId,
         account: AccountAddress,
-    ) -> ContractResult<Option<Timestamp>> {
+        expiry: Timestamp,
+    ) -> ContractResult<ContractTokenAmount> {
+        let account = canonical_account(account);
+        let mut token = self
+            .tokens
+            .get_mut(&token_id)
+            .ok_or(ContractError::InvalidTokenId)?;
+        let amount = {
+            let mut balance = token
+                .balances
+                .get_mut(&account)
+                .ok_or(ContractError::Custom(CustomError::CredentialNotFound))?;
+            self.expiry_index
+                .remove(&ExpiryIndexKey::new(balance.expiry, token_id, account));
+            balance.expiry = expiry;
+            balance.amount
+        };
+        self.expiry_index
+            .insert(ExpiryIndexKey::new(expiry, token_id, account));
+        if amount > ContractTokenAmount::from(0) {
+            token.extend_latest_expiry(expiry);
+        }
+        Ok(amount)
+    }
+
+    /// Extends the expiry of the balances of a token which expire on the same day as `bucket`,
+    /// in the order of their accounts starting at `cursor`.
+    /// - At most `limit` balances are visited, balances expiring at or after `expiry` are kept.
+    /// - Returns the extended balances with their amount and the account to continue at, if any.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    pub(crate) fn extend_expiry_cohort(
+        &mut self,
+        token_id: ContractTokenId,
+        bucket: Timestamp,
+        expiry: Timestamp,
+        cursor: Option<AccountAddress>,
+        limit: u16,
+    ) -> ContractResult<(
+        Vec<(AccountAddress, ContractTokenAmount)>,
+        Option<AccountAddress>,
+    )> {
+        ensure!(self.has_token(token_id), ContractError::InvalidTokenId);
+        let cursor = cursor.map(canonical_account);
+        let bucket_day = day_of(bucket);
+        let mut accounts: Vec<AccountAddress> = self
+            .expiry_index
+            .iter()
+            .skip_while(|key| key.day() < bucket_day)
+            .take_while(|key| key.day() == bucket_day)
+            .filter(|key| key.token_id == token_id && cursor.map_or(true, |c| key.account >= c))
+            .map(|key| key.account)
+            .take(usize::from(limit) + 1)
+            .collect();
+        let next = if accounts.len() > usize::from(limit) {
+            accounts.pop()
+        } else {
+            None
+        };
+
+        let mut extended = Vec::new();
+        for account in accounts {
+            let current = self
+                .tokens
+                .get(&token_id)
+                .and_then(|token| token.get_account_balance_expiry(account));
+            let hard_expiry = self.tokens.get(&token_id).and_then(|token| {
+                token
+                    .balances
+                    .get(&account)
+                    .and_then(|balance| balance.hard_expiry)
+            });
+            // Skip replaced balances, balances which already expire later and balances whose
+            // hard expiry is before the new expiry.
+            match current {
+                Some(current)
+                    if day_of(current) == bucket_day
+                        && current < expiry
+                        && hard_expiry.map_or(true, |hard_expiry| expiry <= hard_expiry) =>
+                {
+                    let amount = self.renew(token_id, account, expiry)?;
+                    extended.push((account, amount));
+                }
+                _ => {}
+            }
+        }
+        Ok((extended, next))
+    }
+
+    /// Gets up to `limit` balances of all tokens, including expired balances, starting at the
+    /// cursor. Balances are ordered by the serialized token id and account, like the state.
+    /// - Returns the balances and the cursor of the next balance, None once all balances are visited.
+    pub(crate) fn export_balances(
+        &self,
+        cursor: Option<(ContractTokenId, AccountAddress)>,
+        limit: u16,
+    ) -> (
+        Vec<(ContractTokenId, AccountAddress, TokenBalanceState)>,
+        Option<(ContractTokenId, AccountAddress)>,
+    ) {
+        let cursor_token = cursor.map(|(token_id, _)| to_bytes(&token_id));
+        let cursor = cursor.map(|(token_id, account)| to_bytes(&(token_id, account)));
+        let mut balances = Vec::new();
+        for (token_id, token) in self.tokens.iter() {
+            // Skip tokens before the cursor without visiting their balances.
+            if cursor_token
+                .as_ref()
+                .map_or(false, |c| &to_bytes(&*token_id) < c)
+            {
+                continue;
+            }
+            for (account, balance) in token.balances.iter() {
+                if cursor
+                    .as_ref()
+                    .map_or(false, |c| &to_bytes(&(*token_id, *account)) < c)
+                {
+                    continue;
+                }
+                if balances.len() == usize::from(limit) {
+                    return (balances, Some((*token_id, *account)));
+                }
+                balances.push((*token_id, *account, balance.clone()));
+            }
+        }
+        (balances, None)
+    }
+
+    /// Replaces the amount of an existing balance, keeping its expiry.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    /// - If the account does not have a balance, CredentialNotFound is thrown.
+    /// - An increase of the amount is added to the total minted amount of the token.
+    /// - Returns the previous amount.
+    pub(crate) fn adjust_amount(
+        &mut self,
+        token_id: ContractTokenId,
+        account: AccountAddress,
+        amount: ContractTokenAmount,
+    ) -> ContractResult<ContractTokenAmount> {
+        let account = canonical_account(account);
+        let mut token = self
+            .tokens
+            .get_mut(&token_id)
+            .ok_or(ContractError::InvalidTokenId)?;
+        let (previous, expiry) = {
+            let mut balance = token
+                .balances
+                .get_mut(&account)
+                .ok_or(ContractError::Custom(CustomError::CredentialNotFound))?;
+            let previous = balance.amount;
+            balance.amount = amount;
+            (previous, balance.expiry)
+        };
+        if previous > ContractTokenAmount::from(0) {
+            token.remove_holder(&mut self.active_balances);
+        }
+        if amount > ContractTokenAmount::from(0) {
+            token.add_holder(expiry, &mut self.active_balances);
+        }
+        if amount > previous {
+            self.mint_count += 1;
+            token.total_minted = token
+                .total_minted
+                .saturating_add(u64::from(amount.0 - previous.0));
+        }
+        Ok(previous)
+    }
+
+    /// Appends an entry to the history of a balance, dropping the oldest entry if the history is full.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    pub(crate) fn record_history(
+        &mut self,
+        token_id: ContractTokenId,
+        account: AccountAddress,
+        entry: HistoryEntry,
+    ) -> ContractResult<()> {
+        let account = canonical_account(account);
+        let mut token = self
+            .tokens
+            .get_mut(&token_id)
+            .ok_or(ContractError::InvalidTokenId)?;
+        let mut history = token.history.entry(account).or_insert_with(Vec::new);
+        if history.len() >= MAX_HISTORY_ENTRIES {
+            history.remove(0);
+        }
+        history.push(entry);
+        Ok(())
+    }
+
+    /// Gets the history of a balance, oldest first.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    /// - If the balance has no history, an empty list is returned.
+    pub(crate) fn get_history(
+        &self,
+        token_id: &ContractTokenId,
+        account: AccountAddress,
+    ) -> ContractResult<Vec<HistoryEntry>> {
+        let account = canonical_account(account);
         self.tokens
-            .get(&token_id)
+            .get(token_id)
             .map_or(Err(ContractError::InvalidTokenId), |token| {
-                Ok(token.get_account_balance_expiry(account))
+                Ok(token
+                    .history
+                    .get(&account)
+                    .map_or_else(Vec::new, |history| history.clone()))
             })
     }
 
-    /// Gets the token metadata of the given token.
+    /// Adds a burned amount to the total burned amount of the token.
     /// - If the token does not exist, InvalidTokenId is thrown.
-    pub(crate) fn get_token_metadata(
+    pub(crate) fn record_burn(
+        &mut self,
+        token_id: ContractTokenId,
+        amount: ContractTokenAmount,
+    ) -> ContractResult<()> {
+        let mut token = self
+            .tokens
+            .get_mut(&token_id)
+            .ok_or(ContractError::InvalidTokenId)?;
+        token.total_burned = token.total_burned.saturating_add(u64::from(amount.0));
+        self.burn_count += 1;
+        Ok(())
+    }
+
+    /// Gets the number of tokens, mints and burns of valid amounts.
+    pub(crate) fn get_counters(&self) -> (u32, u64, u64) {
+        (self.token_count, self.mint_count, self.burn_count)
+    }
+
+    /// Gets the number of stored balances > 0 over all tokens, including expired balances
+    /// which have not been swept yet.
+    pub(crate) fn active_balances(&self) -> u64 {
+        self.active_balances
+    }
+
+    /// Gets the total minted and total burned amounts of the token.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    pub(crate) fn get_token_totals(
         &self,
         token_id: &ContractTokenId,
-    ) -> ContractResult<MetadataUrl> {
+    ) -> ContractResult<(u64, u64)> {
         self.tokens
             .get(token_id)
             .map_or(Err(ContractError::InvalidTokenId), |token| {
-                Ok(token.metadata.clone())
+                Ok((token.total_minted, token.total_burned))
+            })
+    }
+
+    /// Gets the revocation of a token balance.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    /// - If the balance has not been revoked, None is returned.
+    pub(crate) fn get_revocation(
+        &self,
+        token_id: ContractTokenId,
+        account: AccountAddress,
+    ) -> ContractResult<Option<RevocationInfo>> {
+        let account = canonical_account(account);
+        self.tokens
+            .get(&token_id)
+            .map_or(Err(ContractError::InvalidTokenId), |token| {
+                Ok(token
+                    .revocations
+                    .get(&account)
+                    .map(|revocation| revocation.clone()))
             })
     }
+
+    /// Get Account balance for a token.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    /// - If the account does not have a balance, 0 balance is returned.
+    /// - If the balance has expired, 0 balance is returned.
+    /// - If the balance of a token it depends on is not valid, 0 balance is returned.
+    pub(crate) fn get_account_balance(
+        &self,
+        token_id: ContractTokenId,
+        account: AccountAddress,
+        now: Timestamp,
+    ) -> ContractResult<ContractTokenAmount> {
+        let balance = self
+            .tokens
+            .get(&token_id)
+            .map_or(Err(ContractError::InvalidTokenId), |token| {
+                Ok(token.get_account_balance(account, now))
+            })?;
+        if self.has_valid_dependency(&token_id, account, now) {
+            Ok(balance)
+        } else {
+            Ok(ContractTokenAmount::from(0))
+        }
+    }
+
+    /// Get the stored balance state of an account for a token.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    /// - If the account does not have a balance, None is returned.
+    pub(crate) fn get_account_balance_state(
+        &self,
+        token_id: ContractTokenId,
+        account: AccountAddress,
+    ) -> ContractResult<Option<TokenBalanceState>> {
+        let account = canonical_account(account);
+        self.tokens
+            .get(&token_id)
+            .map_or(Err(ContractError::InvalidTokenId), |token| {
+                Ok(token.balances.get(&account).map(|balance| balance.clone()))
+            })
+    }
+
+    /// Gets the stored balances of an account of all tokens, including expired and frozen balances.
+    pub(crate) fn get_account_balances(
+        &self,
+        account: AccountAddress,
+    ) -> Vec<(ContractTokenId, TokenBalanceState)> {
+        let account = canonical_account(account);
+        self.tokens
+            .iter()
+            .filter_map(|(token_id, token)| {
+                token
+                    .balances
+                    .get(&account)
+                    .map(|balance| (*token_id, balance.clone()))
+            })
+            .collect()
+    }
+
+    /// Get the credential status of an account for a token.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    /// - If the balance has been revoked, the status is Revoked.
+    /// - If the balance is active but a balance it depends on is not, the status is DependencyInvalid.
+    /// - If the account does not have a balance, CredentialNotFound is thrown.
+    pub(crate) fn get_credential_status(
+        &self,
+        token_id: ContractTokenId,
+        account: AccountAddress,
+        now: Timestamp,
+    ) -> ContractResult<CredentialStatus> {
+        if let Some(balance) = self.get_account_balance_state(token_id, account)? {
+            return Ok(match balance.get_status(now) {
+                CredentialStatus::Active if !self.has_valid_dependency(&token_id, account, now) => {
+                    CredentialStatus::DependencyInvalid
+                }
+                status => status,
+            });
+        }
+        match self.get_revocation(token_id, account)? {
+            Some(_) => Ok(CredentialStatus::Revoked),
+            None => bail!(ContractError::Custom(CustomError::CredentialNotFound)),
+        }
+    }
+
+    /// Get the Account Balance Expiry for a token.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    /// - If the account does not have a balance, None is returned.
+    pub(crate) fn get_account_balance_expiry(
+        &self,
+        token_id: ContractTokenId,
+        account: AccountAddress,
+    ) -> ContractResult<Option<Timestamp>> {
+        self.tokens
+            .get(&token_id)
+            .map_or(Err(ContractError::InvalidTokenId), |token| {
+                Ok(token.get_account_balance_expiry(account))
+            })
+    }
+
+    /// Get the time from which the Account Balance of a token is valid.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    /// - If the account does not have a balance, None is returned.
+    /// - If the balance is valid immediately, None is returned.
+    pub(crate) fn get_account_balance_valid_from(
+        &self,
+        token_id: ContractTokenId,
+        account: AccountAddress,
+    ) -> ContractResult<Option<Timestamp>> {
+        self.tokens
+            .get(&token_id)
+            .map_or(Err(ContractError::InvalidTokenId), |token| {
+                Ok(token.get_account_balance_valid_from(account))
+            })
+    }
+
+    /// Gets the total supply and the number of holders of the given token.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    pub(crate) fn get_token_supply(
+        &self,
+        token_id: &ContractTokenId,
+        now: Timestamp,
+    ) -> ContractResult<(u64, u32)> {
+        self.tokens
+            .get(token_id)
+            .map_or(Err(ContractError::InvalidTokenId), |token| {
+                Ok(token.get_supply(now))
+            })
+    }
+
+    /// Gets the token metadata of the given token.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    pub(crate) fn get_token_metadata(
+        &self,
+        token_id: &ContractTokenId,
+    ) -> ContractResult<MetadataUrl> {
+        self.tokens
+            .get(token_id)
+            .map_or(Err(ContractError::InvalidTokenId), |token| {
+                Ok(token.metadata.clone())
+            })
+    }
+
+    /// Sets or removes the metadata url of a token for a locale.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    pub(crate) fn set_localized_metadata(
+        &mut self,
+        token_id: ContractTokenId,
+        locale: String,
+        metadata_url: Option<MetadataUrl>,
+    ) -> ContractResult<()> {
+        let mut token = self
+            .tokens
+            .get_mut(&token_id)
+            .ok_or(ContractError::InvalidTokenId)?;
+        match metadata_url {
+            Some(metadata_url) => {
+                token.localized_metadata.insert(locale, metadata_url);
+            }
+            None => {
+                token.localized_metadata.remove(&locale);
+            }
+        }
+        Ok(())
+    }
+
+    /// Gets the metadata url of a token for a locale.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    /// - If the token has no metadata url for the locale, the default metadata url is returned.
+    pub(crate) fn get_localized_metadata(
+        &self,
+        token_id: &ContractTokenId,
+        locale: &String,
+    ) -> ContractResult<MetadataUrl> {
+        self.tokens
+            .get(token_id)
+            .map_or(Err(ContractError::InvalidTokenId), |token| {
+                Ok(token
+                    .localized_metadata
+                    .get(locale)
+                    .map_or_else(|| token.metadata.clone(), |url| url.clone()))
+            })
+    }
+
+    /// Sets or removes the metadata url attached to the balance of an account.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    pub(crate) fn set_holder_metadata(
+        &mut self,
+        token_id: ContractTokenId,
+        account: AccountAddress,
+        metadata_url: Option<MetadataUrl>,
+    ) -> ContractResult<()> {
+        let account = canonical_account(account);
+        let mut token = self
+            .tokens
+            .get_mut(&token_id)
+            .ok_or(ContractError::InvalidTokenId)?;
+        match metadata_url {
+            Some(metadata_url) => {
+                token.holder_metadata.insert(account, metadata_url);
+            }
+            None => {
+                token.holder_metadata.remove(&account);
+            }
+        }
+        Ok(())
+    }
+
+    /// Gets the metadata url attached to the balance of an account.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    /// - If the balance has no metadata url, the metadata url of the token is returned.
+    pub(crate) fn get_holder_metadata(
+        &self,
+        token_id: &ContractTokenId,
+        account: AccountAddress,
+    ) -> ContractResult<MetadataUrl> {
+        let account = canonical_account(account);
+        self.tokens
+            .get(token_id)
+            .map_or(Err(ContractError::InvalidTokenId), |token| {
+                Ok(token
+                    .holder_metadata
+                    .get(&account)
+                    .map_or_else(|| token.metadata.clone(), |url| url.clone()))
+            })
+    }
+
+    /// Sets the category of a token.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    pub(crate) fn set_token_category(
+        &mut self,
+        token_id: ContractTokenId,
+        category: TokenCategory,
+    ) -> ContractResult<()> {
+        let mut token = self
+            .tokens
+            .get_mut(&token_id)
+            .ok_or(ContractError::InvalidTokenId)?;
+        token.category = category;
+        Ok(())
+    }
+
+    /// Gets the category of a token.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    pub(crate) fn get_token_category(
+        &self,
+        token_id: &ContractTokenId,
+    ) -> ContractResult<TokenCategory> {
+        self.tokens
+            .get(token_id)
+            .map_or(Err(ContractError::InvalidTokenId), |token| {
+                Ok(token.category)
+            })
+    }
+
+    /// Pauses or unpauses minting of a token.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    pub(crate) fn set_token_paused(
+        &mut self,
+        token_id: ContractTokenId,
+        paused: bool,
+    ) -> ContractResult<()> {
+        let mut token = self
+            .tokens
+            .get_mut(&token_id)
+            .ok_or(ContractError::InvalidTokenId)?;
+        token.paused = paused;
+        Ok(())
+    }
+
+    /// Pauses or resumes minting of every token of a category and returns the number of tokens.
+    /// Tokens added to the category later are not affected.
+    pub(crate) fn set_category_paused(&mut self, category: TokenCategory, paused: bool) -> u32 {
+        let token_ids = self.tokens_by_category(category);
+        for token_id in token_ids.iter() {
+            if let Some(mut token) = self.tokens.get_mut(token_id) {
+                token.paused = paused;
+            }
+        }
+        token_ids.len() as u32
+    }
+
+    /// Gets the tokens whose minting is paused.
+    pub(crate) fn paused_tokens(&self) -> Vec<ContractTokenId> {
+        self.tokens
+            .iter()
+            .filter(|(_, token)| token.paused)
+            .map(|(token_id, _)| *token_id)
+            .collect()
+    }
+
+    /// Checks if minting of a token is paused.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    pub(crate) fn is_token_paused(&self, token_id: &ContractTokenId) -> ContractResult<bool> {
+        self.tokens
+            .get(token_id)
+            .map_or(Err(ContractError::InvalidTokenId), |token| Ok(token.paused))
+    }
+
+    /// Deprecates a token, which blocks minting for good.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    pub(crate) fn deprecate_token(&mut self, token_id: ContractTokenId) -> ContractResult<()> {
+        let mut token = self
+            .tokens
+            .get_mut(&token_id)
+            .ok_or(ContractError::InvalidTokenId)?;
+        token.deprecated = true;
+        Ok(())
+    }
+
+    /// Checks if a token has been deprecated.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    pub(crate) fn is_token_deprecated(&self, token_id: &ContractTokenId) -> ContractResult<bool> {
+        self.tokens
+            .get(token_id)
+            .map_or(Err(ContractError::InvalidTokenId), |token| {
+                Ok(token.deprecated)
+            })
+    }
+
+    /// Sets the issuer of a token.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    pub(crate) fn set_token_issuer(
+        &mut self,
+        token_id: ContractTokenId,
+        issuer: Option<Address>,
+    ) -> ContractResult<()> {
+        let mut token = self
+            .tokens
+            .get_mut(&token_id)
+            .ok_or(ContractError::InvalidTokenId)?;
+        token.issuer = issuer;
+        Ok(())
+    }
+
+    /// Gets the issuer of a token.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    pub(crate) fn get_token_issuer(
+        &self,
+        token_id: &ContractTokenId,
+    ) -> ContractResult<Option<Address>> {
+        self.tokens
+            .get(token_id)
+            .map_or(Err(ContractError::InvalidTokenId), |token| Ok(token.issuer))
+    }
+
+    /// Sets the validity of minted balances of a token which do not have an expiry.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    pub(crate) fn set_token_default_validity(
+        &mut self,
+        token_id: ContractTokenId,
+        default_validity: Option<Duration>,
+    ) -> ContractResult<()> {
+        let mut token = self
+            .tokens
+            .get_mut(&token_id)
+            .ok_or(ContractError::InvalidTokenId)?;
+        token.default_validity = default_validity;
+        Ok(())
+    }
+
+    /// Gets the validity of minted balances of a token which do not have an expiry.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    pub(crate) fn get_token_default_validity(
+        &self,
+        token_id: &ContractTokenId,
+    ) -> ContractResult<Option<Duration>> {
+        self.tokens
+            .get(token_id)
+            .map_or(Err(ContractError::InvalidTokenId), |token| {
+                Ok(token.default_validity)
+            })
+    }
+
+    /// Sets who may extend the expiry of existing balances of a token.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    pub(crate) fn set_token_renewal_policy(
+        &mut self,
+        token_id: ContractTokenId,
+        renewal_policy: RenewalPolicy,
+    ) -> ContractResult<()> {
+        let mut token = self
+            .tokens
+            .get_mut(&token_id)
+            .ok_or(ContractError::InvalidTokenId)?;
+        token.renewal_policy = renewal_policy;
+        Ok(())
+    }
+
+    /// Gets who may extend the expiry of existing balances of a token.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    pub(crate) fn get_token_renewal_policy(
+        &self,
+        token_id: &ContractTokenId,
+    ) -> ContractResult<RenewalPolicy> {
+        self.tokens
+            .get(token_id)
+            .map_or(Err(ContractError::InvalidTokenId), |token| {
+                Ok(token.renewal_policy)
+            })
+    }
+
+    /// Sets whether minting a token requires the consent of the holder.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    pub(crate) fn set_token_consent_required(
+        &mut self,
+        token_id: ContractTokenId,
+        consent_required: bool,
+    ) -> ContractResult<()> {
+        let mut token = self
+            .tokens
+            .get_mut(&token_id)
+            .ok_or(ContractError::InvalidTokenId)?;
+        token.consent_required = consent_required;
+        Ok(())
+    }
+
+    /// Checks if minting a token requires the consent of the holder.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    pub(crate) fn is_token_consent_required(
+        &self,
+        token_id: &ContractTokenId,
+    ) -> ContractResult<bool> {
+        self.tokens
+            .get(token_id)
+            .map_or(Err(ContractError::InvalidTokenId), |token| {
+                Ok(token.consent_required)
+            })
+    }
+
+    /// Records or withdraws the consent of a holder to be issued a token by an issuer.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    pub(crate) fn set_issuance_consent(
+        &mut self,
+        token_id: ContractTokenId,
+        holder: AccountAddress,
+        issuer: Address,
+        consent: bool,
+    ) -> ContractResult<()> {
+        let holder = canonical_account(holder);
+        let mut token = self
+            .tokens
+            .get_mut(&token_id)
+            .ok_or(ContractError::InvalidTokenId)?;
+        if consent {
+            token.consents.insert((holder, issuer));
+        } else {
+            token.consents.remove(&(holder, issuer));
+        }
+        Ok(())
+    }
+
+    /// Checks if a holder may be issued a token by an issuer.
+    /// - If the token does not require consent, true is returned.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    pub(crate) fn has_issuance_consent(
+        &self,
+        token_id: &ContractTokenId,
+        holder: AccountAddress,
+        issuer: Address,
+    ) -> ContractResult<bool> {
+        let holder = canonical_account(holder);
+        self.tokens
+            .get(token_id)
+            .map_or(Err(ContractError::InvalidTokenId), |token| {
+                Ok(!token.consent_required || token.consents.contains(&(holder, issuer)))
+            })
+    }
+
+    /// Sets whether balances of a token can only be minted to accounts on its allowlist.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    pub(crate) fn set_token_allowlist_required(
+        &mut self,
+        token_id: ContractTokenId,
+        allowlist_required: bool,
+    ) -> ContractResult<()> {
+        let mut token = self
+            .tokens
+            .get_mut(&token_id)
+            .ok_or(ContractError::InvalidTokenId)?;
+        token.allowlist_required = allowlist_required;
+        Ok(())
+    }
+
+    /// Checks if balances of a token can only be minted to accounts on its allowlist.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    pub(crate) fn is_token_allowlist_required(
+        &self,
+        token_id: &ContractTokenId,
+    ) -> ContractResult<bool> {
+        self.tokens
+            .get(token_id)
+            .map_or(Err(ContractError::InvalidTokenId), |token| {
+                Ok(token.allowlist_required)
+            })
+    }
+
+    /// Adds an account to or removes it from the allowlist of a token.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    pub(crate) fn update_allowlist(
+        &mut self,
+        token_id: ContractTokenId,
+        account: AccountAddress,
+        allowed: bool,
+    ) -> ContractResult<()> {
+        let account = canonical_account(account);
+        let mut token = self
+            .tokens
+            .get_mut(&token_id)
+            .ok_or(ContractError::InvalidTokenId)?;
+        if allowed {
+            token.allowlist.insert(account);
+        } else {
+            token.allowlist.remove(&account);
+        }
+        Ok(())
+    }
+
+    /// Checks if a balance of a token may be minted to an account.
+    /// - If the token does not require the allowlist, true is returned.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    pub(crate) fn is_allowlisted(
+        &self,
+        token_id: &ContractTokenId,
+        account: AccountAddress,
+    ) -> ContractResult<bool> {
+        let account = canonical_account(account);
+        self.tokens
+            .get(token_id)
+            .map_or(Err(ContractError::InvalidTokenId), |token| {
+                Ok(!token.allowlist_required || token.allowlist.contains(&account))
+            })
+    }
+
+    /// Sets whether a token only stores commitments of balances.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    pub(crate) fn set_token_private(
+        &mut self,
+        token_id: ContractTokenId,
+        private: bool,
+    ) -> ContractResult<()> {
+        let mut token = self
+            .tokens
+            .get_mut(&token_id)
+            .ok_or(ContractError::InvalidTokenId)?;
+        token.private = private;
+        Ok(())
+    }
+
+    /// Checks if a token only stores commitments of balances.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    pub(crate) fn is_token_private(&self, token_id: &ContractTokenId) -> ContractResult<bool> {
+        self.tokens
+            .get(token_id)
+            .map_or(
+                Err(ContractError::InvalidTokenId),
+                |token| Ok(token.private),
+            )
+    }
+
+    /// Sets whether holders of a token must be confirmed as unique persons.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    pub(crate) fn set_token_unique_person(
+        &mut self,
+        token_id: ContractTokenId,
+        unique_person: bool,
+    ) -> ContractResult<()> {
+        let mut token = self
+            .tokens
+            .get_mut(&token_id)
+            .ok_or(ContractError::InvalidTokenId)?;
+        token.unique_person = unique_person;
+        Ok(())
+    }
+
+    /// Checks if holders of a token must be confirmed as unique persons.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    pub(crate) fn is_token_unique_person(
+        &self,
+        token_id: &ContractTokenId,
+    ) -> ContractResult<bool> {
+        self.tokens
+            .get(token_id)
+            .map_or(Err(ContractError::InvalidTokenId), |token| {
+                Ok(token.unique_person)
+            })
+    }
+
+    /// Sets the decimals and unit of the amounts of a token.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    pub(crate) fn set_token_amount_unit(
+        &mut self,
+        token_id: ContractTokenId,
+        amount_unit: AmountUnit,
+    ) -> ContractResult<()> {
+        let mut token = self
+            .tokens
+            .get_mut(&token_id)
+            .ok_or(ContractError::InvalidTokenId)?;
+        token.amount_unit = amount_unit;
+        Ok(())
+    }
+
+    /// Gets the decimals and unit of the amounts of a token.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    pub(crate) fn get_token_amount_unit(
+        &self,
+        token_id: &ContractTokenId,
+    ) -> ContractResult<AmountUnit> {
+        self.tokens
+            .get(token_id)
+            .map_or(Err(ContractError::InvalidTokenId), |token| {
+                Ok(token.amount_unit.clone())
+            })
+    }
+
+    /// Sets the rule for the amounts of a token which can be minted.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    pub(crate) fn set_token_amount_rule(
+        &mut self,
+        token_id: ContractTokenId,
+        amount_rule: AmountRule,
+    ) -> ContractResult<()> {
+        let mut token = self
+            .tokens
+            .get_mut(&token_id)
+            .ok_or(ContractError::InvalidTokenId)?;
+        token.amount_rule = amount_rule;
+        Ok(())
+    }
+
+    /// Gets the rule for the amounts of a token which can be minted.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    pub(crate) fn get_token_amount_rule(
+        &self,
+        token_id: &ContractTokenId,
+    ) -> ContractResult<AmountRule> {
+        self.tokens
+            .get(token_id)
+            .map_or(Err(ContractError::InvalidTokenId), |token| {
+                Ok(token.amount_rule)
+            })
+    }
+
+    /// Sets the smallest amount of a token which can be minted.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    pub(crate) fn set_token_min_amount(
+        &mut self,
+        token_id: ContractTokenId,
+        min_amount: ContractTokenAmount,
+    ) -> ContractResult<()> {
+        let mut token = self
+            .tokens
+            .get_mut(&token_id)
+            .ok_or(ContractError::InvalidTokenId)?;
+        token.min_amount = min_amount;
+        Ok(())
+    }
+
+    /// Gets the smallest amount of a token which can be minted.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    pub(crate) fn get_token_min_amount(
+        &self,
+        token_id: &ContractTokenId,
+    ) -> ContractResult<ContractTokenAmount> {
+        self.tokens
+            .get(token_id)
+            .map_or(Err(ContractError::InvalidTokenId), |token| {
+                Ok(token.min_amount)
+            })
+    }
+
+    /// Sets or removes the metadata schema checksum of a token.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    pub(crate) fn set_token_schema_ref(
+        &mut self,
+        token_id: ContractTokenId,
+        schema_ref: Option<HashSha2256>,
+    ) -> ContractResult<()> {
+        let mut token = self
+            .tokens
+            .get_mut(&token_id)
+            .ok_or(ContractError::InvalidTokenId)?;
+        token.schema_ref = schema_ref;
+        Ok(())
+    }
+
+    /// Gets the metadata schema checksum of a token.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    pub(crate) fn get_token_schema_ref(
+        &self,
+        token_id: &ContractTokenId,
+    ) -> ContractResult<Option<HashSha2256>> {
+        self.tokens
+            .get(token_id)
+            .map_or(Err(ContractError::InvalidTokenId), |token| {
+                Ok(token.schema_ref)
+            })
+    }
+
+    /// Sets or removes the token whose balances the balances of a token depend on.
+    /// - If either token does not exist, InvalidTokenId is thrown.
+    /// - If the dependency would form a cycle, InvalidDependency is thrown.
+    pub(crate) fn set_token_dependency(
+        &mut self,
+        token_id: ContractTokenId,
+        depends_on: Option<ContractTokenId>,
+    ) -> ContractResult<()> {
+        ensure!(self.has_token(token_id), ContractError::InvalidTokenId);
+        let mut next = depends_on;
+        while let Some(dependency) = next {
+            ensure!(
+                dependency != token_id,
+                ContractError::Custom(CustomError::InvalidDependency)
+            );
+            next = self
+                .tokens
+                .get(&dependency)
+                .ok_or(ContractError::InvalidTokenId)?
+                .depends_on;
+        }
+        let mut token = self
+            .tokens
+            .get_mut(&token_id)
+            .ok_or(ContractError::InvalidTokenId)?;
+        token.depends_on = depends_on;
+        Ok(())
+    }
+
+    /// Gets the token whose balances the balances of a token depend on.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    pub(crate) fn get_token_dependency(
+        &self,
+        token_id: &ContractTokenId,
+    ) -> ContractResult<Option<ContractTokenId>> {
+        self.tokens
+            .get(token_id)
+            .map_or(Err(ContractError::InvalidTokenId), |token| {
+                Ok(token.depends_on)
+            })
+    }
+
+    /// Sets or removes the exclusive group of a token.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    pub(crate) fn set_token_exclusive_group(
+        &mut self,
+        token_id: ContractTokenId,
+        group: Option<ExclusiveGroup>,
+    ) -> ContractResult<()> {
+        let mut token = self
+            .tokens
+            .get_mut(&token_id)
+            .ok_or(ContractError::InvalidTokenId)?;
+        token.exclusive_group = group;
+        Ok(())
+    }
+
+    /// Gets the exclusive group of a token.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    pub(crate) fn get_token_exclusive_group(
+        &self,
+        token_id: &ContractTokenId,
+    ) -> ContractResult<Option<ExclusiveGroup>> {
+        self.tokens
+            .get(token_id)
+            .map_or(Err(ContractError::InvalidTokenId), |token| {
+                Ok(token.exclusive_group)
+            })
+    }
+
+    /// Gets the other tokens of the exclusive group of a token of which an account has a balance.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    /// - Returns no tokens if the token has no exclusive group.
+    pub(crate) fn get_excluded_tokens(
+        &self,
+        token_id: &ContractTokenId,
+        account: AccountAddress,
+    ) -> ContractResult<Vec<ContractTokenId>> {
+        let group = match self.get_token_exclusive_group(token_id)? {
+            Some(group) => group,
+            None => return Ok(Vec::new()),
+        };
+        let account = canonical_account(account);
+        Ok(self
+            .tokens
+            .iter()
+            .filter(|(other, token)| {
+                **other != *token_id
+                    && token.exclusive_group == Some(group)
+                    && token.balances.get(&account).is_some()
+            })
+            .map(|(other, _)| *other)
+            .collect())
+    }
+
+    /// Sets whether a new expiry may not shorten the expiry of an unexpired balance of a token.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    pub(crate) fn set_token_monotonic_expiry(
+        &mut self,
+        token_id: ContractTokenId,
+        monotonic_expiry: bool,
+    ) -> ContractResult<()> {
+        let mut token = self
+            .tokens
+            .get_mut(&token_id)
+            .ok_or(ContractError::InvalidTokenId)?;
+        token.monotonic_expiry = monotonic_expiry;
+        Ok(())
+    }
+
+    /// Gets whether a new expiry may not shorten the expiry of an unexpired balance of a token.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    pub(crate) fn is_token_monotonic_expiry(
+        &self,
+        token_id: &ContractTokenId,
+    ) -> ContractResult<bool> {
+        self.tokens
+            .get(token_id)
+            .map_or(Err(ContractError::InvalidTokenId), |token| {
+                Ok(token.monotonic_expiry)
+            })
+    }
+
+    /// Sets the maximum time between the first mint of a balance of a token and its expiry.
+    /// Existing balances keep their hard expiry.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    pub(crate) fn set_token_max_lifetime(
+        &mut self,
+        token_id: ContractTokenId,
+        max_lifetime: Option<Duration>,
+    ) -> ContractResult<()> {
+        let mut token = self
+            .tokens
+            .get_mut(&token_id)
+            .ok_or(ContractError::InvalidTokenId)?;
+        token.max_lifetime = max_lifetime;
+        Ok(())
+    }
+
+    /// Gets the maximum time between the first mint of a balance of a token and its expiry.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    pub(crate) fn get_token_max_lifetime(
+        &self,
+        token_id: &ContractTokenId,
+    ) -> ContractResult<Option<Duration>> {
+        self.tokens
+            .get(token_id)
+            .map_or(Err(ContractError::InvalidTokenId), |token| {
+                Ok(token.max_lifetime)
+            })
+    }
+
+    /// Gets the hard expiry of the balance of an account minted or renewed now: the hard expiry
+    /// of the stored balance, or the maximum lifetime of the token from now for a first mint.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    pub(crate) fn get_hard_expiry(
+        &self,
+        token_id: &ContractTokenId,
+        account: AccountAddress,
+        now: Timestamp,
+    ) -> ContractResult<Option<Timestamp>> {
+        let token = self
+            .tokens
+            .get(token_id)
+            .ok_or(ContractError::InvalidTokenId)?;
+        let account = canonical_account(account);
+        Ok(match token.balances.get(&account) {
+            Some(balance) => balance.hard_expiry,
+            None => token
+                .max_lifetime
+                .and_then(|max_lifetime| now.checked_add(max_lifetime)),
+        })
+    }
+
+    /// Sets the hard expiry of the balance of an account.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    /// - If the account does not have a balance, CredentialNotFound is thrown.
+    pub(crate) fn set_hard_expiry(
+        &mut self,
+        token_id: ContractTokenId,
+        account: AccountAddress,
+        hard_expiry: Option<Timestamp>,
+    ) -> ContractResult<()> {
+        let account = canonical_account(account);
+        let mut token = self
+            .tokens
+            .get_mut(&token_id)
+            .ok_or(ContractError::InvalidTokenId)?;
+        let mut balance = token
+            .balances
+            .get_mut(&account)
+            .ok_or(ContractError::Custom(CustomError::CredentialNotFound))?;
+        balance.hard_expiry = hard_expiry;
+        Ok(())
+    }
+
+    /// Checks a new expiry of the balance of an account.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    /// - If the expiry is not after `valid_from`, InvalidExpiry is thrown.
+    /// - If the token has monotonic expiries and the expiry is before the expiry of the
+    ///   unexpired balance of the account, InvalidExpiry is thrown.
+    /// - If the expiry is after the hard expiry of the balance, LifetimeExceeded is thrown.
+    pub(crate) fn ensure_valid_expiry(
+        &self,
+        token_id: &ContractTokenId,
+        account: AccountAddress,
+        expiry: Timestamp,
+        valid_from: Option<Timestamp>,
+        now: Timestamp,
+    ) -> ContractResult<()> {
+        let token = self
+            .tokens
+            .get(token_id)
+            .ok_or(ContractError::InvalidTokenId)?;
+        ensure!(
+            valid_from.map_or(true, |valid_from| expiry > valid_from),
+            ContractError::Custom(CustomError::InvalidExpiry)
+        );
+        if token.monotonic_expiry {
+            if let Some(current) = token.get_account_balance_expiry(account) {
+                ensure!(
+                    current <= now || expiry >= current,
+                    ContractError::Custom(CustomError::InvalidExpiry)
+                );
+            }
+        }
+        ensure!(
+            self.get_hard_expiry(token_id, account, now)?
+                .map_or(true, |hard_expiry| expiry <= hard_expiry),
+            ContractError::Custom(CustomError::LifetimeExceeded)
+        );
+        Ok(())
+    }
+
+    /// Checks that an account has a valid balance of every token a token depends on,
+    /// following the dependencies of the dependencies.
+    /// - Tokens without a dependency are always valid.
+    /// - A dependency on a removed token is never valid.
+    pub(crate) fn has_valid_dependency(
+        &self,
+        token_id: &ContractTokenId,
+        account: AccountAddress,
+        now: Timestamp,
+    ) -> bool {
+        let mut next = self.tokens.get(token_id).and_then(|token| token.depends_on);
+        while let Some(dependency) = next {
+            match self.tokens.get(&dependency) {
+                Some(token)
+                    if token.get_account_balance(account, now) > ContractTokenAmount::from(0) =>
+                {
+                    next = token.depends_on;
+                }
+                _ => return false,
+            }
+        }
+        true
+    }
+
+    /// Replaces or removes the commitment of the balance of an account.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    /// - If the token is not private, PublicToken is thrown.
+    pub(crate) fn set_commitment(
+        &mut self,
+        token_id: ContractTokenId,
+        account: AccountAddress,
+        commitment: Option<HashSha2256>,
+    ) -> ContractResult<()> {
+        let account = canonical_account(account);
+        let mut token = self
+            .tokens
+            .get_mut(&token_id)
+            .ok_or(ContractError::InvalidTokenId)?;
+        ensure!(
+            token.private,
+            ContractError::Custom(CustomError::PublicToken)
+        );
+        match commitment {
+            Some(commitment) => {
+                token.commitments.insert(account, commitment);
+            }
+            None => {
+                token.commitments.remove(&account);
+            }
+        }
+        Ok(())
+    }
+
+    /// Gets the commitment of the balance of an account.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    /// - If the account does not have a commitment, None is returned.
+    pub(crate) fn get_commitment(
+        &self,
+        token_id: &ContractTokenId,
+        account: &AccountAddress,
+    ) -> ContractResult<Option<HashSha2256>> {
+        let account = canonical_account(*account);
+        self.tokens
+            .get(token_id)
+            .map_or(Err(ContractError::InvalidTokenId), |token| {
+                Ok(token
+                    .commitments
+                    .get(&account)
+                    .map(|commitment| *commitment))
+            })
+    }
+
+    /// Registers an issuer key of a token or replaces its validity, e.g. to retire it.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    pub(crate) fn set_issuer_key(
+        &mut self,
+        token_id: ContractTokenId,
+        key: PublicKeyEd25519,
+        validity: IssuerKeyValidity,
+    ) -> ContractResult<()> {
+        let mut token = self
+            .tokens
+            .get_mut(&token_id)
+            .ok_or(ContractError::InvalidTokenId)?;
+        token.issuer_keys.insert(key, validity);
+        Ok(())
+    }
+
+    /// Checks that a key was an issuer key of a token at the time a voucher was issued.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    /// - If the key is not registered or was not valid at the time, UnknownIssuerKey is thrown.
+    pub(crate) fn ensure_issuer_key(
+        &self,
+        token_id: &ContractTokenId,
+        key: &PublicKeyEd25519,
+        issued_at: Timestamp,
+    ) -> ContractResult<()> {
+        let token = self
+            .tokens
+            .get(token_id)
+            .ok_or(ContractError::InvalidTokenId)?;
+        let valid = token
+            .issuer_keys
+            .get(key)
+            .map_or(false, |validity| validity.is_valid_at(issued_at));
+        ensure!(valid, ContractError::Custom(CustomError::UnknownIssuerKey));
+        Ok(())
+    }
+
+    /// Marks a voucher of a token as claimed.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    /// - If the voucher has already been claimed, VoucherAlreadyUsed is thrown.
+    pub(crate) fn use_voucher(
+        &mut self,
+        token_id: ContractTokenId,
+        voucher_id: u64,
+    ) -> ContractResult<()> {
+        let mut token = self
+            .tokens
+            .get_mut(&token_id)
+            .ok_or(ContractError::InvalidTokenId)?;
+        ensure!(
+            token.used_vouchers.insert(voucher_id),
+            ContractError::Custom(CustomError::VoucherAlreadyUsed)
+        );
+        Ok(())
+    }
+
+    /// Stores a mint of a token which the holder has to activate, replacing an earlier one.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    pub(crate) fn add_pending_mint(
+        &mut self,
+        token_id: ContractTokenId,
+        account: AccountAddress,
+        pending_mint: PendingMint,
+    ) -> ContractResult<()> {
+        let account = canonical_account(account);
+        let mut token = self
+            .tokens
+            .get_mut(&token_id)
+            .ok_or(ContractError::InvalidTokenId)?;
+        token.pending_mints.insert(account, pending_mint);
+        Ok(())
+    }
+
+    /// Removes and returns the pending mint of a token for an account.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    /// - If the account has no pending mint, PendingMintNotFound is thrown.
+    pub(crate) fn take_pending_mint(
+        &mut self,
+        token_id: ContractTokenId,
+        account: AccountAddress,
+    ) -> ContractResult<PendingMint> {
+        let account = canonical_account(account);
+        let mut token = self
+            .tokens
+            .get_mut(&token_id)
+            .ok_or(ContractError::InvalidTokenId)?;
+        token
+            .pending_mints
+            .remove_and_get(&account)
+            .ok_or(ContractError::Custom(CustomError::PendingMintNotFound))
+    }
+
+    /// Publishes a Merkle root of balances of a token which holders can claim.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    pub(crate) fn add_issuance_root(
+        &mut self,
+        token_id: ContractTokenId,
+        root: HashSha2256,
+        publisher: Address,
+    ) -> ContractResult<()> {
+        let mut token = self
+            .tokens
+            .get_mut(&token_id)
+            .ok_or(ContractError::InvalidTokenId)?;
+        token.issuance_roots.insert(root, publisher);
+        Ok(())
+    }
+
+    /// Gets the publisher of a Merkle root of a token.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    /// - If the root has not been published, UnknownIssuanceRoot is thrown.
+    pub(crate) fn get_issuance_root_publisher(
+        &self,
+        token_id: &ContractTokenId,
+        root: &HashSha2256,
+    ) -> ContractResult<Address> {
+        let token = self
+            .tokens
+            .get(token_id)
+            .ok_or(ContractError::InvalidTokenId)?;
+        let publisher = token
+            .issuance_roots
+            .get(root)
+            .map(|publisher| *publisher)
+            .ok_or(ContractError::Custom(CustomError::UnknownIssuanceRoot))?;
+        Ok(publisher)
+    }
+
+    /// Records that a leaf of a Merkle root has been claimed.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    /// - If the leaf has already been claimed, AlreadyClaimed is thrown.
+    pub(crate) fn use_issuance_leaf(
+        &mut self,
+        token_id: ContractTokenId,
+        leaf: HashSha2256,
+    ) -> ContractResult<()> {
+        let mut token = self
+            .tokens
+            .get_mut(&token_id)
+            .ok_or(ContractError::InvalidTokenId)?;
+        ensure!(
+            token.claimed_leaves.insert(leaf),
+            ContractError::Custom(CustomError::AlreadyClaimed)
+        );
+        Ok(())
+    }
+
+    /// Checks if the sender may mint and revoke balances of a token.
+    /// - If the token has an issuer, only the issuer may, not even the admin of the contract.
+    /// - If the token has no issuer, only the admin of the contract may, or minters when minting.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    pub(crate) fn can_issue(
+        &self,
+        token_id: &ContractTokenId,
+        sender: &Address,
+        is_owner: bool,
+    ) -> ContractResult<bool> {
+        Ok(match self.get_token_issuer(token_id)? {
+            Some(issuer) => issuer == *sender,
+            None => is_owner,
+        })
+    }
+
+    /// Gets a page of the tokens ordered by token id, optionally with their metadata url.
+    /// - Returns the page and whether more tokens are left.
+    pub(crate) fn list_tokens(
+        &self,
+        skip: u32,
+        limit: u16,
+        include_metadata: bool,
+    ) -> (Vec<(ContractTokenId, Option<MetadataUrl>)>, bool) {
+        let mut tokens = self
+            .tokens
+            .iter()
+            .skip(skip as usize)
+            .map(|(token_id, token)| (*token_id, include_metadata.then(|| token.metadata.clone())));
+        let page = tokens.by_ref().take(usize::from(limit)).collect();
+        (page, tokens.next().is_some())
+    }
+
+    /// Gets all the tokens of a category.
+    pub(crate) fn tokens_by_category(&self, category: TokenCategory) -> Vec<ContractTokenId> {
+        self.tokens
+            .iter()
+            .filter(|(_, token)| token.category == category)
+            .map(|(token_id, _)| *token_id)
+            .collect()
+    }
+
+    /// Gets the sum of the balances of an account over all tokens of a category.
+    /// - Expired balances and balances whose dependency is not valid are not included.
+    /// - Balances of tokens for which `visible` returns false are not included.
+    pub(crate) fn get_category_score(
+        &self,
+        category: TokenCategory,
+        account: AccountAddress,
+        now: Timestamp,
+        visible: impl Fn(&ContractTokenId) -> bool,
+    ) -> u64 {
+        self.tokens
+            .iter()
+            .filter(|(token_id, token)| {
+                token.category == category
+                    && visible(token_id)
+                    && self.has_valid_dependency(token_id, account, now)
+            })
+            .map(|(_, token)| u64::from(token.get_account_balance(account, now).0))
+            .sum()
+    }
+
+    /// Calls the given function with the token metadata of the given token, without cloning it.
+    /// - If the token does not exist, InvalidTokenId is thrown.
+    pub(crate) fn with_token_metadata<R>(
+        &self,
+        token_id: &ContractTokenId,
+        f: impl FnOnce(&MetadataUrl) -> R,
+    ) -> ContractResult<R> {
+        self.tokens
+            .get(token_id)
+            .map_or(Err(ContractError::InvalidTokenId), |token| {
+                Ok(f(&token.metadata))
+            })
+    }
+
+    /// Records the supply and holder count of every token under a new snapshot id.
+    /// - Returns the id of the new snapshot.
+    pub(crate) fn take_snapshot(&mut self, name: String, now: Timestamp) -> SnapshotId {
+        let tokens = self
+            .tokens
+            .iter()
+            .map(|(token_id, token)| {
+                let (total_supply, holders) = token.get_supply(now);
+                TokenSnapshot {
+                    token_id: *token_id,
+                    total_supply,
+                    holders,
+                }
+            })
+            .collect();
+
+        let snapshot_id = self.next_snapshot_id;
+        self.snapshots.insert(
+            snapshot_id,
+            Snapshot {
+                name,
+                timestamp: now,
+                tokens,
+            },
+        );
+        self.next_snapshot_id += 1;
+        snapshot_id
+    }
+
+    /// Gets a snapshot.
+    /// - If the snapshot does not exist, SnapshotNotFound is thrown.
+    pub(crate) fn get_snapshot(&self, snapshot_id: SnapshotId) -> ContractResult<Snapshot> {
+        self.snapshots
+            .get(&snapshot_id)
+            .map(|snapshot| snapshot.clone())
+            .ok_or(ContractError::Custom(CustomError::SnapshotNotFound))
+    }
+
+    /// Adds a contract to the subscribers.
+    /// - This function does not fail if the contract already is a subscriber.
+    /// - If the contract would exceed MAX_SUBSCRIBERS subscribers, TooManySubscribers is thrown.
+    pub(crate) fn add_subscriber(&mut self, address: ContractAddress) -> ContractResult<()> {
+        if self.subscribers.contains(&address) {
+            return Ok(());
+        }
+        ensure!(
+            self.subscribers.iter().count() < MAX_SUBSCRIBERS,
+            ContractError::Custom(CustomError::TooManySubscribers)
+        );
+        self.subscribers.insert(address);
+        Ok(())
+    }
+
+    /// Removes a contract from the subscribers.
+    /// - This function does not fail if the contract is not a subscriber.
+    pub(crate) fn remove_subscriber(&mut self, address: &ContractAddress) {
+        self.subscribers.remove(address);
+    }
+
+    /// Gets all the subscribers.
+    pub(crate) fn subscribers(&self) -> Vec<ContractAddress> {
+        self.subscribers.iter().map(|address| *address).collect()
+    }
+
+    /// Registers a revocation key.
+    /// - This function does not reset the nonce of an already registered key.
+    pub(crate) fn add_revocation_key(&mut self, key: PublicKeyEd25519) {
+        self.revocation_keys.entry(key).or_insert(0);
+    }
+
+    /// Removes a revocation key.
+    /// - This function does not fail if the key is not registered.
+    pub(crate) fn remove_revocation_key(&mut self, key: &PublicKeyEd25519) {
+        self.revocation_keys.remove(key);
+    }
+
+    /// Gets all revocation keys with their next nonce.
+    pub(crate) fn revocation_keys(&self) -> Vec<(PublicKeyEd25519, u64)> {
+        self.revocation_keys
+            .iter()
+            .map(|(key, nonce)| (*key, *nonce))
+            .collect()
+    }
+
+    /// Checks that the nonce matches the next nonce of a revocation key and increments it.
+    /// - If the key is not registered, UnknownRevocationKey is thrown.
+    /// - If the nonce does not match, NonceMismatch is thrown.
+    pub(crate) fn use_revocation_nonce(
+        &mut self,
+        key: &PublicKeyEd25519,
+        nonce: u64,
+    ) -> ContractResult<()> {
+        let mut next_nonce = self
+            .revocation_keys
+            .get_mut(key)
+            .ok_or(ContractError::Custom(CustomError::UnknownRevocationKey))?;
+        ensure_eq!(
+            *next_nonce,
+            nonce,
+            ContractError::Custom(CustomError::NonceMismatch)
+        );
+        *next_nonce += 1;
+        Ok(())
+    }
+
+    /// Registers an issuer contract.
+    /// - This function does not reset the nonce of an already registered contract.
+    pub(crate) fn add_issuer_contract(&mut self, contract: ContractAddress) {
+        self.issuer_contracts.entry(contract).or_insert(0);
+    }
+
+    /// Removes an issuer contract.
+    /// - This function does not fail if the contract is not registered.
+    pub(crate) fn remove_issuer_contract(&mut self, contract: &ContractAddress) {
+        self.issuer_contracts.remove(contract);
+    }
+
+    /// Gets all issuer contracts with their next nonce.
+    pub(crate) fn issuer_contracts(&self) -> Vec<(ContractAddress, u64)> {
+        self.issuer_contracts
+            .iter()
+            .map(|(contract, nonce)| (*contract, *nonce))
+            .collect()
+    }
+
+    /// Checks that the nonce matches the next nonce of an issuer contract and increments it.
+    /// - If the contract is not registered, Unauthorized is thrown.
+    /// - If the nonce does not match, NonceMismatch is thrown.
+    pub(crate) fn use_issuer_contract_nonce(
+        &mut self,
+        contract: &ContractAddress,
+        nonce: u64,
+    ) -> ContractResult<()> {
+        let mut next_nonce = self
+            .issuer_contracts
+            .get_mut(contract)
+            .ok_or(ContractError::Unauthorized)?;
+        ensure_eq!(
+            *next_nonce,
+            nonce,
+            ContractError::Custom(CustomError::NonceMismatch)
+        );
+        *next_nonce += 1;
+        Ok(())
+    }
+
+    /// Counts a balance minted by an issuing address.
+    pub(crate) fn record_minter_mint(&mut self, minter: Address, amount: ContractTokenAmount) {
+        let mut stats = self
+            .minter_stats
+            .entry(minter)
+            .or_insert_with(MinterStats::default);
+        stats.mints = stats.mints.saturating_add(1);
+        stats.amount = stats.amount.saturating_add(u64::from(amount.0));
+    }
+
+    /// Counts a balance revoked by an issuing address.
+    pub(crate) fn record_minter_revoke(&mut self, minter: Address) {
+        let mut stats = self
+            .minter_stats
+            .entry(minter)
+            .or_insert_with(MinterStats::default);
+        stats.revokes = stats.revokes.saturating_add(1);
+    }
+
+    /// Gets the issuance activity of an issuing address, all zeros if it never issued.
+    pub(crate) fn get_minter_stats(&self, minter: &Address) -> MinterStats {
+        self.minter_stats
+            .get(minter)
+            .map_or_else(MinterStats::default, |stats| *stats)
+    }
+
+    /// Checks if a mint idempotency key has recently been used.
+    pub(crate) fn has_mint_request_key(&self, key: &HashSha2256) -> bool {
+        self.mint_request_keys.contains(key)
+    }
+
+    /// Records a mint idempotency key.
+    /// - If the key has recently been used, DuplicateMintRequest is thrown.
+    /// - Only the last MAX_MINT_REQUEST_KEYS keys are remembered, the oldest key is evicted.
+    pub(crate) fn use_mint_request_key(&mut self, key: HashSha2256) -> ContractResult<()> {
+        ensure!(
+            self.mint_request_keys.insert(key),
+            ContractError::Custom(CustomError::DuplicateMintRequest)
+        );
+        let slot = self.next_mint_request % MAX_MINT_REQUEST_KEYS;
+        if let Some(evicted) = self.mint_request_slots.insert(slot, key) {
+            self.mint_request_keys.remove(&evicted);
+        }
+        self.next_mint_request += 1;
+        Ok(())
+    }
+
+    /// Replaces the admins and the number of approvals required for destructive admin actions.
+    /// - If the threshold is larger than the number of admins, InvalidThreshold is thrown.
+    pub(crate) fn set_admins(
+        &mut self,
+        admins: Vec<AccountAddress>,
+        threshold: u8,
+    ) -> ContractResult<()> {
+        self.admins.clear();
+        for admin in admins {
+            self.admins.insert(admin);
+        }
+        ensure!(
+            usize::from(threshold) <= self.admins.iter().count(),
+            ContractError::Custom(CustomError::InvalidThreshold)
+        );
+        self.approval_threshold = threshold;
+        Ok(())
+    }
+
+    /// Allocates a range of token ids to an issuer or removes its allocation.
+    /// - If the range is empty or overlaps the range of another issuer, InvalidIdRange is thrown.
+    pub(crate) fn set_id_range(
+        &mut self,
+        issuer: Address,
+        range: Option<TokenIdRange>,
+    ) -> ContractResult<()> {
+        match range {
+            Some(range) => {
+                ensure!(
+                    range.first <= range.last
+                        && !self
+                            .id_ranges
+                            .iter()
+                            .any(|(other, other_range)| *other != issuer
+                                && other_range.overlaps(&range)),
+                    ContractError::Custom(CustomError::InvalidIdRange)
+                );
+                self.id_ranges.insert(issuer, range);
+            }
+            None => {
+                self.id_ranges.remove(&issuer);
+            }
+        }
+        Ok(())
+    }
+
+    /// Creates or replaces a bundle, an empty list of components removes it.
+    /// - If any of the tokens does not exist, InvalidTokenId is thrown.
+    pub(crate) fn set_bundle(
+        &mut self,
+        bundle_id: BundleId,
+        components: Vec<BundleComponent>,
+    ) -> ContractResult<()> {
+        if components.is_empty() {
+            self.bundles.remove(&bundle_id);
+            return Ok(());
+        }
+        ensure!(
+            components
+                .iter()
+                .all(|component| self.has_token(component.token_id)),
+            ContractError::InvalidTokenId
+        );
+        self.bundles.insert(bundle_id, components);
+        Ok(())
+    }
+
+    /// Gets the components of a bundle.
+    /// - If the bundle does not exist, UnknownBundle is thrown.
+    pub(crate) fn get_bundle(&self, bundle_id: &BundleId) -> ContractResult<Vec<BundleComponent>> {
+        self.bundles
+            .get(bundle_id)
+            .map(|components| components.clone())
+            .ok_or(ContractError::Custom(CustomError::UnknownBundle))
+    }
+
+    /// Stores a proposed token, replacing an earlier proposal of the same token id.
+    /// - If the token already exists, InvalidTokenId is thrown.
+    pub(crate) fn propose_token(&mut self, proposal: TokenProposal) -> ContractResult<()> {
+        let token_id = proposal.token.token_id;
+        ensure!(!self.has_token(token_id), ContractError::InvalidTokenId);
+        self.token_proposals.insert(token_id, proposal);
+        Ok(())
+    }
+
+    /// Removes and returns the proposal of a token.
+    /// - If the token has not been proposed, ProposalNotFound is thrown.
+    pub(crate) fn take_token_proposal(
+        &mut self,
+        token_id: &ContractTokenId,
+    ) -> ContractResult<TokenProposal> {
+        self.token_proposals
+            .remove_and_get(token_id)
+            .ok_or(ContractError::Custom(CustomError::ProposalNotFound))
+    }
+
+    /// Gets the token id ranges allocated to issuers.
+    pub(crate) fn id_ranges(&self) -> Vec<(Address, TokenIdRange)> {
+        self.id_ranges
+            .iter()
+            .map(|(issuer, range)| (*issuer, *range))
+            .collect()
+    }
+
+    /// Checks that a token id may be used for a token of an issuer.
+    /// - If the token id is allocated to another issuer, TokenIdNotAllocated is thrown.
+    /// - If the issuer has a range which does not contain the token id, TokenIdNotAllocated is thrown.
+    pub(crate) fn check_id_range(
+        &self,
+        token_id: &ContractTokenId,
+        issuer: Option<Address>,
+    ) -> ContractResult<()> {
+        for (owner, range) in self.id_ranges.iter() {
+            // The range of the issuer must contain the token id, other ranges must not.
+            ensure!(
+                range.contains(token_id) == (issuer == Some(*owner)),
+                ContractError::Custom(CustomError::TokenIdNotAllocated)
+            );
+        }
+        Ok(())
+    }
+
+    /// Gets the account with the admin role.
+    /// - If no admin has been set, the owner of the instance is returned.
+    pub(crate) fn contract_admin(&self, owner: AccountAddress) -> AccountAddress {
+        self.contract_admin.unwrap_or(owner)
+    }
+
+    /// Checks if the sender has the admin role.
+    pub(crate) fn is_contract_admin(&self, sender: &Address, owner: AccountAddress) -> bool {
+        sender.matches_account(&self.contract_admin(owner))
+    }
+
+    /// Sets the account with the admin role.
+    pub(crate) fn set_contract_admin(&mut self, admin: AccountAddress) {
+        self.contract_admin = Some(admin);
+    }
+
+    /// Checks if an admin has been set, see `initAdmin`.
+    pub(crate) fn has_contract_admin(&self) -> bool {
+        self.contract_admin.is_some()
+    }
+
+    /// Adds or removes a minter.
+    pub(crate) fn update_minter(&mut self, address: Address, add: bool) {
+        if add {
+            self.minters.insert(address);
+        } else {
+            self.minters.remove(&address);
+        }
+    }
+
+    /// Checks if an address is a minter.
+    pub(crate) fn is_minter(&self, address: &Address) -> bool {
+        self.minters.contains(address)
+    }
+
+    /// Grants or revokes a role of an address.
+    pub(crate) fn update_role(&mut self, role: Role, address: Address, grant: bool) {
+        if grant {
+            self.roles.insert((role, address));
+        } else {
+            self.roles.remove(&(role, address));
+        }
+    }
+
+    /// Checks if an address has been granted a role.
+    pub(crate) fn has_role(&self, role: Role, address: &Address) -> bool {
+        self.roles.contains(&(role, *address))
+    }
+
+    /// Sets or removes the CIS-2 token in which renewal fees are paid.
+    pub(crate) fn set_fee_token(&mut self, fee_token: Option<FeeToken>) {
+        self.fee_token = fee_token;
+    }
+
+    /// Gets the CIS-2 token in which renewal fees are paid.
+    pub(crate) fn fee_token(&self) -> Option<FeeToken> {
+        self.fee_token.clone()
+    }
+
+    /// Adds a collected fee to the fee ledger of the fee token.
+    pub(crate) fn credit_fee(&mut self, fee_token: &FeeToken) {
+        let mut collected = self
+            .fee_ledger
+            .entry((fee_token.contract, fee_token.token_id.clone()))
+            .or_insert(TokenAmountU64(0));
+        collected.0 = collected.0.saturating_add(fee_token.amount.0);
+    }
+
+    /// Gets the fees collected for each fee token as (contract, token id, amount).
+    pub(crate) fn fee_ledger(&self) -> Vec<(ContractAddress, TokenIdVec, TokenAmountU64)> {
+        self.fee_ledger
+            .iter()
+            .map(|(key, amount)| (key.0, key.1.clone(), *amount))
+            .collect()
+    }
+
+    /// Sets the fee in CCD paid by holders renewing their own balance.
+    pub(crate) fn set_renewal_fee(&mut self, renewal_fee: Amount) {
+        self.renewal_fee = renewal_fee;
+    }
+
+    /// Gets the fee in CCD paid by holders renewing their own balance.
+    pub(crate) fn renewal_fee(&self) -> Amount {
+        self.renewal_fee
+    }
+
+    /// Adds a renewal fee paid in CCD to the collected renewal fees.
+    pub(crate) fn credit_renewal_fee(&mut self, fee: Amount) {
+        self.collected_renewal_fees = Amount::from_micro_ccd(
+            self.collected_renewal_fees
+                .micro_ccd
+                .saturating_add(fee.micro_ccd),
+        );
+    }
+
+    /// Deducts a withdrawal from the collected renewal fees.
+    /// - Fails if the amount exceeds the collected renewal fees.
+    pub(crate) fn withdraw_renewal_fees(&mut self, amount: Amount) -> ContractResult<()> {
+        ensure!(
+            amount <= self.collected_renewal_fees,
+            ContractError::InsufficientFunds
+        );
+        self.collected_renewal_fees -= amount;
+        Ok(())
+    }
+
+    /// Gets the renewal fees in CCD collected by `renew` and not withdrawn yet.
+    pub(crate) fn collected_renewal_fees(&self) -> Amount {
+        self.collected_renewal_fees
+    }
+
+    /// Sets or removes the key with which an account signs `permit` messages.
+    pub(crate) fn set_permit_key(
+        &mut self,
+        account: AccountAddress,
+        key: Option<PublicKeyEd25519>,
+    ) {
+        let account = canonical_account(account);
+        match key {
+            Some(key) => {
+                self.permit_keys.insert(account, key);
+            }
+            None => self.permit_keys.remove(&account),
+        }
+    }
+
+    /// Gets the key with which an account signs `permit` messages.
+    pub(crate) fn permit_key(&self, account: AccountAddress) -> Option<PublicKeyEd25519> {
+        self.permit_keys
+            .get(&canonical_account(account))
+            .map(|key| *key)
+    }
+
+    /// Uses the nonce of a `permit` message of an account.
+    /// - If the nonce is not the next nonce of the account, NonceMismatch is thrown.
+    pub(crate) fn use_permit_nonce(
+        &mut self,
+        account: AccountAddress,
+        nonce: u64,
+    ) -> ContractResult<()> {
+        ensure_eq!(
+            self.permit_nonce(account),
+            nonce,
+            ContractError::Custom(CustomError::NonceMismatch)
+        );
+        self.permit_nonces
+            .insert(canonical_account(account), nonce + 1);
+        Ok(())
+    }
+
+    /// Gets the nonce the next `permit` message of an account has to be signed with.
+    pub(crate) fn permit_nonce(&self, account: AccountAddress) -> u64 {
+        self.permit_nonces
+            .get(&canonical_account(account))
+            .map_or(0, |nonce| *nonce)
+    }
+
+    /// Gets all granted roles.
+    pub(crate) fn roles(&self) -> Vec<(Role, Address)> {
+        self.roles.iter().map(|role| *role).collect()
+    }
+
+    /// Checks if an address is an operator of an owner.
+    pub(crate) fn is_operator(&self, owner: &Address, address: &Address) -> bool {
+        self.operators.contains(&(*owner, *address))
+    }
+
+    /// Gets all minters.
+    pub(crate) fn minters(&self) -> Vec<Address> {
+        self.minters.iter().map(|minter| *minter).collect()
+    }
+
+    /// Records or withdraws the consent of an account to off-chain renewal reminders.
+    /// - Consenting again keeps the time of the original consent.
+    pub(crate) fn set_reminder_consent(
+        &mut self,
+        account: AccountAddress,
+        consent: bool,
+        now: Timestamp,
+    ) {
+        let account = canonical_account(account);
+        if !consent {
+            self.reminder_consents.remove(&account);
+        } else if self.reminder_consents.get(&account).is_none() {
+            self.reminder_consents.insert(account, now);
+        }
+    }
+
+    /// Gets the time an account consented to off-chain renewal reminders, None if it has not.
+    pub(crate) fn get_reminder_consent(&self, account: AccountAddress) -> Option<Timestamp> {
+        self.reminder_consents
+            .get(&canonical_account(account))
+            .map(|since| *since)
+    }
+
+    /// Gets the admins which can approve destructive admin actions.
+    pub(crate) fn admins(&self) -> Vec<AccountAddress> {
+        self.admins.iter().map(|admin| *admin).collect()
+    }
+
+    /// Checks if an account is an admin.
+    pub(crate) fn is_admin(&self, account: &AccountAddress) -> bool {
+        self.admins.contains(account)
+    }
+
+    /// Gets the number of approvals required for destructive admin actions.
+    pub(crate) fn approval_threshold(&self) -> u8 {
+        self.approval_threshold
+    }
+
+    /// Records the approval of an admin action by an admin.
+    /// - The expiry of the first approval is used for the action.
+    /// - Approving an action twice has no effect.
+    pub(crate) fn approve_action(
+        &mut self,
+        action_hash: HashSha2256,
+        admin: AccountAddress,
+        expiry: Timestamp,
+    ) {
+        let mut action = self
+            .pending_actions
+            .entry(action_hash)
+            .or_insert(PendingAction {
+                expiry,
+                approvals: Vec::new(),
+            });
+        if !action.approvals.contains(&admin) {
+            action.approvals.push(admin);
+        }
+    }
+
+    /// Removes the approvals of an admin action which is about to be executed.
+    /// - Only approvals of accounts which are still admins are counted.
+    /// - If the action has not been approved by enough admins, ActionNotApproved is thrown.
+    /// - If the approvals have expired, ActionExpired is thrown.
+    pub(crate) fn use_approvals(
+        &mut self,
+        action_hash: &HashSha2256,
+        now: Timestamp,
+    ) -> ContractResult<()> {
+        let action = self
+            .pending_actions
+            .remove_and_get(action_hash)
+            .ok_or(ContractError::Custom(CustomError::ActionNotApproved))?;
+        ensure!(
+            action.expiry > now,
+            ContractError::Custom(CustomError::ActionExpired)
+        );
+        let approvals = action
+            .approvals
+            .iter()
+            .filter(|admin| self.admins.contains(admin))
+            .count();
+        ensure!(
+            approvals >= usize::from(self.approval_threshold),
+            ContractError::Custom(CustomError::ActionNotApproved)
+        );
+        Ok(())
+    }
+
+    /// Gets the delay between scheduling and executing timelocked actions.
+    pub(crate) fn timelock_delay(&self) -> Duration {
+        self.timelock_delay
+    }
+
+    /// Replaces the delay between scheduling and executing timelocked actions.
+    /// - Already scheduled actions keep their time of execution.
+    pub(crate) fn set_timelock_delay(&mut self, delay: Duration) {
+        self.timelock_delay = delay;
+    }
+
+    /// Schedules an action and returns the time from which it can be executed.
+    /// - Scheduling an already scheduled action restarts the delay.
+    pub(crate) fn schedule_action(
+        &mut self,
+        action_hash: HashSha2256,
+        now: Timestamp,
+    ) -> Timestamp {
+        let ready_at = now
+            .checked_add(self.timelock_delay)
+            .unwrap_or(Timestamp::from_timestamp_millis(u64::MAX));
+        self.scheduled_actions.insert(action_hash, ready_at);
+        ready_at
+    }
+
+    /// Removes a scheduled action.
+    /// - If the action has not been scheduled, ActionNotScheduled is thrown.
+    pub(crate) fn cancel_action(&mut self, action_hash: &HashSha2256) -> ContractResult<()> {
+        self.scheduled_actions
+            .remove_and_get(action_hash)
+            .map(|_| ())
+            .ok_or(ContractError::Custom(CustomError::ActionNotScheduled))
+    }
+
+    /// Removes a scheduled action which is about to be executed.
+    /// - If the action has not been scheduled, ActionNotScheduled is thrown.
+    /// - If the delay has not elapsed, TimelockNotElapsed is thrown.
+    pub(crate) fn use_scheduled_action(
+        &mut self,
+        action_hash: &HashSha2256,
+        now: Timestamp,
+    ) -> ContractResult<()> {
+        let ready_at = self
+            .scheduled_actions
+            .remove_and_get(action_hash)
+            .ok_or(ContractError::Custom(CustomError::ActionNotScheduled))?;
+        ensure!(
+            ready_at <= now,
+            ContractError::Custom(CustomError::TimelockNotElapsed)
+        );
+        Ok(())
+    }
 }
@@ -1,13 +1,22 @@
+use concordium_std::*;
+
 use concordium_cis2::{
-    BalanceOfQuery, BalanceOfQueryParams, BalanceOfQueryResponse, TokenMetadataQueryParams,
-    TransferParams,
+    BalanceOfQuery, BalanceOfQueryParams, BalanceOfQueryResponse, Cis2Event,
+    TokenMetadataQueryParams, TransferParams,
 };
 
 pub type ContractTokenId = concordium_cis2::TokenIdU8;
 pub type ContractTokenAmount = concordium_cis2::TokenAmountU16;
 pub type ContractError = concordium_cis2::Cis2Error<crate::errors::CustomError>;
-pub type ContractEvent = concordium_cis2::Cis2Event<ContractTokenId, ContractTokenAmount>;
 pub type ContractResult<T> = Result<T, ContractError>;
+/// Identifier of a snapshot, assigned incrementally by the contract.
+pub type SnapshotId = u32;
+/// Category of a token, used to manage tokens as a group.
+pub type TokenCategory = u8;
+/// Identifier of a bundle of tokens, chosen by the admin, see `createBundle`.
+pub type BundleId = u32;
+/// Identifier of a group of mutually exclusive tokens, chosen by the admin, see `setExclusiveGroup`.
+pub type ExclusiveGroup = u32;
 
 /// Parameter type for the CIS-2 function `balanceOf` specialized to the subset
 /// of TokenIDs used by this contract.
@@ -22,3 +31,882 @@ pub type ContractBalanceOfQueryResponse = BalanceOfQueryResponse<ContractTokenAm
 /// subset of TokenIDs used by this contract.
 pub type ContractTokenMetadataQueryParams = TokenMetadataQueryParams<ContractTokenId>;
 pub type ContractTransferParams = TransferParams<ContractTokenId, ContractTokenAmount>;
+
+/// Balance minted with `mintPending`, which is stored once the holder activates it.
+#[derive(Serial, Deserial, SchemaType, Clone, Debug, PartialEq)]
+pub struct PendingMint {
+    pub amount: ContractTokenAmount,
+    /// Expiry of the balance once activated.
+    pub expiry: Timestamp,
+    /// The time from which the balance is valid, None if it is valid immediately.
+    pub valid_from: Option<Timestamp>,
+    /// Metadata url of the balance, None to use the metadata url of the token.
+    pub metadata_url: Option<concordium_cis2::MetadataUrl>,
+    /// Address which minted the balance.
+    pub issuer: Address,
+    /// Time after which the pending mint can no longer be activated, None if it does not expire.
+    pub deadline: Option<Timestamp>,
+}
+
+/// Describes how the amounts of a token are interpreted by verifiers.
+#[derive(Serial, Deserial, SchemaType, Clone, Debug, Default, PartialEq, Eq)]
+pub struct AmountUnit {
+    /// Number of decimals of the amounts, e.g. an amount of 1234 with 2 decimals is 12.34.
+    pub decimals: u8,
+    /// Unit of the amounts, e.g. `points` or `percent`, None if the amounts have no unit.
+    pub unit: Option<String>,
+}
+
+/// Amounts of a token which may be minted, in addition to its minimum amount.
+#[derive(Serial, Deserial, SchemaType, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AmountRule {
+    /// Any amount.
+    #[default]
+    Any,
+    /// Only this amount, e.g. 1 for membership tokens.
+    Exact(ContractTokenAmount),
+    /// Amounts between min and max, both included, e.g. 0 to 1000 for scores.
+    Range {
+        min: ContractTokenAmount,
+        max: ContractTokenAmount,
+    },
+    /// Multiples of this amount.
+    MultipleOf(ContractTokenAmount),
+}
+
+impl AmountRule {
+    /// Checks if the rule allows minting the amount.
+    pub fn allows(&self, amount: ContractTokenAmount) -> bool {
+        match self {
+            AmountRule::Any => true,
+            AmountRule::Exact(exact) => amount == *exact,
+            AmountRule::Range { min, max } => *min <= amount && amount <= *max,
+            AmountRule::MultipleOf(multiple) => amount.0.checked_rem(multiple.0) == Some(0),
+        }
+    }
+}
+
+/// Who may extend the expiry of an existing balance of a token using `renew`.
+#[derive(Serial, Deserial, SchemaType, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RenewalPolicy {
+    /// Only the issuer of the token, or the admin of the contract if the token has no issuer.
+    #[default]
+    IssuerOnly,
+    /// The holder may also renew their own balance, paying for the transaction.
+    /// Holders always renew by the default validity of the token.
+    HolderPaid,
+    /// The issuer may renew, and anyone may renew a balance using `renewWithProof` with a
+    /// voucher signed by an issuer key of the token.
+    AutoOnProof,
+}
+
+/// Period in which an issuer key signs valid vouchers, see `setIssuerKey`.
+#[derive(Serial, Deserial, SchemaType, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IssuerKeyValidity {
+    /// Vouchers issued before this time are rejected.
+    pub active_from: Timestamp,
+    /// Vouchers issued at or after this time are rejected, None if the key has not been retired.
+    pub retired_at: Option<Timestamp>,
+}
+
+impl IssuerKeyValidity {
+    /// Checks if a voucher issued at the given time may be signed by the key.
+    pub fn is_valid_at(&self, issued_at: Timestamp) -> bool {
+        self.active_from <= issued_at
+            && self
+                .retired_at
+                .map_or(true, |retired_at| issued_at < retired_at)
+    }
+}
+
+/// Inclusive range of token ids allocated to an issuer, see `setIdRange`.
+#[derive(Serial, Deserial, SchemaType, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TokenIdRange {
+    pub first: u8,
+    pub last: u8,
+}
+
+impl TokenIdRange {
+    /// Checks if the range contains a token id.
+    pub fn contains(&self, token_id: &ContractTokenId) -> bool {
+        self.first <= token_id.0 && token_id.0 <= self.last
+    }
+
+    /// Checks if two ranges have a token id in common.
+    pub fn overlaps(&self, other: &TokenIdRange) -> bool {
+        self.first <= other.last && other.first <= self.last
+    }
+}
+
+/// Kind of a change of a balance recorded in its history.
+#[derive(Serial, Deserial, SchemaType, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BalanceChange {
+    /// The balance was minted, replacing any previous balance.
+    Mint,
+    /// The balance was revoked.
+    Revoke,
+    /// The expiry of the balance was extended.
+    Renew,
+    /// The amount of the balance was corrected.
+    Adjust,
+}
+
+/// Token of a bundle, minted by `mintBundle`.
+#[derive(Serial, Deserial, SchemaType, Clone, Debug, PartialEq)]
+pub struct BundleComponent {
+    pub token_id: ContractTokenId,
+    pub amount: ContractTokenAmount,
+    /// Time between minting and the expiry of the balance.
+    /// None uses the default validity of the token.
+    pub validity: Option<Duration>,
+}
+
+/// Token added by `add`, or proposed by `proposeToken`.
+#[derive(SchemaType, Deserial, Serial)]
+pub struct AddTokenParams {
+    pub token_id: ContractTokenId,
+    pub metadata_url: concordium_cis2::MetadataUrl,
+    /// Category of the token, e.g. KYC, reputation or membership.
+    pub category: TokenCategory,
+    /// If set, only this address may mint and revoke the token, not even the admin of the contract.
+    pub issuer: Option<Address>,
+    /// Validity of minted balances which do not have an expiry.
+    pub default_validity: Option<Duration>,
+    /// Who may extend the expiry of existing balances using `renew`.
+    pub renewal_policy: RenewalPolicy,
+    /// If set, only hash commitments of balances are stored, see `commit`.
+    pub private: bool,
+    /// If set, holders must be confirmed as unique persons by the identity registry of the
+    /// contract configuration before minting.
+    pub unique_person: bool,
+    /// Decimals and unit of the amounts, so verifiers interpret scores and quantities alike.
+    pub amount_unit: AmountUnit,
+    /// Amounts which may be minted, e.g. exactly 1 for membership tokens.
+    pub amount_rule: AmountRule,
+}
+
+/// Token proposed by an admin, added once another admin approves it, see `approveToken`.
+#[derive(SchemaType, Deserial, Serial)]
+pub struct TokenProposal {
+    /// Admin which proposed the token.
+    pub proposer: AccountAddress,
+    pub token: AddTokenParams,
+}
+
+/// Role which may be delegated by the admin of the contract, see `updateRoles`.
+#[derive(Serial, Deserial, SchemaType, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Role {
+    /// May pause minting of tokens, but not unpause them.
+    Pauser,
+    /// May freeze and unfreeze balances of any token.
+    Freezer,
+    /// May set the fee token of `renewWithToken` and the renewal fee in CCD, see `setFeeToken` and `setRenewalFee`.
+    FeeManager,
+    /// May read balances hidden by their holders, see `setVisibility`.
+    Verifier,
+}
+
+/// Entry of the history of a balance, see `historyOf`.
+#[derive(Serial, Deserial, SchemaType, Clone, Debug, PartialEq)]
+pub struct HistoryEntry {
+    pub timestamp: Timestamp,
+    pub change: BalanceChange,
+    /// Amount of the balance after the change.
+    pub amount: ContractTokenAmount,
+    /// Expiry of the balance after the change.
+    pub expiry: Timestamp,
+    /// Address which made the change.
+    pub actor: Address,
+}
+
+/// Contract wide configuration which can be updated by the admin of the contract.
+#[derive(Serial, Deserial, SchemaType, Clone, Default, Debug, PartialEq)]
+pub struct ContractConfig {
+    /// Reject adding a token whose metadata url (including the hash) is already used
+    /// by another token.
+    pub unique_metadata_urls: bool,
+    /// Reject adding a token whose metadata url does not have a SHA-256 hash.
+    pub metadata_hash_required: bool,
+    /// Maximum number of items in a single call.
+    pub batch_limits: BatchLimits,
+    /// Expiries of minted and renewed balances are rounded up to a multiple of this duration,
+    /// e.g. a day rounds up to the end of the day (UTC). None keeps expiries as given.
+    pub expiry_granularity: Option<Duration>,
+    /// Minimum time between self-service calls (`claim`, `renounce`) for the same account.
+    /// None disables the cooldown.
+    pub self_service_cooldown: Option<Duration>,
+    /// Maximum time between minting or renewing a balance and its expiry.
+    /// None allows any expiry.
+    pub max_validity: Option<Duration>,
+    /// Minting of newly added tokens is paused until `unpauseToken` is called.
+    pub new_tokens_paused: bool,
+    /// Contract confirming that holders of unique person tokens are unique persons,
+    /// see `ensure_unique_person`.
+    pub identity_registry: Option<ContractAddress>,
+    /// Log a single `DsidBatchMint` event per `mint` call instead of the events of each minted
+    /// balance, keeping large batches under the log limits.
+    pub summarize_mint_events: bool,
+    /// Time within which the holder has to activate a pending mint, see `mintPending`.
+    /// None keeps pending mints until they are activated.
+    pub activation_period: Option<Duration>,
+    /// Minting is paused once more balances or a larger amount than allowed are minted within
+    /// a window, until `resumeMinting` is called. None disables the limit.
+    pub mint_volume_limit: Option<MintVolumeLimit>,
+    /// `expiryOf` reports balances which expired longer ago than this duration as `NoBalance`.
+    /// None always reports the expiry.
+    pub hide_expired_after: Option<Duration>,
+    /// Log a `TransferAttempted` event for each rejected transfer, to measure attempts of wallets.
+    pub log_transfer_attempts: bool,
+    /// Sibling contracts, e.g. the instances of other regions, whose scores are added by
+    /// `federatedScoreOf`.
+    pub federation: Vec<ContractAddress>,
+    /// `statusOf` flags balances which expire within this duration as expiring soon.
+    /// None never flags a balance.
+    pub soon_window: Option<Duration>,
+    /// Tokens can only be added by `proposeToken` and the approval of a second admin,
+    /// `add` and the `AddToken` admin operation fail.
+    pub token_approval_required: bool,
+    /// Mints with an expiry up to this duration before the current time are accepted,
+    /// absorbing the clock drift of issuer backends. None rejects any expiry in the past.
+    pub clock_skew_tolerance: Option<Duration>,
+}
+
+impl ContractConfig {
+    /// Rounds an expiry up to a multiple of the expiry granularity.
+    pub fn round_expiry(&self, expiry: Timestamp) -> Timestamp {
+        match self
+            .expiry_granularity
+            .map(|granularity| granularity.millis())
+        {
+            Some(granularity) if granularity > 0 => {
+                let millis = expiry.timestamp_millis();
+                let rounded = millis
+                    .checked_add(granularity - 1)
+                    .map_or(millis, |millis| millis / granularity * granularity);
+                Timestamp::from_timestamp_millis(rounded)
+            }
+            _ => expiry,
+        }
+    }
+
+    /// Checks if an expiry of a mint has passed, allowing it to be up to the clock skew
+    /// tolerance in the past.
+    pub fn is_past_mint_expiry(&self, now: Timestamp, expiry: Timestamp) -> bool {
+        let earliest = match self.clock_skew_tolerance {
+            Some(tolerance) => now
+                .checked_sub(tolerance)
+                .unwrap_or(Timestamp::from_timestamp_millis(0)),
+            None => now,
+        };
+        expiry <= earliest
+    }
+
+    /// Checks if an expiry is further from the current time than the maximum validity.
+    pub fn exceeds_max_validity(&self, now: Timestamp, expiry: Timestamp) -> bool {
+        match self.max_validity {
+            Some(max_validity) => expiry
+                .duration_since(now)
+                .map_or(false, |validity| validity > max_validity),
+            None => false,
+        }
+    }
+
+    /// Checks if an unexpired expiry is closer to the current time than the soon window.
+    pub fn is_expiring_soon(&self, now: Timestamp, expiry: Timestamp) -> bool {
+        match self.soon_window {
+            Some(soon_window) => expiry.duration_since(now).map_or(false, |remaining| {
+                remaining.millis() > 0 && remaining < soon_window
+            }),
+            None => false,
+        }
+    }
+
+    /// Checks if an expiry has passed longer ago than the configured duration to hide it.
+    pub fn hides_expiry(&self, now: Timestamp, expiry: Timestamp) -> bool {
+        match self.hide_expired_after {
+            Some(hide_after) => expiry
+                .checked_add(hide_after)
+                .map_or(false, |hidden_at| hidden_at <= now),
+            None => false,
+        }
+    }
+}
+
+/// Maximum number of items in a single call of an entrypoint, None if there is no limit.
+#[derive(Serial, Deserial, SchemaType, Clone, Default, Debug, PartialEq)]
+pub struct BatchLimits {
+    /// Tokens in a `mint` call.
+    pub mint: Option<u16>,
+    /// Revocations in a `revoke` call.
+    pub revoke: Option<u16>,
+    /// Queries in a call of a batched view, e.g. `balanceOf`.
+    pub query: Option<u16>,
+    /// Items in a call of an admin entrypoint, e.g. `add` or `batchAdmin`.
+    pub admin: Option<u16>,
+}
+
+/// Volume of `mint` and `mintPending` calls allowed within a window before minting is paused.
+#[derive(Serial, Deserial, SchemaType, Clone, Debug, PartialEq)]
+pub struct MintVolumeLimit {
+    /// Length of the window, which starts with the first mint after the previous window ended.
+    pub window: Duration,
+    /// Maximum number of balances minted within the window, None if there is no limit.
+    pub max_count: Option<u32>,
+    /// Maximum sum of the amounts minted within the window, None if there is no limit.
+    pub max_amount: Option<u64>,
+}
+
+/// Volume minted within the current window of the mint volume limit.
+#[derive(Serial, Deserial, SchemaType, Clone, Debug, PartialEq)]
+pub struct MintVolume {
+    /// Start of the window.
+    pub window_start: Timestamp,
+    /// Number of balances minted within the window.
+    pub count: u32,
+    /// Sum of the amounts minted within the window.
+    pub amount: u64,
+}
+
+/// CIS-2 token in which renewal fees are paid, see `renewWithToken`.
+#[derive(Serial, Deserial, SchemaType, Clone, Debug, PartialEq)]
+pub struct FeeToken {
+    /// Contract of the fee token, e.g. a stablecoin.
+    pub contract: ContractAddress,
+    pub token_id: concordium_cis2::TokenIdVec,
+    /// Fee of a single renewal.
+    pub amount: concordium_cis2::TokenAmountU64,
+    /// Account receiving the fees.
+    pub recipient: AccountAddress,
+}
+
+/// Issuance activity of an issuing address, see `minterStats`.
+#[derive(Serial, Deserial, SchemaType, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MinterStats {
+    /// Number of balances minted.
+    pub mints: u64,
+    /// Sum of the amounts minted.
+    pub amount: u64,
+    /// Number of balances revoked using `revoke`.
+    pub revokes: u64,
+}
+
+#[derive(Serial, Deserial, SchemaType, Clone, Debug, PartialEq)]
+pub struct TokenSnapshot {
+    pub token_id: ContractTokenId,
+    /// Sum of all balances which had not expired at the time of the snapshot.
+    pub total_supply: u64,
+    /// Number of accounts with a balance which had not expired at the time of the snapshot.
+    pub holders: u32,
+}
+
+#[derive(Serial, Deserial, SchemaType, Clone, Debug, PartialEq)]
+pub struct Snapshot {
+    /// Name of the checkpoint given by the admin of the contract.
+    pub name: String,
+    /// Time at which the snapshot was taken.
+    pub timestamp: Timestamp,
+    /// Supply of every token which existed at the time of the snapshot.
+    pub tokens: Vec<TokenSnapshot>,
+}
+
+/// Status of a credential, following the statuses of the CIS-4 standard.
+#[derive(Serial, Deserial, SchemaType, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CredentialStatus {
+    /// The credential is valid.
+    Active,
+    /// The expiry of the credential has passed.
+    Expired,
+    /// The credential has been revoked.
+    Revoked,
+    /// The credential is not valid yet.
+    NotActivated,
+    /// The credential has been frozen by its issuer and is not valid until it is unfrozen.
+    Frozen,
+    /// The credential is not valid because the credential it depends on is not valid.
+    DependencyInvalid,
+}
+
+/// Identifies a credential, which is the balance of a token for an account.
+#[derive(Serial, Deserial, SchemaType, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CredentialQuery {
+    pub token_id: ContractTokenId,
+    pub account: AccountAddress,
+}
+
+#[derive(Serial, Deserial, SchemaType)]
+pub struct CredentialQueryParams {
+    #[concordium(size_length = 2)]
+    pub queries: Vec<CredentialQuery>,
+}
+
+/// Record of a revoked balance.
+#[derive(Serial, Deserial, SchemaType, Clone, Debug, PartialEq)]
+pub struct RevocationInfo {
+    /// Reason code given by the revoker, e.g. fraud or user request.
+    pub reason: u8,
+    /// Time at which the balance was revoked.
+    pub timestamp: Timestamp,
+}
+
+/// Revocation of a balance which takes effect after a dispute window, see `scheduleRevoke`.
+#[derive(Serial, Deserial, SchemaType, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ScheduledRevocation {
+    /// Reason code given by the revoker, e.g. fraud or user request.
+    pub reason: u8,
+    /// Time from which the revocation can be executed.
+    pub effective_at: Timestamp,
+}
+
+/// Tags of the events of this contract, counting down below the tags reserved for CIS-2 events.
+pub const ACTION_SCHEDULED_EVENT_TAG: u8 = 250;
+pub const ACTION_CANCELLED_EVENT_TAG: u8 = 249;
+pub const DSID_MINT_EVENT_TAG: u8 = 248;
+pub const DSID_RENEW_EVENT_TAG: u8 = 247;
+pub const DSID_BATCH_MINT_EVENT_TAG: u8 = 246;
+pub const DSID_FREEZE_EVENT_TAG: u8 = 245;
+pub const DSID_UNFREEZE_EVENT_TAG: u8 = 244;
+pub const DSID_BURN_EVENT_TAG: u8 = 243;
+pub const MINTING_PAUSED_EVENT_TAG: u8 = 242;
+pub const DSID_SCHEMA_REF_EVENT_TAG: u8 = 241;
+pub const TRANSFER_ATTEMPTED_EVENT_TAG: u8 = 240;
+pub const DSID_IMPORT_EVENT_TAG: u8 = 239;
+pub const FEE_COLLECTED_EVENT_TAG: u8 = 238;
+pub const FEES_WITHDRAWN_EVENT_TAG: u8 = 237;
+pub const RENEWAL_REQUESTED_EVENT_TAG: u8 = 236;
+pub const SUBSCRIBER_NOTIFY_FAILED_EVENT_TAG: u8 = 235;
+
+/// Reason code of burns of a balance replaced by a new mint.
+pub const SUPERSEDED_REASON: u8 = u8::MAX - 1;
+/// Reason code of burns of an amount lowered using `adjustAmount`.
+pub const ADJUSTED_REASON: u8 = u8::MAX - 2;
+/// Reason code of burns of balances removed together with their token using `forceRemove`.
+pub const REMOVED_REASON: u8 = u8::MAX - 3;
+/// Reason code of burns of a balance replaced by a mint of another token of its exclusive group.
+pub const EXCLUDED_REASON: u8 = u8::MAX - 4;
+
+/// Events logged by the contract, the CIS-2 events followed by the events of this contract.
+#[derive(Debug)]
+pub enum ContractEvent {
+    Cis2(Cis2Event<ContractTokenId, ContractTokenAmount>),
+    /// A timelocked action has been scheduled.
+    ActionScheduled(ActionScheduledEvent),
+    /// A scheduled action has been cancelled.
+    ActionCancelled(ActionCancelledEvent),
+    /// Tokens have been minted, logged after the CIS-2 `Mint` event.
+    DsidMint(DsidMintEvent),
+    /// The expiry of an existing balance has been extended.
+    DsidRenew(DsidRenewEvent),
+    /// Tokens have been minted in a batch whose events are summarized.
+    DsidBatchMint(DsidBatchMintEvent),
+    /// A balance has been frozen.
+    DsidFreeze(DsidFreezeEvent),
+    /// A frozen balance has been unfrozen.
+    DsidUnfreeze(DsidFreezeEvent),
+    /// Tokens have been burned, logged after the CIS-2 `Burn` event.
+    DsidBurn(DsidBurnEvent),
+    /// Minting has been paused because the mint volume limit was exceeded.
+    MintingPaused(MintVolume),
+    /// The metadata schema checksum of a token has been set or removed.
+    DsidSchemaRef(DsidSchemaRefEvent),
+    /// A transfer has been attempted and rejected.
+    TransferAttempted(TransferAttemptedEvent),
+    /// A balance has been imported from a legacy contract, logged instead of the CIS-2 `Mint` event.
+    DsidImport(DsidImportEvent),
+    /// A renewal fee has been paid in CCD using `renew`.
+    FeeCollected(FeeCollectedEvent),
+    /// Renewal fees collected in CCD have been withdrawn using `withdrawFees`.
+    FeesWithdrawn(FeesWithdrawnEvent),
+    /// A holder has asked for their balance to be renewed using `requestRenewal`.
+    RenewalRequested(RenewalRequestedEvent),
+    /// A subscriber rejected a notification and has been skipped.
+    SubscriberNotifyFailed(SubscriberNotifyFailedEvent),
+}
+
+#[derive(Serial, Deserial, SchemaType, Clone, Debug, PartialEq)]
+pub struct ActionScheduledEvent {
+    /// Hash of the scheduled action.
+    pub action_hash: HashSha2256,
+    /// Time from which the action can be executed.
+    pub ready_at: Timestamp,
+}
+
+#[derive(Serial, Deserial, SchemaType, Clone, Debug, PartialEq)]
+pub struct ActionCancelledEvent {
+    /// Hash of the cancelled action.
+    pub action_hash: HashSha2256,
+}
+
+/// Mint event which includes the expiry, so indexers do not need to query `expiryOf`.
+#[derive(Serial, Deserial, SchemaType, Clone, Debug, PartialEq)]
+pub struct DsidMintEvent {
+    pub token_id: ContractTokenId,
+    /// Owner of the minted tokens.
+    pub owner: Address,
+    pub amount: ContractTokenAmount,
+    /// Time at which the minted tokens expire.
+    pub expiry: Timestamp,
+    /// Address which minted the tokens.
+    pub issuer: Address,
+}
+
+/// Summary of a `mint` call, logged instead of the events of each minted balance.
+#[derive(Serial, Deserial, SchemaType, Clone, Debug, PartialEq)]
+pub struct DsidBatchMintEvent {
+    /// Owner of the minted tokens.
+    pub owner: Address,
+    /// Address which minted the tokens.
+    pub issuer: Address,
+    /// Number of minted balances.
+    pub entries: u32,
+    /// SHA-256 hash of the serialized `mint` parameter.
+    pub batch_hash: HashSha2256,
+}
+
+/// Burn event which includes the reason, so revocations can be segmented without off-chain joins.
+#[derive(Serial, Deserial, SchemaType, Clone, Debug, PartialEq)]
+pub struct DsidBurnEvent {
+    pub token_id: ContractTokenId,
+    /// Owner of the burned tokens.
+    pub owner: Address,
+    pub amount: ContractTokenAmount,
+    /// Reason code, the reason of a revocation or e.g. `SUPERSEDED_REASON`.
+    pub reason: u8,
+}
+
+/// New metadata schema checksum of a token, so verifiers notice changes of the credential format.
+#[derive(Serial, Deserial, SchemaType, Clone, Debug, PartialEq)]
+pub struct DsidSchemaRefEvent {
+    pub token_id: ContractTokenId,
+    /// SHA-256 checksum of the JSON schema of the metadata, None if it was removed.
+    pub schema_ref: Option<HashSha2256>,
+}
+
+/// Balance imported using `importBalances`.
+#[derive(Serial, Deserial, SchemaType, Clone, Debug, PartialEq)]
+pub struct DsidImportEvent {
+    pub token_id: ContractTokenId,
+    /// Owner of the imported tokens.
+    pub owner: Address,
+    pub amount: ContractTokenAmount,
+    /// Time at which the imported tokens expire.
+    pub expiry: Timestamp,
+    /// Time at which the tokens were minted by the legacy contract.
+    pub issued_at: Timestamp,
+}
+
+/// CCD received by `renew`, of which the amount above the renewal fee is returned to the sender.
+#[derive(Serial, Deserial, SchemaType, Clone, Debug, PartialEq)]
+pub struct FeeCollectedEvent {
+    /// Amount of CCD sent with the call.
+    pub amount: Amount,
+    /// Amount of CCD credited to the collected renewal fees, 0 for renewals by the issuer.
+    pub fee: Amount,
+    /// Amount of CCD returned to the sender.
+    pub refund: Amount,
+}
+
+/// Renewal fees in CCD transferred out of the contract by `withdrawFees`.
+#[derive(Serial, Deserial, SchemaType, Clone, Debug, PartialEq)]
+pub struct FeesWithdrawnEvent {
+    /// Account receiving the fees.
+    pub to: AccountAddress,
+    pub amount: Amount,
+}
+
+/// Request of a holder to renew their balance, watched by issuers.
+#[derive(Serial, Deserial, SchemaType, Clone, Debug, PartialEq)]
+pub struct RenewalRequestedEvent {
+    pub token_id: ContractTokenId,
+    /// Owner of the balance to renew.
+    pub owner: Address,
+    /// Current expiry of the balance.
+    pub expiry: Timestamp,
+}
+
+/// Subscriber which rejected `dsidNotify`, the balance change is applied regardless.
+#[derive(Serial, Deserial, SchemaType, Clone, Debug, PartialEq)]
+pub struct SubscriberNotifyFailedEvent {
+    pub subscriber: ContractAddress,
+}
+
+/// Rejected transfer of a token, logged if `log_transfer_attempts` is configured.
+#[derive(Serial, Deserial, SchemaType, Clone, Debug, PartialEq)]
+pub struct TransferAttemptedEvent {
+    pub token_id: ContractTokenId,
+    /// Address which attempted the transfer.
+    pub sender: Address,
+}
+
+/// Freeze or unfreeze of a single balance, the balance itself is kept.
+#[derive(Serial, Deserial, SchemaType, Clone, Debug, PartialEq)]
+pub struct DsidFreezeEvent {
+    pub token_id: ContractTokenId,
+    /// Owner of the balance.
+    pub owner: Address,
+    /// Address which froze or unfroze the balance.
+    pub actor: Address,
+}
+
+#[derive(Serial, Deserial, SchemaType, Clone, Debug, PartialEq)]
+pub struct DsidRenewEvent {
+    pub token_id: ContractTokenId,
+    /// Owner of the renewed balance.
+    pub owner: Address,
+    /// New expiry of the balance.
+    pub expiry: Timestamp,
+    /// Address which renewed the balance.
+    pub renewer: Address,
+}
+
+impl Serial for ContractEvent {
+    fn serial<W: Write>(&self, out: &mut W) -> Result<(), W::Err> {
+        match self {
+            ContractEvent::Cis2(event) => event.serial(out),
+            ContractEvent::ActionScheduled(event) => {
+                ACTION_SCHEDULED_EVENT_TAG.serial(out)?;
+                event.serial(out)
+            }
+            ContractEvent::ActionCancelled(event) => {
+                ACTION_CANCELLED_EVENT_TAG.serial(out)?;
+                event.serial(out)
+            }
+            ContractEvent::DsidMint(event) => {
+                DSID_MINT_EVENT_TAG.serial(out)?;
+                event.serial(out)
+            }
+            ContractEvent::DsidRenew(event) => {
+                DSID_RENEW_EVENT_TAG.serial(out)?;
+                event.serial(out)
+            }
+            ContractEvent::DsidBatchMint(event) => {
+                DSID_BATCH_MINT_EVENT_TAG.serial(out)?;
+                event.serial(out)
+            }
+            ContractEvent::DsidFreeze(event) => {
+                DSID_FREEZE_EVENT_TAG.serial(out)?;
+                event.serial(out)
+            }
+            ContractEvent::DsidUnfreeze(event) => {
+                DSID_UNFREEZE_EVENT_TAG.serial(out)?;
+                event.serial(out)
+            }
+            ContractEvent::DsidBurn(event) => {
+                DSID_BURN_EVENT_TAG.serial(out)?;
+                event.serial(out)
+            }
+            ContractEvent::MintingPaused(event) => {
+                MINTING_PAUSED_EVENT_TAG.serial(out)?;
+                event.serial(out)
+            }
+            ContractEvent::DsidSchemaRef(event) => {
+                DSID_SCHEMA_REF_EVENT_TAG.serial(out)?;
+                event.serial(out)
+            }
+            ContractEvent::TransferAttempted(event) => {
+                TRANSFER_ATTEMPTED_EVENT_TAG.serial(out)?;
+                event.serial(out)
+            }
+            ContractEvent::DsidImport(event) => {
+                DSID_IMPORT_EVENT_TAG.serial(out)?;
+                event.serial(out)
+            }
+            ContractEvent::FeeCollected(event) => {
+                FEE_COLLECTED_EVENT_TAG.serial(out)?;
+                event.serial(out)
+            }
+            ContractEvent::FeesWithdrawn(event) => {
+                FEES_WITHDRAWN_EVENT_TAG.serial(out)?;
+                event.serial(out)
+            }
+            ContractEvent::RenewalRequested(event) => {
+                RENEWAL_REQUESTED_EVENT_TAG.serial(out)?;
+                event.serial(out)
+            }
+            ContractEvent::SubscriberNotifyFailed(event) => {
+                SUBSCRIBER_NOTIFY_FAILED_EVENT_TAG.serial(out)?;
+                event.serial(out)
+            }
+        }
+    }
+}
+
+impl schema::SchemaType for ContractEvent {
+    fn get_type() -> schema::Type {
+        // Extend the schema of the CIS-2 events with the events of this contract.
+        let mut events = match Cis2Event::<ContractTokenId, ContractTokenAmount>::get_type() {
+            schema::Type::TaggedEnum(events) => events,
+            _ => collections::BTreeMap::new(),
+        };
+        events.insert(
+            ACTION_SCHEDULED_EVENT_TAG,
+            (
+                String::from("ActionScheduled"),
+                schema::Fields::Named(vec![
+                    (String::from("action_hash"), HashSha2256::get_type()),
+                    (String::from("ready_at"), Timestamp::get_type()),
+                ]),
+            ),
+        );
+        events.insert(
+            ACTION_CANCELLED_EVENT_TAG,
+            (
+                String::from("ActionCancelled"),
+                schema::Fields::Named(vec![(String::from("action_hash"), HashSha2256::get_type())]),
+            ),
+        );
+        events.insert(
+            DSID_MINT_EVENT_TAG,
+            (
+                String::from("DsidMint"),
+                schema::Fields::Named(vec![
+                    (String::from("token_id"), ContractTokenId::get_type()),
+                    (String::from("owner"), Address::get_type()),
+                    (String::from("amount"), ContractTokenAmount::get_type()),
+                    (String::from("expiry"), Timestamp::get_type()),
+                    (String::from("issuer"), Address::get_type()),
+                ]),
+            ),
+        );
+        events.insert(
+            DSID_RENEW_EVENT_TAG,
+            (
+                String::from("DsidRenew"),
+                schema::Fields::Named(vec![
+                    (String::from("token_id"), ContractTokenId::get_type()),
+                    (String::from("owner"), Address::get_type()),
+                    (String::from("expiry"), Timestamp::get_type()),
+                    (String::from("renewer"), Address::get_type()),
+                ]),
+            ),
+        );
+        events.insert(
+            DSID_BATCH_MINT_EVENT_TAG,
+            (
+                String::from("DsidBatchMint"),
+                schema::Fields::Named(vec![
+                    (String::from("owner"), Address::get_type()),
+                    (String::from("issuer"), Address::get_type()),
+                    (String::from("entries"), u32::get_type()),
+                    (String::from("batch_hash"), HashSha2256::get_type()),
+                ]),
+            ),
+        );
+        events.insert(
+            DSID_BURN_EVENT_TAG,
+            (
+                String::from("DsidBurn"),
+                schema::Fields::Named(vec![
+                    (String::from("token_id"), ContractTokenId::get_type()),
+                    (String::from("owner"), Address::get_type()),
+                    (String::from("amount"), ContractTokenAmount::get_type()),
+                    (String::from("reason"), u8::get_type()),
+                ]),
+            ),
+        );
+        events.insert(
+            MINTING_PAUSED_EVENT_TAG,
+            (
+                String::from("MintingPaused"),
+                schema::Fields::Named(vec![
+                    (String::from("window_start"), Timestamp::get_type()),
+                    (String::from("count"), u32::get_type()),
+                    (String::from("amount"), u64::get_type()),
+                ]),
+            ),
+        );
+        events.insert(
+            DSID_SCHEMA_REF_EVENT_TAG,
+            (
+                String::from("DsidSchemaRef"),
+                schema::Fields::Named(vec![
+                    (String::from("token_id"), ContractTokenId::get_type()),
+                    (
+                        String::from("schema_ref"),
+                        Option::<HashSha2256>::get_type(),
+                    ),
+                ]),
+            ),
+        );
+        events.insert(
+            TRANSFER_ATTEMPTED_EVENT_TAG,
+            (
+                String::from("TransferAttempted"),
+                schema::Fields::Named(vec![
+                    (String::from("token_id"), ContractTokenId::get_type()),
+                    (String::from("sender"), Address::get_type()),
+                ]),
+            ),
+        );
+        events.insert(
+            DSID_IMPORT_EVENT_TAG,
+            (
+                String::from("DsidImport"),
+                schema::Fields::Named(vec![
+                    (String::from("token_id"), ContractTokenId::get_type()),
+                    (String::from("owner"), Address::get_type()),
+                    (String::from("amount"), ContractTokenAmount::get_type()),
+                    (String::from("expiry"), Timestamp::get_type()),
+                    (String::from("issued_at"), Timestamp::get_type()),
+                ]),
+            ),
+        );
+        events.insert(
+            FEE_COLLECTED_EVENT_TAG,
+            (
+                String::from("FeeCollected"),
+                schema::Fields::Named(vec![
+                    (String::from("amount"), Amount::get_type()),
+                    (String::from("refund"), Amount::get_type()),
+                ]),
+            ),
+        );
+        events.insert(
+            FEES_WITHDRAWN_EVENT_TAG,
+            (
+                String::from("FeesWithdrawn"),
+                schema::Fields::Named(vec![
+                    (String::from("to"), AccountAddress::get_type()),
+                    (String::from("amount"), Amount::get_type()),
+                ]),
+            ),
+        );
+        events.insert(
+            RENEWAL_REQUESTED_EVENT_TAG,
+            (
+                String::from("RenewalRequested"),
+                schema::Fields::Named(vec![
+                    (String::from("token_id"), ContractTokenId::get_type()),
+                    (String::from("owner"), Address::get_type()),
+                    (String::from("expiry"), Timestamp::get_type()),
+                ]),
+            ),
+        );
+        events.insert(
+            SUBSCRIBER_NOTIFY_FAILED_EVENT_TAG,
+            (
+                String::from("SubscriberNotifyFailed"),
+                schema::Fields::Named(vec![(
+                    String::from("subscriber"),
+                    ContractAddress::get_type(),
+                )]),
+            ),
+        );
+        for (tag, name) in [
+            (DSID_FREEZE_EVENT_TAG, "DsidFreeze"),
+            (DSID_UNFREEZE_EVENT_TAG, "DsidUnfreeze"),
+        ] {
+            events.insert(
+                tag,
+                (
+                    String::from(name),
+                    schema::Fields::Named(vec![
+                        (String::from("token_id"), ContractTokenId::get_type()),
+                        (String::from("owner"), Address::get_type()),
+                        (String::from("actor"), Address::get_type()),
+                    ]),
+                ),
+            );
+        }
+        schema::Type::TaggedEnum(events)
+    }
+}